@@ -1,17 +1,63 @@
+// the same worlds.txt reader the game uses, so the rom embeds exactly the levels the index lists
+#[allow(dead_code)]
+#[path = "src/runtime/world_list.rs"]
+mod world_list;
+
 use std::{
-	env, fs,
+	env,
+	fmt::Write,
+	fs,
 	path::{Path, PathBuf},
 };
 
+use world_list::{WorldList, parse_world_list};
+
 fn copy_if_exists(src: &Path, dst: &Path) {
 	if src.exists() {
 		let _ = fs::copy(src, dst);
 	}
 }
 
+// the gba rom can't read worlds/ at run time, so every level in worlds/worlds.txt is written into
+// $OUT_DIR/embedded_levels.rs as an EMBEDDED_LEVELS table for platform/level_loader.rs to include
+fn write_embedded_levels() {
+	let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+	let worlds_dir = manifest_dir.join("..").join("worlds");
+	let index_path = worlds_dir.join("worlds.txt");
+	println!("cargo:rerun-if-changed={}", index_path.display());
+
+	let index_text = fs::read_to_string(&index_path).unwrap_or_else(|e| panic!("{}: {}", index_path.display(), e));
+	let list: WorldList = parse_world_list(&index_text).unwrap_or_else(|e| panic!("{}: {}", index_path.display(), e));
+
+	let mut code = String::new();
+	let _ = writeln!(code, "// generated by build.rs from worlds/worlds.txt");
+	let _ = writeln!(code, "const EMBEDDED_LEVELS: &[EmbeddedLevel] = &[");
+	for level in &list.levels {
+		// the rom's table is keyed by bytes; the level's name is read from its .lvlb at run time
+		let world_id: u8 = u8::try_from(level.world_id).unwrap_or_else(|_| panic!("{}: world {} does not fit the rom", index_path.display(), level.world_id));
+		let level_id: u8 = u8::try_from(level.level_id).unwrap_or_else(|_| panic!("{}: level {} does not fit the rom", index_path.display(), level.level_id));
+
+		let level_path = worlds_dir.join(format!("{:02}", world_id)).join(format!("{:02}.lvlb", level_id));
+		println!("cargo:rerun-if-changed={}", level_path.display());
+		let _ = writeln!(
+			code,
+			"\tEmbeddedLevel {{ world_id: {}, level_id: {}, bytes: include_bytes!({:?}) }},",
+			world_id,
+			level_id,
+			level_path.display().to_string()
+		);
+	}
+	let _ = writeln!(code, "];");
+
+	let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("embedded_levels.rs");
+	fs::write(&out_path, code).unwrap_or_else(|e| panic!("{}: {}", out_path.display(), e));
+}
+
 fn main() {
 	println!("cargo:rerun-if-changed=build.rs");
 
+	write_embedded_levels();
+
 	if env::var("CARGO_CFG_TARGET_OS").unwrap_or_default() != "windows" {
 		return;
 	}
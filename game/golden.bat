@@ -0,0 +1,5 @@
+cls
+cargo run --features pc -- --level ../worlds/00/01.lvlb --screenshot-at 120 --screenshot-size 640x360 --screenshot target/golden_00_01.png --golden golden/00_01.png
+cargo run --features pc -- --level ../worlds/00/02.lvlb --screenshot-at 120 --screenshot-size 640x360 --screenshot target/golden_00_02.png --golden golden/00_02.png
+cargo run --features pc -- --level ../worlds/01/01.lvlb --screenshot-at 120 --screenshot-size 640x360 --screenshot target/golden_01_01.png --golden golden/01_01.png
+cargo run --features pc -- --level ../worlds/02/01.lvlb --screenshot-at 120 --screenshot-size 640x360 --screenshot target/golden_02_01.png --golden golden/02_01.png
@@ -1,16 +1,25 @@
+use alloc::string::String;
+
 use jumpy::{
 	debugln,
 	platform::{
 		level_loader::load_level_from_name,
 		render::{BackgroundDrawParams, BackgroundId, backend::RenderBackend, gba::GbaRenderer},
 	},
+	runtime::world_index::WorldIndex,
 };
 
 pub fn run(mut gba: agb::Gba) -> ! {
 	let mut renderer = GbaRenderer::new_with_gba(&mut gba);
 	renderer.init();
 
-	let bootstrap_level = load_level_from_name("../worlds/01/01.lvlb");
+	// the first level of worlds/worlds.txt, which build.rs also embedded
+	let first_level_name: String = WorldIndex::load()
+		.ok()
+		.and_then(|index| index.get_first_level())
+		.map(|key| key.get_level_name())
+		.unwrap_or_else(|| String::from("../worlds/01/01.lvlb"));
+	let bootstrap_level = load_level_from_name(first_level_name.as_str());
 	let background_id = bootstrap_level.unwrap().background_id;
 
 	let background_draw_params: BackgroundDrawParams = BackgroundDrawParams {
@@ -18,6 +27,8 @@ pub fn run(mut gba: agb::Gba) -> ! {
 		camera_left: 0,
 		camera_top: 0,
 		scale: 4.0,
+		level_height: 0,
+		tick: 0,
 	};
 
 	debugln!("Before Drawing background... {}", renderer.loaded_background.to_u8());
@@ -1,29 +1,70 @@
-use crate::main_pc::platform::render::pc::PcRenderer;
+use crate::main_pc::platform::render::pc::{PcRenderContext, PcRenderer, PcWindow, open_window};
 use jumpy::{
-	BookId, BookSlug, debugln, physics,
+	BookId, debugln,
 	platform::{
 		self,
 		audio::{AudioEngine, pc::PcAudio},
-		input::TriggerPresses,
-		level_loader::{load_level_from_file, load_level_from_name},
-		render::backend::RenderBackend,
+		input::{InputState, TriggerPresses},
+		level_loader::{LevelPreload, load_level_from_file, load_level_from_name},
+		render::{
+			backend::RenderBackend,
+			snapshot::RenderSnapshot,
+			software::{
+				DEFAULT_SCREEN_HEIGHT_PIXELS, DEFAULT_SCREEN_WIDTH_PIXELS, SoftwareRenderer,
+				screenshot::{compare_with_golden, render_level_frame},
+			},
+			triple_buffer::{TripleBufferWriter, triple_buffer},
+		},
+		timer::{
+			backend::TimerBackend,
+			fixed_step::{FixedStep, SIMULATION_HZ},
+			pc::PcTimer,
+		},
+	},
+	runtime::{
+		Settings,
+		accessibility::{
+			ASSIST_EXTRA_JUMP_FRAMES_MAX, ASSIST_EXTRA_JUMP_FRAMES_STEP, ASSIST_GAME_SPEED_PERCENT_MIN, ASSIST_GAME_SPEED_PERCENT_STEP, Accessibility, AssistMode,
+		},
+		book::reading_state::BookReadingState,
+		camera,
+		game_mode::GameMode,
+		inventory::InventorySlot,
+		level::Level,
+		menu::{Menu, MenuAction},
+		message_table::MessageTable,
+		music::MusicId,
+		pickup_map::PICKUP_MAP_BOOK_PREFIX,
+		play_step::{PlayInput, run_play_step},
+		progress::Progress,
+		session::{PlayerPersistentState, Session},
+		settings::VOLUME_LEVEL_MAX,
+		speedrun::Ghost,
+		state::State,
+		transition::{TransitionEffect, TransitionPhase, TransitionState},
+		world_index::{WorldIndex, WorldLevelEntry},
 	},
-	runtime::{inventory::Inventory, music::MusicId, session::Session, state::State, triggers},
 };
 
-pub fn run() {
-	use std::path::Path;
-
-	let mut session = Session::new();
-
-	let audio: Box<dyn AudioEngine> = {
-		let mut a = PcAudio::new();
-		a.init();
-		Box::new(a)
-	};
+use std::{
+	path::{Path, PathBuf},
+	sync::mpsc::{self, Receiver, TryRecvError},
+};
 
-	let default_level_path: &str = "../worlds/01/01.lvlb"; // Default to 1-1
-	let mut first_level_path: String = default_level_path.to_string();
+pub fn run() {
+	// a new game starts at the first level of worlds/worlds.txt, 1-1 if the index can't be read
+	let mut first_level_path: String = WorldIndex::load()
+		.ok()
+		.and_then(|index| index.get_first_level())
+		.map(|key| key.get_level_name())
+		.unwrap_or_else(|| String::from("../worlds/01/01.lvlb"));
+	let mut screenshot_frame: Option<u32> = None;
+	let mut screenshot_path: PathBuf = PathBuf::from("screenshot.png");
+	let mut screenshot_size: (u32, u32) = (DEFAULT_SCREEN_WIDTH_PIXELS, DEFAULT_SCREEN_HEIGHT_PIXELS);
+	let mut golden_path: Option<PathBuf> = None;
+	let mut is_threaded: bool = false;
+	// the title screen only comes up when no level was asked for
+	let mut show_title: bool = true;
 
 	let args: Vec<String> = std::env::args().collect();
 	let mut i: usize = 1;
@@ -36,6 +77,7 @@ pub fn run() {
 		if a == "--level" || a == "-level" {
 			if i + 1 < args.len() {
 				first_level_path = args[i + 1].clone();
+				show_title = false;
 				i += 2;
 				continue;
 			}
@@ -44,42 +86,104 @@ pub fn run() {
 
 		if let Some(rest) = a.strip_prefix("--level=") {
 			first_level_path = rest.to_string();
+			show_title = false;
 			i += 1;
 			continue;
 		}
 
+		// --threaded: simulate on a worker thread, render on this one
+		if a == "--threaded" {
+			is_threaded = true;
+			i += 1;
+			continue;
+		}
+
+		// headless capture:
+		// --screenshot-at <frame>   run with no input and no window, save that frame
+		// --screenshot <path>       where to save it (default screenshot.png)
+		// --screenshot-size <w>x<h> frame size in pixels (default 1280x720)
+		// --golden <path>           compare against a reference png, exit 1 on any difference
+		if a == "--screenshot-at" || a == "--screenshot" || a == "--screenshot-size" || a == "--golden" {
+			if i + 1 >= args.len() {
+				panic!("missing value after {}", a);
+			}
+
+			let value: &str = args[i + 1].as_str();
+			match a {
+				"--screenshot-at" => screenshot_frame = Some(value.parse().unwrap_or_else(|_| panic!("invalid frame number '{}'", value))),
+				"--screenshot" => screenshot_path = PathBuf::from(value),
+				"--screenshot-size" => screenshot_size = parse_screenshot_size(value).unwrap_or_else(|| panic!("invalid screenshot size '{}', expected WxH", value)),
+				_ => golden_path = Some(PathBuf::from(value)),
+			}
+			i += 2;
+			continue;
+		}
+
 		i += 1;
 	}
 
-	let path = Path::new(&first_level_path);
-	let bootstrap_level = load_level_from_file(path);
-	let mut state = State::new(bootstrap_level, audio);
+	if let Some(frame) = screenshot_frame {
+		let exit_code: i32 = run_screenshot(&first_level_path, frame, screenshot_size, &screenshot_path, golden_path.as_deref());
+		std::process::exit(exit_code);
+	}
+
+	if is_threaded {
+		run_threaded(first_level_path, show_title);
+		return;
+	}
 
-	state.spawn_level_entities();
-	state.apply_player_from_persistent(&session);
-	session.current_level_name = Some(first_level_path.to_string());
+	let timer = PcTimer::new();
+	let mut game = match Game::new(&first_level_path, show_title, create_audio(), &timer) {
+		Ok(game) => game,
+		Err(e) => {
+			eprintln!("{}", e);
+			std::process::exit(1);
+		}
+	};
+
+	let window: PcWindow = exit_on_error(open_window());
+	let render_context: PcRenderContext = exit_on_error(PcRenderContext::new(&window));
+	let mut renderer: PcRenderer = exit_on_error(PcRenderer::new(window, &render_context));
+	renderer.init();
+
+	let mut snapshot = RenderSnapshot::from_state(&game.state, &game.session, renderer.get_screen_size(), renderer.get_render_scale());
+	let mut copy_was_down: bool = false;
 
-	if session.settings.is_background_music_enabled {
-		let music_id: MusicId = state.level.music_id;
-		state.audio.play_music(music_id, true);
-		session.active_music_id = music_id;
+	loop {
+		let input: platform::input::InputState = renderer.poll_input();
+		if input.quit {
+			game.save_progress();
+			break;
+		}
+
+		game.state.camera.set_view_size(renderer.get_screen_size(), renderer.get_render_scale());
+		game.update(&input, &timer);
+
+		snapshot.capture(&game.state, &game.session, renderer.get_screen_size(), renderer.get_render_scale());
+		if snapshot.is_quit_requested {
+			break;
+		}
+		present_snapshot(&mut renderer, &snapshot, &input, &mut copy_was_down);
 	}
+}
 
-	let mut renderer = PcRenderer::new();
+// --threaded: the simulation runs on its own thread and hands finished frames to this one through a
+// triple buffer; SDL stays on the main thread because windows, events and the clipboard must
+fn run_threaded(first_level_path: String, show_title: bool) {
+	let window: PcWindow = exit_on_error(open_window());
+	let render_context: PcRenderContext = exit_on_error(PcRenderContext::new(&window));
+	let mut renderer: PcRenderer = exit_on_error(PcRenderer::new(window, &render_context));
 	renderer.init();
-	renderer.draw_background_by_id(state.level.background_id);
-
-	let mut up_was_down: bool = false;
-	let mut down_was_down: bool = false;
-	let mut left_was_down: bool = false;
-	let mut right_was_down: bool = false;
-	let mut action_was_down: bool = false; // "action" is jump for now
-	let mut inventory_was_down: bool = false;
-	let mut read_was_down: bool = false;
-	let mut page_up_was_down: bool = false;
-	let mut page_down_was_down: bool = false;
+
+	let (input_sender, input_receiver) = mpsc::channel::<FrameInput>();
+	let (snapshot_writer, mut snapshot_reader) = triple_buffer::<Option<RenderSnapshot>>(None);
+
+	let simulation = std::thread::Builder::new()
+		.name("simulation".to_string())
+		.spawn(move || run_simulation_thread(&first_level_path, show_title, input_receiver, snapshot_writer))
+		.expect("failed to start simulation thread");
+
 	let mut copy_was_down: bool = false;
-	let mut escape_was_down: bool = false;
 
 	loop {
 		let input: platform::input::InputState = renderer.poll_input();
@@ -87,186 +191,963 @@ pub fn run() {
 			break;
 		}
 
-		let inventory_pressed: bool = input.inventory && !inventory_was_down;
-		let read_pressed: bool = input.read && !read_was_down;
+		let frame_input: FrameInput = FrameInput {
+			input,
+			screen_size_pixels: renderer.get_screen_size(),
+			render_scale: renderer.get_render_scale(),
+		};
 
-		if inventory_pressed {
-			inventory_was_down = true;
-			print_inventory(&session.inventory);
-			continue;
+		// the simulation thread only goes away if it panicked
+		if input_sender.send(frame_input).is_err() {
+			break;
+		}
+
+		let mut is_quit_requested: bool = false;
+		match snapshot_reader.read() {
+			Some(snapshot) => {
+				is_quit_requested = snapshot.is_quit_requested;
+				present_snapshot(&mut renderer, snapshot, &input, &mut copy_was_down);
+			}
+			None => {
+				// nothing simulated yet
+				renderer.begin_frame();
+				renderer.commit();
+			}
+		}
+		if is_quit_requested {
+			break;
+		}
+	}
+
+	// closing the channel tells the simulation thread to stop
+	drop(input_sender);
+	if simulation.join().is_err() {
+		debugln!("simulation thread panicked");
+	}
+}
+
+// what the render thread sends the simulation thread once per frame
+struct FrameInput {
+	input: InputState,
+	screen_size_pixels: (i32, i32),
+	render_scale: f32,
+}
+
+fn run_simulation_thread(level_path: &str, show_title: bool, inputs: Receiver<FrameInput>, mut snapshots: TripleBufferWriter<Option<RenderSnapshot>>) {
+	// sdl_mixer chunks can't cross threads, so the audio engine is created here with the state that owns it
+	let timer = PcTimer::new();
+	let mut game = match Game::new(level_path, show_title, create_audio(), &timer) {
+		Ok(game) => game,
+		Err(e) => {
+			// dropping the snapshot writer without publishing tells the render thread there is nothing to draw
+			eprintln!("{}", e);
+			return;
+		}
+	};
+
+	let Ok(first) = inputs.recv() else {
+		return;
+	};
+
+	let mut screen_size_pixels: (i32, i32) = first.screen_size_pixels;
+	let mut render_scale: f32 = first.render_scale;
+	let mut latest_input: InputState = first.input;
+	let mut pending_input: InputState = first.input;
+
+	loop {
+		let mut has_new_input: bool = false;
+		loop {
+			match inputs.try_recv() {
+				Ok(frame) => {
+					// keep presses until a step has seen them so taps shorter than a step are not lost
+					pending_input = merge_presses(&pending_input, &frame.input);
+					latest_input = frame.input;
+					screen_size_pixels = frame.screen_size_pixels;
+					render_scale = frame.render_scale;
+					has_new_input = true;
+				}
+				Err(TryRecvError::Empty) => break,
+				Err(TryRecvError::Disconnected) => {
+					game.save_progress();
+					return;
+				}
+			}
 		}
 
-		if session.book_reading.is_open {
-			if input.quit {
+		game.state.camera.set_view_size(screen_size_pixels, render_scale);
+		let has_consumed_input: bool = game.update(&pending_input, &timer);
+		if has_consumed_input {
+			pending_input = latest_input;
+		}
+
+		if has_consumed_input || has_new_input {
+			// the render thread draws whole steps; it never sees a half-way state to interpolate from
+			game.state.render_alpha = 1.0;
+
+			let back: &mut Option<RenderSnapshot> = snapshots.back_mut();
+			match back {
+				Some(snapshot) => snapshot.capture(&game.state, &game.session, screen_size_pixels, render_scale),
+				None => *back = Some(RenderSnapshot::from_state(&game.state, &game.session, screen_size_pixels, render_scale)),
+			}
+			snapshots.publish();
+		}
+
+		timer.sleep_ms(1);
+	}
+}
+
+fn merge_presses(pending: &InputState, next: &InputState) -> InputState {
+	return InputState {
+		quit: pending.quit || next.quit,
+		left: pending.left || next.left,
+		right: pending.right || next.right,
+		jump: pending.jump || next.jump,
+		up: pending.up || next.up,
+		down: pending.down || next.down,
+		inventory: pending.inventory || next.inventory,
+		read: pending.read || next.read,
+		escape: pending.escape || next.escape,
+		pause: pending.pause || next.pause,
+		page_up: pending.page_up || next.page_up,
+		page_down: pending.page_down || next.page_down,
+		copy: pending.copy || next.copy,
+	};
+}
+
+// the level asked for, or the first level of the world index if that one won't load
+fn load_bootstrap_level(level_path: &str, session: &Session) -> Result<(String, Level), String> {
+	let error: String = match load_level_from_file(Path::new(level_path)) {
+		Ok(level) => return Ok((String::from(level_path), level)),
+		Err(e) => e,
+	};
+	debugln!("level load failed: {}", error);
+
+	let Some(first_level_name) = session.world_index.get_first_level().map(|key| key.get_level_name()) else {
+		return Err(error);
+	};
+	if first_level_name == level_path {
+		return Err(error);
+	}
+	return load_level_from_name(&first_level_name)
+		.map(|level| (first_level_name, level))
+		.map_err(|e| format!("{}; {}", error, e));
+}
+
+fn create_audio() -> Box<dyn AudioEngine> {
+	let mut audio = PcAudio::new();
+	audio.init();
+	return Box::new(audio);
+}
+
+// the book overlay and clipboard live on the render side; everything else comes from the snapshot
+// a window or font that can't be had ends the game with the reason, like a level that won't load
+fn exit_on_error<T>(result: Result<T, String>) -> T {
+	match result {
+		Ok(value) => return value,
+		Err(e) => {
+			eprintln!("{}", e);
+			std::process::exit(1);
+		}
+	}
+}
+
+fn present_snapshot(renderer: &mut PcRenderer, snapshot: &RenderSnapshot, input: &InputState, copy_was_down: &mut bool) {
+	if let Some(book_reading) = &snapshot.book_reading {
+		if !*copy_was_down && input.copy {
+			renderer.copy_book_page_to_clipboard(&book_reading.page_text);
+		}
+	}
+	*copy_was_down = input.copy;
+
+	renderer.begin_frame();
+	renderer.draw_level(snapshot);
+	if let Some(book_reading) = &snapshot.book_reading {
+		renderer.draw_book_overlay(book_reading);
+	}
+	renderer.commit();
+}
+
+// everything the simulation owns; runs on the main thread, or on the simulation thread with --threaded
+struct Game {
+	session: Session,
+	state: State,
+	fixed_step: FixedStep,
+	up_was_down: bool,
+	down_was_down: bool,
+	left_was_down: bool,
+	right_was_down: bool,
+	action_was_down: bool, // "action" is jump for now
+	inventory_was_down: bool,
+	read_was_down: bool,
+	page_up_was_down: bool,
+	page_down_was_down: bool,
+	escape_was_down: bool,
+	pause_was_down: bool,
+	// the next level, loading on a worker thread while the transition covers the screen
+	level_preload: Option<LevelPreload>,
+	// when the last frame's update ran, in timer ticks, for the speedrun timer's real time
+	last_update_ticks: u64,
+}
+
+impl Game {
+	// fails only when neither the requested level nor the first level of the world index loads
+	fn new(level_path: &str, show_title: bool, audio: Box<dyn AudioEngine>, timer: &PcTimer) -> Result<Game, String> {
+		let mut session = Session::with_settings(Settings::load());
+		session.progress = Progress::load(&mut session.inventory);
+
+		let (level_path, bootstrap_level) = load_bootstrap_level(level_path, &session)?;
+		let level_path: &str = level_path.as_str();
+		let mut state = State::new(bootstrap_level, audio);
+
+		state.spawn_level_entities();
+		state.apply_player_from_persistent(&session);
+		session.current_level_name = Some(level_path.to_string());
+		session.apply_level_progress(&mut state, level_path);
+
+		state.audio.set_music_volume(session.settings.music_volume);
+		state.audio.set_sound_effects_volume(session.settings.sound_effects_volume);
+		if session.settings.is_background_music_enabled {
+			let music_id: MusicId = state.level.music_id;
+			state.audio.play_music(music_id, true);
+			session.active_music_id = music_id;
+		}
+
+		let mut game: Game = Game {
+			session,
+			state,
+			// the simulation runs at a fixed 60 Hz whatever the refresh rate; rendering interpolates between steps
+			fixed_step: FixedStep::new(timer, SIMULATION_HZ),
+			up_was_down: false,
+			down_was_down: false,
+			left_was_down: false,
+			right_was_down: false,
+			action_was_down: false,
+			inventory_was_down: false,
+			read_was_down: false,
+			page_up_was_down: false,
+			page_down_was_down: false,
+			escape_was_down: false,
+			pause_was_down: false,
+			level_preload: None,
+			last_update_ticks: timer.now_ticks(),
+		};
+		game.start_speedrun_level();
+
+		// the first level waits behind the title screen
+		if show_title {
+			game.push_mode(GameMode::Title);
+		}
+		return Ok(game);
+	}
+
+	// one frame of input for the mode on top of the stack. returns true once the input has been
+	// acted on (a step ran, or a menu or the book handled it)
+	fn update(&mut self, input: &InputState, timer: &PcTimer) -> bool {
+		let now_ticks: u64 = timer.now_ticks();
+		let elapsed_ticks: u64 = now_ticks.saturating_sub(self.last_update_ticks);
+		self.last_update_ticks = now_ticks;
+		self.session.speedrun.add_real_time(elapsed_ticks * 1_000_000 / timer.ticks_per_second());
+
+		let mode: GameMode = self.session.modes.get_top();
+		let has_consumed_input: bool = match mode {
+			GameMode::Playing => self.update_playing(input, timer),
+			GameMode::Book => self.update_book(input),
+			GameMode::Inventory => self.update_inventory(input),
+			GameMode::LevelSelect => self.update_level_select(input),
+			GameMode::Results => self.update_results(input),
+			GameMode::LevelTransition => self.update_level_transition(timer),
+			GameMode::Title | GameMode::Paused | GameMode::Settings | GameMode::Accessibility | GameMode::GameOver => self.update_menu(input),
+		};
+
+		// nothing simulates under the other modes; don't let time spent in them pile up as steps.
+		// a mode change resets it too, so a transition doesn't start with play's leftover time
+		if !mode.runs_on_fixed_step() || self.session.modes.get_top() != mode {
+			self.fixed_step.reset(timer);
+		}
+		return has_consumed_input;
+	}
+
+	fn push_mode(&mut self, mode: GameMode) {
+		let menu: Option<Menu> = Menu::for_mode(mode, &self.session);
+		self.session.modes.push(mode, menu);
+		self.enter_mode(mode);
+		return;
+	}
+
+	fn pop_mode(&mut self) {
+		if let Some(mode) = self.session.modes.pop() {
+			self.exit_mode(mode);
+		}
+		return;
+	}
+
+	// closes every menu and overlay down to play itself
+	fn pop_to_playing(&mut self) {
+		while self.session.modes.get_top() != GameMode::Playing {
+			let Some(mode) = self.session.modes.pop() else {
 				break;
+			};
+			self.exit_mode(mode);
+		}
+		return;
+	}
+
+	fn enter_mode(&mut self, mode: GameMode) {
+		match mode {
+			GameMode::Paused | GameMode::GameOver => self.state.audio.pause_music(),
+			GameMode::LevelTransition => {
+				// a new level closes in on the player; the same level again just fades
+				let is_restart: bool = self.session.pending_level_name.is_none();
+				let effect: TransitionEffect = if is_restart { TransitionEffect::Fade } else { TransitionEffect::Iris };
+				self.session.transition = Some(TransitionState::new(effect, is_restart));
 			}
+			_ => {}
+		}
+		return;
+	}
 
-			if !copy_was_down && input.copy {
-				renderer.copy_book_page_to_clipboard(&session.book_reading.page_text);
+	fn exit_mode(&mut self, mode: GameMode) {
+		match mode {
+			GameMode::Paused | GameMode::GameOver => self.state.audio.resume_music(),
+			GameMode::LevelTransition => {
+				// a preload still running finishes on its own; its result goes nowhere
+				self.session.transition = None;
+				self.level_preload = None;
 			}
+			GameMode::Results => self.session.results = None,
+			GameMode::Book => {
+				// the book opens where it was left next time
+				let reading: &BookReadingState = &self.session.book_reading;
+				if let Some(book_id) = self.session.pickup_map.get_id(PICKUP_MAP_BOOK_PREFIX, reading.book_slug.as_str()) {
+					self.session.inventory.set_book_page(book_id, reading.page_index, reading.total_pages);
+				}
+				self.session.book_reader.close_book(&mut self.session.book_reading);
+			}
+			GameMode::Settings => {
+				if let Err(e) = self.session.settings.save() {
+					debugln!("settings save failed: {}", e);
+				}
+			}
+			_ => {}
+		}
+		return;
+	}
 
-			if !escape_was_down && input.escape {
-				session.book_reader.close_book(&mut session.book_reading);
+	// the press that closes a menu or a message must not also make the player jump
+	fn hold_jump(&mut self, is_jump_down: bool) {
+		if let Some(player_id) = self.state.try_get_player_id() {
+			if let Some(jump_state) = self.state.jump_states.get_mut(player_id) {
+				jump_state.jump_was_down = is_jump_down;
 			}
+		}
+		return;
+	}
 
-			if (!left_was_down && input.left) || (!page_up_was_down && input.page_up) {
-				let _ = session.book_reader.turn_book_page(&mut session.book_reading, -1);
+	// up/down pick an item, jump or start runs it, left/right step a setting, escape backs out
+	fn update_menu(&mut self, input: &InputState) -> bool {
+		let up_pressed: bool = input.up && !self.up_was_down;
+		let down_pressed: bool = input.down && !self.down_was_down;
+		let left_pressed: bool = input.left && !self.left_was_down;
+		let right_pressed: bool = input.right && !self.right_was_down;
+		let confirm_pressed: bool = (input.jump && !self.action_was_down) || (input.pause && !self.pause_was_down);
+		let back_pressed: bool = input.escape && !self.escape_was_down;
+
+		self.up_was_down = input.up;
+		self.down_was_down = input.down;
+		self.left_was_down = input.left;
+		self.right_was_down = input.right;
+		self.action_was_down = input.jump;
+		self.pause_was_down = input.pause;
+		self.escape_was_down = input.escape;
+		self.inventory_was_down = input.inventory;
+
+		if let Some(menu) = self.session.modes.get_menu_mut() {
+			if up_pressed {
+				menu.move_selection(-1);
+			}
+			if down_pressed {
+				menu.move_selection(1);
 			}
+		}
 
-			if (!right_was_down && input.right) || (!page_down_was_down && input.page_down) {
-				let _ = session.book_reader.turn_book_page(&mut session.book_reading, 1);
+		let (action, is_adjustable): (MenuAction, bool) = match self.session.modes.get_menu() {
+			Some(menu) => (menu.get_selected_action(), menu.is_selected_adjustable()),
+			None => (MenuAction::None, false),
+		};
+
+		if back_pressed {
+			self.run_menu_action(MenuAction::Back);
+		} else if is_adjustable && (left_pressed || right_pressed) {
+			self.adjust_setting(action, if left_pressed { -1 } else { 1 });
+		} else if confirm_pressed {
+			self.run_menu_action(action);
+		}
+
+		self.hold_jump(input.jump);
+		return true;
+	}
+
+	fn run_menu_action(&mut self, action: MenuAction) {
+		match action {
+			MenuAction::None => {}
+			MenuAction::Start | MenuAction::Retry => {
+				// a fresh start or another go after a game over gets all its hearts back
+				for player in self.session.players.iter_mut() {
+					*player = PlayerPersistentState::new_default();
+				}
+				self.pop_to_playing();
+				self.push_mode(GameMode::LevelTransition);
+			}
+			MenuAction::OpenLevelSelect => self.push_mode(GameMode::LevelSelect),
+			MenuAction::Resume => self.pop_mode(),
+			MenuAction::RestartLevel => {
+				self.pop_to_playing();
+				self.push_mode(GameMode::LevelTransition);
+			}
+			MenuAction::OpenSettings => self.push_mode(GameMode::Settings),
+			MenuAction::OpenAccessibility => self.push_mode(GameMode::Accessibility),
+			MenuAction::QuitToTitle => {
+				self.save_progress();
+				self.pop_to_playing();
+				self.push_mode(GameMode::Title);
+			}
+			MenuAction::QuitGame => {
+				self.save_progress();
+				self.session.is_quit_requested = true;
+			}
+			MenuAction::ToggleMusic => {
+				let settings: &mut Settings = &mut self.session.settings;
+				settings.is_background_music_enabled = !settings.is_background_music_enabled;
+				if settings.is_background_music_enabled {
+					let music_id: MusicId = self.state.level.music_id;
+					self.state.audio.play_music(music_id, true);
+					self.session.active_music_id = music_id;
+				} else {
+					self.state.audio.stop_music();
+					self.session.active_music_id = MusicId::None;
+				}
+			}
+			MenuAction::ChangeMusicVolume => {
+				// confirm steps up and wraps back to silent
+				let volume: u8 = self.session.settings.music_volume;
+				self.session.settings.music_volume = if volume >= VOLUME_LEVEL_MAX { 0 } else { volume + 1 };
+				self.state.audio.set_music_volume(self.session.settings.music_volume);
 			}
+			MenuAction::ToggleSoundEffects => self.session.settings.are_sound_effects_enabled = !self.session.settings.are_sound_effects_enabled,
+			MenuAction::ChangeSoundEffectsVolume => {
+				let volume: u8 = self.session.settings.sound_effects_volume;
+				self.session.settings.sound_effects_volume = if volume >= VOLUME_LEVEL_MAX { 0 } else { volume + 1 };
+				self.state.audio.set_sound_effects_volume(self.session.settings.sound_effects_volume);
+			}
+			MenuAction::ChangeLanguage => self.change_language(1),
+			MenuAction::ToggleScreenShake => self.session.settings.is_screen_shake_enabled = !self.session.settings.is_screen_shake_enabled,
+			MenuAction::ToggleShowTriggers => self.session.settings.show_triggers = !self.session.settings.show_triggers,
+			MenuAction::ToggleSpeedrunTimer => self.session.settings.show_speedrun_timer = !self.session.settings.show_speedrun_timer,
+			MenuAction::ToggleGhost => self.session.settings.show_ghost = !self.session.settings.show_ghost,
+			MenuAction::ChangeTextFont | MenuAction::ChangeTextSize => self.adjust_setting(action, 1),
+			MenuAction::ToggleHighContrast => {
+				let accessibility: &mut Accessibility = &mut self.session.settings.accessibility;
+				accessibility.is_high_contrast = !accessibility.is_high_contrast;
+			}
+			MenuAction::ToggleAssistMode => {
+				let assist: &mut AssistMode = &mut self.session.settings.accessibility.assist;
+				assist.is_enabled = !assist.is_enabled;
+			}
+			MenuAction::ChangeGameSpeed => {
+				// confirm steps up and wraps back to the slowest
+				let assist: &mut AssistMode = &mut self.session.settings.accessibility.assist;
+				assist.game_speed_percent = if assist.game_speed_percent >= 100 {
+					ASSIST_GAME_SPEED_PERCENT_MIN
+				} else {
+					assist.game_speed_percent + ASSIST_GAME_SPEED_PERCENT_STEP
+				};
+			}
+			MenuAction::ChangeExtraJumpFrames => {
+				let assist: &mut AssistMode = &mut self.session.settings.accessibility.assist;
+				assist.extra_jump_frames = if assist.extra_jump_frames >= ASSIST_EXTRA_JUMP_FRAMES_MAX {
+					0
+				} else {
+					assist.extra_jump_frames + ASSIST_EXTRA_JUMP_FRAMES_STEP
+				};
+			}
+			MenuAction::ToggleInfiniteHearts => {
+				let assist: &mut AssistMode = &mut self.session.settings.accessibility.assist;
+				assist.has_infinite_hit_points = !assist.has_infinite_hit_points;
+			}
+			MenuAction::ToggleHoldToFloat => {
+				let assist: &mut AssistMode = &mut self.session.settings.accessibility.assist;
+				assist.can_hold_to_float = !assist.can_hold_to_float;
+			}
+			MenuAction::Back => {
+				// the title screen and game over have nowhere to go back to
+				let mode: GameMode = self.session.modes.get_top();
+				if mode != GameMode::Title && mode != GameMode::GameOver {
+					self.pop_mode();
+				}
+			}
+		}
 
-			right_was_down = input.right;
-			left_was_down = input.left;
-			page_down_was_down = input.page_down;
-			page_up_was_down = input.page_up;
-			copy_was_down = input.copy;
-			escape_was_down = input.escape;
+		if let Some(menu) = self.session.modes.get_menu_mut() {
+			menu.refresh_settings(&self.session.settings);
+		}
+		return;
+	}
 
-			renderer.begin_frame();
-			renderer.draw_level(&state, &session);
-			renderer.draw_book_overlay(&session);
-			renderer.commit();
-			continue;
+	// left/right on a settings line; volumes and the assist amounts stop at the ends instead of wrapping
+	fn adjust_setting(&mut self, action: MenuAction, delta: i32) {
+		match action {
+			MenuAction::ChangeMusicVolume => {
+				let volume: i32 = self.session.settings.music_volume as i32 + delta;
+				self.session.settings.music_volume = volume.clamp(0, VOLUME_LEVEL_MAX as i32) as u8;
+				self.state.audio.set_music_volume(self.session.settings.music_volume);
+			}
+			MenuAction::ChangeSoundEffectsVolume => {
+				let volume: i32 = self.session.settings.sound_effects_volume as i32 + delta;
+				self.session.settings.sound_effects_volume = volume.clamp(0, VOLUME_LEVEL_MAX as i32) as u8;
+				self.state.audio.set_sound_effects_volume(self.session.settings.sound_effects_volume);
+			}
+			MenuAction::ChangeLanguage => self.change_language(delta),
+			MenuAction::ChangeTextFont => {
+				let accessibility: &mut Accessibility = &mut self.session.settings.accessibility;
+				accessibility.text_font = accessibility.text_font.cycle(delta);
+			}
+			MenuAction::ChangeTextSize => {
+				let accessibility: &mut Accessibility = &mut self.session.settings.accessibility;
+				accessibility.text_size = accessibility.text_size.cycle(delta);
+			}
+			MenuAction::ChangeGameSpeed => {
+				let assist: &mut AssistMode = &mut self.session.settings.accessibility.assist;
+				let percent: i32 = assist.game_speed_percent as i32 + delta * ASSIST_GAME_SPEED_PERCENT_STEP as i32;
+				assist.game_speed_percent = percent.clamp(ASSIST_GAME_SPEED_PERCENT_MIN as i32, 100) as u8;
+			}
+			MenuAction::ChangeExtraJumpFrames => {
+				let assist: &mut AssistMode = &mut self.session.settings.accessibility.assist;
+				let frames: i32 = assist.extra_jump_frames as i32 + delta * ASSIST_EXTRA_JUMP_FRAMES_STEP as i32;
+				assist.extra_jump_frames = frames.clamp(0, ASSIST_EXTRA_JUMP_FRAMES_MAX as i32) as u8;
+			}
+			// the toggles flip whichever way
+			_ => {
+				self.run_menu_action(action);
+				return;
+			}
 		}
 
-		if read_pressed {
-			read_was_down = true;
+		if let Some(menu) = self.session.modes.get_menu_mut() {
+			menu.refresh_settings(&self.session.settings);
+		}
+		return;
+	}
 
-			let book_id: BookId = 100;
-			let book_slug: BookSlug = "tom_sawyer";
+	// steps through the messages.<code>.txt files; the new table is swapped in straight away so
+	// the menu itself changes language
+	fn change_language(&mut self, delta: i32) {
+		let languages: Vec<String> = MessageTable::get_available_languages();
+		if languages.is_empty() {
+			return;
+		}
 
-			let Some(_) = session.inventory.get_book(book_id) else {
-				debugln!("tom_sawyer not in inventory");
-				continue;
-			};
+		let current: &str = self.session.settings.language_code.as_str();
+		let index: i32 = languages.iter().position(|code| code == current).unwrap_or(0) as i32;
+		let next: &String = &languages[(index + delta).rem_euclid(languages.len() as i32) as usize];
+		if next == current {
+			return;
+		}
 
-			// open the ui at page 0 (or the saved page later)
-			let result = session.book_reader.open_book(&mut session.book_reading, book_slug, 0);
-			if let Err(e) = result {
-				debugln!("open book failed: {}", e);
+		match MessageTable::load(next.as_str()) {
+			Ok(table) => {
+				self.session.message_table = table;
+				self.session.settings.language_code = next.clone();
+			}
+			Err(e) => {
+				debugln!("message table load failed: {}", e);
 			}
-			continue;
 		}
+		return;
+	}
 
-		// if triggers requested a level change last frame, do it now
-		if let Some(next_level_name) = session.pending_level_name.take() {
-			session.transition_to_level(&mut state, &next_level_name, load_level_from_name);
-			renderer.draw_background_by_id(state.level.background_id);
+	// the arrows move around the grid, jump or enter opens the selected book, escape or the
+	// inventory key closes the screen
+	fn update_inventory(&mut self, input: &InputState) -> bool {
+		let up_pressed: bool = input.up && !self.up_was_down;
+		let down_pressed: bool = input.down && !self.down_was_down;
+		let left_pressed: bool = input.left && !self.left_was_down;
+		let right_pressed: bool = input.right && !self.right_was_down;
+		let confirm_pressed: bool = (input.jump && !self.action_was_down) || (input.pause && !self.pause_was_down);
+		let back_pressed: bool = (input.escape && !self.escape_was_down) || (input.inventory && !self.inventory_was_down);
+
+		self.up_was_down = input.up;
+		self.down_was_down = input.down;
+		self.left_was_down = input.left;
+		self.right_was_down = input.right;
+		self.action_was_down = input.jump;
+		self.pause_was_down = input.pause;
+		self.escape_was_down = input.escape;
+		self.inventory_was_down = input.inventory;
+
+		let slot_count: usize = self.session.inventory.get_slot_count();
+		let column_delta: i32 = if left_pressed { -1 } else if right_pressed { 1 } else { 0 };
+		let row_delta: i32 = if up_pressed { -1 } else if down_pressed { 1 } else { 0 };
+		if column_delta != 0 || row_delta != 0 {
+			self.session.inventory_cursor.move_selection(column_delta, row_delta, slot_count);
 		}
 
-		let Some(player_id) = state.try_get_player_id() else {
-			// no player yet; still tick/render so you can see what's going on
-			state.tick = state.tick.wrapping_add(1);
-			renderer.begin_frame();
-			renderer.draw_level(&state, &session);
-			renderer.commit();
-			continue;
-		};
+		if back_pressed {
+			self.pop_mode();
+		} else if confirm_pressed {
+			if let Some(InventorySlot::Book(book_index)) = self.session.inventory.get_slot(self.session.inventory_cursor.selected) {
+				let book_id: BookId = self.session.inventory.books[book_index].book_id;
+				self.open_book(book_id);
+			}
+		}
 
-		// left/right movement (held)
-		let desired_x: f32 = if input.left && !input.right {
-			-2.0
-		} else if input.right && !input.left {
-			2.0
-		} else {
-			0.0
-		};
+		self.hold_jump(input.jump);
+		return true;
+	}
+
+	// the world index as a grid: arrows pick a level, confirm plays it if it's unlocked
+	fn update_level_select(&mut self, input: &InputState) -> bool {
+		let up_pressed: bool = input.up && !self.up_was_down;
+		let down_pressed: bool = input.down && !self.down_was_down;
+		let left_pressed: bool = input.left && !self.left_was_down;
+		let right_pressed: bool = input.right && !self.right_was_down;
+		let confirm_pressed: bool = (input.jump && !self.action_was_down) || (input.pause && !self.pause_was_down);
+		let back_pressed: bool = input.escape && !self.escape_was_down;
+
+		self.up_was_down = input.up;
+		self.down_was_down = input.down;
+		self.left_was_down = input.left;
+		self.right_was_down = input.right;
+		self.action_was_down = input.jump;
+		self.pause_was_down = input.pause;
+		self.escape_was_down = input.escape;
 
-		if let Some(velocity) = state.velocities.get_mut(player_id) {
-			velocity.set_x(desired_x);
+		let column_delta: i32 = if left_pressed { -1 } else if right_pressed { 1 } else { 0 };
+		let row_delta: i32 = if up_pressed { -1 } else if down_pressed { 1 } else { 0 };
+		if column_delta != 0 || row_delta != 0 {
+			self.session.level_select_cursor.move_selection(column_delta, row_delta, &self.session.world_index);
 		}
 
-		// --- edge detection ---
-		let jump_down: bool = input.jump;
-		let mut jump_pressed: bool = false;
-		let mut jump_released: bool = false;
+		if back_pressed {
+			self.pop_mode();
+		} else if confirm_pressed {
+			let selected: Option<&WorldLevelEntry> = self.session.level_select_cursor.get_selected_level(&self.session.world_index);
+			let level_name: Option<String> = selected
+				.filter(|l| self.session.world_index.is_unlocked(l.key, &self.session.progress))
+				.map(|l| l.key.get_level_name());
+			if level_name.is_some() {
+				// a level picked from the map starts with full hearts like a new game
+				self.session.pending_level_name = level_name;
+				for player in self.session.players.iter_mut() {
+					*player = PlayerPersistentState::new_default();
+				}
+				self.pop_to_playing();
+				self.push_mode(GameMode::LevelTransition);
+			}
+		}
+
+		self.hold_jump(input.jump);
+		return true;
+	}
+
+	// the level's score until confirm or escape; closing it lets the pending level load
+	fn update_results(&mut self, input: &InputState) -> bool {
+		let confirm_pressed: bool = (input.jump && !self.action_was_down) || (input.pause && !self.pause_was_down);
+		let back_pressed: bool = input.escape && !self.escape_was_down;
 
-		if let Some(js) = state.jump_states.get_mut(player_id) {
-			jump_pressed = jump_down && !js.jump_was_down;
-			jump_released = !jump_down && js.jump_was_down;
-			js.jump_was_down = jump_down;
+		self.action_was_down = input.jump;
+		self.pause_was_down = input.pause;
+		self.escape_was_down = input.escape;
+
+		if confirm_pressed || back_pressed {
+			self.pop_mode();
 		}
 
-		// for now, "action" == jump button
-		let presses = TriggerPresses {
-			action_pressed: input.jump && !action_was_down, // or a dedicated action button later
-			up_pressed: input.up && !up_was_down,
-			down_pressed: input.down && !down_was_down,
-			left_pressed: input.left && !left_was_down,
-			right_pressed: input.right && !right_was_down,
+		self.hold_jump(input.jump);
+		return true;
+	}
+
+	// at the page the player last left it on
+	fn open_book(&mut self, book_id: BookId) {
+		let Some(book) = self.session.inventory.get_book(book_id) else {
+			debugln!("book {} not in inventory", book_id);
+			return;
 		};
+		let start_page: u16 = book.current_page;
 
-		action_was_down = input.jump;
-		up_was_down = input.up;
-		down_was_down = input.down;
-		left_was_down = input.left;
-		right_was_down = input.right;
-		inventory_was_down = input.inventory;
-		read_was_down = input.read;
-		page_down_was_down = input.page_down;
-		page_up_was_down = input.page_up;
-		copy_was_down = input.copy;
+		let Some(book_slug) = self.session.pickup_map.get_slug(PICKUP_MAP_BOOK_PREFIX, book_id) else {
+			debugln!("book {} not in the pickup map", book_id);
+			return;
+		};
+		let book_slug: String = String::from(book_slug);
 
-		// --- triggers run before gameplay consumes jump ---
-		let mut jump_consumed_by_triggers: bool = false;
+		let session: &mut Session = &mut self.session;
+		match session.book_reader.open_book(&mut session.book_reading, book_slug.as_str(), start_page) {
+			Ok(_) => self.push_mode(GameMode::Book),
+			Err(e) => {
+				debugln!("open book failed: {}", e);
+			}
+		}
+		return;
+	}
+
+	// left/right or page up/down turn pages, escape closes the book
+	fn update_book(&mut self, input: &InputState) -> bool {
+		let session: &mut Session = &mut self.session;
+
+		if (!self.left_was_down && input.left) || (!self.page_up_was_down && input.page_up) {
+			let _ = session.book_reader.turn_book_page(&mut session.book_reading, -1);
+		}
 
-		if triggers::handle_message_triggers(&session, &mut state, presses) {
-			jump_consumed_by_triggers = true;
+		if (!self.right_was_down && input.right) || (!self.page_down_was_down && input.page_down) {
+			let _ = session.book_reader.turn_book_page(&mut session.book_reading, 1);
 		}
 
-		triggers::handle_level_exit_triggers(&mut session, &mut state, presses);
+		let escape_pressed: bool = !self.escape_was_down && input.escape;
 
-		if triggers::handle_pickup_triggers(&mut session, &mut state, presses) {
-			jump_consumed_by_triggers = true;
+		self.right_was_down = input.right;
+		self.left_was_down = input.left;
+		self.page_down_was_down = input.page_down;
+		self.page_up_was_down = input.page_up;
+		self.escape_was_down = input.escape;
+
+		if escape_pressed {
+			self.pop_mode();
+		}
+		return true;
+	}
+
+	// collected pickups, opened doors and the inventory; after every level change and on the way out
+	fn save_progress(&mut self) {
+		if let Err(e) = self.session.save_progress() {
+			debugln!("progress save failed: {}", e);
 		}
+		return;
+	}
+
+	// the clock starts over as the level opens, racing the level's best run
+	fn start_speedrun_level(&mut self) {
+		let ghost: Option<Ghost> = self.session.get_current_level_key().and_then(Ghost::load);
+		self.session.speedrun.start_level(ghost);
+		return;
+	}
 
-		// --- gameplay jump logic (only if not consumed) ---
-		if jump_pressed && !jump_consumed_by_triggers {
-			if let Some(jump_state) = state.jump_states.get_mut(player_id) {
-				jump_state.jump_buffer_frames_left = session.settings.jump_buffer_frames_max;
+	// a level exit fired, or a menu asked for the level to start over: the screen closes, the level
+	// loads on a worker thread, and the screen opens on it, or on the old level if the load failed
+	fn update_level_transition(&mut self, timer: &PcTimer) -> bool {
+		let Some(mut transition) = self.session.transition else {
+			self.pop_mode();
+			return true;
+		};
+
+		let step_count: u32 = self.fixed_step.advance(timer);
+		for _ in 0..step_count {
+			if transition.tick() {
+				self.pop_mode();
+				return true;
 			}
 		}
 
-		if jump_released {
-			if let Some(velocity) = state.velocities.get_mut(player_id) {
-				if velocity.y < 0.0 {
-					velocity.y *= session.settings.jump_cut_multiplier;
+		if transition.phase == TransitionPhase::Loading {
+			match &self.level_preload {
+				None => {
+					let level_name: Option<String> = if transition.is_restart {
+						self.session.current_level_name.clone()
+					} else {
+						self.session.pending_level_name.take()
+					};
+					match level_name {
+						Some(level_name) => self.level_preload = Some(LevelPreload::start(&level_name)),
+						None => transition.start_opening(),
+					}
+				}
+				Some(preload) => {
+					if let Some(loaded) = preload.poll() {
+						let level_name: String = preload.level_name.clone();
+						self.level_preload = None;
+						if self.session.apply_loaded_level(&mut self.state, &level_name, loaded, transition.is_restart) {
+							self.save_progress();
+						} else {
+							debugln!("level transition failed; staying in the current level");
+						}
+						self.start_speedrun_level();
+
+						// the opening shows the camera already on the player
+						if self.state.camera.is_snap_pending() {
+							camera::update_camera(&mut self.state, &self.session);
+						}
+						transition.start_opening();
+					}
 				}
 			}
-			if let Some(jump_state) = state.jump_states.get_mut(player_id) {
-				jump_state.jump_buffer_frames_left = 0;
+		}
+
+		self.session.transition = Some(transition);
+		return true;
+	}
+
+	// as many fixed steps as are due, unless something on screen holds up play
+	fn update_playing(&mut self, input: &InputState, timer: &PcTimer) -> bool {
+		let inventory_pressed: bool = input.inventory && !self.inventory_was_down;
+		let read_pressed: bool = input.read && !self.read_was_down;
+		let pause_pressed: bool = input.pause && !self.pause_was_down;
+		let mut escape_pressed: bool = input.escape && !self.escape_was_down;
+		self.pause_was_down = input.pause;
+		self.escape_was_down = input.escape;
+
+		// assist mode slows the game by stepping it less often; each step stays the same
+		let game_speed_percent: u64 = self.session.settings.get_game_speed_percent() as u64;
+		self.fixed_step.set_hz(timer, SIMULATION_HZ * game_speed_percent / 100);
+
+		// a blocking message holds the simulation like the book does: jump types out the rest of
+		// the text or moves to the next message, escape closes it
+		if self.session.message_box.is_blocking() {
+			if !self.action_was_down && input.jump {
+				self.session.message_box.advance();
+			}
+			if escape_pressed {
+				self.session.message_box.dismiss();
+			}
+
+			let step_count: u32 = self.fixed_step.advance(timer);
+			for _ in 0..step_count {
+				self.session.message_box.tick();
 			}
+
+			self.action_was_down = input.jump;
+			self.hold_jump(input.jump);
+			return true;
+		}
+
+		// escape closes a message that doesn't hold up play, and pauses otherwise
+		if escape_pressed && self.session.message_box.is_open() {
+			self.session.message_box.dismiss();
+			escape_pressed = false;
+		}
+
+		if pause_pressed || escape_pressed {
+			self.push_mode(GameMode::Paused);
+			return true;
 		}
 
-		state.tick = state.tick.wrapping_add(1);
+		if inventory_pressed {
+			self.inventory_was_down = true;
+			self.push_mode(GameMode::Inventory);
+			return true;
+		}
+
+		if read_pressed {
+			self.read_was_down = true;
 
-		physics::movement::patrol(&mut state);
-		physics::gravity::apply(&mut state, &session);
-		physics::movement::move_and_collide(&mut state, &session);
+			// the first book picked up; the inventory screen opens the others
+			let Some(book_id) = self.session.inventory.books.first().map(|book| book.book_id) else {
+				debugln!("no books in inventory");
+				return false;
+			};
+			self.open_book(book_id);
+			return false;
+		}
+
+		// a level exit fired last frame: its results first, then the level change
+		if self.session.results.is_some() {
+			self.push_mode(GameMode::Results);
+			return true;
+		}
 
-		state.tick_enemy_deaths();
+		// if triggers requested a level change last frame, do it now
+		if self.session.pending_level_name.is_some() {
+			self.push_mode(GameMode::LevelTransition);
+			return true;
+		}
+
+		let session: &mut Session = &mut self.session;
+		let state: &mut State = &mut self.state;
+
+		// a new level or a respawn: put the camera on the player before the first frame is drawn
+		if state.camera.is_snap_pending() {
+			camera::update_camera(state, session);
+		}
+
+		let step_count: u32 = self.fixed_step.advance(timer);
+		for _ in 0..step_count {
+			// a level exit fired; switch levels before simulating further
+			if session.pending_level_name.is_some() {
+				break;
+			}
 
-		renderer.begin_frame();
-		renderer.draw_level(&state, &session);
-		renderer.commit();
+			// for now, "action" == jump button
+			let presses = TriggerPresses {
+				action_pressed: input.jump && !self.action_was_down, // or a dedicated action button later
+				up_pressed: input.up && !self.up_was_down,
+				down_pressed: input.down && !self.down_was_down,
+				left_pressed: input.left && !self.left_was_down,
+				right_pressed: input.right && !self.right_was_down,
+			};
+
+			self.action_was_down = input.jump;
+			self.up_was_down = input.up;
+			self.down_was_down = input.down;
+			self.left_was_down = input.left;
+			self.right_was_down = input.right;
+			self.inventory_was_down = input.inventory;
+			self.read_was_down = input.read;
+			self.page_down_was_down = input.page_down;
+			self.page_up_was_down = input.page_up;
+
+			let play_input: PlayInput = PlayInput {
+				left: input.left,
+				right: input.right,
+				jump: input.jump,
+			};
+			run_play_step(session, state, play_input, presses);
+		}
+
+		state.render_alpha = self.fixed_step.get_alpha();
+
+		// out of hearts
+		if let Some(player_id) = self.state.try_get_player_id() {
+			if self.state.hit_points.get(player_id).copied().unwrap_or(1) == 0 {
+				self.push_mode(GameMode::GameOver);
+			}
+		}
+		return step_count > 0;
 	}
 }
 
-fn print_inventory(inv: &Inventory) {
-	debugln!("--- inventory ---");
-	debugln!("coins={}", inv.coins);
+// with every button up and a SoftwareRenderer instead of the window; exits 1 on a golden mismatch
+fn run_screenshot(level_path: &str, frame: u32, size: (u32, u32), screenshot_path: &Path, golden_path: Option<&Path>) -> i32 {
+	let renderer: SoftwareRenderer = match render_level_frame(Path::new(level_path), frame, size) {
+		Ok(renderer) => renderer,
+		Err(e) => {
+			eprintln!("{}", e);
+			return 2;
+		}
+	};
 
-	debugln!("keys={}", inv.keys.len());
-	for k in &inv.keys {
-		debugln!(" key id={} used={}", k.key_id, k.is_used);
+	if let Err(e) = renderer.save_screenshot(screenshot_path) {
+		eprintln!("{}", e);
+		return 2;
 	}
+	println!("wrote frame {} to {}", frame, screenshot_path.display());
+
+	let Some(golden_path) = golden_path else {
+		return 0;
+	};
 
-	debugln!("books={}", inv.books.len());
-	for b in &inv.books {
-		debugln!(" book id={} page={}/{}", b.book_id, b.current_page, b.total_pages);
+	if let Err(e) = compare_with_golden(renderer.framebuffer.image(), golden_path) {
+		eprintln!("{}", e);
+		return 1;
 	}
 
-	return;
+	println!("matches {}", golden_path.display());
+	return 0;
+}
+
+fn parse_screenshot_size(value: &str) -> Option<(u32, u32)> {
+	let (width, height) = value.split_once('x')?;
+	let width: u32 = width.parse().ok()?;
+	let height: u32 = height.parse().ok()?;
+	if width == 0 || height == 0 {
+		return None;
+	}
+	return Some((width, height));
 }
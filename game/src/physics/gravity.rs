@@ -10,5 +10,13 @@ pub fn apply(state: &mut State, session: &Session) {
 		}
 
 		vel.y += session.settings.gravity * grav as f32;
+
+		// assist mode: a player holding jump drifts down instead of dropping
+		if let Some(fall_speed_max) = session.settings.get_float_fall_speed_max() {
+			let is_jump_down: bool = state.jump_states.get(id).map(|jump_state| jump_state.jump_was_down).unwrap_or(false);
+			if is_jump_down && vel.y > fall_speed_max {
+				vel.y = fall_speed_max;
+			}
+		}
 	}
 }
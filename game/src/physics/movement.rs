@@ -10,7 +10,10 @@ use crate::{
 	physics::collision::{HitSide, classify_aabb_hit_side, resolve_ceiling_collision, resolve_floor_collision, resolve_wall_collision},
 	platform::audio::SfxId,
 	runtime::{
+		animation::clip::AnimState,
+		camera::{STOMP_SHAKE_FRAMES, STOMP_SHAKE_WORLD},
 		level::Level,
+		player_physics,
 		session::Session,
 		state::{DeathAnim, EntityId, EntityKind, State},
 	},
@@ -218,6 +221,13 @@ pub fn move_and_collide(state: &mut State, session: &Session) {
 
 					if damage >= hit_points {
 						state.start_enemy_death(target_id, DeathAnim::SlimeFlatten);
+						if kind == EntityKind::Player {
+							state.score.add_stomp(chain);
+						}
+
+						if session.settings.is_screen_shake_enabled {
+							state.camera.add_shake(STOMP_SHAKE_WORLD, STOMP_SHAKE_FRAMES);
+						}
 
 						if session.settings.are_sound_effects_enabled {
 							state.audio.play_sfx(SfxId::Stomp);
@@ -228,12 +238,14 @@ pub fn move_and_collide(state: &mut State, session: &Session) {
 						}
 					} else {
 						state.hit_points.set(target_id, hit_points - damage);
+						state.play_one_shot_animation(target_id, AnimState::Hurt);
 					}
 				}
-				CollisionOutcome::Damaged { source: _ } => {
+				CollisionOutcome::Damaged { source } => {
 					if is_player {
 						//TODO: Calc Damage kill player if needed.
 						debugln!("Damaged");
+						state.play_one_shot_animation(source, AnimState::Attack);
 						state.kill_player(session, player_id);
 					}
 				}
@@ -262,23 +274,10 @@ pub fn move_and_collide(state: &mut State, session: &Session) {
 				let on_wall_right = state.on_wall_right(entity_id);
 				let grounded_now: bool = state.is_grounded_now(entity_id);
 
-				if grounded_now {
-					if state.camera_baseline_max_bottom_world.is_none() {
-						let (_half_width, half_height) = state.get_entity_half_values(entity_id);
-						if let Some(pos) = state.positions.get(entity_id) {
-							let tile_height_world: f32 = state.level.tile_height as f32;
-							let pad_world: f32 = session.settings.camera_bottom_padding_tiles as f32 * tile_height_world;
-
-							let ground_world_y: f32 = pos.y + half_height;
-							state.camera_baseline_max_bottom_world = Some(ground_world_y + pad_world);
-						}
-					}
-				}
-
 				if let Some(jump_state) = state.jump_states.get_mut(entity_id) {
 					// coyote update (your existing code)
 					if grounded_now {
-						jump_state.coyote_frames_left = session.settings.coyote_frames_max;
+						jump_state.coyote_frames_left = session.settings.get_coyote_frames_max();
 					} else if jump_state.coyote_frames_left > 0 {
 						jump_state.coyote_frames_left -= 1;
 					}
@@ -306,6 +305,11 @@ pub fn move_and_collide(state: &mut State, session: &Session) {
 					}
 				}
 
+				// a stomp chain lasts until the player is back on the ground
+				if grounded_now {
+					state.stomp_chains.set(entity_id, 0);
+				}
+
 				// tick respawn cooldown every frame
 				if let Some(respawn_state) = state.respawn_states.get_mut(entity_id) {
 					if respawn_state.respawn_cooldown_frames > 0 {
@@ -399,7 +403,7 @@ pub fn try_jump(state: &mut State, session: &Session, entity_id: EntityId) -> bo
 
 		// wall jump push (only if not grounded/coyote jump)
 		if !grounded && !coyote_ok {
-			let wall_push: f32 = 2.5;
+			let wall_push: f32 = player_physics::WALL_JUMP_PUSH;
 			if on_left {
 				velocity.x = wall_push;
 			} else if on_right {
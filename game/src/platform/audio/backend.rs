@@ -33,11 +33,9 @@ pub trait AudioEngine {
 	fn update(&mut self);
 	fn stop(&mut self, handle: AudioHandle);
 	fn stop_music(&mut self);
-}
-
-#[derive(Copy, Clone, PartialEq, Eq)]
-pub enum LocomotionAnim {
-	Walk,
-	Run,
-	Death,
+	fn pause_music(&mut self);
+	fn resume_music(&mut self);
+	// 0 is silent, VOLUME_LEVEL_MAX is full
+	fn set_music_volume(&mut self, level: u8);
+	fn set_sound_effects_volume(&mut self, level: u8);
 }
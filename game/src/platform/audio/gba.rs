@@ -27,4 +27,12 @@ impl AudioEngine for GbaAudio {
 	fn stop(&mut self, _handle: AudioHandle) {}
 
 	fn stop_music(&mut self) {}
+
+	fn pause_music(&mut self) {}
+
+	fn resume_music(&mut self) {}
+
+	fn set_music_volume(&mut self, _level: u8) {}
+
+	fn set_sound_effects_volume(&mut self, _level: u8) {}
 }
@@ -27,6 +27,10 @@ impl AudioEngine for NullAudio {
 	fn play_music(&mut self, _id: MusicId, _looped: bool) {}
 
 	fn stop_music(&mut self) {}
+	fn pause_music(&mut self) {}
+	fn resume_music(&mut self) {}
+	fn set_music_volume(&mut self, _level: u8) {}
+	fn set_sound_effects_volume(&mut self, _level: u8) {}
 	fn stop(&mut self, _handle: AudioHandle) {}
 	fn update(&mut self) {}
 }
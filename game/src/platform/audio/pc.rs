@@ -3,7 +3,7 @@
 use crate::{
 	debugln,
 	platform::audio::{AudioEngine, SfxId, backend::AudioHandle},
-	runtime::{assets::get_audio_root, music::MusicId},
+	runtime::{assets::get_audio_root, music::MusicId, settings::VOLUME_LEVEL_MAX},
 };
 use sdl2::mixer::{self, Channel, Chunk, Music};
 use std::{collections::HashMap, path::PathBuf};
@@ -26,10 +26,17 @@ impl PcAudio {
 		self.sfx.insert(id, chunk);
 	}
 
+	// a level whose music is missing plays in silence rather than taking the game down with it
 	fn load_music(&mut self, id: MusicId, file: &str) {
 		let path = Self::asset_path(file);
-		let music = Music::from_file(&path).unwrap_or_else(|_| panic!("missing music file: {}", path.display()));
-		self.music.insert(id, music);
+		match Music::from_file(&path) {
+			Ok(music) => {
+				self.music.insert(id, music);
+			}
+			Err(e) => {
+				debugln!("music load failed: {}: {}", path.display(), e);
+			}
+		}
 	}
 
 	fn ensure_music_loaded(&mut self, id: MusicId) {
@@ -92,6 +99,22 @@ impl AudioEngine for PcAudio {
 		mixer::Music::halt();
 	}
 
+	fn pause_music(&mut self) {
+		mixer::Music::pause();
+	}
+
+	fn resume_music(&mut self) {
+		mixer::Music::resume();
+	}
+
+	fn set_music_volume(&mut self, level: u8) {
+		mixer::Music::set_volume(to_mixer_volume(level));
+	}
+
+	fn set_sound_effects_volume(&mut self, level: u8) {
+		let _ = Channel::all().set_volume(to_mixer_volume(level));
+	}
+
 	fn play_sfx(&mut self, id: SfxId) -> Option<AudioHandle> {
 		let chunk = self.sfx.get(&id)?;
 		let channel = Channel::all().play(chunk, 0).ok()?;
@@ -128,6 +151,15 @@ impl AudioEngine for PcAudio {
 	}
 }
 
+fn to_mixer_volume(level: u8) -> i32 {
+	return level.min(VOLUME_LEVEL_MAX) as i32 * mixer::MAX_VOLUME / VOLUME_LEVEL_MAX as i32;
+}
+
+// where a level's music lives, for the level preload to read it ahead
+pub fn get_music_file_path(id: MusicId) -> Option<PathBuf> {
+	return music_path(id).map(PcAudio::asset_path);
+}
+
 fn music_path(id: MusicId) -> Option<&'static str> {
 	return match id {
 		MusicId::None => None,
@@ -9,6 +9,8 @@ pub struct InputState {
 	pub inventory: bool,
 	pub read: bool,
 	pub escape: bool,
+	// start on a pad, enter on a keyboard
+	pub pause: bool,
 	pub page_up: bool,
 	pub page_down: bool,
 	pub copy: bool,
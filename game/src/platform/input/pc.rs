@@ -35,6 +35,7 @@ pub fn poll(event_pump: &mut EventPump) -> InputState {
 	input.page_up = keys.is_scancode_pressed(Scancode::PageUp);
 	input.page_down = keys.is_scancode_pressed(Scancode::PageDown);
 	input.copy = keys.is_scancode_pressed(Scancode::C) && ctrl_down;
+	input.pause = keys.is_scancode_pressed(Scancode::Return) || keys.is_scancode_pressed(Scancode::KpEnter);
 	input.escape = keys.is_scancode_pressed(Scancode::Escape);
 
 	return input;
@@ -5,7 +5,22 @@ extern crate alloc;
 use alloc::string::String;
 
 #[cfg(feature = "pc")]
-use std::{fs, path::Path};
+use crate::{
+	debugln,
+	platform::{
+		audio::pc::get_music_file_path,
+		render::background::{BackgroundId, BackgroundLibrary},
+	},
+	runtime::assets::get_gfx_root,
+};
+
+#[cfg(feature = "pc")]
+use std::{
+	fs,
+	path::Path,
+	sync::mpsc::{self, Receiver, TryRecvError},
+	thread,
+};
 
 use crate::runtime::level::Level;
 
@@ -16,29 +31,9 @@ struct EmbeddedLevel {
 	bytes: &'static [u8],
 }
 
+// EMBEDDED_LEVELS, one entry per level in worlds/worlds.txt; written by build.rs
 #[cfg(feature = "gba")]
-const EMBEDDED_LEVELS: &[EmbeddedLevel] = &[
-	EmbeddedLevel {
-		world_id: 0,
-		level_id: 1,
-		bytes: include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/../worlds/00/01.lvlb")),
-	},
-	EmbeddedLevel {
-		world_id: 0,
-		level_id: 2,
-		bytes: include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/../worlds/00/02.lvlb")),
-	},
-	EmbeddedLevel {
-		world_id: 1,
-		level_id: 1,
-		bytes: include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/../worlds/01/01.lvlb")),
-	},
-	EmbeddedLevel {
-		world_id: 2,
-		level_id: 1,
-		bytes: include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/../worlds/02/01.lvlb")),
-	},
-];
+include!(concat!(env!("OUT_DIR"), "/embedded_levels.rs"));
 
 // expects something like ".../worlds/01/01.lvlb" or "worlds/01/01.lvlb"
 #[cfg(feature = "gba")]
@@ -75,17 +70,83 @@ fn parse_world_level_from_path(level_name: &str) -> Option<(u8, u8)> {
 }
 
 #[cfg(feature = "pc")]
-pub fn load_level_from_file(path: &Path) -> Level {
-	let bytes = fs::read(path).expect("failed to read level file");
-	return Level::load_binary(&bytes).unwrap();
+pub fn load_level_from_file(path: &Path) -> Result<Level, String> {
+	let bytes: Vec<u8> = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+	return Level::load_binary(&bytes).map_err(|e| format!("{}: {}", path.display(), e));
 }
 
+// the header's name, which the world index shows for the level
 #[cfg(feature = "pc")]
-pub fn load_level_from_name(level_name: &str) -> Result<crate::runtime::level::Level, String> {
-	use std::path::Path;
-	let path = Path::new(level_name);
-	let level = crate::platform::level_loader::load_level_from_file(path);
-	return Ok(level);
+pub fn read_level_name_from_file(path: &Path) -> Result<String, String> {
+	let bytes: Vec<u8> = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+	return Level::read_name(&bytes).map_err(|e| format!("{}: {}", path.display(), e));
+}
+
+#[cfg(feature = "pc")]
+pub fn load_level_from_name(level_name: &str) -> Result<Level, String> {
+	return load_level_from_file(Path::new(level_name));
+}
+
+// a level being read and parsed on a worker thread while the transition covers the screen. sdl
+// textures and music belong to the threads that draw and play them, so the worker can't make
+// those; it reads the background images and the music file ahead instead, so making them on the
+// owning threads afterwards finds the files already in memory
+#[cfg(feature = "pc")]
+pub struct LevelPreload {
+	pub level_name: String,
+	receiver: Receiver<Result<Level, String>>,
+}
+
+#[cfg(feature = "pc")]
+impl LevelPreload {
+	pub fn start(level_name: &str) -> LevelPreload {
+		let (sender, receiver) = mpsc::channel::<Result<Level, String>>();
+		let worker_level_name: String = String::from(level_name);
+
+		// if the thread can't start, or dies on a broken file, the dropped sender shows up in poll()
+		let spawned = thread::Builder::new().name("level preload".to_string()).spawn(move || {
+			let result: Result<Level, String> = load_level_from_name(&worker_level_name);
+			if let Ok(level) = &result {
+				read_level_assets_ahead(level);
+			}
+			let _ = sender.send(result);
+		});
+		if let Err(e) = spawned {
+			debugln!("level preload thread failed to start: {}", e);
+		}
+
+		return LevelPreload {
+			level_name: String::from(level_name),
+			receiver,
+		};
+	}
+
+	// None while the worker is still busy
+	pub fn poll(&self) -> Option<Result<Level, String>> {
+		return match self.receiver.try_recv() {
+			Ok(result) => Some(result),
+			Err(TryRecvError::Empty) => None,
+			Err(TryRecvError::Disconnected) => Some(Err(format!("{}: the level preload stopped", self.level_name))),
+		};
+	}
+}
+
+#[cfg(feature = "pc")]
+fn read_level_assets_ahead(level: &Level) {
+	if level.background_id != BackgroundId::NONE {
+		if let Ok(backgrounds) = BackgroundLibrary::load() {
+			if let Some(def) = backgrounds.get(level.background_id) {
+				for layer in &def.layers {
+					let _ = fs::read(get_gfx_root().join("pc").join(layer.image.as_str()));
+				}
+			}
+		}
+	}
+
+	if let Some(path) = get_music_file_path(level.music_id) {
+		let _ = fs::read(path);
+	}
+	return;
 }
 
 #[cfg(feature = "gba")]
@@ -96,6 +157,13 @@ pub fn load_level_from_name(level_name: &str) -> Result<Level, String> {
 	return Ok(level);
 }
 
+#[cfg(feature = "gba")]
+pub fn read_level_name(level_name: &str) -> Result<String, String> {
+	let (world_id, level_id) = parse_world_level_from_path(level_name).ok_or_else(|| String::from("level path must contain worlds/WW/LL.lvlb"))?;
+	let bytes = find_embedded_level(world_id, level_id).ok_or_else(|| String::from("level not embedded in ROM"))?;
+	return Level::read_name(bytes);
+}
+
 #[cfg(feature = "gba")]
 fn find_embedded_level(world_id: u8, level_id: u8) -> Option<&'static [u8]> {
 	for e in EMBEDDED_LEVELS {
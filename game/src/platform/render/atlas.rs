@@ -0,0 +1,239 @@
+use crate::{
+	platform::render::software::{RgbaImage, load_png},
+	runtime::assets::get_gfx_root,
+};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	time::SystemTime,
+};
+
+// pages are square; 4096 is the smallest max texture size we still expect from a desktop gpu
+pub const ATLAS_PAGE_SIZE_PIXELS: u32 = 4096;
+
+// one empty pixel between sheets so scaled draws never sample a neighbour
+const ATLAS_PADDING_PIXELS: u32 = 1;
+
+// a sheet registered with a TextureAtlas; stays valid across rebuilds and hot reloads
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SheetHandle(pub u16);
+
+// where a sheet ended up: page index plus its rect on that page
+#[derive(Copy, Clone, Debug)]
+pub struct AtlasRegion {
+	pub page: u16,
+	pub left: i32,
+	pub top: i32,
+	pub width: u32,
+	pub height: u32,
+}
+
+struct AtlasSheet {
+	// relative to the platform gfx root, as written in the data files
+	name: String,
+	path: PathBuf,
+	modified: Option<SystemTime>,
+	region: Option<AtlasRegion>,
+}
+
+// packs sprite sheets and icons into a few large pages at startup. renderers look regions up by
+// handle and turn the pages into whatever they draw from (sdl textures, or plain images)
+pub struct TextureAtlas {
+	pub pages: Vec<RgbaImage>,
+	sheets: Vec<AtlasSheet>,
+	is_dirty: bool,
+	// bumps every build so renderers know to re-upload their copies of the pages
+	generation: u32,
+}
+
+impl TextureAtlas {
+	pub fn new() -> TextureAtlas {
+		return TextureAtlas {
+			pages: Vec::new(),
+			sheets: Vec::new(),
+			is_dirty: false,
+			generation: 0,
+		};
+	}
+
+	// registering the same name twice returns the same handle; the sheet is packed on the next build
+	pub fn add_sheet(&mut self, name: &str) -> SheetHandle {
+		if let Some(handle) = self.find_sheet(name) {
+			return handle;
+		}
+
+		self.sheets.push(AtlasSheet {
+			name: String::from(name),
+			path: get_gfx_root().join("pc").join(name),
+			modified: None,
+			region: None,
+		});
+		self.is_dirty = true;
+		return SheetHandle((self.sheets.len() - 1) as u16);
+	}
+
+	pub fn find_sheet(&self, name: &str) -> Option<SheetHandle> {
+		return self.sheets.iter().position(|s| s.name == name).map(|index| SheetHandle(index as u16));
+	}
+
+	// maps ids from a data file's sheet list (e.g. RenderSnapshot::sprite_sheets) to handles,
+	// registering any names seen for the first time
+	pub fn map_sheets(&mut self, names: &[String], handles: &mut Vec<SheetHandle>) {
+		for name in names.iter().skip(handles.len()) {
+			let handle: SheetHandle = self.add_sheet(name.as_str());
+			handles.push(handle);
+		}
+		return;
+	}
+
+	pub fn get_region(&self, handle: SheetHandle) -> Option<AtlasRegion> {
+		return self.sheets.get(handle.0 as usize)?.region;
+	}
+
+	pub fn is_dirty(&self) -> bool {
+		return self.is_dirty;
+	}
+
+	pub fn get_generation(&self) -> u32 {
+		return self.generation;
+	}
+
+	// true when any packed sheet's file changed since the last build (hot reload during development)
+	pub fn has_changed_on_disk(&self) -> bool {
+		return self.sheets.iter().any(|sheet| get_modified(&sheet.path) != sheet.modified);
+	}
+
+	// decodes every registered sheet and shelf-packs them, tallest first. sheets that fail to load
+	// or don't fit a page get no region and come back as errors; the rest of the atlas still builds
+	pub fn build(&mut self) -> Vec<String> {
+		let mut errors: Vec<String> = Vec::new();
+		let mut images: Vec<Option<RgbaImage>> = Vec::with_capacity(self.sheets.len());
+
+		for sheet in &mut self.sheets {
+			sheet.modified = get_modified(&sheet.path);
+			sheet.region = None;
+
+			match load_png(&sheet.path) {
+				Ok(image) if image.width + ATLAS_PADDING_PIXELS > ATLAS_PAGE_SIZE_PIXELS || image.height + ATLAS_PADDING_PIXELS > ATLAS_PAGE_SIZE_PIXELS => {
+					errors.push(format!("{} is {}x{}, larger than an atlas page", sheet.name, image.width, image.height));
+					images.push(None);
+				}
+				Ok(image) => images.push(Some(image)),
+				Err(e) => {
+					errors.push(e);
+					images.push(None);
+				}
+			}
+		}
+
+		let mut order: Vec<usize> = (0..images.len()).filter(|index| images[*index].is_some()).collect();
+		order.sort_by_key(|index| core::cmp::Reverse(images[*index].as_ref().map(|image| image.height).unwrap_or(0)));
+
+		let mut packer: ShelfPacker = ShelfPacker::new();
+		for index in order {
+			let Some(image) = images[index].as_ref() else {
+				continue;
+			};
+			self.sheets[index].region = Some(packer.place(image.width, image.height));
+		}
+
+		self.pages = packer.finish_pages();
+		for (index, sheet) in self.sheets.iter().enumerate() {
+			if let (Some(region), Some(image)) = (sheet.region, images[index].as_ref()) {
+				copy_into(&mut self.pages[region.page as usize], image, region.left, region.top);
+			}
+		}
+
+		self.is_dirty = false;
+		self.generation = self.generation.wrapping_add(1);
+		return errors;
+	}
+}
+
+struct ShelfPacker {
+	// used height per page
+	page_heights: Vec<u32>,
+	page_widths: Vec<u32>,
+	shelf_left: u32,
+	shelf_top: u32,
+	shelf_height: u32,
+}
+
+impl ShelfPacker {
+	fn new() -> ShelfPacker {
+		return ShelfPacker {
+			page_heights: Vec::new(),
+			page_widths: Vec::new(),
+			shelf_left: 0,
+			shelf_top: 0,
+			shelf_height: 0,
+		};
+	}
+
+	fn place(&mut self, width: u32, height: u32) -> AtlasRegion {
+		let padded_width: u32 = width + ATLAS_PADDING_PIXELS;
+		let padded_height: u32 = height + ATLAS_PADDING_PIXELS;
+
+		if self.page_heights.is_empty() {
+			self.start_page();
+		}
+
+		// next shelf, then next page
+		if self.shelf_left + padded_width > ATLAS_PAGE_SIZE_PIXELS {
+			self.shelf_top += self.shelf_height;
+			self.shelf_left = 0;
+			self.shelf_height = 0;
+		}
+		if self.shelf_top + padded_height > ATLAS_PAGE_SIZE_PIXELS {
+			self.start_page();
+		}
+
+		let page: usize = self.page_heights.len() - 1;
+		let region: AtlasRegion = AtlasRegion {
+			page: page as u16,
+			left: self.shelf_left as i32,
+			top: self.shelf_top as i32,
+			width,
+			height,
+		};
+
+		self.shelf_left += padded_width;
+		self.shelf_height = self.shelf_height.max(padded_height);
+		self.page_widths[page] = self.page_widths[page].max(self.shelf_left);
+		self.page_heights[page] = self.page_heights[page].max(self.shelf_top + self.shelf_height);
+		return region;
+	}
+
+	fn start_page(&mut self) {
+		self.page_heights.push(0);
+		self.page_widths.push(0);
+		self.shelf_left = 0;
+		self.shelf_top = 0;
+		self.shelf_height = 0;
+		return;
+	}
+
+	// pages are only as large as what was packed onto them
+	fn finish_pages(&self) -> Vec<RgbaImage> {
+		return self
+			.page_widths
+			.iter()
+			.zip(self.page_heights.iter())
+			.map(|(width, height)| RgbaImage::new((*width).max(1), (*height).max(1)))
+			.collect();
+	}
+}
+
+fn copy_into(page: &mut RgbaImage, image: &RgbaImage, left: i32, top: i32) {
+	let row_bytes: usize = image.width as usize * 4;
+	for row in 0..image.height as usize {
+		let source_start: usize = row * row_bytes;
+		let destination_start: usize = ((top as usize + row) * page.width as usize + left as usize) * 4;
+		page.pixels[destination_start..destination_start + row_bytes].copy_from_slice(&image.pixels[source_start..source_start + row_bytes]);
+	}
+	return;
+}
+
+fn get_modified(path: &Path) -> Option<SystemTime> {
+	return fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+}
@@ -0,0 +1,289 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{format, string::String, vec::Vec};
+
+#[cfg(feature = "pc")]
+use crate::runtime::assets::get_backgrounds_path;
+
+#[cfg(feature = "pc")]
+use std::{fs, path::PathBuf};
+
+// the compiled level stores this; 0 means the level has no background
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BackgroundId(pub u8);
+
+impl BackgroundId {
+	pub const NONE: BackgroundId = BackgroundId(0);
+
+	pub fn from_u8(value: u8) -> BackgroundId {
+		return BackgroundId(value);
+	}
+
+	pub fn to_u8(&self) -> u8 {
+		return self.0;
+	}
+}
+
+// where a layer sits vertically before offset_top is applied
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LayerAnchor {
+	// follows the camera but never leaves a gap above or below the image
+	Clamp,
+	// image top on the level's top edge
+	Top,
+	// image bottom on the level's bottom edge
+	Bottom,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LayerTiling {
+	None,
+	Horizontal,
+	Both,
+}
+
+#[derive(Clone, Debug)]
+pub struct BackgroundLayer {
+	// png relative to the platform gfx root
+	pub image: String,
+	pub parallax_x: f32,
+	pub parallax_y: f32,
+	pub anchor: LayerAnchor,
+	pub tiling: LayerTiling,
+	// pixels per tick
+	pub scroll_x: f32,
+	pub scroll_y: f32,
+	pub offset_top: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct BackgroundDef {
+	pub id: BackgroundId,
+	pub name: String,
+	pub fill_color: (u8, u8, u8),
+	// back to front
+	pub layers: Vec<BackgroundLayer>,
+}
+
+// where to draw the first copy of a layer image; the renderer repeats it by the image size
+// along each tiled axis until the screen is covered
+#[derive(Copy, Clone, Debug)]
+pub struct LayerPlacement {
+	pub left: i32,
+	pub top: i32,
+	pub repeat_x: bool,
+	pub repeat_y: bool,
+}
+
+// everything get_layer_placement needs to know about the view, in screen pixels
+#[derive(Copy, Clone, Debug)]
+pub struct BackgroundView {
+	pub screen_width: i32,
+	pub screen_height: i32,
+	pub camera_left: f32,
+	pub camera_top: f32,
+	// camera_top when the view touches the bottom of the level
+	pub max_camera_top: f32,
+	pub tick: u32,
+}
+
+// layered backgrounds as described by assets/gfx/pc/backgrounds.txt
+pub struct BackgroundLibrary {
+	backgrounds: Vec<BackgroundDef>,
+}
+
+impl BackgroundLibrary {
+	pub fn empty() -> BackgroundLibrary {
+		return BackgroundLibrary { backgrounds: Vec::new() };
+	}
+
+	#[cfg(feature = "pc")]
+	pub fn load() -> Result<BackgroundLibrary, String> {
+		let path: PathBuf = get_backgrounds_path();
+		let text: String = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+		return BackgroundLibrary::parse(text.as_str()).map_err(|e| format!("{}: {}", path.display(), e));
+	}
+
+	pub fn get(&self, id: BackgroundId) -> Option<&BackgroundDef> {
+		return self.backgrounds.iter().find(|def| def.id == id);
+	}
+
+	pub fn parse(text: &str) -> Result<BackgroundLibrary, String> {
+		let mut library: BackgroundLibrary = BackgroundLibrary::empty();
+		let mut background: Option<BackgroundDef> = None;
+		let mut layer: Option<BackgroundLayer> = None;
+
+		for (line_index, raw_line) in text.lines().enumerate() {
+			let line_number: usize = line_index + 1;
+			let line: &str = raw_line.trim();
+
+			if line.is_empty() || line.starts_with('#') || line == "{" {
+				continue;
+			}
+
+			if line == "}" {
+				if let Some(finished_layer) = layer.take() {
+					if finished_layer.image.is_empty() {
+						return Err(format!("layer without an image (line {})", line_number));
+					}
+					if let Some(current) = background.as_mut() {
+						current.layers.push(finished_layer);
+					}
+					continue;
+				}
+
+				let Some(finished) = background.take() else {
+					return Err(format!("unexpected '}}' at line {}", line_number));
+				};
+				if library.get(finished.id).is_some() {
+					return Err(format!("background {} is defined twice (line {})", finished.id.0, line_number));
+				}
+				library.backgrounds.push(finished);
+				continue;
+			}
+
+			if let Some(rest) = line.strip_prefix("background ") {
+				if background.is_some() {
+					return Err(format!("background inside a background at line {}", line_number));
+				}
+				let rest: &str = rest.trim();
+				let split_index: usize = rest.find(char::is_whitespace).unwrap_or(rest.len());
+				let id: u8 = parse_number(&rest[..split_index], line_number)?;
+				if id == 0 {
+					return Err(format!("background id 0 means 'no background' at line {}", line_number));
+				}
+				background = Some(BackgroundDef {
+					id: BackgroundId(id),
+					name: parse_quoted(rest[split_index..].trim(), line_number)?,
+					fill_color: (60, 110, 190),
+					layers: Vec::new(),
+				});
+				continue;
+			}
+
+			if line == "layer" {
+				if background.is_none() || layer.is_some() {
+					return Err(format!("layer must be directly inside a background at line {}", line_number));
+				}
+				layer = Some(BackgroundLayer {
+					image: String::new(),
+					parallax_x: 1.0,
+					parallax_y: 1.0,
+					anchor: LayerAnchor::Clamp,
+					tiling: LayerTiling::Horizontal,
+					scroll_x: 0.0,
+					scroll_y: 0.0,
+					offset_top: 0,
+				});
+				continue;
+			}
+
+			let Some(eq_index) = line.find('=') else {
+				return Err(format!("invalid line {}: missing '='", line_number));
+			};
+			let key: &str = line[..eq_index].trim();
+			let value: &str = line[eq_index + 1..].trim();
+
+			if let Some(current) = layer.as_mut() {
+				match key {
+					"image" => current.image = parse_quoted(value, line_number)?,
+					"parallax" => (current.parallax_x, current.parallax_y) = parse_pair(value, line_number)?,
+					"scroll" => (current.scroll_x, current.scroll_y) = parse_pair(value, line_number)?,
+					"offset_top" => current.offset_top = parse_number(value, line_number)?,
+					"anchor" => {
+						current.anchor = match value {
+							"clamp" => LayerAnchor::Clamp,
+							"top" => LayerAnchor::Top,
+							"bottom" => LayerAnchor::Bottom,
+							_ => return Err(format!("unknown anchor '{}' at line {}", value, line_number)),
+						};
+					}
+					"tile" => {
+						current.tiling = match value {
+							"none" => LayerTiling::None,
+							"x" => LayerTiling::Horizontal,
+							"both" => LayerTiling::Both,
+							_ => return Err(format!("unknown tile mode '{}' at line {}", value, line_number)),
+						};
+					}
+					_ => return Err(format!("unknown layer key '{}' at line {}", key, line_number)),
+				}
+				continue;
+			}
+
+			let Some(current) = background.as_mut() else {
+				return Err(format!("'{}' outside a background at line {}", key, line_number));
+			};
+			match key {
+				"color" => {
+					let parts: Vec<&str> = value.split_whitespace().collect();
+					if parts.len() != 3 {
+						return Err(format!("color needs r g b at line {}", line_number));
+					}
+					current.fill_color = (parse_number(parts[0], line_number)?, parse_number(parts[1], line_number)?, parse_number(parts[2], line_number)?);
+				}
+				_ => return Err(format!("unknown background key '{}' at line {}", key, line_number)),
+			}
+		}
+
+		if background.is_some() || layer.is_some() {
+			return Err(String::from("missing '}' at end of file"));
+		}
+
+		return Ok(library);
+	}
+}
+
+// shared by every renderer so the layers line up the same way everywhere
+pub fn get_layer_placement(layer: &BackgroundLayer, image_width: i32, image_height: i32, view: &BackgroundView) -> LayerPlacement {
+	let scroll_left: f32 = view.camera_left * layer.parallax_x - layer.scroll_x * view.tick as f32;
+	let scroll_top: f32 = view.camera_top * layer.parallax_y - layer.scroll_y * view.tick as f32;
+	let repeat_x: bool = layer.tiling != LayerTiling::None;
+	let repeat_y: bool = layer.tiling == LayerTiling::Both;
+
+	let mut left: i32 = -(scroll_left as i32);
+	if repeat_x {
+		left = -((((scroll_left as i32) % image_width) + image_width) % image_width);
+	}
+
+	let mut top: i32 = match layer.anchor {
+		LayerAnchor::Clamp => {
+			if image_height >= view.screen_height {
+				(-(scroll_top as i32)).clamp(view.screen_height - image_height, 0)
+			} else {
+				// shorter than the screen: pin to the top, the fill colour covers the rest
+				0
+			}
+		}
+		LayerAnchor::Top => -(scroll_top as i32),
+		LayerAnchor::Bottom => view.screen_height - image_height + ((view.max_camera_top * layer.parallax_y - scroll_top) as i32),
+	};
+	top += layer.offset_top;
+
+	if repeat_y {
+		top = ((top % image_height) + image_height) % image_height - image_height;
+	}
+
+	return LayerPlacement { left, top, repeat_x, repeat_y };
+}
+
+fn parse_quoted(value: &str, line_number: usize) -> Result<String, String> {
+	if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+		return Err(format!("expected a quoted string at line {}", line_number));
+	}
+	return Ok(String::from(&value[1..value.len() - 1]));
+}
+
+fn parse_pair(value: &str, line_number: usize) -> Result<(f32, f32), String> {
+	let parts: Vec<&str> = value.split_whitespace().collect();
+	if parts.len() != 2 {
+		return Err(format!("expected two numbers at line {}", line_number));
+	}
+	return Ok((parse_number(parts[0], line_number)?, parse_number(parts[1], line_number)?));
+}
+
+fn parse_number<T: core::str::FromStr>(value: &str, line_number: usize) -> Result<T, String> {
+	return value.trim().parse::<T>().map_err(|_| format!("invalid number '{}' at line {}", value.trim(), line_number));
+}
@@ -0,0 +1,33 @@
+use crate::{
+	engine_math::Vec2,
+	runtime::{session::Session, state::State},
+};
+
+pub const GHOST_FILL_COLOR: (u8, u8, u8, u8) = (200, 225, 255, 80);
+pub const GHOST_OUTLINE_COLOR: (u8, u8, u8, u8) = (200, 225, 255, 170);
+
+// the best run of the level, drawn as a see-through player behind the real one
+#[derive(Copy, Clone, Debug)]
+pub struct GhostSnapshot {
+	// world position of the body's middle, like EntitySnapshot
+	pub position: Vec2,
+	pub half_width: f32,
+	pub half_height: f32,
+}
+
+impl GhostSnapshot {
+	// None when ghosts are off, the level has none, or its run has already finished
+	pub fn capture(state: &State, session: &Session) -> Option<GhostSnapshot> {
+		if !session.settings.show_ghost {
+			return None;
+		}
+		let player_id = state.try_get_player_id()?;
+		let position: Vec2 = session.speedrun.get_ghost_position(state.render_alpha)?;
+		let (half_width, half_height) = state.get_entity_half_values(player_id);
+		return Some(GhostSnapshot {
+			position,
+			half_width,
+			half_height,
+		});
+	}
+}
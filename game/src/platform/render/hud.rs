@@ -0,0 +1,487 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::vec::Vec;
+
+use crate::{
+	physics::movement::stomp_bonus,
+	runtime::{
+		hud::{HudCounter, PICKUP_FLIGHT_TICKS},
+		inventory::KeyId,
+		scoring::Rank,
+		session::{PLAYER_MAX_HIT_POINTS, Session},
+		speedrun::{LevelTime, Split},
+		state::State,
+	},
+};
+
+// gold_coin in assets/gfx/pc/icons.txt
+pub(crate) const HUD_COIN_ICON_ID: u16 = 2;
+
+// hud pixels are this many screen pixels per 180 rows of screen, so the hud keeps its size
+// relative to the window (4 at 720p)
+const HUD_REFERENCE_HEIGHT_PIXELS: i32 = 180;
+
+const HEART_FULL_COLOR: (u8, u8, u8, u8) = (230, 50, 70, 255);
+const HEART_EMPTY_COLOR: (u8, u8, u8, u8) = (60, 20, 30, 200);
+const COUNTER_COLOR: (u8, u8, u8, u8) = (255, 255, 255, 255);
+const COUNTER_PULSE_COLOR: (u8, u8, u8, u8) = (255, 230, 90, 255);
+const CHAIN_COLOR: (u8, u8, u8, u8) = (255, 200, 80, 255);
+const SHADOW_COLOR: (u8, u8, u8, u8) = (0, 0, 0, 150);
+const TIMER_RTA_COLOR: (u8, u8, u8, u8) = (180, 180, 195, 255);
+const SPLIT_FASTER_COLOR: (u8, u8, u8, u8) = (90, 220, 110, 255);
+const SPLIT_SLOWER_COLOR: (u8, u8, u8, u8) = (235, 90, 80, 255);
+const KEY_COLORS: [(u8, u8, u8, u8); 4] = [(240, 200, 60, 255), (200, 210, 220, 255), (220, 80, 70, 255), (90, 200, 110, 255)];
+
+// a pickup on its way to the hud, already in screen pixels
+#[derive(Copy, Clone, Debug)]
+pub struct HudFlight {
+	pub counter: HudCounter,
+	pub value: u16,
+	pub start_left: f32,
+	pub start_top: f32,
+	// 0 when collected, 1 when it reaches the counter
+	pub progress: f32,
+}
+
+// the level just left, against its best before this run
+#[derive(Copy, Clone, Debug)]
+pub struct HudSplit {
+	pub time_centis: u32,
+	// None the first time the level is finished
+	pub delta_centis: Option<i32>,
+	pub is_new_best: bool,
+}
+
+// the speedrun timer, in hundredths of a second
+#[derive(Copy, Clone, Debug)]
+pub struct HudTimer {
+	pub igt_centis: u32,
+	pub rta_centis: u32,
+	pub split: Option<HudSplit>,
+}
+
+// what the hud shows, copied out of State and Session with the rest of the snapshot
+#[derive(Clone, Debug)]
+pub struct HudSnapshot {
+	pub hit_points: u16,
+	pub max_hit_points: u16,
+	// without the coins and keys still flying in
+	pub coins: u16,
+	pub keys: Vec<KeyId>,
+	pub stomp_chain: u16,
+	pub stomp_bonus: u16,
+	pub coin_pulse_ticks: u8,
+	pub key_pulse_ticks: u8,
+	pub flights: Vec<HudFlight>,
+	// None unless the speedrun timer is on in the settings
+	pub timer: Option<HudTimer>,
+}
+
+impl HudSnapshot {
+	pub fn new() -> HudSnapshot {
+		return HudSnapshot {
+			hit_points: 0,
+			max_hit_points: PLAYER_MAX_HIT_POINTS,
+			coins: 0,
+			keys: Vec::new(),
+			stomp_chain: 0,
+			stomp_bonus: 0,
+			coin_pulse_ticks: 0,
+			key_pulse_ticks: 0,
+			flights: Vec::new(),
+			timer: None,
+		};
+	}
+
+	pub fn capture(&mut self, state: &State, session: &Session, camera_left: i32, camera_top: i32, render_scale: f32) {
+		self.hit_points = 0;
+		self.stomp_chain = 0;
+		if let Some(player_id) = state.try_get_player_id() {
+			self.hit_points = state.hit_points.get(player_id).copied().unwrap_or(0);
+			self.stomp_chain = state.stomp_chains.get(player_id).copied().unwrap_or(0);
+		}
+		self.max_hit_points = PLAYER_MAX_HIT_POINTS;
+		self.stomp_bonus = stomp_bonus(self.stomp_chain, session.settings.stomp_chain_gain_per_stomp).min(session.settings.stomp_bonus_cap as u16);
+
+		self.coins = session.inventory.coins.saturating_sub(state.hud.get_coins_in_flight());
+		self.keys.clear();
+		for key in &session.inventory.keys {
+			// a key spent on a door stays in the inventory but leaves the hud
+			if !key.is_used && !state.hud.is_key_in_flight(key.key_id) {
+				self.keys.push(key.key_id);
+			}
+		}
+
+		self.coin_pulse_ticks = state.hud.coin_pulse_ticks;
+		self.key_pulse_ticks = state.hud.key_pulse_ticks;
+
+		self.flights.clear();
+		for flight in &state.hud.flights {
+			let elapsed: f32 = state.tick.wrapping_sub(flight.start_tick) as f32 + state.render_alpha;
+			self.flights.push(HudFlight {
+				counter: flight.counter,
+				value: flight.value,
+				start_left: (flight.start_left - camera_left as f32) * render_scale,
+				start_top: (flight.start_top - camera_top as f32) * render_scale,
+				progress: (elapsed / PICKUP_FLIGHT_TICKS as f32).clamp(0.0, 1.0),
+			});
+		}
+
+		self.timer = None;
+		if session.settings.show_speedrun_timer {
+			let time: LevelTime = session.speedrun.get_time();
+			self.timer = Some(HudTimer {
+				igt_centis: time.get_igt_centis(),
+				rta_centis: time.get_rta_centis(),
+				split: session.speedrun.get_showing_split().map(|split: &Split| HudSplit {
+					time_centis: split.time.get_igt_centis(),
+					delta_centis: split.get_delta_centis(),
+					is_new_best: split.is_new_best,
+				}),
+			});
+		}
+
+		return;
+	}
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HudGlyph {
+	Heart,
+	Key,
+	Stomp,
+	Plus,
+	Times,
+	Minus,
+	Colon,
+	Dot,
+	Digit(u8),
+	// the results screen's rank letter
+	Rank(Rank),
+}
+
+// one row per byte, leftmost pixel in the highest of the glyph's `width` bits
+#[derive(Copy, Clone, Debug)]
+pub struct GlyphBitmap {
+	pub width: u32,
+	pub rows: &'static [u8],
+}
+
+impl GlyphBitmap {
+	pub fn is_set(&self, column: u32, row: u32) -> bool {
+		return (self.rows[row as usize] >> (self.width - 1 - column)) & 1 == 1;
+	}
+}
+
+// everything the hud draws is one of these; a renderer only needs to fill squares and draw
+// icons from the icon registry
+#[derive(Copy, Clone, Debug)]
+pub enum HudElement {
+	// each set bitmap pixel is a pixel_size square
+	Glyph {
+		glyph: HudGlyph,
+		left: i32,
+		top: i32,
+		pixel_size: u32,
+		color: (u8, u8, u8, u8),
+	},
+	// an IconRegistry icon scaled to size x size
+	Icon {
+		icon_id: u16,
+		left: i32,
+		top: i32,
+		size: u32,
+	},
+}
+
+const DIGIT_ROWS: [[u8; 5]; 10] = [
+	[0b111, 0b101, 0b101, 0b101, 0b111],
+	[0b010, 0b110, 0b010, 0b010, 0b111],
+	[0b111, 0b001, 0b111, 0b100, 0b111],
+	[0b111, 0b001, 0b111, 0b001, 0b111],
+	[0b101, 0b101, 0b111, 0b001, 0b001],
+	[0b111, 0b100, 0b111, 0b001, 0b111],
+	[0b111, 0b100, 0b111, 0b101, 0b111],
+	[0b111, 0b001, 0b001, 0b010, 0b010],
+	[0b111, 0b101, 0b111, 0b101, 0b111],
+	[0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+const HEART_ROWS: [u8; 6] = [0b0110110, 0b1111111, 0b1111111, 0b0111110, 0b0011100, 0b0001000];
+const KEY_ROWS: [u8; 5] = [0b0110000, 0b1001000, 0b1001111, 0b1001011, 0b0110000];
+const STOMP_ROWS: [u8; 5] = [0b00100, 0b00100, 0b11111, 0b01110, 0b00100];
+const PLUS_ROWS: [u8; 5] = [0b000, 0b010, 0b111, 0b010, 0b000];
+const TIMES_ROWS: [u8; 5] = [0b000, 0b101, 0b010, 0b101, 0b000];
+const MINUS_ROWS: [u8; 5] = [0b000, 0b000, 0b111, 0b000, 0b000];
+const COLON_ROWS: [u8; 5] = [0b0, 0b1, 0b0, 0b1, 0b0];
+const DOT_ROWS: [u8; 5] = [0b0, 0b0, 0b0, 0b0, 0b1];
+const RANK_S_ROWS: [u8; 5] = [0b111, 0b100, 0b111, 0b001, 0b111];
+const RANK_A_ROWS: [u8; 5] = [0b010, 0b101, 0b111, 0b101, 0b101];
+const RANK_B_ROWS: [u8; 5] = [0b110, 0b101, 0b110, 0b101, 0b110];
+const RANK_C_ROWS: [u8; 5] = [0b011, 0b100, 0b100, 0b100, 0b011];
+const RANK_D_ROWS: [u8; 5] = [0b110, 0b101, 0b101, 0b101, 0b110];
+
+pub fn get_glyph_bitmap(glyph: HudGlyph) -> GlyphBitmap {
+	return match glyph {
+		HudGlyph::Heart => GlyphBitmap { width: 7, rows: &HEART_ROWS },
+		HudGlyph::Key => GlyphBitmap { width: 7, rows: &KEY_ROWS },
+		HudGlyph::Stomp => GlyphBitmap { width: 5, rows: &STOMP_ROWS },
+		HudGlyph::Plus => GlyphBitmap { width: 3, rows: &PLUS_ROWS },
+		HudGlyph::Times => GlyphBitmap { width: 3, rows: &TIMES_ROWS },
+		HudGlyph::Minus => GlyphBitmap { width: 3, rows: &MINUS_ROWS },
+		HudGlyph::Colon => GlyphBitmap { width: 1, rows: &COLON_ROWS },
+		HudGlyph::Dot => GlyphBitmap { width: 1, rows: &DOT_ROWS },
+		HudGlyph::Digit(digit) => GlyphBitmap {
+			width: 3,
+			rows: &DIGIT_ROWS[(digit % 10) as usize],
+		},
+		HudGlyph::Rank(rank) => GlyphBitmap {
+			width: 3,
+			rows: match rank {
+				Rank::S => &RANK_S_ROWS,
+				Rank::A => &RANK_A_ROWS,
+				Rank::B => &RANK_B_ROWS,
+				Rank::C => &RANK_C_ROWS,
+				Rank::D => &RANK_D_ROWS,
+			},
+		},
+	};
+}
+
+// hearts along the top left, the coin counter under them, then the keys; the stomp chain sits in
+// the top right while it lasts, with the speedrun timer under it. pickups in flight are drawn
+// last, on top of everything
+pub fn layout_hud(hud: &HudSnapshot, screen_width: i32, screen_height: i32, elements: &mut Vec<HudElement>) {
+	elements.clear();
+
+	let pixel: i32 = (screen_height / HUD_REFERENCE_HEIGHT_PIXELS).max(1);
+	let margin: i32 = pixel * 4;
+
+	// hearts
+	let hearts_top: i32 = margin;
+	let heart_count: u16 = hud.max_hit_points.max(hud.hit_points);
+	for index in 0..heart_count {
+		let color: (u8, u8, u8, u8) = if index < hud.hit_points { HEART_FULL_COLOR } else { HEART_EMPTY_COLOR };
+		push_glyph(elements, HudGlyph::Heart, margin + index as i32 * pixel * 9, hearts_top, pixel, color);
+	}
+
+	// coins
+	let coins_top: i32 = hearts_top + pixel * 10;
+	let coin_icon_size: i32 = pixel * 8;
+	elements.push(HudElement::Icon {
+		icon_id: HUD_COIN_ICON_ID,
+		left: margin - pixel,
+		top: coins_top - pixel,
+		size: coin_icon_size as u32,
+	});
+	let coin_color: (u8, u8, u8, u8) = if hud.coin_pulse_ticks > 0 { COUNTER_PULSE_COLOR } else { COUNTER_COLOR };
+	push_number(elements, hud.coins as u32, margin + pixel * 9, coins_top + pixel, pixel, coin_color);
+
+	// keys
+	let keys_top: i32 = coins_top + pixel * 10;
+	let key_pulse_index: usize = if hud.key_pulse_ticks > 0 && !hud.keys.is_empty() {
+		hud.keys.len() - 1
+	} else {
+		usize::MAX
+	};
+	for (index, key_id) in hud.keys.iter().enumerate() {
+		let mut color: (u8, u8, u8, u8) = get_key_color(*key_id);
+		if index == key_pulse_index {
+			color = COUNTER_PULSE_COLOR;
+		}
+		push_glyph(elements, HudGlyph::Key, margin + index as i32 * pixel * 9, keys_top, pixel, color);
+	}
+
+	// stomp chain, right aligned: "v x3 +1"
+	if hud.stomp_chain > 0 {
+		let mut width: i32 = pixel * 6 + pixel * 4 + get_number_width(hud.stomp_chain as u32, pixel);
+		if hud.stomp_bonus > 0 {
+			width += pixel * 2 + pixel * 4 + get_number_width(hud.stomp_bonus as u32, pixel);
+		}
+
+		let mut left: i32 = screen_width - margin - width;
+		push_glyph(elements, HudGlyph::Stomp, left, margin, pixel, CHAIN_COLOR);
+		left += pixel * 6;
+		push_glyph(elements, HudGlyph::Times, left, margin, pixel, CHAIN_COLOR);
+		left += pixel * 4;
+		left = push_number(elements, hud.stomp_chain as u32, left, margin, pixel, CHAIN_COLOR);
+		if hud.stomp_bonus > 0 {
+			left += pixel * 2;
+			push_glyph(elements, HudGlyph::Plus, left, margin, pixel, CHAIN_COLOR);
+			left += pixel * 4;
+			push_number(elements, hud.stomp_bonus as u32, left, margin, pixel, CHAIN_COLOR);
+		}
+	}
+
+	// speedrun timer, right aligned: in-game time, real time, then the last split for a while
+	if let Some(timer) = &hud.timer {
+		let igt_top: i32 = margin + pixel * 8;
+		push_time(
+			elements,
+			timer.igt_centis,
+			screen_width - margin - get_time_width(timer.igt_centis, pixel),
+			igt_top,
+			pixel,
+			COUNTER_COLOR,
+		);
+		let rta_top: i32 = igt_top + pixel * 8;
+		push_time(
+			elements,
+			timer.rta_centis,
+			screen_width - margin - get_time_width(timer.rta_centis, pixel),
+			rta_top,
+			pixel,
+			TIMER_RTA_COLOR,
+		);
+
+		if let Some(split) = &timer.split {
+			// "0:41.20 -0:01.35"; a first finish has no delta
+			let mut width: i32 = get_time_width(split.time_centis, pixel);
+			if let Some(delta_centis) = split.delta_centis {
+				width += pixel * 3 + pixel * 4 + get_time_width(delta_centis.unsigned_abs(), pixel);
+			}
+
+			let split_top: i32 = rta_top + pixel * 8;
+			let time_color: (u8, u8, u8, u8) = if split.is_new_best { COUNTER_PULSE_COLOR } else { COUNTER_COLOR };
+			let mut left: i32 = screen_width - margin - width;
+			left = push_time(elements, split.time_centis, left, split_top, pixel, time_color);
+			if let Some(delta_centis) = split.delta_centis {
+				left += pixel * 3;
+				let (sign, delta_color): (HudGlyph, (u8, u8, u8, u8)) = if delta_centis < 0 {
+					(HudGlyph::Minus, SPLIT_FASTER_COLOR)
+				} else {
+					(HudGlyph::Plus, SPLIT_SLOWER_COLOR)
+				};
+				push_glyph(elements, sign, left, split_top, pixel, delta_color);
+				left += pixel * 4;
+				push_time(elements, delta_centis.unsigned_abs(), left, split_top, pixel, delta_color);
+			}
+		}
+	}
+
+	// pickups flying in; keys line up behind the ones already shown
+	let mut key_slot: i32 = hud.keys.len() as i32;
+	for flight in &hud.flights {
+		let (target_left, target_top) = match flight.counter {
+			HudCounter::Coins => (margin - pixel + coin_icon_size / 2, coins_top - pixel + coin_icon_size / 2),
+			HudCounter::Keys => {
+				let target: (i32, i32) = (margin + key_slot * pixel * 9 + pixel * 7 / 2, keys_top + pixel * 5 / 2);
+				key_slot += 1;
+				target
+			}
+		};
+
+		// smoothstep along the way, with a small hop up at the start
+		let t: f32 = flight.progress;
+		let eased: f32 = t * t * (3.0 - 2.0 * t);
+		let hop: f32 = 4.0 * t * (1.0 - t) * (pixel * 12) as f32;
+		let center_left: i32 = (flight.start_left + (target_left as f32 - flight.start_left) * eased) as i32;
+		let center_top: i32 = (flight.start_top + (target_top as f32 - flight.start_top) * eased - hop) as i32;
+
+		match flight.counter {
+			HudCounter::Coins => elements.push(HudElement::Icon {
+				icon_id: HUD_COIN_ICON_ID,
+				left: center_left - coin_icon_size / 2,
+				top: center_top - coin_icon_size / 2,
+				size: coin_icon_size as u32,
+			}),
+			HudCounter::Keys => push_glyph(
+				elements,
+				HudGlyph::Key,
+				center_left - pixel * 7 / 2,
+				center_top - pixel * 5 / 2,
+				pixel,
+				get_key_color(flight.value),
+			),
+		}
+	}
+
+	return;
+}
+
+pub(crate) fn get_key_color(key_id: KeyId) -> (u8, u8, u8, u8) {
+	return KEY_COLORS[(key_id as usize) % KEY_COLORS.len()];
+}
+
+// the glyph with a drop shadow one hud pixel down and right
+pub(crate) fn push_glyph(elements: &mut Vec<HudElement>, glyph: HudGlyph, left: i32, top: i32, pixel: i32, color: (u8, u8, u8, u8)) {
+	elements.push(HudElement::Glyph {
+		glyph,
+		left: left + pixel,
+		top: top + pixel,
+		pixel_size: pixel as u32,
+		color: SHADOW_COLOR,
+	});
+	elements.push(HudElement::Glyph {
+		glyph,
+		left,
+		top,
+		pixel_size: pixel as u32,
+		color,
+	});
+	return;
+}
+
+pub(crate) fn get_number_width(value: u32, pixel: i32) -> i32 {
+	let mut digit_count: i32 = 1;
+	let mut rest: u32 = value / 10;
+	while rest > 0 {
+		digit_count += 1;
+		rest /= 10;
+	}
+	return digit_count * pixel * 4 - pixel;
+}
+
+// returns the left edge after the last digit
+pub(crate) fn push_number(elements: &mut Vec<HudElement>, value: u32, left: i32, top: i32, pixel: i32, color: (u8, u8, u8, u8)) -> i32 {
+	let mut digits: [u8; 10] = [0; 10];
+	let mut digit_count: usize = 0;
+	let mut rest: u32 = value;
+	loop {
+		digits[digit_count] = (rest % 10) as u8;
+		digit_count += 1;
+		rest /= 10;
+		if rest == 0 {
+			break;
+		}
+	}
+
+	let mut next_left: i32 = left;
+	for index in (0..digit_count).rev() {
+		push_glyph(elements, HudGlyph::Digit(digits[index]), next_left, top, pixel, color);
+		next_left += pixel * 4;
+	}
+
+	return next_left - pixel;
+}
+
+// "m:ss.cc" from hundredths of a second
+pub(crate) fn get_time_width(centis: u32, pixel: i32) -> i32 {
+	let minutes: u32 = centis / 6000;
+	return get_number_width(minutes, pixel) + pixel * 2 + pixel * 8 + pixel * 2 + pixel * 8;
+}
+
+// returns the left edge after the last digit
+pub(crate) fn push_time(elements: &mut Vec<HudElement>, centis: u32, left: i32, top: i32, pixel: i32, color: (u8, u8, u8, u8)) -> i32 {
+	let minutes: u32 = centis / 6000;
+	let seconds: u32 = centis / 100 % 60;
+	let hundredths: u32 = centis % 100;
+
+	let mut next_left: i32 = push_number(elements, minutes, left, top, pixel, color) + pixel;
+	push_glyph(elements, HudGlyph::Colon, next_left, top, pixel, color);
+	next_left += pixel * 2;
+	for digit in [seconds / 10, seconds % 10] {
+		push_glyph(elements, HudGlyph::Digit(digit as u8), next_left, top, pixel, color);
+		next_left += pixel * 4;
+	}
+	push_glyph(elements, HudGlyph::Dot, next_left, top, pixel, color);
+	next_left += pixel * 2;
+	for digit in [hundredths / 10, hundredths % 10] {
+		push_glyph(elements, HudGlyph::Digit(digit as u8), next_left, top, pixel, color);
+		next_left += pixel * 4;
+	}
+
+	return next_left - pixel;
+}
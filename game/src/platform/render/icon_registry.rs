@@ -1,36 +1,151 @@
-pub const ICON_FRAME_WIDTH_PIXELS: u32 = 32;
-pub const ICON_FRAME_HEIGHT_PIXELS: u32 = 32;
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{format, string::String, vec::Vec};
+
+#[cfg(feature = "pc")]
+use crate::runtime::assets::get_icons_path;
+
+#[cfg(feature = "pc")]
+use std::{fs, path::PathBuf};
 
 #[derive(Copy, Clone, Debug)]
 pub struct IconDef {
+	pub icon_id: u16,
+	pub row: u16,
 	pub frame_count: u8,          // 1 means static
 	pub frame_duration_ticks: u8, // 0 means no animation
 }
 
-pub fn resolve_icon(icon_id: u16) -> Option<IconDef> {
-	match icon_id {
-		// row 1: book (static)
-		1 => Some(IconDef {
-			frame_count: 1,
-			frame_duration_ticks: 0,
-		}),
-		// row 2: gold coin (animated strip)
-		2 => Some(IconDef {
-			frame_count: 14,
-			frame_duration_ticks: 3,
-		}),
-		// row 3: silver coin (animated strip)
-		3 => Some(IconDef {
-			frame_count: 14,
-			frame_duration_ticks: 3,
-		}),
-		_ => None,
+// trigger icons as described by the atlas metadata (assets/gfx/pc/icons.txt)
+pub struct IconRegistry {
+	// sheet path relative to the platform gfx root
+	pub sheet: String,
+	pub frame_width_pixels: u32,
+	pub frame_height_pixels: u32,
+	icons: Vec<IconDef>,
+}
+
+impl IconRegistry {
+	pub fn empty() -> IconRegistry {
+		return IconRegistry {
+			sheet: String::new(),
+			frame_width_pixels: 32,
+			frame_height_pixels: 32,
+			icons: Vec::new(),
+		};
+	}
+
+	#[cfg(feature = "pc")]
+	pub fn load() -> Result<IconRegistry, String> {
+		let path: PathBuf = get_icons_path();
+		let text: String = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+		return IconRegistry::parse(text.as_str()).map_err(|e| format!("{}: {}", path.display(), e));
+	}
+
+	pub fn resolve_icon(&self, icon_id: u16) -> Option<IconDef> {
+		return self.icons.iter().find(|def| def.icon_id == icon_id).copied();
+	}
+
+	pub fn get_frame_index(&self, def: &IconDef, tick: u32) -> u16 {
+		if def.frame_count <= 1 || def.frame_duration_ticks == 0 {
+			return 0;
+		}
+
+		return ((tick / def.frame_duration_ticks as u32) % def.frame_count as u32) as u16;
+	}
+
+	// source rect in pixels inside the icon sheet for a given icon + frame_index
+	pub fn get_icon_src_rect_pixels(&self, def: &IconDef, frame_index: u16) -> (i32, i32, u32, u32) {
+		let left_pixels: i32 = (frame_index as u32 * self.frame_width_pixels) as i32;
+		let top_pixels: i32 = (def.row as u32 * self.frame_height_pixels) as i32;
+		return (left_pixels, top_pixels, self.frame_width_pixels, self.frame_height_pixels);
+	}
+
+	pub fn parse(text: &str) -> Result<IconRegistry, String> {
+		let mut registry: IconRegistry = IconRegistry::empty();
+		let mut icon: Option<IconDef> = None;
+
+		for (line_index, raw_line) in text.lines().enumerate() {
+			let line_number: usize = line_index + 1;
+			let line: &str = raw_line.trim();
+
+			if line.is_empty() || line.starts_with('#') || line == "{" {
+				continue;
+			}
+
+			if line == "}" {
+				let Some(finished) = icon.take() else {
+					return Err(format!("unexpected '}}' at line {}", line_number));
+				};
+				if registry.resolve_icon(finished.icon_id).is_some() {
+					return Err(format!("icon {} is defined twice (line {})", finished.icon_id, line_number));
+				}
+				registry.icons.push(finished);
+				continue;
+			}
+
+			if let Some(rest) = line.strip_prefix("icon ") {
+				if icon.is_some() {
+					return Err(format!("icon inside an icon at line {}", line_number));
+				}
+				// the quoted name is only there for people reading the file
+				let id_text: &str = rest.split_whitespace().next().unwrap_or("");
+				let icon_id: u16 = parse_number(id_text, line_number)?;
+				if icon_id == 0 {
+					return Err(format!("icon id 0 means 'no icon' at line {}", line_number));
+				}
+				icon = Some(IconDef {
+					icon_id,
+					row: 0,
+					frame_count: 1,
+					frame_duration_ticks: 0,
+				});
+				continue;
+			}
+
+			let Some(eq_index) = line.find('=') else {
+				return Err(format!("invalid line {}: missing '='", line_number));
+			};
+			let key: &str = line[..eq_index].trim();
+			let value: &str = line[eq_index + 1..].trim();
+
+			if let Some(current) = icon.as_mut() {
+				match key {
+					"row" => current.row = parse_number(value, line_number)?,
+					"frames" => current.frame_count = parse_number(value, line_number)?,
+					"duration" => current.frame_duration_ticks = parse_number(value, line_number)?,
+					_ => return Err(format!("unknown icon key '{}' at line {}", key, line_number)),
+				}
+				continue;
+			}
+
+			match key {
+				"sheet" => {
+					if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+						return Err(format!("expected a quoted string at line {}", line_number));
+					}
+					registry.sheet = String::from(&value[1..value.len() - 1]);
+				}
+				"frame_width" => registry.frame_width_pixels = parse_number(value, line_number)?,
+				"frame_height" => registry.frame_height_pixels = parse_number(value, line_number)?,
+				_ => return Err(format!("unknown key '{}' at line {}", key, line_number)),
+			}
+		}
+
+		if icon.is_some() {
+			return Err(String::from("missing '}' at end of file"));
+		}
+
+		if registry.sheet.is_empty() && !registry.icons.is_empty() {
+			return Err(String::from("icons need a sheet"));
+		}
+
+		return Ok(registry);
 	}
 }
 
-// helper: compute source rect in pixels for a given icon_id + frame_index
-pub fn get_icon_src_rect_pixels(icon_id: u16, frame_index: u16) -> (i32, i32, u32, u32) {
-	let left_pixels: i32 = (frame_index as u32 * ICON_FRAME_WIDTH_PIXELS) as i32;
-	let top_pixels: i32 = (icon_id as u32 * ICON_FRAME_HEIGHT_PIXELS) as i32;
-	return (left_pixels, top_pixels, ICON_FRAME_WIDTH_PIXELS, ICON_FRAME_HEIGHT_PIXELS);
+fn parse_number<T: core::str::FromStr>(value: &str, line_number: usize) -> Result<T, String> {
+	return value.trim().parse::<T>().map_err(|_| format!("invalid number '{}' at line {}", value.trim(), line_number));
 }
@@ -0,0 +1,286 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt::Write;
+
+use crate::{
+	platform::render::hud::{HUD_COIN_ICON_ID, HudElement, HudGlyph, get_key_color, get_number_width, push_glyph, push_number},
+	runtime::{
+		book::Book,
+		inventory::{INVENTORY_COLUMNS, InventorySlot, Key, KeyId},
+		menu::{MESSAGE_MENU_COINS, MESSAGE_MENU_INVENTORY},
+		pickup_map::{PICKUP_MAP_BOOK_PREFIX, PICKUP_MAP_KEY_PREFIX},
+		session::Session,
+	},
+};
+
+// the inventory screen's own text; item names come from item_<pickup name> in the message table
+const MESSAGE_INVENTORY_PAGE: u16 = 136;
+const MESSAGE_INVENTORY_KEY_USED: u16 = 137;
+const MESSAGE_INVENTORY_KEY_UNUSED: u16 = 138;
+const MESSAGE_INVENTORY_HINT: u16 = 139;
+
+// book in assets/gfx/pc/icons.txt
+const INVENTORY_BOOK_ICON_ID: u16 = 1;
+
+// same scale as the hud and the menus
+const INVENTORY_REFERENCE_HEIGHT_PIXELS: i32 = 180;
+
+pub const INVENTORY_CELL_COLOR: (u8, u8, u8, u8) = (36, 36, 50, 255);
+pub const INVENTORY_BAR_BACK_COLOR: (u8, u8, u8, u8) = (10, 10, 16, 255);
+pub const INVENTORY_BAR_FILL_COLOR: (u8, u8, u8, u8) = (110, 190, 120, 255);
+const INVENTORY_KEY_USED_COLOR: (u8, u8, u8, u8) = (90, 90, 100, 255);
+const INVENTORY_COUNT_COLOR: (u8, u8, u8, u8) = (255, 255, 255, 255);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InventoryItemKind {
+	Coins,
+	Book,
+	Key,
+}
+
+#[derive(Clone, Debug)]
+pub struct InventoryItemSnapshot {
+	pub kind: InventoryItemKind,
+	pub name: String,
+	// "Page 12/200", "Used", the coin count
+	pub detail: String,
+	pub count: u16,
+	// picks the key's colour
+	pub key_id: KeyId,
+	// how far into a book the reader got, 0..=1
+	pub progress: f32,
+	pub is_used: bool,
+}
+
+impl InventoryItemSnapshot {
+	fn new() -> InventoryItemSnapshot {
+		return InventoryItemSnapshot {
+			kind: InventoryItemKind::Coins,
+			name: String::new(),
+			detail: String::new(),
+			count: 0,
+			key_id: 0,
+			progress: 0.0,
+			is_used: false,
+		};
+	}
+}
+
+// the inventory with every name already looked up, in slot order
+#[derive(Clone, Debug)]
+pub struct InventorySnapshot {
+	pub title: String,
+	pub items: Vec<InventoryItemSnapshot>,
+	pub selected: usize,
+	pub hint: String,
+}
+
+impl InventorySnapshot {
+	pub fn new() -> InventorySnapshot {
+		return InventorySnapshot {
+			title: String::new(),
+			items: Vec::new(),
+			selected: 0,
+			hint: String::new(),
+		};
+	}
+
+	pub fn capture(&mut self, session: &Session) {
+		self.title.clear();
+		self.title.push_str(session.message_table.get(MESSAGE_MENU_INVENTORY));
+		self.hint.clear();
+		self.hint.push_str(session.message_table.get(MESSAGE_INVENTORY_HINT));
+
+		let slot_count: usize = session.inventory.get_slot_count();
+		self.items.truncate(slot_count);
+		while self.items.len() < slot_count {
+			self.items.push(InventoryItemSnapshot::new());
+		}
+		self.selected = session.inventory_cursor.selected.min(slot_count - 1);
+
+		for (index, item) in self.items.iter_mut().enumerate() {
+			item.name.clear();
+			item.detail.clear();
+			item.count = 0;
+			item.key_id = 0;
+			item.progress = 0.0;
+			item.is_used = false;
+
+			match session.inventory.get_slot(index) {
+				Some(InventorySlot::Coins) => {
+					item.kind = InventoryItemKind::Coins;
+					item.name.push_str(session.message_table.get(MESSAGE_MENU_COINS));
+					item.count = session.inventory.coins;
+					let _ = write!(item.detail, "{}", item.count);
+				}
+				Some(InventorySlot::Book(book_index)) => {
+					let book: &Book = &session.inventory.books[book_index];
+					item.kind = InventoryItemKind::Book;
+					write_item_name(&mut item.name, session, PICKUP_MAP_BOOK_PREFIX, book.book_id);
+					let _ = write!(
+						item.detail,
+						"{} {}/{}",
+						session.message_table.get(MESSAGE_INVENTORY_PAGE),
+						book.current_page + 1,
+						book.total_pages.max(1)
+					);
+					if book.total_pages > 1 {
+						item.progress = (book.current_page as f32 / (book.total_pages - 1) as f32).clamp(0.0, 1.0);
+					}
+				}
+				Some(InventorySlot::Key(key_index)) => {
+					let key: &Key = &session.inventory.keys[key_index];
+					item.kind = InventoryItemKind::Key;
+					item.key_id = key.key_id;
+					item.is_used = key.is_used;
+					write_item_name(&mut item.name, session, PICKUP_MAP_KEY_PREFIX, key.key_id);
+					let detail_id: u16 = if key.is_used { MESSAGE_INVENTORY_KEY_USED } else { MESSAGE_INVENTORY_KEY_UNUSED };
+					item.detail.push_str(session.message_table.get(detail_id));
+				}
+				None => {}
+			}
+		}
+
+		return;
+	}
+}
+
+// the item_<prefix>_<slug> message if the language has one, the pickup map's slug if not, and
+// the bare id for something the map doesn't know
+fn write_item_name(name: &mut String, session: &Session, prefix: &str, id: u16) {
+	let Some(slug) = session.pickup_map.get_slug(prefix, id) else {
+		let _ = write!(name, "{} {}", prefix, id);
+		return;
+	};
+
+	let mut message_name: String = String::new();
+	let _ = write!(message_name, "item_{}_{}", prefix, slug.replace(':', "_"));
+	match session.message_table.get_by_name(message_name.as_str()) {
+		Some(text) => name.push_str(text),
+		None => name.push_str(slug),
+	}
+	return;
+}
+
+// screen rectangles of the inventory screen, in screen pixels
+#[derive(Copy, Clone, Debug)]
+pub struct InventoryLayout {
+	pub panel_left: i32,
+	pub panel_top: i32,
+	pub panel_width: u32,
+	pub panel_height: u32,
+	pub border_size: u32,
+	pub title_top: i32,
+	pub title_height: u32,
+	pub grid_left: i32,
+	pub grid_top: i32,
+	pub cell_size: u32,
+	pub cell_gap: u32,
+	// the selected item's name, its detail and the key hint, one line each under the grid
+	pub text_left: i32,
+	pub text_width: u32,
+	pub line_height: u32,
+	pub name_top: i32,
+	pub detail_top: i32,
+	pub hint_top: i32,
+	pixel: i32,
+}
+
+impl InventoryLayout {
+	pub fn get_cell(&self, index: usize) -> (i32, i32) {
+		let step: i32 = (self.cell_size + self.cell_gap) as i32;
+		let column: i32 = (index % INVENTORY_COLUMNS) as i32;
+		let row: i32 = (index / INVENTORY_COLUMNS) as i32;
+		return (self.grid_left + column * step, self.grid_top + row * step);
+	}
+
+	// the whole bar along the bottom of a book's cell, and how much of it is filled
+	pub fn get_progress_bar(&self, index: usize, progress: f32) -> (i32, i32, u32, u32, u32) {
+		let (cell_left, cell_top) = self.get_cell(index);
+		let width: u32 = (self.pixel * 14) as u32;
+		let fill_width: u32 = (width as f32 * progress.clamp(0.0, 1.0)) as u32;
+		return (cell_left + self.pixel * 3, cell_top + self.pixel * 16, width, (self.pixel * 2) as u32, fill_width);
+	}
+}
+
+// a panel in the middle of the screen: the title, a grid of INVENTORY_COLUMNS cells, then the
+// selected item's name and detail. the icons, keys and coin count go into `elements` to be drawn
+// over the cells like the hud
+pub fn layout_inventory(inventory: &InventorySnapshot, screen_width: i32, screen_height: i32, elements: &mut Vec<HudElement>) -> InventoryLayout {
+	elements.clear();
+
+	let pixel: i32 = (screen_height / INVENTORY_REFERENCE_HEIGHT_PIXELS).max(1);
+	let padding: i32 = pixel * 5;
+	let cell_size: i32 = pixel * 20;
+	let cell_gap: i32 = pixel * 4;
+	let line_height: i32 = pixel * 8;
+	let title_height: i32 = pixel * 12;
+
+	let columns: i32 = INVENTORY_COLUMNS as i32;
+	let rows: i32 = (inventory.items.len() as i32 + columns - 1) / columns;
+	let grid_width: i32 = columns * cell_size + (columns - 1) * cell_gap;
+	let grid_height: i32 = rows.max(1) * cell_size + (rows.max(1) - 1) * cell_gap;
+
+	let panel_width: i32 = grid_width + padding * 2;
+	let panel_height: i32 = padding * 3 + title_height + grid_height + line_height * 3;
+	let panel_left: i32 = (screen_width - panel_width) / 2;
+	let panel_top: i32 = (screen_height - panel_height) / 2;
+	let grid_top: i32 = panel_top + padding + title_height;
+	let name_top: i32 = grid_top + grid_height + padding;
+
+	let layout: InventoryLayout = InventoryLayout {
+		panel_left,
+		panel_top,
+		panel_width: panel_width as u32,
+		panel_height: panel_height as u32,
+		border_size: (pixel / 2).max(1) as u32,
+		title_top: panel_top + padding,
+		title_height: title_height as u32,
+		grid_left: panel_left + padding,
+		grid_top,
+		cell_size: cell_size as u32,
+		cell_gap: cell_gap as u32,
+		text_left: panel_left + padding,
+		text_width: grid_width as u32,
+		line_height: line_height as u32,
+		name_top,
+		detail_top: name_top + line_height,
+		hint_top: name_top + line_height * 2,
+		pixel,
+	};
+
+	for (index, item) in inventory.items.iter().enumerate() {
+		let (cell_left, cell_top) = layout.get_cell(index);
+		match item.kind {
+			InventoryItemKind::Coins => {
+				elements.push(HudElement::Icon {
+					icon_id: HUD_COIN_ICON_ID,
+					left: cell_left + pixel * 4,
+					top: cell_top + pixel,
+					size: (pixel * 12) as u32,
+				});
+				let count_left: i32 = cell_left + (cell_size - get_number_width(item.count as u32, pixel)) / 2;
+				push_number(elements, item.count as u32, count_left, cell_top + pixel * 14, pixel, INVENTORY_COUNT_COLOR);
+			}
+			InventoryItemKind::Book => {
+				elements.push(HudElement::Icon {
+					icon_id: INVENTORY_BOOK_ICON_ID,
+					left: cell_left + pixel * 4,
+					top: cell_top + pixel * 2,
+					size: (pixel * 12) as u32,
+				});
+			}
+			InventoryItemKind::Key => {
+				// the hud's key glyph at twice its size; a used key is greyed out
+				let color: (u8, u8, u8, u8) = if item.is_used { INVENTORY_KEY_USED_COLOR } else { get_key_color(item.key_id) };
+				push_glyph(elements, HudGlyph::Key, cell_left + pixel * 3, cell_top + pixel * 5, pixel * 2, color);
+			}
+		}
+	}
+
+	return layout;
+}
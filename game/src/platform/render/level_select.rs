@@ -0,0 +1,261 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt::Write;
+
+use crate::{
+	platform::render::hud::{HudElement, get_number_width, push_number},
+	runtime::{
+		menu::{MESSAGE_MENU_LEVEL_COMPLETION, MESSAGE_MENU_SELECT_LEVEL},
+		progress::LevelKey,
+		session::Session,
+		world_index::WorldEntry,
+	},
+};
+
+// the level select screen's own text
+const MESSAGE_LEVEL_SELECT_LOCKED: u16 = 147;
+const MESSAGE_LEVEL_SELECT_COMPLETED: u16 = 148;
+const MESSAGE_LEVEL_SELECT_NOT_COMPLETED: u16 = 149;
+const MESSAGE_LEVEL_SELECT_HINT: u16 = 150;
+
+// same scale as the hud and the menus
+const LEVEL_SELECT_REFERENCE_HEIGHT_PIXELS: i32 = 180;
+
+// the grid is never narrower than this many cells, so a world with one level doesn't squeeze the text
+const LEVEL_SELECT_MIN_COLUMNS: i32 = 6;
+
+pub const LEVEL_SELECT_LOCKED_CELL_COLOR: (u8, u8, u8, u8) = (14, 14, 20, 255);
+pub const LEVEL_SELECT_COMPLETED_CELL_COLOR: (u8, u8, u8, u8) = (36, 64, 44, 255);
+const LEVEL_SELECT_NUMBER_COLOR: (u8, u8, u8, u8) = (255, 255, 255, 255);
+const LEVEL_SELECT_LOCKED_NUMBER_COLOR: (u8, u8, u8, u8) = (70, 70, 84, 255);
+
+#[derive(Clone, Debug)]
+pub struct LevelSelectCellSnapshot {
+	pub level_id: u16,
+	pub is_unlocked: bool,
+	pub is_completed: bool,
+	// the share of the level's pickups found, 0..=100
+	pub completion_percent: u8,
+}
+
+#[derive(Clone, Debug)]
+pub struct LevelSelectRowSnapshot {
+	// the world's display name
+	pub name: String,
+	pub cells: Vec<LevelSelectCellSnapshot>,
+}
+
+// the world index with the player's progress folded in, one row per world
+#[derive(Clone, Debug)]
+pub struct LevelSelectSnapshot {
+	pub title: String,
+	pub rows: Vec<LevelSelectRowSnapshot>,
+	pub selected_row: usize,
+	pub selected_column: usize,
+	// the selected level's display name, whether it's done, and how much of it was found
+	pub name: String,
+	pub status: String,
+	pub completion: String,
+	pub hint: String,
+}
+
+impl LevelSelectSnapshot {
+	pub fn new() -> LevelSelectSnapshot {
+		return LevelSelectSnapshot {
+			title: String::new(),
+			rows: Vec::new(),
+			selected_row: 0,
+			selected_column: 0,
+			name: String::new(),
+			status: String::new(),
+			completion: String::new(),
+			hint: String::new(),
+		};
+	}
+
+	pub fn capture(&mut self, session: &Session) {
+		self.title.clear();
+		self.title.push_str(session.message_table.get(MESSAGE_MENU_SELECT_LEVEL));
+		self.hint.clear();
+		self.hint.push_str(session.message_table.get(MESSAGE_LEVEL_SELECT_HINT));
+		self.name.clear();
+		self.status.clear();
+		self.completion.clear();
+
+		let worlds: &[WorldEntry] = &session.world_index.worlds;
+		self.rows.truncate(worlds.len());
+		while self.rows.len() < worlds.len() {
+			self.rows.push(LevelSelectRowSnapshot {
+				name: String::new(),
+				cells: Vec::new(),
+			});
+		}
+
+		for (row, world) in self.rows.iter_mut().zip(worlds) {
+			row.name.clear();
+			row.name.push_str(world.name.as_str());
+			row.cells.clear();
+			for level in session.world_index.levels.iter().filter(|l| l.key.world_id == world.world_id) {
+				row.cells.push(LevelSelectCellSnapshot {
+					level_id: level.key.level_id,
+					is_unlocked: session.world_index.is_unlocked(level.key, &session.progress),
+					is_completed: session.progress.is_level_completed(level.key),
+					completion_percent: session.progress.get_completion_percent(level.key),
+				});
+			}
+		}
+
+		self.selected_row = session.level_select_cursor.row;
+		self.selected_column = session.level_select_cursor.column;
+
+		let Some(world) = worlds.get(self.selected_row) else {
+			return;
+		};
+		let Some(level) = session.world_index.get_world_level(world.world_id, self.selected_column) else {
+			return;
+		};
+		let key: LevelKey = level.key;
+		self.name.push_str(level.name.as_str());
+
+		if !session.world_index.is_unlocked(key, &session.progress) {
+			self.status.push_str(session.message_table.get(MESSAGE_LEVEL_SELECT_LOCKED));
+			return;
+		}
+
+		let status_id: u16 = if session.progress.is_level_completed(key) {
+			MESSAGE_LEVEL_SELECT_COMPLETED
+		} else {
+			MESSAGE_LEVEL_SELECT_NOT_COMPLETED
+		};
+		self.status.push_str(session.message_table.get(status_id));
+		let _ = write!(
+			self.completion,
+			"{}: {}%",
+			session.message_table.get(MESSAGE_MENU_LEVEL_COMPLETION),
+			session.progress.get_completion_percent(key)
+		);
+		return;
+	}
+}
+
+// screen rectangles of the level select screen, in screen pixels
+#[derive(Copy, Clone, Debug)]
+pub struct LevelSelectLayout {
+	pub panel_left: i32,
+	pub panel_top: i32,
+	pub panel_width: u32,
+	pub panel_height: u32,
+	pub border_size: u32,
+	pub title_top: i32,
+	pub title_height: u32,
+	pub grid_left: i32,
+	pub grid_top: i32,
+	pub cell_size: u32,
+	pub cell_gap: u32,
+	// each world's name sits one line above its row of cells; the selected level's name, status,
+	// pickups found and the key hint follow the grid one line each
+	pub text_left: i32,
+	pub text_width: u32,
+	pub line_height: u32,
+	pub name_top: i32,
+	pub status_top: i32,
+	pub completion_top: i32,
+	pub hint_top: i32,
+	pixel: i32,
+}
+
+impl LevelSelectLayout {
+	fn get_row_step(&self) -> i32 {
+		return (self.line_height + self.cell_size + self.cell_gap) as i32;
+	}
+
+	pub fn get_row_label_top(&self, row: usize) -> i32 {
+		return self.grid_top + row as i32 * self.get_row_step();
+	}
+
+	pub fn get_cell(&self, row: usize, column: usize) -> (i32, i32) {
+		let step: i32 = (self.cell_size + self.cell_gap) as i32;
+		return (self.grid_left + column as i32 * step, self.get_row_label_top(row) + self.line_height as i32);
+	}
+
+	// the bar along the bottom of a level's cell for the pickups found, and how much of it is filled
+	pub fn get_progress_bar(&self, row: usize, column: usize, percent: u8) -> (i32, i32, u32, u32, u32) {
+		let (cell_left, cell_top) = self.get_cell(row, column);
+		let width: u32 = (self.pixel * 14) as u32;
+		let fill_width: u32 = width * percent.min(100) as u32 / 100;
+		return (cell_left + self.pixel * 3, cell_top + self.pixel * 16, width, (self.pixel * 2) as u32, fill_width);
+	}
+}
+
+// a panel in the middle of the screen: the title, then per world its name over a row of level
+// cells, then the selected level's details. level numbers go into `elements` like the hud
+pub fn layout_level_select(level_select: &LevelSelectSnapshot, screen_width: i32, screen_height: i32, elements: &mut Vec<HudElement>) -> LevelSelectLayout {
+	elements.clear();
+
+	let pixel: i32 = (screen_height / LEVEL_SELECT_REFERENCE_HEIGHT_PIXELS).max(1);
+	let padding: i32 = pixel * 5;
+	let cell_size: i32 = pixel * 20;
+	let cell_gap: i32 = pixel * 4;
+	let line_height: i32 = pixel * 8;
+	let title_height: i32 = pixel * 12;
+
+	let columns: i32 = level_select
+		.rows
+		.iter()
+		.map(|r| r.cells.len() as i32)
+		.max()
+		.unwrap_or(0)
+		.max(LEVEL_SELECT_MIN_COLUMNS);
+	let rows: i32 = (level_select.rows.len() as i32).max(1);
+	let grid_width: i32 = columns * cell_size + (columns - 1) * cell_gap;
+	let grid_height: i32 = rows * (line_height + cell_size + cell_gap) - cell_gap;
+
+	let panel_width: i32 = grid_width + padding * 2;
+	let panel_height: i32 = padding * 3 + title_height + grid_height + line_height * 4;
+	let panel_left: i32 = (screen_width - panel_width) / 2;
+	let panel_top: i32 = (screen_height - panel_height) / 2;
+	let grid_top: i32 = panel_top + padding + title_height;
+	let name_top: i32 = grid_top + grid_height + padding;
+
+	let layout: LevelSelectLayout = LevelSelectLayout {
+		panel_left,
+		panel_top,
+		panel_width: panel_width as u32,
+		panel_height: panel_height as u32,
+		border_size: (pixel / 2).max(1) as u32,
+		title_top: panel_top + padding,
+		title_height: title_height as u32,
+		grid_left: panel_left + padding,
+		grid_top,
+		cell_size: cell_size as u32,
+		cell_gap: cell_gap as u32,
+		text_left: panel_left + padding,
+		text_width: grid_width as u32,
+		line_height: line_height as u32,
+		name_top,
+		status_top: name_top + line_height,
+		completion_top: name_top + line_height * 2,
+		hint_top: name_top + line_height * 3,
+		pixel,
+	};
+
+	// the level number at twice the hud's size, centred in the top of the cell
+	for (row_index, row) in level_select.rows.iter().enumerate() {
+		for (column_index, cell) in row.cells.iter().enumerate() {
+			let (cell_left, cell_top) = layout.get_cell(row_index, column_index);
+			let color: (u8, u8, u8, u8) = if cell.is_unlocked {
+				LEVEL_SELECT_NUMBER_COLOR
+			} else {
+				LEVEL_SELECT_LOCKED_NUMBER_COLOR
+			};
+			let number_left: i32 = cell_left + (cell_size - get_number_width(cell.level_id as u32, pixel * 2)) / 2;
+			push_number(elements, cell.level_id as u32, number_left, cell_top + pixel * 3, pixel * 2, color);
+		}
+	}
+
+	return layout;
+}
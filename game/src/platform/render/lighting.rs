@@ -0,0 +1,238 @@
+use crate::{
+	platform::render::snapshot::RenderSnapshot,
+	runtime::triggers::{LevelTrigger, TriggerKind},
+	tile::TileKind,
+};
+
+// lightmap cells are this many world units square; 2 is 8 screen pixels at the default scale
+const LIGHTMAP_CELL_SIZE_WORLD: f32 = 2.0;
+
+// torch_glow tiles light themselves; radius in tiles
+const TORCH_LIGHT_RADIUS_TILES: f32 = 3.5;
+const TORCH_LIGHT_COLOR: (u8, u8, u8) = (255, 190, 110);
+
+// a light in world units, ready to be splatted into the lightmap
+#[derive(Copy, Clone, Debug)]
+pub struct PointLight {
+	pub center_left: f32,
+	pub center_top: f32,
+	pub radius: f32,
+	pub color: (u8, u8, u8),
+}
+
+// low resolution rgb light levels for the visible part of the level. cells are aligned to the
+// world grid so the lighting doesn't swim when the camera moves
+pub struct Lightmap {
+	// world position of cell 0,0
+	pub left: f32,
+	pub top: f32,
+	pub cell_size: f32,
+	pub columns: u32,
+	pub rows: u32,
+	// rgb per cell, row major
+	pub pixels: Vec<u8>,
+	lights: Vec<PointLight>,
+}
+
+impl Lightmap {
+	pub fn new() -> Lightmap {
+		return Lightmap {
+			left: 0.0,
+			top: 0.0,
+			cell_size: LIGHTMAP_CELL_SIZE_WORLD,
+			columns: 0,
+			rows: 0,
+			pixels: Vec::new(),
+			lights: Vec::new(),
+		};
+	}
+
+	// false when the level isn't dark, so the renderer can skip compositing entirely
+	pub fn build(&mut self, snapshot: &RenderSnapshot, view_width_world: f32, view_height_world: f32) -> bool {
+		if snapshot.level.darkness == 0 {
+			return false;
+		}
+
+		let camera_left: f32 = snapshot.camera_left as f32;
+		let camera_top: f32 = snapshot.camera_top as f32;
+
+		self.left = (camera_left / self.cell_size).floor() * self.cell_size;
+		self.top = (camera_top / self.cell_size).floor() * self.cell_size;
+		self.columns = (view_width_world / self.cell_size).ceil() as u32 + 1;
+		self.rows = (view_height_world / self.cell_size).ceil() as u32 + 1;
+
+		let ambient: u8 = 255 - snapshot.level.darkness;
+		self.pixels.clear();
+		self.pixels.resize((self.columns * self.rows * 3) as usize, ambient);
+
+		self.collect_lights(snapshot, camera_left, camera_top, view_width_world, view_height_world);
+		for index in 0..self.lights.len() {
+			let light: PointLight = self.lights[index];
+			self.add_light(snapshot, &light);
+		}
+
+		return true;
+	}
+
+	fn collect_lights(&mut self, snapshot: &RenderSnapshot, camera_left: f32, camera_top: f32, view_width_world: f32, view_height_world: f32) {
+		self.lights.clear();
+
+		let tile_width: f32 = snapshot.level.tile_width as f32;
+		let tile_height: f32 = snapshot.level.tile_height as f32;
+
+		for trigger in &snapshot.level.triggers {
+			if TriggerKind::from_u8(trigger.kind) != TriggerKind::Light {
+				continue;
+			}
+
+			let radius: f32 = trigger.get_light_radius() as f32;
+			let color: (u8, u8, u8) = trigger.get_light_color();
+			let attached_kind: u8 = trigger.get_light_attached_kind();
+
+			if attached_kind == 0 {
+				let (center_left, center_top) = get_trigger_center(trigger, tile_width, tile_height);
+				self.lights.push(PointLight {
+					center_left,
+					center_top,
+					radius,
+					color,
+				});
+				continue;
+			}
+
+			for entity in &snapshot.entities {
+				if entity.kind as u8 != attached_kind {
+					continue;
+				}
+				self.lights.push(PointLight {
+					center_left: entity.position.x,
+					center_top: entity.position.y,
+					radius,
+					color,
+				});
+			}
+		}
+
+		// torches just outside the view still light its edge
+		let margin: f32 = TORCH_LIGHT_RADIUS_TILES * tile_width;
+		let start_left: i32 = ((camera_left - margin) / tile_width).floor().max(0.0) as i32;
+		let start_top: i32 = ((camera_top - margin) / tile_height).floor().max(0.0) as i32;
+		let end_left: i32 = ((camera_left + view_width_world + margin) / tile_width).ceil() as i32;
+		let end_top: i32 = ((camera_top + view_height_world + margin) / tile_height).ceil() as i32;
+
+		for layer in 0..(snapshot.level.layer_count as u32) {
+			for tile_top in start_top..end_top {
+				for tile_left in start_left..end_left {
+					if snapshot.level.get_tile_at_layer(layer, tile_left, tile_top) != TileKind::TorchGlow {
+						continue;
+					}
+
+					self.lights.push(PointLight {
+						center_left: (tile_left as f32 + 0.5) * tile_width,
+						center_top: (tile_top as f32 + 0.5) * tile_height,
+						radius: TORCH_LIGHT_RADIUS_TILES * tile_width * get_flicker(snapshot.tick, tile_left, tile_top),
+						color: TORCH_LIGHT_COLOR,
+					});
+				}
+			}
+		}
+
+		return;
+	}
+
+	fn add_light(&mut self, snapshot: &RenderSnapshot, light: &PointLight) {
+		if light.radius <= 0.0 {
+			return;
+		}
+
+		let first_column: i32 = (((light.center_left - light.radius - self.left) / self.cell_size).floor() as i32).max(0);
+		let first_row: i32 = (((light.center_top - light.radius - self.top) / self.cell_size).floor() as i32).max(0);
+		let last_column: i32 = (((light.center_left + light.radius - self.left) / self.cell_size).ceil() as i32).min(self.columns as i32 - 1);
+		let last_row: i32 = (((light.center_top + light.radius - self.top) / self.cell_size).ceil() as i32).min(self.rows as i32 - 1);
+
+		for row in first_row..=last_row {
+			for column in first_column..=last_column {
+				let cell_left: f32 = self.left + (column as f32 + 0.5) * self.cell_size;
+				let cell_top: f32 = self.top + (row as f32 + 0.5) * self.cell_size;
+
+				let distance_left: f32 = cell_left - light.center_left;
+				let distance_top: f32 = cell_top - light.center_top;
+				let distance: f32 = (distance_left * distance_left + distance_top * distance_top).sqrt();
+				if distance >= light.radius {
+					continue;
+				}
+
+				if is_occluded(snapshot, light.center_left, light.center_top, cell_left, cell_top) {
+					continue;
+				}
+
+				let falloff: f32 = 1.0 - distance / light.radius;
+				let intensity: f32 = falloff * falloff;
+				let index: usize = ((row as u32 * self.columns + column as u32) * 3) as usize;
+				self.pixels[index] = add_light_channel(self.pixels[index], light.color.0, intensity);
+				self.pixels[index + 1] = add_light_channel(self.pixels[index + 1], light.color.1, intensity);
+				self.pixels[index + 2] = add_light_channel(self.pixels[index + 2], light.color.2, intensity);
+			}
+		}
+
+		return;
+	}
+}
+
+fn get_trigger_center(trigger: &LevelTrigger, tile_width: f32, tile_height: f32) -> (f32, f32) {
+	let center_left: f32 = (trigger.left + trigger.width * 0.5) * tile_width;
+	let center_top: f32 = (trigger.top + trigger.height * 0.5) * tile_height;
+	return (center_left, center_top);
+}
+
+// simple hard shadows: walk from the light towards the cell in half-tile steps and stop at the
+// first solid tile. the tile the cell sits in doesn't count, and inside a solid tile the walk
+// aims at the point of that tile nearest the light, so a wall facing a light is lit evenly
+fn is_occluded(snapshot: &RenderSnapshot, light_left: f32, light_top: f32, cell_left: f32, cell_top: f32) -> bool {
+	let tile_width: f32 = snapshot.level.tile_width as f32;
+	let tile_height: f32 = snapshot.level.tile_height as f32;
+
+	let target_tile_left: i32 = (cell_left / tile_width).floor() as i32;
+	let target_tile_top: i32 = (cell_top / tile_height).floor() as i32;
+
+	let (mut target_left, mut target_top) = (cell_left, cell_top);
+	if snapshot.level.is_solid_at_tile(target_tile_left, target_tile_top) {
+		// a hair inside the tile so the walk still ends in it
+		let inset: f32 = 0.01;
+		let tile_left_world: f32 = target_tile_left as f32 * tile_width;
+		let tile_top_world: f32 = target_tile_top as f32 * tile_height;
+		target_left = light_left.clamp(tile_left_world + inset, tile_left_world + tile_width - inset);
+		target_top = light_top.clamp(tile_top_world + inset, tile_top_world + tile_height - inset);
+	}
+
+	let distance_left: f32 = target_left - light_left;
+	let distance_top: f32 = target_top - light_top;
+	let step_size: f32 = tile_width.min(tile_height) * 0.5;
+	let steps: i32 = ((distance_left.abs().max(distance_top.abs())) / step_size).ceil() as i32;
+
+	for step in 1..steps {
+		let t: f32 = step as f32 / steps as f32;
+		let tile_left: i32 = ((light_left + distance_left * t) / tile_width).floor() as i32;
+		let tile_top: i32 = ((light_top + distance_top * t) / tile_height).floor() as i32;
+
+		if tile_left == target_tile_left && tile_top == target_tile_top {
+			return false;
+		}
+		if snapshot.level.is_solid_at_tile(tile_left, tile_top) {
+			return true;
+		}
+	}
+
+	return false;
+}
+
+// a few percent of radius wobble, different per torch
+fn get_flicker(tick: u32, tile_left: i32, tile_top: i32) -> f32 {
+	let seed: f32 = (tile_left * 7 + tile_top * 13) as f32;
+	let time: f32 = tick as f32;
+	return 1.0 + 0.05 * (time * 0.23 + seed).sin() + 0.03 * (time * 0.61 + seed * 1.7).sin();
+}
+
+fn add_light_channel(current: u8, light: u8, intensity: f32) -> u8 {
+	return (current as f32 + light as f32 * intensity).min(255.0) as u8;
+}
@@ -0,0 +1,149 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt::Write;
+
+use crate::runtime::{
+	game_mode::GameMode,
+	menu::{MESSAGE_MENU_OFF, MESSAGE_MENU_ON, Menu, MenuAction, MenuValue},
+	session::Session,
+	settings::VOLUME_LEVEL_MAX,
+};
+
+// same scale as the hud: menu pixels per 180 rows of screen
+const MENU_REFERENCE_HEIGHT_PIXELS: i32 = 180;
+
+// laid over the level behind a menu; the title screen hides more of it
+pub const MENU_DIM_COLOR: (u8, u8, u8, u8) = (0, 0, 0, 140);
+pub const MENU_TITLE_SCREEN_DIM_COLOR: (u8, u8, u8, u8) = (0, 0, 0, 190);
+pub const MENU_PANEL_COLOR: (u8, u8, u8, u8) = (20, 20, 28, 235);
+pub const MENU_BORDER_COLOR: (u8, u8, u8, u8) = (90, 90, 120, 255);
+pub const MENU_SELECTION_COLOR: (u8, u8, u8, u8) = (60, 60, 96, 255);
+pub const MENU_TITLE_COLOR: (u8, u8, u8, u8) = (255, 255, 255, 255);
+pub const MENU_TEXT_COLOR: (u8, u8, u8, u8) = (200, 200, 220, 255);
+pub const MENU_SELECTED_TEXT_COLOR: (u8, u8, u8, u8) = (255, 230, 90, 255);
+
+#[derive(Clone, Debug)]
+pub struct MenuLineSnapshot {
+	pub text: String,
+	pub is_selected: bool,
+}
+
+// the menu of the mode on top of the stack, with its text already looked up
+#[derive(Clone, Debug)]
+pub struct MenuSnapshot {
+	pub title: String,
+	pub lines: Vec<MenuLineSnapshot>,
+	// the title screen has a big title and no panel
+	pub is_title_screen: bool,
+}
+
+impl MenuSnapshot {
+	pub fn new() -> MenuSnapshot {
+		return MenuSnapshot {
+			title: String::new(),
+			lines: Vec::new(),
+			is_title_screen: false,
+		};
+	}
+
+	pub fn capture(&mut self, menu: &Menu, session: &Session) {
+		self.title.clear();
+		self.title.push_str(session.message_table.get(menu.title_id));
+		self.is_title_screen = session.modes.get_top() == GameMode::Title;
+
+		self.lines.truncate(menu.items.len());
+		while self.lines.len() < menu.items.len() {
+			self.lines.push(MenuLineSnapshot {
+				text: String::new(),
+				is_selected: false,
+			});
+		}
+
+		for (index, item) in menu.items.iter().enumerate() {
+			let line: &mut MenuLineSnapshot = &mut self.lines[index];
+			line.text.clear();
+			line.text.push_str(session.message_table.get(item.label_id));
+			match item.value {
+				MenuValue::None => {}
+				MenuValue::Toggle(is_on) => {
+					let value_id: u16 = if is_on { MESSAGE_MENU_ON } else { MESSAGE_MENU_OFF };
+					let _ = write!(line.text, ": {}", session.message_table.get(value_id));
+				}
+				MenuValue::Count(count) => {
+					let _ = write!(line.text, ": {}", count);
+				}
+				MenuValue::Level(level) => {
+					let _ = write!(line.text, ": {}/{}", level, VOLUME_LEVEL_MAX);
+				}
+				MenuValue::Language => {
+					let _ = write!(line.text, ": {}", session.settings.language_code);
+				}
+				MenuValue::TextFont(font) => {
+					let _ = write!(line.text, ": {}", font.get_name());
+				}
+				MenuValue::TextSize(size) => {
+					let _ = write!(line.text, ": {}", session.message_table.get(size.get_label_id()));
+				}
+				MenuValue::Percent(percent) => {
+					let _ = write!(line.text, ": {}%", percent);
+				}
+			}
+			line.is_selected = index == menu.selected && item.action != MenuAction::None;
+		}
+
+		return;
+	}
+}
+
+// screen rectangles of a menu, in screen pixels
+#[derive(Copy, Clone, Debug)]
+pub struct MenuLayout {
+	pub panel_left: i32,
+	pub panel_top: i32,
+	pub panel_width: u32,
+	pub panel_height: u32,
+	pub border_size: u32,
+	pub title_top: i32,
+	pub title_height: u32,
+	pub first_line_top: i32,
+	pub line_left: i32,
+	pub line_width: u32,
+	pub line_height: u32,
+}
+
+impl MenuLayout {
+	pub fn get_line_top(&self, index: usize) -> i32 {
+		return self.first_line_top + (index as u32 * self.line_height) as i32;
+	}
+}
+
+// a panel in the middle of the screen: the title, then one line per item
+pub fn layout_menu(menu: &MenuSnapshot, screen_width: i32, screen_height: i32) -> MenuLayout {
+	let pixel: i32 = (screen_height / MENU_REFERENCE_HEIGHT_PIXELS).max(1);
+	let padding: i32 = pixel * 4;
+	let line_height: i32 = pixel * 8;
+	let title_height: i32 = if menu.is_title_screen { pixel * 28 } else { pixel * 12 };
+
+	let panel_width: i32 = (pixel * 120).min(screen_width - padding * 2).max(1);
+	let panel_height: i32 = padding * 2 + title_height + menu.lines.len() as i32 * line_height;
+	let panel_left: i32 = (screen_width - panel_width) / 2;
+	let panel_top: i32 = (screen_height - panel_height) / 2;
+
+	return MenuLayout {
+		panel_left,
+		panel_top,
+		panel_width: panel_width as u32,
+		panel_height: panel_height as u32,
+		border_size: (pixel / 2).max(1) as u32,
+		title_top: panel_top + padding,
+		title_height: title_height as u32,
+		first_line_top: panel_top + padding + title_height,
+		line_left: panel_left + padding,
+		line_width: (panel_width - padding * 2).max(1) as u32,
+		line_height: line_height as u32,
+	};
+}
@@ -0,0 +1,166 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{string::String, vec::Vec};
+
+use crate::runtime::message_box::MessageBoxState;
+
+// same scale as the hud: message box pixels per 180 rows of screen
+const MESSAGE_BOX_REFERENCE_HEIGHT_PIXELS: i32 = 180;
+
+pub const MESSAGE_BOX_PANEL_COLOR: (u8, u8, u8, u8) = (20, 20, 28, 225);
+pub const MESSAGE_BOX_BORDER_COLOR: (u8, u8, u8, u8) = (90, 90, 120, 255);
+pub const MESSAGE_BOX_PORTRAIT_COLOR: (u8, u8, u8, u8) = (40, 40, 56, 255);
+pub const MESSAGE_BOX_TEXT_COLOR: (u8, u8, u8, u8) = (240, 240, 255, 255);
+pub const MESSAGE_BOX_CONTINUE_COLOR: (u8, u8, u8, u8) = (255, 230, 90, 255);
+
+// the message on screen, copied out of the session with the rest of the snapshot
+#[derive(Clone, Debug)]
+pub struct MessageBoxSnapshot {
+	// the whole text; the renderer wraps all of it so words don't jump lines as they are typed
+	pub text: String,
+	pub revealed_chars: usize,
+	pub is_fully_revealed: bool,
+	pub speaker_icon_id: u16,
+	pub is_blocking: bool,
+	pub open_ticks: u32,
+}
+
+impl MessageBoxSnapshot {
+	pub fn new() -> MessageBoxSnapshot {
+		return MessageBoxSnapshot {
+			text: String::new(),
+			revealed_chars: 0,
+			is_fully_revealed: false,
+			speaker_icon_id: 0,
+			is_blocking: false,
+			open_ticks: 0,
+		};
+	}
+
+	pub fn capture(&mut self, message_box: &MessageBoxState) {
+		let Some(message) = &message_box.current else {
+			return;
+		};
+
+		self.text.clone_from(&message.text);
+		self.revealed_chars = message_box.revealed_chars;
+		self.is_fully_revealed = message_box.is_fully_revealed();
+		self.speaker_icon_id = message.speaker_icon_id;
+		self.is_blocking = message.is_blocking;
+		self.open_ticks = message_box.open_ticks;
+		return;
+	}
+
+	// byte length of the part of the text typed out so far
+	pub fn get_revealed_len(&self) -> usize {
+		return match self.text.char_indices().nth(self.revealed_chars) {
+			Some((index, _)) => index,
+			None => self.text.len(),
+		};
+	}
+
+	// the continue marker blinks once the text is out of a message waiting for the player
+	pub fn is_continue_marker_visible(&self) -> bool {
+		return self.is_blocking && self.is_fully_revealed && (self.open_ticks / 30) % 2 == 0;
+	}
+}
+
+// screen rectangles of one message box, in screen pixels
+#[derive(Copy, Clone, Debug)]
+pub struct MessageBoxLayout {
+	pub panel_left: i32,
+	pub panel_top: i32,
+	pub panel_width: u32,
+	pub panel_height: u32,
+	pub border_size: u32,
+	// (left, top, size) of the speaker's portrait
+	pub portrait: Option<(i32, i32, u32)>,
+	pub text_left: i32,
+	pub text_top: i32,
+	pub text_width: u32,
+	// (left, top, size) of the continue marker, a triangle pointing down
+	pub continue_marker: (i32, i32, u32),
+}
+
+// the panel always has room for this many lines of text, however big the font
+const MESSAGE_BOX_MIN_TEXT_LINES: i32 = 3;
+
+// a panel along the bottom of the screen, with the portrait on its left. `text_line_height` is the
+// renderer's line spacing, 0 for a renderer that draws no text
+pub fn layout_message_box(message: &MessageBoxSnapshot, screen_width: i32, screen_height: i32, text_line_height: i32) -> MessageBoxLayout {
+	let pixel: i32 = (screen_height / MESSAGE_BOX_REFERENCE_HEIGHT_PIXELS).max(1);
+	let margin: i32 = pixel * 4;
+	let padding: i32 = pixel * 3;
+
+	let panel_width: i32 = (screen_width - margin * 2).max(1);
+	let panel_height: i32 = (pixel * 34).max(padding * 2 + text_line_height * MESSAGE_BOX_MIN_TEXT_LINES);
+	let panel_left: i32 = margin;
+	let panel_top: i32 = screen_height - margin - panel_height;
+
+	let mut text_left: i32 = panel_left + padding;
+	let mut portrait: Option<(i32, i32, u32)> = None;
+	if message.speaker_icon_id != 0 {
+		let size: i32 = panel_height - padding * 2;
+		portrait = Some((panel_left + padding, panel_top + padding, size as u32));
+		text_left += size + padding;
+	}
+
+	let continue_size: i32 = pixel * 4;
+	return MessageBoxLayout {
+		panel_left,
+		panel_top,
+		panel_width: panel_width as u32,
+		panel_height: panel_height as u32,
+		border_size: (pixel / 2).max(1) as u32,
+		portrait,
+		text_left,
+		text_top: panel_top + padding,
+		text_width: (panel_left + panel_width - padding - text_left).max(1) as u32,
+		continue_marker: (
+			panel_left + panel_width - padding - continue_size,
+			panel_top + panel_height - padding - continue_size / 2,
+			continue_size as u32,
+		),
+	};
+}
+
+// greedy word wrap: fills `lines` with byte ranges of `text` no wider than `max_width` as told by
+// `measure`. a word wider than the line gets a line of its own; '\n' always breaks
+pub fn wrap_text<F>(text: &str, max_width: u32, measure: F, lines: &mut Vec<(usize, usize)>)
+where
+	F: Fn(&str) -> u32,
+{
+	lines.clear();
+
+	let mut paragraph_start: usize = 0;
+	for paragraph in text.split('\n') {
+		let mut line_start: usize = paragraph_start;
+		let mut line_end: usize = paragraph_start;
+
+		let mut word_start: usize = paragraph_start;
+		for word in paragraph.split(' ') {
+			let word_end: usize = word_start + word.len();
+			let next_word_start: usize = word_end + 1;
+			if word.is_empty() {
+				word_start = next_word_start;
+				continue;
+			}
+
+			if line_end == line_start {
+				line_start = word_start;
+			} else if measure(&text[line_start..word_end]) > max_width {
+				lines.push((line_start, line_end));
+				line_start = word_start;
+			}
+			line_end = word_end;
+			word_start = next_word_start;
+		}
+
+		lines.push((line_start, line_end));
+		paragraph_start += paragraph.len() + 1;
+	}
+
+	return;
+}
@@ -1,46 +1,50 @@
 use crate::platform::input;
 
 pub mod backend;
+pub mod background;
 pub mod common;
+pub mod ghost;
+pub mod hud;
+pub mod inventory;
+pub mod level_select;
+pub mod message_box;
+pub mod palette;
+pub mod results;
 pub mod icon_registry;
+pub mod menu;
+pub mod snapshot;
+pub mod transition;
+
+#[cfg(feature = "pc")]
+pub mod atlas;
+
+#[cfg(feature = "pc")]
+pub mod lighting;
+
+#[cfg(feature = "pc")]
+pub mod triple_buffer;
 
 #[cfg(feature = "pc")]
 pub mod pc;
 
+#[cfg(feature = "pc")]
+pub mod software;
+
 #[cfg(feature = "gba")]
 pub mod gba;
 
 #[cfg(feature = "psp")]
 pub mod psp;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-#[repr(u8)]
-pub enum BackgroundId {
-	None = 0,
-	Library = 1,
-	ParallaxForest = 2,
-}
-impl BackgroundId {
-	pub fn from_u8(value: u8) -> BackgroundId {
-		return match value {
-			1 => BackgroundId::Library,
-			2 => BackgroundId::ParallaxForest,
-			_ => BackgroundId::None,
-		};
-	}
-
-	pub fn to_u8(&self) -> u8 {
-		return match self {
-			BackgroundId::Library => 1,
-			BackgroundId::ParallaxForest => 2,
-			_ => 0,
-		};
-	}
-}
+pub use background::BackgroundId;
 
 pub struct BackgroundDrawParams {
 	pub background_id: BackgroundId,
 	pub camera_left: i32,
 	pub camera_top: i32,
 	pub scale: f32,
+	// world units, for layers anchored to the bottom of the level
+	pub level_height: i32,
+	// drives the layers' auto-scroll
+	pub tick: u32,
 }
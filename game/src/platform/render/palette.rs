@@ -0,0 +1,30 @@
+use crate::tile::TileKind;
+
+// high contrast: the background is mostly blacked out and tiles become flat colours by what they
+// do, so ground, platforms and hazards can be told apart without reading the art
+pub const HIGH_CONTRAST_BACKGROUND_DIM_COLOR: (u8, u8, u8, u8) = (0, 0, 0, 215);
+pub const HIGH_CONTRAST_SOLID_COLOR: (u8, u8, u8, u8) = (235, 235, 235, 255);
+pub const HIGH_CONTRAST_PLATFORM_COLOR: (u8, u8, u8, u8) = (60, 190, 255, 255);
+pub const HIGH_CONTRAST_HAZARD_COLOR: (u8, u8, u8, u8) = (255, 40, 40, 255);
+pub const HIGH_CONTRAST_LIQUID_COLOR: (u8, u8, u8, u8) = (40, 80, 255, 255);
+pub const HIGH_CONTRAST_SIGN_COLOR: (u8, u8, u8, u8) = (255, 210, 0, 255);
+
+// None keeps the tile's normal look; the colour-only tiles are flat already
+pub fn get_high_contrast_tile_color(tile_kind: TileKind) -> Option<(u8, u8, u8, u8)> {
+	if tile_kind.is_hazard() {
+		return Some(HIGH_CONTRAST_HAZARD_COLOR);
+	}
+
+	return match tile_kind {
+		TileKind::Dirt | TileKind::GrassTop | TileKind::Stone => Some(HIGH_CONTRAST_SOLID_COLOR),
+		TileKind::MovingPlatformLeft
+		| TileKind::MovingPlatformMiddle
+		| TileKind::MovingPlatformRight
+		| TileKind::PlatformLeft
+		| TileKind::PlatformMiddle
+		| TileKind::PlatformRight => Some(HIGH_CONTRAST_PLATFORM_COLOR),
+		TileKind::Water | TileKind::WaterBody => Some(HIGH_CONTRAST_LIQUID_COLOR),
+		TileKind::SignBegin | TileKind::SignEnd => Some(HIGH_CONTRAST_SIGN_COLOR),
+		_ => None,
+	};
+}
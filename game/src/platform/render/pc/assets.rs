@@ -0,0 +1,172 @@
+use crate::{
+	debugln,
+	platform::render::atlas::{AtlasRegion, TextureAtlas},
+};
+use sdl2::{
+	image::LoadTexture,
+	pixels::PixelFormatEnum,
+	render::{BlendMode, Texture, TextureCreator},
+	video::WindowContext,
+};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	time::SystemTime,
+};
+
+// how often (in frames) debug builds look for changed files
+const HOT_RELOAD_INTERVAL_FRAMES: u32 = 30;
+
+// a texture loaded through PcAssets; stays valid when the file is reloaded
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TextureHandle(pub u16);
+
+struct CachedTexture<'a> {
+	path: PathBuf,
+	modified: Option<SystemTime>,
+	texture: Texture<'a>,
+}
+
+// every texture the pc renderer draws from: standalone textures (tiles, backgrounds) by handle,
+// plus the sprite atlas uploaded as one texture per page
+pub struct PcAssets<'a> {
+	texture_creator: &'a TextureCreator<WindowContext>,
+	textures: Vec<CachedTexture<'a>>,
+	pub atlas: TextureAtlas,
+	atlas_pages: Vec<Texture<'a>>,
+	atlas_generation: u32,
+	frames_until_reload_check: u32,
+}
+
+impl<'a> PcAssets<'a> {
+	pub fn new(texture_creator: &'a TextureCreator<WindowContext>) -> PcAssets<'a> {
+		return PcAssets {
+			texture_creator,
+			textures: Vec::new(),
+			atlas: TextureAtlas::new(),
+			atlas_pages: Vec::new(),
+			atlas_generation: 0,
+			frames_until_reload_check: HOT_RELOAD_INTERVAL_FRAMES,
+		};
+	}
+
+	// loading the same path twice returns the cached handle
+	pub fn load_texture(&mut self, path: PathBuf) -> Result<TextureHandle, String> {
+		if let Some(index) = self.textures.iter().position(|t| t.path == path) {
+			return Ok(TextureHandle(index as u16));
+		}
+
+		let texture: Texture<'a> = load_texture(self.texture_creator, &path)?;
+		self.textures.push(CachedTexture {
+			modified: get_modified(&path),
+			path,
+			texture,
+		});
+
+		return Ok(TextureHandle((self.textures.len() - 1) as u16));
+	}
+
+	pub fn get_texture(&self, handle: TextureHandle) -> Option<&Texture<'a>> {
+		return self.textures.get(handle.0 as usize).map(|t| &t.texture);
+	}
+
+	pub fn get_texture_mut(&mut self, handle: TextureHandle) -> Option<&mut Texture<'a>> {
+		return self.textures.get_mut(handle.0 as usize).map(|t| &mut t.texture);
+	}
+
+	pub fn get_atlas_page(&self, region: &AtlasRegion) -> Option<&Texture<'a>> {
+		return self.atlas_pages.get(region.page as usize);
+	}
+
+	// packs newly registered sheets and uploads the pages; call before drawing from the atlas
+	pub fn prepare_atlas(&mut self) {
+		if self.atlas.is_dirty() {
+			for error in self.atlas.build() {
+				debugln!("atlas: {}", error);
+			}
+		}
+
+		if self.atlas_generation == self.atlas.get_generation() {
+			return;
+		}
+
+		self.atlas_generation = self.atlas.get_generation();
+		self.atlas_pages.clear();
+		for page in &self.atlas.pages {
+			match self.upload_page(page.width, page.height, &page.pixels) {
+				Ok(texture) => self.atlas_pages.push(texture),
+				Err(e) => {
+					// without every page the regions would point at the wrong texture
+					debugln!("atlas upload failed: {}", e);
+					self.atlas_pages.clear();
+					return;
+				}
+			}
+		}
+
+		return;
+	}
+
+	fn upload_page(&self, width: u32, height: u32, pixels: &[u8]) -> Result<Texture<'a>, String> {
+		let mut texture = self
+			.texture_creator
+			.create_texture_static(PixelFormatEnum::RGBA32, width, height)
+			.map_err(|e| e.to_string())?;
+		texture.update(None, pixels, (width * 4) as usize).map_err(|e| e.to_string())?;
+		texture.set_blend_mode(BlendMode::Blend);
+		return Ok(texture);
+	}
+
+	// development aid: picks up edited pngs without restarting. release builds never touch the disk here
+	pub fn poll_hot_reload(&mut self) {
+		if !cfg!(debug_assertions) {
+			return;
+		}
+
+		self.frames_until_reload_check = self.frames_until_reload_check.saturating_sub(1);
+		if self.frames_until_reload_check > 0 {
+			return;
+		}
+		self.frames_until_reload_check = HOT_RELOAD_INTERVAL_FRAMES;
+
+		for cached in &mut self.textures {
+			let modified: Option<SystemTime> = get_modified(&cached.path);
+			if modified == cached.modified {
+				continue;
+			}
+
+			cached.modified = modified;
+			match load_texture(self.texture_creator, &cached.path) {
+				Ok(texture) => {
+					debugln!("reloaded {}", cached.path.display());
+					cached.texture = texture;
+				}
+				// keep drawing the old texture until the file is valid again
+				Err(e) => {
+					debugln!("reload failed: {}", e);
+				}
+			}
+		}
+
+		if self.atlas.has_changed_on_disk() {
+			debugln!("atlas sheets changed on disk, repacking");
+			for error in self.atlas.build() {
+				debugln!("atlas: {}", error);
+			}
+		}
+
+		return;
+	}
+}
+
+fn load_texture<'a>(texture_creator: &'a TextureCreator<WindowContext>, path: &Path) -> Result<Texture<'a>, String> {
+	let mut texture = texture_creator
+		.load_texture(path)
+		.map_err(|err| format!("missing texture file\npath: {}\nsdl error: {}", path.display(), err))?;
+	texture.set_blend_mode(BlendMode::Blend);
+	return Ok(texture);
+}
+
+fn get_modified(path: &Path) -> Option<SystemTime> {
+	return fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+}
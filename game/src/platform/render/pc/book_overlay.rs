@@ -1,10 +1,10 @@
-use crate::runtime::session::Session;
+use crate::runtime::book::reading_state::BookReadingState;
 
 use sdl2::rect::Rect;
 
 use super::{BOOK_BAR_COLOR, BOOK_BAR_TEXT_TOP_OFFSET_PIXELS, BOOK_DIVIDER_COLOR, BOOK_FOOTER_HEIGHT_PIXELS, BOOK_HEADER_HEIGHT_PIXELS, BOOK_PANEL_COLOR, PcRenderer};
 
-impl PcRenderer {
+impl PcRenderer<'_> {
 	pub fn copy_book_page_to_clipboard(&self, text: &str) {
 		let clipboard = self.video.clipboard();
 		let _ = clipboard.set_clipboard_text(text);
@@ -34,8 +34,8 @@ impl PcRenderer {
 		let right_text: &str = "B: copy page";
 		let right_left: i32 = footer_left + (footer_width_pixels as i32) - padding_pixels - (right_text.len() as i32 * 8);
 
-		self.draw_book_text_line(left_left, text_top, left_text);
-		self.draw_book_text_line(right_left, text_top, right_text);
+		self.draw_book_text_line(left_left, text_top, left_text, false);
+		self.draw_book_text_line(right_left, text_top, right_text, false);
 
 		return;
 	}
@@ -59,13 +59,14 @@ impl PcRenderer {
 		let text_top: i32 = top + BOOK_BAR_TEXT_TOP_OFFSET_PIXELS;
 		let text_left: i32 = header_left + padding_pixels;
 
-		self.draw_book_text_line(text_left, text_top, "library");
+		self.draw_book_text_line(text_left, text_top, "library", false);
 
 		return;
 	}
 
-	fn draw_book_text_line(&mut self, left: i32, top: i32, text: &str) {
-		let surface = self.font.render(text).blended(sdl2::pixels::Color::RGBA(240, 240, 255, 255)).unwrap();
+	fn draw_book_text_line(&mut self, left: i32, top: i32, text: &str, is_page_text: bool) {
+		let font = if is_page_text { &self.text_font } else { &self.font };
+		let surface = font.render(text).blended(sdl2::pixels::Color::RGBA(240, 240, 255, 255)).unwrap();
 		let texture = self.texture_creator.create_texture_from_surface(&surface).unwrap();
 
 		let width_pixels: u32 = surface.width();
@@ -79,8 +80,8 @@ impl PcRenderer {
 		return;
 	}
 
-	pub fn draw_book_overlay(&mut self, session: &Session) {
-		let state = &session.book_reading;
+	// in the text font draw_level picked up from the snapshot
+	pub fn draw_book_overlay(&mut self, state: &BookReadingState) {
 		if !state.is_open {
 			return;
 		}
@@ -102,10 +103,17 @@ impl PcRenderer {
 		let text_left: i32 = panel_left + 20;
 		let text_top: i32 = panel_top + BOOK_HEADER_HEIGHT_PIXELS + 12;
 
+		// bigger sizes and opendyslexic run taller than the 14 pixel lines small dejavu gets away with
+		let line_height: i32 = self.text_font.recommended_line_spacing().max(14);
+		let text_bottom: i32 = panel_top + panel_height_pixels as i32 - BOOK_FOOTER_HEIGHT_PIXELS;
 		let mut y: i32 = text_top;
 		for line in state.page_text.lines() {
-			self.draw_book_text_line(text_left, y, line);
-			y += 14;
+			// a page set in a big font can run past the footer; the rest is cut off rather than drawn over it
+			if y + line_height > text_bottom {
+				break;
+			}
+			self.draw_book_text_line(text_left, y, line, true);
+			y += line_height;
 		}
 
 		return;
@@ -1,44 +1,45 @@
 use crate::{
-	Session,
 	common::coords::{PixelSize, Pointf32, Size, clamp_camera_to_level_world, get_screen, visible_tile_bounds},
 	debugln,
 	engine_math::Vec2,
-	platform::{
-		audio::backend::LocomotionAnim,
-		render::{
-			backend::RenderBackend,
-			icon_registry::{ICON_FRAME_HEIGHT_PIXELS, ICON_FRAME_WIDTH_PIXELS, get_icon_src_rect_pixels, resolve_icon},
-		},
+	platform::render::{
+		BackgroundDrawParams,
+		backend::RenderBackend,
+		background::{BackgroundView, LayerPlacement, get_layer_placement},
+		ghost::{GHOST_FILL_COLOR, GHOST_OUTLINE_COLOR, GhostSnapshot},
+		hud::{GlyphBitmap, HudElement, HudSnapshot, get_glyph_bitmap, layout_hud},
+		palette::{HIGH_CONTRAST_BACKGROUND_DIM_COLOR, get_high_contrast_tile_color},
+		snapshot::{EntitySnapshot, RenderSnapshot, SpriteFrame},
 	},
 	runtime::{
 		level::Level,
-		state::{EntityKind, State},
+		state::EntityKind,
 		triggers::TriggerKind,
 	},
 	tile::TileKind,
 };
 use sdl2::{
-	pixels::Color,
+	pixels::{Color, PixelFormatEnum},
 	rect::Rect,
 	render::{BlendMode, Texture},
 };
 
-use super::{PcRenderer, renderer::SlimeTextureKey};
+use super::PcRenderer;
 
-impl PcRenderer {
-	fn draw_debug_triggers(&mut self, state: &State, session: &Session, cam_left_world: f32, cam_top_world: f32, scale: f32) {
-		if !session.settings.show_triggers {
+impl PcRenderer<'_> {
+	fn draw_debug_triggers(&mut self, snapshot: &RenderSnapshot, cam_left_world: f32, cam_top_world: f32, scale: f32) {
+		if !snapshot.show_triggers {
 			return;
 		}
 
 		use sdl2::{pixels::Color, rect::Rect};
 
-		let tile_width_world: f32 = state.level.tile_width as f32;
-		let tile_height_world: f32 = state.level.tile_height as f32;
+		let tile_width_world: f32 = snapshot.level.tile_width as f32;
+		let tile_height_world: f32 = snapshot.level.tile_height as f32;
 
-		for t in &state.level.triggers {
+		for t in &snapshot.level.triggers {
 			let idx: usize = t.id as usize;
-			if idx < state.triggers_armed.len() && state.triggers_armed[idx] {
+			if snapshot.is_trigger_armed(idx) {
 				continue; // consumed -> don't draw
 			}
 
@@ -63,24 +64,6 @@ impl PcRenderer {
 		}
 	}
 
-	fn get_slime_texture_key(&self, kind: EntityKind, anim: LocomotionAnim) -> SlimeTextureKey {
-		match (kind, anim) {
-			(EntityKind::SlimeBlue, LocomotionAnim::Walk) => return SlimeTextureKey::BlueWalk,
-			(EntityKind::SlimeBlue, LocomotionAnim::Run) => return SlimeTextureKey::BlueRun,
-			(EntityKind::SlimeBlue, LocomotionAnim::Death) => return SlimeTextureKey::BlueDeath,
-
-			(EntityKind::SlimeUndead, LocomotionAnim::Walk) => return SlimeTextureKey::UndeadWalk,
-			(EntityKind::SlimeUndead, LocomotionAnim::Run) => return SlimeTextureKey::UndeadRun,
-			(EntityKind::SlimeUndead, LocomotionAnim::Death) => return SlimeTextureKey::UndeadDeath,
-
-			(EntityKind::SlimeLava, LocomotionAnim::Walk) => return SlimeTextureKey::LavaWalk,
-			(EntityKind::SlimeLava, LocomotionAnim::Run) => return SlimeTextureKey::LavaRun,
-			(EntityKind::SlimeLava, LocomotionAnim::Death) => return SlimeTextureKey::LavaDeath,
-
-			_ => panic!("get_slime_texture_key called for non-slime {:?}", kind),
-		}
-	}
-
 	fn draw_filled_rect(&mut self, x: i32, y: i32, w: u32, h: u32, color: Color) {
 		self.canvas.set_draw_color(color);
 		let rect = Rect::new(x, y, w, h);
@@ -117,17 +100,10 @@ impl PcRenderer {
 			}
 
 			TileKind::TorchGlow => {
-				// make this area less dark than full blackout
+				// the flame itself; the light it throws comes from the lightmap
 				self.canvas.set_blend_mode(BlendMode::Blend);
-				self.canvas.set_draw_color(Color::RGBA(0, 0, 0, 200));
-				let _ = self.canvas.fill_rect(destination);
-
-				// add warm light (needs a real alpha)
-				self.canvas.set_blend_mode(BlendMode::Add);
-				self.canvas.set_draw_color(Color::RGBA(255, 235, 160, 220)); // pale warm yellow
+				self.canvas.set_draw_color(Color::RGBA(235, 210, 145, 255));
 				let _ = self.canvas.fill_rect(destination);
-
-				self.canvas.set_blend_mode(BlendMode::Blend);
 			}
 
 			TileKind::DarkBrownRock => {
@@ -140,6 +116,21 @@ impl PcRenderer {
 				self.canvas.set_blend_mode(BlendMode::Blend);
 			}
 
+			TileKind::Door => {
+				// dark frame around lighter planks
+				let inset: i32 = (destination.width() / 8).max(1) as i32;
+				self.canvas.set_blend_mode(BlendMode::Blend);
+				self.canvas.set_draw_color(Color::RGBA(70, 44, 22, 255));
+				let _ = self.canvas.fill_rect(destination);
+				self.canvas.set_draw_color(Color::RGBA(130, 84, 42, 255));
+				let _ = self.canvas.fill_rect(Rect::new(
+					destination.x() + inset,
+					destination.y() + inset,
+					destination.width().saturating_sub((inset * 2) as u32),
+					destination.height().saturating_sub((inset * 2) as u32),
+				));
+			}
+
 			_ => {
 				// Silently do nothing instead of drawing the wrong thing
 			}
@@ -201,69 +192,64 @@ impl PcRenderer {
 		return;
 	}
 
-	pub(crate) fn draw_background_internal(&mut self, camera_left: i32, camera_top: i32, scale: f32) {
+	pub(crate) fn draw_background_internal(&mut self, params: &BackgroundDrawParams) {
 		let (sw_u32, sh_u32) = match self.canvas.output_size() {
 			Ok(v) => v,
 			Err(_) => self.canvas.window().size(),
 		};
 
-		self.canvas.set_draw_color(Color::RGB(60, 110, 190));
-		let _ = self.canvas.fill_rect(Rect::new(0, 0, sw_u32, sh_u32));
-
-		let Some(bg) = self.bg_texture.as_ref() else {
+		let Some(def) = self.backgrounds.get(self.background_id) else {
+			self.canvas.set_draw_color(Color::RGB(60, 110, 190));
+			let _ = self.canvas.fill_rect(Rect::new(0, 0, sw_u32, sh_u32));
 			return;
 		};
 
-		let query = bg.query();
-		if query.width == 0 || query.height == 0 {
-			return;
-		}
-
-		let bg_tile_width_pixels: i32 = query.width as i32;
-		let bg_tile_height_pixels: i32 = query.height as i32;
-
-		if bg_tile_width_pixels <= 0 || bg_tile_height_pixels <= 0 {
-			return;
-		}
-
-		let sw: i32 = sw_u32 as i32;
-		let sh: i32 = sh_u32 as i32;
-
-		// camera -> pixels
-		let cam_left_pixels: f32 = camera_left as f32 * scale;
-		let cam_top_pixels: f32 = camera_top as f32 * scale;
+		let (red, green, blue) = def.fill_color;
+		self.canvas.set_draw_color(Color::RGB(red, green, blue));
+		let _ = self.canvas.fill_rect(Rect::new(0, 0, sw_u32, sh_u32));
 
-		// parallax offsets in pixels
-		let bg_cam_left_pixels: i32 = (cam_left_pixels * self.bg_parallax_x) as i32;
-		let bg_cam_top_pixels: i32 = (cam_top_pixels * self.bg_parallax_y) as i32;
+		let view: BackgroundView = BackgroundView {
+			screen_width: sw_u32 as i32,
+			screen_height: sh_u32 as i32,
+			camera_left: params.camera_left as f32 * params.scale,
+			camera_top: params.camera_top as f32 * params.scale,
+			max_camera_top: (params.level_height as f32 * params.scale - sh_u32 as f32).max(0.0),
+			tick: params.tick,
+		};
 
-		// horizontal wrap (repeat)
-		let start_left: i32 = -(((bg_cam_left_pixels % bg_tile_width_pixels) + bg_tile_width_pixels) % bg_tile_width_pixels);
+		for (layer, handle) in def.layers.iter().zip(self.bg_layers.iter()) {
+			let Some(texture) = handle.and_then(|h| self.assets.get_texture(h)) else {
+				continue;
+			};
 
-		// vertical clamp (no repeat)
-		let mut top: i32 = -bg_cam_top_pixels;
-		if bg_tile_height_pixels >= sh {
-			let min_top: i32 = sh - bg_tile_height_pixels; // negative or 0
-			if top < min_top {
-				top = min_top;
-			}
-			if top > 0 {
-				top = 0;
+			let query = texture.query();
+			if query.width == 0 || query.height == 0 {
+				continue;
 			}
-		} else {
-			// bg shorter than screen: pin to top (sky fill covers the rest)
-			top = 0;
-		}
 
-		let mut left: i32 = start_left;
-		while left < sw {
-			let dst = Rect::new(left, top, bg_tile_width_pixels as u32, bg_tile_height_pixels as u32);
-			let _ = self.canvas.copy(bg, None, dst);
-			left += bg_tile_width_pixels;
+			let width: i32 = query.width as i32;
+			let height: i32 = query.height as i32;
+			let placement: LayerPlacement = get_layer_placement(layer, width, height, &view);
+
+			let mut top: i32 = placement.top;
+			while top < view.screen_height {
+				let mut left: i32 = placement.left;
+				while left < view.screen_width {
+					let _ = self.canvas.copy(texture, None, Rect::new(left, top, query.width, query.height));
+					if !placement.repeat_x {
+						break;
+					}
+					left += width;
+				}
+				if !placement.repeat_y {
+					break;
+				}
+				top += height;
+			}
 		}
 	}
 
-	fn draw_tiles_layer_atlas(&mut self, level: &Level, layer: u32, camera_left: f32, camera_top: f32, scale: f32, _frame_index: u32) {
+	fn draw_tiles_layer_atlas(&mut self, level: &Level, layer: u32, is_high_contrast: bool, camera_left: f32, camera_top: f32, scale: f32, _frame_index: u32) {
 		let tile_width: f32 = level.tile_width as f32;
 		let tile_height: f32 = level.tile_height as f32;
 		let cam = Pointf32::new(camera_left, camera_top);
@@ -283,7 +269,9 @@ impl PcRenderer {
 		let end_tile_top: i32 = bounds.end_top;
 		let atlas_tile_width_pixels: u32 = self.atlas_tile_width_pixels;
 		let atlas_tile_height_pixels: u32 = self.atlas_tile_height_pixels;
-		let tile_cols: u32 = self.tile_texture.as_mut().unwrap().query().width / atlas_tile_width_pixels;
+		let (Some(tile_handle), Some(tile_cols)) = (self.tile_texture, self.get_tile_cols()) else {
+			return;
+		};
 
 		for tile_top in start_tile_top..end_tile_top {
 			for tile_left in start_tile_left..end_tile_left {
@@ -309,105 +297,245 @@ impl PcRenderer {
 					continue;
 				}
 
+				if is_high_contrast {
+					if let Some(color) = get_high_contrast_tile_color(tile_kind) {
+						self.canvas.set_draw_color(to_color(color));
+						let _ = self.canvas.fill_rect(destination);
+						continue;
+					}
+				}
+
 				// normal atlas draw path (interactive / solid / regular tiles)
 				let id: u32 = tile_id as u32;
 				let source_left: i32 = ((id % tile_cols) * atlas_tile_width_pixels) as i32;
 				let source_top: i32 = ((id / tile_cols) * atlas_tile_height_pixels) as i32;
 				let source = Rect::new(source_left, source_top, atlas_tile_width_pixels, atlas_tile_height_pixels);
-				let texture = self.tile_texture.as_mut().unwrap();
-				let _ = self.canvas.copy(&texture, source, destination).unwrap();
+				let Some(texture) = self.assets.get_texture(tile_handle) else {
+					continue;
+				};
+				let _ = self.canvas.copy(texture, source, destination);
 			}
 		}
 		return;
 	}
 
-	pub fn draw_level_internal(&mut self, state: &State, session: &Session) {
-		let (camera_left, camera_top) = self.common.compute_camera(self, state, session);
+	// None when the tile atlas failed to load
+	pub(crate) fn get_tile_cols(&self) -> Option<u32> {
+		let texture: &Texture = self.assets.get_texture(self.tile_texture?)?;
+		let tile_cols: u32 = texture.query().width / self.atlas_tile_width_pixels;
+		return if tile_cols == 0 { None } else { Some(tile_cols) };
+	}
+
+	pub fn draw_level_internal(&mut self, snapshot: &RenderSnapshot) {
+		let (camera_left, camera_top) = (snapshot.camera_left, snapshot.camera_top);
 		let scale: f32 = self.get_render_scale();
 
-		let tile_cols: u32 = self.tile_texture.as_mut().expect("tile_texture does not have a value").query().width / self.atlas_tile_width_pixels;
-		for layer in 0..(state.level.layer_count as u32) {
-			self.draw_tiles_layer_atlas(&state.level, layer, camera_left as f32, camera_top as f32, scale, self.frame_index);
+		// new sprite sheets from the animation data get packed before anything draws from the atlas
+		self.assets.atlas.map_sheets(&snapshot.sprite_sheets, &mut self.sprite_sheet_handles);
+		self.assets.prepare_atlas();
+
+		if snapshot.is_high_contrast {
+			let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+			self.canvas.set_blend_mode(BlendMode::Blend);
+			self.canvas.set_draw_color(to_color(HIGH_CONTRAST_BACKGROUND_DIM_COLOR));
+			let _ = self
+				.canvas
+				.fill_rect(Rect::new(0, 0, screen_width_pixels as u32, screen_height_pixels as u32));
+		}
+
+		let tile_cols: Option<u32> = self.get_tile_cols();
+		for layer in 0..(snapshot.level.layer_count as u32) {
+			self.draw_tiles_layer_atlas(&snapshot.level, layer, snapshot.is_high_contrast, camera_left as f32, camera_top as f32, scale, self.frame_index);
 		}
 
 		self.frame_index = self.frame_index.wrapping_add(1);
-		self.draw_entities(state, session, tile_cols, camera_left as f32, camera_top as f32, scale, self.frame_index);
-		self.draw_debug_triggers(state, session, camera_left as f32, camera_top as f32, scale);
-		self.draw_trigger_icons(state, session, camera_left as f32, camera_top as f32, scale);
+		if let Some(ghost) = &snapshot.ghost {
+			self.draw_ghost(ghost, camera_left as f32, camera_top as f32, scale);
+		}
+		self.draw_entities(snapshot, tile_cols, camera_left as f32, camera_top as f32, scale, self.frame_index);
+		self.draw_debug_triggers(snapshot, camera_left as f32, camera_top as f32, scale);
+		self.draw_trigger_icons(snapshot, camera_left as f32, camera_top as f32, scale);
+		self.draw_lighting(snapshot, scale);
+		self.draw_hud(&snapshot.hud);
+		if let Some(message_box) = &snapshot.message_box {
+			self.draw_message_box(message_box);
+		}
+		if let Some(menu) = &snapshot.menu {
+			self.draw_menu(menu);
+		}
+		if let Some(inventory) = &snapshot.inventory {
+			self.draw_inventory(inventory);
+		}
+		if let Some(level_select) = &snapshot.level_select {
+			self.draw_level_select(level_select);
+		}
+		if let Some(results) = &snapshot.results {
+			self.draw_results(results);
+		}
+		if let Some(transition) = &snapshot.transition {
+			self.draw_transition(transition);
+		}
 		return;
 	}
 
-	pub fn draw_death_entity_internal(
-		&mut self,
-		state: &State,
-		session: &Session,
-		entity_kind: EntityKind,
-		pos: &Vec2,
-		half_height: f32,
-		camera_left: f32,
-		camera_top: f32,
-		scale: f32,
-		death_timer: u16,
-	) {
-		// 64x64 frames in the death sheet
-		let frame_size_pixels: i32 = 64;
-
-		// how long death lasts total
-		let total: u16 = session.settings.enemy_death_frame_count as u16;
-
-		// how many frames are in the sheet (tune this once)
-		let frame_count: u32 = session.settings.frame_count;
-
-		// elapsed frames since death started
-		let elapsed: u16 = total.saturating_sub(death_timer);
-
-		// spread elapsed over frame_count
-		let frame_index: u32 = ((elapsed as u32) * frame_count / (total as u32)).min(frame_count - 1);
-
-		let row_index: u32 = 0;
-
-		let src_left_pixels: i32 = (frame_index as i32) * frame_size_pixels;
-		let src_top_pixels: i32 = (row_index as i32) * frame_size_pixels;
-		let src = Rect::new(src_left_pixels, src_top_pixels, frame_size_pixels as u32, frame_size_pixels as u32);
-		let sprite_world_scale: f32 = state.enemy_sprite_scale as f32;
-		let dest_width_pixels_u32: u32 = (64.0 * sprite_world_scale * scale).max(1.0) as u32;
-		let dest_height_pixels_u32: u32 = (64.0 * sprite_world_scale * scale).max(1.0) as u32;
-
-		// physics anchor: bottom-center
-		let entity_bottom_center_world_x: f32 = pos.x;
-		let entity_bottom_center_world_y: f32 = pos.y + half_height;
-
-		let entity_bottom_center_screen_left: i32 = ((entity_bottom_center_world_x - camera_left) * scale) as i32;
-		let entity_bottom_center_screen_top: i32 = ((entity_bottom_center_world_y - camera_top) * scale) as i32;
-
-		// tune these once for your death sheet
-		let anchor_left_frac: f32 = 32.0 / 64.0;
-		let anchor_top_frac: f32 = 40.0 / 64.0;
-
-		let anchor_left_pixels: i32 = (dest_width_pixels_u32 as f32 * anchor_left_frac) as i32;
-		let anchor_top_pixels: i32 = (dest_height_pixels_u32 as f32 * anchor_top_frac) as i32;
-
-		let dest_left_pixels: i32 = entity_bottom_center_screen_left - anchor_left_pixels;
-		let dest_top_pixels: i32 = entity_bottom_center_screen_top - anchor_top_pixels;
-
-		let dest = Rect::new(dest_left_pixels, dest_top_pixels, dest_width_pixels_u32, dest_height_pixels_u32);
-
-		// pick texture based on kind (or reuse one texture sheet)
-		let tex = match entity_kind {
-			EntityKind::SlimeBlue => &self.slime_blue_death_texture,
-			EntityKind::SlimeUndead => &self.slime_undead_death_texture,
-			EntityKind::SlimeLava => &self.slime_lava_death_texture,
-			_ => &self.slime_blue_death_texture,
+	// screen space, after the lighting so the hud is never darkened
+	fn draw_hud(&mut self, hud: &HudSnapshot) {
+		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+		let mut elements: Vec<HudElement> = std::mem::take(&mut self.hud_elements);
+		layout_hud(hud, screen_width_pixels, screen_height_pixels, &mut elements);
+		self.draw_hud_elements(&elements);
+		self.hud_elements = elements;
+		return;
+	}
+
+	// bitmap glyphs square by square, icons from the icon sheet
+	pub(crate) fn draw_hud_elements(&mut self, elements: &[HudElement]) {
+		self.canvas.set_blend_mode(BlendMode::Blend);
+		for element in elements {
+			match *element {
+				HudElement::Glyph {
+					glyph,
+					left,
+					top,
+					pixel_size,
+					color,
+				} => {
+					let bitmap: GlyphBitmap = get_glyph_bitmap(glyph);
+					self.canvas.set_draw_color(Color::RGBA(color.0, color.1, color.2, color.3));
+					for row in 0..(bitmap.rows.len() as u32) {
+						for column in 0..bitmap.width {
+							if !bitmap.is_set(column, row) {
+								continue;
+							}
+							let pixel_left: i32 = left + (column * pixel_size) as i32;
+							let pixel_top: i32 = top + (row * pixel_size) as i32;
+							let _ = self.canvas.fill_rect(Rect::new(pixel_left, pixel_top, pixel_size, pixel_size));
+						}
+					}
+				}
+				HudElement::Icon { icon_id, left, top, size } => self.draw_hud_icon(icon_id, left, top, size),
+			}
+		}
+		return;
+	}
+
+	pub(crate) fn draw_hud_icon(&mut self, icon_id: u16, left: i32, top: i32, size: u32) {
+		let Some(region) = self.icon_sheet.and_then(|handle| self.assets.atlas.get_region(handle)) else {
+			return;
+		};
+		let Some(def) = self.icons.resolve_icon(icon_id) else {
+			return;
 		};
 
-		let _ = self.canvas.copy_ex(tex, src, dest, 0.0, None, false, false).unwrap();
+		let frame_index: u16 = self.icons.get_frame_index(&def, self.frame_index);
+		let (src_left_pixels, src_top_pixels, src_width_pixels, src_height_pixels) = self.icons.get_icon_src_rect_pixels(&def, frame_index);
+		let src: Rect = Rect::new(region.left + src_left_pixels, region.top + src_top_pixels, src_width_pixels, src_height_pixels);
+
+		let Some(page) = self.assets.get_atlas_page(&region) else {
+			return;
+		};
+		let _ = self.canvas.copy(page, Some(src), Rect::new(left, top, size, size));
+		return;
 	}
 
-	fn draw_trigger_icons(&mut self, state: &State, _session: &Session, cam_left_world: f32, cam_top_world: f32, scale: f32) {
-		let atlas: &Texture<'static> = &self.trigger_texture;
+	// multiplies everything drawn so far by the lightmap; a no-op in levels without darkness
+	fn draw_lighting(&mut self, snapshot: &RenderSnapshot, scale: f32) {
+		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+		if !self.lightmap.build(snapshot, screen_width_pixels as f32 / scale, screen_height_pixels as f32 / scale) {
+			return;
+		}
+
+		let columns: u32 = self.lightmap.columns;
+		let rows: u32 = self.lightmap.rows;
+		let needs_texture: bool = match self.lightmap_texture.as_ref() {
+			Some(texture) => {
+				let query = texture.query();
+				query.width != columns || query.height != rows
+			}
+			None => true,
+		};
+
+		if needs_texture {
+			match self.texture_creator.create_texture_streaming(PixelFormatEnum::RGB24, columns, rows) {
+				Ok(mut texture) => {
+					texture.set_blend_mode(BlendMode::Mod);
+					self.lightmap_texture = Some(texture);
+				}
+				Err(e) => {
+					debugln!("lightmap texture failed: {}", e);
+					self.lightmap_texture = None;
+					return;
+				}
+			}
+		}
 
-		let tile_width: f32 = state.level.tile_width as f32;
-		let tile_height: f32 = state.level.tile_height as f32;
+		let Some(texture) = self.lightmap_texture.as_mut() else {
+			return;
+		};
+		if let Err(e) = texture.update(None, &self.lightmap.pixels, (columns * 3) as usize) {
+			debugln!("lightmap upload failed: {}", e);
+			return;
+		}
+
+		let cell_size_pixels: f32 = self.lightmap.cell_size * scale;
+		let dest: Rect = Rect::new(
+			((self.lightmap.left - snapshot.camera_left as f32) * scale) as i32,
+			((self.lightmap.top - snapshot.camera_top as f32) * scale) as i32,
+			(columns as f32 * cell_size_pixels) as u32,
+			(rows as f32 * cell_size_pixels) as u32,
+		);
+		let _ = self.canvas.copy(texture, None, dest);
+		self.canvas.set_blend_mode(BlendMode::Blend);
+		return;
+	}
+
+	// returns false when the sheet is unavailable so the caller can fall back to a shape
+	fn draw_sprite_entity(&mut self, snapshot: &RenderSnapshot, entity: &EntitySnapshot, sprite: &SpriteFrame, camera_left: f32, camera_top: f32, scale: f32) -> bool {
+		let Some(handle) = self.sprite_sheet_handles.get(sprite.sheet_id as usize).copied() else {
+			return false;
+		};
+		let Some(region) = self.assets.atlas.get_region(handle) else {
+			return false;
+		};
+		let Some(texture) = self.assets.get_atlas_page(&region) else {
+			return false;
+		};
+
+		let src: Rect = Rect::new(region.left + sprite.src_left, region.top + sprite.src_top, sprite.src_width, sprite.src_height);
+
+		let sprite_world_scale: f32 = snapshot.enemy_sprite_scale as f32;
+		let dest_width_pixels: u32 = (sprite.src_width as f32 * sprite_world_scale * scale).max(1.0) as u32;
+		let dest_height_pixels: u32 = (sprite.src_height as f32 * sprite_world_scale * scale).max(1.0) as u32;
+
+		// anchor point on physics body: bottom-center
+		let entity_bottom_center_screen_left: i32 = ((entity.position.x - camera_left) * scale) as i32;
+		let entity_bottom_center_screen_top: i32 = ((entity.position.y + entity.half_height - camera_top) * scale) as i32;
+
+		// mirrored frames keep the anchor on the same side of the body
+		let anchor_left: i32 = if sprite.flip_horizontal { sprite.src_width as i32 - sprite.anchor_left } else { sprite.anchor_left };
+		let sprite_feet_left_pixels: i32 = (dest_width_pixels as f32 * (anchor_left as f32 / sprite.src_width as f32)) as i32;
+		let sprite_feet_top_pixels: i32 = (dest_height_pixels as f32 * (sprite.anchor_top as f32 / sprite.src_height as f32)) as i32;
+
+		let dest: Rect = Rect::new(
+			entity_bottom_center_screen_left - sprite_feet_left_pixels,
+			entity_bottom_center_screen_top - sprite_feet_top_pixels,
+			dest_width_pixels,
+			dest_height_pixels,
+		);
+
+		let _ = self.canvas.copy_ex(texture, src, dest, 0.0, None, sprite.flip_horizontal, false);
+		return true;
+	}
+
+	fn draw_trigger_icons(&mut self, snapshot: &RenderSnapshot, cam_left_world: f32, cam_top_world: f32, scale: f32) {
+		let Some(region) = self.icon_sheet.and_then(|handle| self.assets.atlas.get_region(handle)) else {
+			return;
+		};
+		let icon_width_pixels: u32 = self.icons.frame_width_pixels;
+		let icon_height_pixels: u32 = self.icons.frame_height_pixels;
+
+		let tile_width: f32 = snapshot.level.tile_width as f32;
+		let tile_height: f32 = snapshot.level.tile_height as f32;
 
 		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
 		let view_width_world: f32 = (screen_width_pixels as f32) / scale;
@@ -420,17 +548,17 @@ impl PcRenderer {
 
 		let padding_world: f32 = 16.0;
 
-		for trigger in &state.level.triggers {
+		for trigger in &snapshot.level.triggers {
 			if trigger.icon_id == 0 {
 				continue;
 			}
 
 			let trigger_id: usize = trigger.id as usize;
-			if trigger_id < state.triggers_armed.len() && state.triggers_armed[trigger_id] {
+			if snapshot.is_trigger_armed(trigger_id) {
 				continue; // consumed -> don't draw
 			}
 
-			let Some(def) = resolve_icon(trigger.icon_id) else {
+			let Some(def) = self.icons.resolve_icon(trigger.icon_id) else {
 				continue;
 			};
 
@@ -453,46 +581,56 @@ impl PcRenderer {
 
 			let trigger_center_left_world: f32 = trigger_left_world + (trigger_width_world * 0.5);
 
-			let half_icon_height_world: f32 = (ICON_FRAME_HEIGHT_PIXELS as f32) / scale * 0.5;
+			let half_icon_height_world: f32 = (icon_height_pixels as f32) / scale * 0.5;
 			let bottom_padding_world: f32 = 2.0 / scale; // 2 pixels padding
 
 			let icon_world_left: f32 = trigger_center_left_world;
 			let icon_world_top: f32 = trigger_bottom_world - half_icon_height_world - bottom_padding_world;
 
-			let frame_index: u16 = if def.frame_count <= 1 || def.frame_duration_ticks == 0 {
-				0
-			} else {
-				let frame_u32: u32 = (self.frame_index as u32 / def.frame_duration_ticks as u32) % def.frame_count as u32;
-				frame_u32 as u16
-			};
-
-			let (src_left_pixels, src_top_pixels, src_width_pixels, src_height_pixels) = get_icon_src_rect_pixels(trigger.icon_id, frame_index);
+			let frame_index: u16 = self.icons.get_frame_index(&def, self.frame_index);
+			let (src_left_pixels, src_top_pixels, src_width_pixels, src_height_pixels) = self.icons.get_icon_src_rect_pixels(&def, frame_index);
 
-			let src = sdl2::rect::Rect::new(src_left_pixels, src_top_pixels, src_width_pixels, src_height_pixels);
+			let src = sdl2::rect::Rect::new(region.left + src_left_pixels, region.top + src_top_pixels, src_width_pixels, src_height_pixels);
 
-			// screen-space: keep icon size constant (32x32), don’t multiply by world scale
-			let screen_left: i32 = ((icon_world_left - cam_left_world) * scale) as i32 - (ICON_FRAME_WIDTH_PIXELS as i32 / 2);
-			let screen_top: i32 = ((icon_world_top - cam_top_world) * scale) as i32 - (ICON_FRAME_HEIGHT_PIXELS as i32 / 2);
+			// screen-space: keep icon size constant, don’t multiply by world scale
+			let screen_left: i32 = ((icon_world_left - cam_left_world) * scale) as i32 - (icon_width_pixels as i32 / 2);
+			let screen_top: i32 = ((icon_world_top - cam_top_world) * scale) as i32 - (icon_height_pixels as i32 / 2);
 
-			let dest = sdl2::rect::Rect::new(screen_left, screen_top, ICON_FRAME_WIDTH_PIXELS, ICON_FRAME_HEIGHT_PIXELS);
+			let dest = sdl2::rect::Rect::new(screen_left, screen_top, icon_width_pixels, icon_height_pixels);
 
-			let _ = self.canvas.copy(atlas, Some(src), dest);
+			let Some(page) = self.assets.get_atlas_page(&region) else {
+				return;
+			};
+			let _ = self.canvas.copy(page, Some(src), dest);
 		}
 	}
 
-	fn draw_entities(&mut self, state: &State, session: &Session, tile_cols: u32, camera_left: f32, camera_top: f32, scale: f32, _frame_index: u32) {
-		//let texture = self.tile_texture.as_mut().expect("tile_texture does not have a value");
-		for (id, pos) in state.positions.iter() {
-			let kind = *state.entity_kinds.get(id).unwrap_or(&0);
-			let entity_kind = EntityKind::from_u8(kind);
+	// behind the entities, so the real player always shows on top
+	fn draw_ghost(&mut self, ghost: &GhostSnapshot, camera_left: f32, camera_top: f32, scale: f32) {
+		let world: Pointf32 = Pointf32 {
+			left: ghost.position.x - ghost.half_width,
+			top: ghost.position.y - ghost.half_height,
+		};
+		let screen = get_screen(world, Pointf32::new(camera_left, camera_top), scale);
+		let width: u32 = ((ghost.half_width * 2.0) * scale) as u32;
+		let height: u32 = ((ghost.half_height * 2.0) * scale) as u32;
 
-			if entity_kind == EntityKind::Empty {
-				debugln!("Warning: entity id {} has unknown kind {}", id, kind);
-				continue;
-			}
+		self.canvas.set_blend_mode(BlendMode::Blend);
+		self.draw_filled_rect(screen.left, screen.top, width, height, to_color(GHOST_FILL_COLOR));
+		self.canvas.set_draw_color(to_color(GHOST_OUTLINE_COLOR));
+		let _ = self.canvas.draw_rect(Rect::new(screen.left, screen.top, width, height));
+		return;
+	}
+
+	fn draw_entities(&mut self, snapshot: &RenderSnapshot, tile_cols: Option<u32>, camera_left: f32, camera_top: f32, scale: f32, _frame_index: u32) {
+		//let texture = self.tile_texture.as_mut().expect("tile_texture does not have a value");
+		for entity in &snapshot.entities {
+			// already interpolated between the last two simulation steps
+			let pos: &Vec2 = &entity.position;
+			let entity_kind: EntityKind = entity.kind;
 
-			let style: u8 = *state.render_styles.get(id).unwrap_or(&0);
-			let (half_width, half_height) = state.get_entity_half_values(id);
+			let style: u8 = entity.render_style;
+			let (half_width, half_height) = (entity.half_width, entity.half_height);
 			let world_left: f32 = pos.x - half_width;
 			let world_top: f32 = pos.y - half_height;
 			let cam: Pointf32 = Pointf32::new(camera_left, camera_top);
@@ -508,81 +646,17 @@ impl PcRenderer {
 			let width: u32 = ((half_width * 2.0) * scale) as u32;
 			let height: u32 = ((half_height * 2.0) * scale) as u32;
 
-			if entity_kind == EntityKind::SlimeBlue || entity_kind == EntityKind::SlimeUndead || entity_kind == EntityKind::SlimeLava {
-				let death_timer: u16 = state.death_timers.get(id).copied().unwrap_or(0);
-				if death_timer > 0 {
-					self.draw_death_entity(state, session, entity_kind, pos, half_height, camera_left, camera_top, scale, death_timer);
+			if let Some(sprite) = &entity.sprite {
+				if self.draw_sprite_entity(snapshot, entity, sprite, camera_left, camera_top, scale) {
 					continue;
 				}
-
-				let vel: Vec2 = state.velocities.get(id).copied().unwrap_or_default();
-				let abs_vx: f32 = vel.x.abs();
-
-				let is_dying: bool = state.death_timers.get(id).copied().unwrap_or(0) > 0;
-
-				let anim: LocomotionAnim = if is_dying {
-					LocomotionAnim::Death
-				} else if abs_vx >= 3.0 {
-					LocomotionAnim::Run
-				} else {
-					LocomotionAnim::Walk
-				};
-
-				let frame_count: u32 = if anim == LocomotionAnim::Death { 10 } else { 8 };
-				let frame_index: u32 = (_frame_index / 6) % frame_count;
-
-				// row_index: keep what you were doing for walk/run. for death you probably want row 0.
-				let row_index: u32 = if anim == LocomotionAnim::Death { 0 } else { 2 };
-
-				let src_left_pixels: i32 = (frame_index as i32) * 64;
-				let src_top_pixels: i32 = (row_index as i32) * 64;
-				let src: Rect = Rect::new(src_left_pixels, src_top_pixels, 64, 64);
-
-				let sprite_world_scale: f32 = state.enemy_sprite_scale as f32;
-				let dest_width_pixels: u32 = (64.0 * sprite_world_scale * scale) as u32;
-				let dest_height_pixels: u32 = (64.0 * sprite_world_scale * scale) as u32;
-
-				// anchor point on physics body: bottom-center
-				let entity_bottom_center_world_x: f32 = pos.x;
-				let entity_bottom_center_world_y: f32 = pos.y + half_height;
-
-				let entity_bottom_center_screen_left: i32 = ((entity_bottom_center_world_x - camera_left) * scale) as i32;
-				let entity_bottom_center_screen_top: i32 = ((entity_bottom_center_world_y - camera_top) * scale) as i32;
-
-				let anchor_left_frac: f32 = 32.0 / 64.0;
-				let anchor_top_frac: f32 = 40.0 / 64.0;
-
-				let sprite_feet_left_pixels: i32 = (dest_width_pixels as f32 * anchor_left_frac) as i32;
-				let sprite_feet_top_pixels: i32 = (dest_height_pixels as f32 * anchor_top_frac) as i32;
-
-				let dest_left_pixels: i32 = entity_bottom_center_screen_left - sprite_feet_left_pixels;
-				let dest_top_pixels: i32 = entity_bottom_center_screen_top - sprite_feet_top_pixels;
-				let dest: Rect = Rect::new(dest_left_pixels, dest_top_pixels, dest_width_pixels, dest_height_pixels);
-				let flip_horizontal: bool = vel.x > 0.0;
-
-				let key: SlimeTextureKey = self.get_slime_texture_key(entity_kind, anim);
-
-				let _ = match key {
-					SlimeTextureKey::BlueWalk => self.canvas.copy_ex(&self.slime_blue_walk_texture, src, dest, 0.0, None, flip_horizontal, false),
-					SlimeTextureKey::BlueRun => self.canvas.copy_ex(&self.slime_blue_run_texture, src, dest, 0.0, None, flip_horizontal, false),
-					SlimeTextureKey::BlueDeath => self.canvas.copy_ex(&self.slime_blue_death_texture, src, dest, 0.0, None, flip_horizontal, false),
-
-					SlimeTextureKey::UndeadWalk => self.canvas.copy_ex(&self.slime_undead_walk_texture, src, dest, 0.0, None, flip_horizontal, false),
-					SlimeTextureKey::UndeadRun => self.canvas.copy_ex(&self.slime_undead_run_texture, src, dest, 0.0, None, flip_horizontal, false),
-					SlimeTextureKey::UndeadDeath => self.canvas.copy_ex(&self.slime_undead_death_texture, src, dest, 0.0, None, flip_horizontal, false),
-
-					SlimeTextureKey::LavaWalk => self.canvas.copy_ex(&self.slime_lava_walk_texture, src, dest, 0.0, None, flip_horizontal, false),
-					SlimeTextureKey::LavaRun => self.canvas.copy_ex(&self.slime_lava_run_texture, src, dest, 0.0, None, flip_horizontal, false),
-					SlimeTextureKey::LavaDeath => self.canvas.copy_ex(&self.slime_lava_death_texture, src, dest, 0.0, None, flip_horizontal, false),
-				}
-				.unwrap();
-
-				continue;
 			}
 
-			if entity_kind == EntityKind::MovingPlatform {
-				let width_pixels: f32 = *state.widths.get(id).unwrap_or(&16) as f32;
-				let tile_width: f32 = state.level.tile_width as f32;
+			// without the tile atlas a platform falls through to a plain rect
+			if entity_kind == EntityKind::MovingPlatform && tile_cols.is_some() {
+				let tile_cols: u32 = tile_cols.unwrap_or(1);
+				let width_pixels: f32 = entity.width_pixels as f32;
+				let tile_width: f32 = snapshot.level.tile_width as f32;
 				let width_tiles: i32 = ((width_pixels / tile_width).ceil() as i32).max(1);
 
 				self.draw_platform_entity_tiles(
@@ -591,7 +665,7 @@ impl PcRenderer {
 					world_left,
 					world_top,
 					width_tiles,
-					&state.level,
+					&snapshot.level,
 					camera_left,
 					camera_top,
 					scale,
@@ -631,3 +705,7 @@ impl PcRenderer {
 		return;
 	}
 }
+
+fn to_color(color: (u8, u8, u8, u8)) -> Color {
+	return Color::RGBA(color.0, color.1, color.2, color.3);
+}
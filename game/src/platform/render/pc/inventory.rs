@@ -0,0 +1,83 @@
+use crate::platform::render::{
+	backend::RenderBackend,
+	hud::HudElement,
+	inventory::{INVENTORY_BAR_BACK_COLOR, INVENTORY_BAR_FILL_COLOR, INVENTORY_CELL_COLOR, InventoryItemKind, InventoryLayout, InventorySnapshot, layout_inventory},
+	menu::{MENU_BORDER_COLOR, MENU_DIM_COLOR, MENU_PANEL_COLOR, MENU_SELECTED_TEXT_COLOR, MENU_SELECTION_COLOR, MENU_TEXT_COLOR, MENU_TITLE_COLOR},
+};
+
+use sdl2::{pixels::Color, rect::Rect, render::BlendMode};
+
+use super::PcRenderer;
+
+fn to_color(color: (u8, u8, u8, u8)) -> Color {
+	return Color::RGBA(color.0, color.1, color.2, color.3);
+}
+
+impl PcRenderer<'_> {
+	// over the level like a menu: the grid of items, then the selected one's name and detail
+	pub(crate) fn draw_inventory(&mut self, inventory: &InventorySnapshot) {
+		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+		let mut elements: Vec<HudElement> = std::mem::take(&mut self.hud_elements);
+		let layout: InventoryLayout = layout_inventory(inventory, screen_width_pixels, screen_height_pixels, &mut elements);
+
+		self.canvas.set_blend_mode(BlendMode::Blend);
+		self.canvas.set_draw_color(to_color(MENU_DIM_COLOR));
+		let _ = self.canvas.fill_rect(Rect::new(0, 0, screen_width_pixels as u32, screen_height_pixels as u32));
+
+		self.canvas.set_draw_color(to_color(MENU_PANEL_COLOR));
+		let _ = self
+			.canvas
+			.fill_rect(Rect::new(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height));
+
+		self.canvas.set_draw_color(to_color(MENU_BORDER_COLOR));
+		for inset in 0..(layout.border_size as i32) {
+			let _ = self.canvas.draw_rect(Rect::new(
+				layout.panel_left + inset,
+				layout.panel_top + inset,
+				layout.panel_width - (inset as u32) * 2,
+				layout.panel_height - (inset as u32) * 2,
+			));
+		}
+
+		for (index, item) in inventory.items.iter().enumerate() {
+			let (cell_left, cell_top) = layout.get_cell(index);
+			let cell_rect: Rect = Rect::new(cell_left, cell_top, layout.cell_size, layout.cell_size);
+			let is_selected: bool = index == inventory.selected;
+			self
+				.canvas
+				.set_draw_color(to_color(if is_selected { MENU_SELECTION_COLOR } else { INVENTORY_CELL_COLOR }));
+			let _ = self.canvas.fill_rect(cell_rect);
+			if is_selected {
+				self.canvas.set_draw_color(to_color(MENU_SELECTED_TEXT_COLOR));
+				let _ = self.canvas.draw_rect(cell_rect);
+			}
+
+			if item.kind == InventoryItemKind::Book {
+				let (bar_left, bar_top, bar_width, bar_height, fill_width) = layout.get_progress_bar(index, item.progress);
+				self.canvas.set_draw_color(to_color(INVENTORY_BAR_BACK_COLOR));
+				let _ = self.canvas.fill_rect(Rect::new(bar_left, bar_top, bar_width, bar_height));
+				if fill_width > 0 {
+					self.canvas.set_draw_color(to_color(INVENTORY_BAR_FILL_COLOR));
+					let _ = self.canvas.fill_rect(Rect::new(bar_left, bar_top, fill_width, bar_height));
+				}
+			}
+		}
+
+		self.draw_hud_elements(&elements);
+		self.hud_elements = elements;
+
+		let title_rect: Rect = Rect::new(layout.text_left, layout.title_top, layout.text_width, layout.title_height);
+		self.draw_menu_text(&inventory.title, title_rect, MENU_TITLE_COLOR, false);
+
+		if let Some(item) = inventory.items.get(inventory.selected) {
+			let name_rect: Rect = Rect::new(layout.text_left, layout.name_top, layout.text_width, layout.line_height);
+			self.draw_menu_text(&item.name, name_rect, MENU_SELECTED_TEXT_COLOR, false);
+			let detail_rect: Rect = Rect::new(layout.text_left, layout.detail_top, layout.text_width, layout.line_height);
+			self.draw_menu_text(&item.detail, detail_rect, MENU_TEXT_COLOR, false);
+		}
+
+		let hint_rect: Rect = Rect::new(layout.text_left, layout.hint_top, layout.text_width, layout.line_height);
+		self.draw_menu_text(&inventory.hint, hint_rect, MENU_TEXT_COLOR, false);
+		return;
+	}
+}
@@ -0,0 +1,98 @@
+use crate::platform::render::{
+	backend::RenderBackend,
+	hud::HudElement,
+	inventory::{INVENTORY_BAR_BACK_COLOR, INVENTORY_BAR_FILL_COLOR, INVENTORY_CELL_COLOR},
+	level_select::{LEVEL_SELECT_COMPLETED_CELL_COLOR, LEVEL_SELECT_LOCKED_CELL_COLOR, LevelSelectLayout, LevelSelectSnapshot, layout_level_select},
+	menu::{MENU_BORDER_COLOR, MENU_PANEL_COLOR, MENU_SELECTED_TEXT_COLOR, MENU_SELECTION_COLOR, MENU_TEXT_COLOR, MENU_TITLE_COLOR, MENU_TITLE_SCREEN_DIM_COLOR},
+};
+
+use sdl2::{pixels::Color, rect::Rect, render::BlendMode};
+
+use super::PcRenderer;
+
+fn to_color(color: (u8, u8, u8, u8)) -> Color {
+	return Color::RGBA(color.0, color.1, color.2, color.3);
+}
+
+impl PcRenderer<'_> {
+	// over the title screen: each world's name over its levels, then the selected level's details
+	pub(crate) fn draw_level_select(&mut self, level_select: &LevelSelectSnapshot) {
+		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+		let mut elements: Vec<HudElement> = std::mem::take(&mut self.hud_elements);
+		let layout: LevelSelectLayout = layout_level_select(level_select, screen_width_pixels, screen_height_pixels, &mut elements);
+
+		self.canvas.set_blend_mode(BlendMode::Blend);
+		self.canvas.set_draw_color(to_color(MENU_TITLE_SCREEN_DIM_COLOR));
+		let _ = self.canvas.fill_rect(Rect::new(0, 0, screen_width_pixels as u32, screen_height_pixels as u32));
+
+		self.canvas.set_draw_color(to_color(MENU_PANEL_COLOR));
+		let _ = self
+			.canvas
+			.fill_rect(Rect::new(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height));
+
+		self.canvas.set_draw_color(to_color(MENU_BORDER_COLOR));
+		for inset in 0..(layout.border_size as i32) {
+			let _ = self.canvas.draw_rect(Rect::new(
+				layout.panel_left + inset,
+				layout.panel_top + inset,
+				layout.panel_width - (inset as u32) * 2,
+				layout.panel_height - (inset as u32) * 2,
+			));
+		}
+
+		for (row_index, row) in level_select.rows.iter().enumerate() {
+			for (column_index, cell) in row.cells.iter().enumerate() {
+				let (cell_left, cell_top) = layout.get_cell(row_index, column_index);
+				let cell_rect: Rect = Rect::new(cell_left, cell_top, layout.cell_size, layout.cell_size);
+				let is_selected: bool = row_index == level_select.selected_row && column_index == level_select.selected_column;
+				let cell_color: (u8, u8, u8, u8) = if is_selected {
+					MENU_SELECTION_COLOR
+				} else if !cell.is_unlocked {
+					LEVEL_SELECT_LOCKED_CELL_COLOR
+				} else if cell.is_completed {
+					LEVEL_SELECT_COMPLETED_CELL_COLOR
+				} else {
+					INVENTORY_CELL_COLOR
+				};
+				self.canvas.set_draw_color(to_color(cell_color));
+				let _ = self.canvas.fill_rect(cell_rect);
+				if is_selected {
+					self.canvas.set_draw_color(to_color(MENU_SELECTED_TEXT_COLOR));
+					let _ = self.canvas.draw_rect(cell_rect);
+				}
+
+				if cell.is_unlocked {
+					let (bar_left, bar_top, bar_width, bar_height, fill_width) = layout.get_progress_bar(row_index, column_index, cell.completion_percent);
+					self.canvas.set_draw_color(to_color(INVENTORY_BAR_BACK_COLOR));
+					let _ = self.canvas.fill_rect(Rect::new(bar_left, bar_top, bar_width, bar_height));
+					if fill_width > 0 {
+						self.canvas.set_draw_color(to_color(INVENTORY_BAR_FILL_COLOR));
+						let _ = self.canvas.fill_rect(Rect::new(bar_left, bar_top, fill_width, bar_height));
+					}
+				}
+			}
+		}
+
+		self.draw_hud_elements(&elements);
+		self.hud_elements = elements;
+
+		let title_rect: Rect = Rect::new(layout.text_left, layout.title_top, layout.text_width, layout.title_height);
+		self.draw_menu_text(&level_select.title, title_rect, MENU_TITLE_COLOR, false);
+
+		for (row_index, row) in level_select.rows.iter().enumerate() {
+			let label_rect: Rect = Rect::new(layout.text_left, layout.get_row_label_top(row_index), layout.text_width, layout.line_height);
+			self.draw_menu_text(&row.name, label_rect, MENU_TEXT_COLOR, false);
+		}
+
+		let name_rect: Rect = Rect::new(layout.text_left, layout.name_top, layout.text_width, layout.line_height);
+		self.draw_menu_text(&level_select.name, name_rect, MENU_SELECTED_TEXT_COLOR, false);
+		let status_rect: Rect = Rect::new(layout.text_left, layout.status_top, layout.text_width, layout.line_height);
+		self.draw_menu_text(&level_select.status, status_rect, MENU_TEXT_COLOR, false);
+		let completion_rect: Rect = Rect::new(layout.text_left, layout.completion_top, layout.text_width, layout.line_height);
+		self.draw_menu_text(&level_select.completion, completion_rect, MENU_TEXT_COLOR, false);
+
+		let hint_rect: Rect = Rect::new(layout.text_left, layout.hint_top, layout.text_width, layout.line_height);
+		self.draw_menu_text(&level_select.hint, hint_rect, MENU_TEXT_COLOR, false);
+		return;
+	}
+}
@@ -0,0 +1,83 @@
+use crate::platform::render::{
+	backend::RenderBackend,
+	menu::{
+		MENU_BORDER_COLOR, MENU_DIM_COLOR, MENU_PANEL_COLOR, MENU_SELECTED_TEXT_COLOR, MENU_SELECTION_COLOR, MENU_TEXT_COLOR, MENU_TITLE_COLOR,
+		MENU_TITLE_SCREEN_DIM_COLOR, MenuLayout, MenuSnapshot, layout_menu,
+	},
+};
+
+use sdl2::{pixels::Color, rect::Rect, render::BlendMode};
+
+use super::PcRenderer;
+
+fn to_color(color: (u8, u8, u8, u8)) -> Color {
+	return Color::RGBA(color.0, color.1, color.2, color.3);
+}
+
+impl PcRenderer<'_> {
+	// over everything else the level draws
+	pub(crate) fn draw_menu(&mut self, menu: &MenuSnapshot) {
+		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+		let layout: MenuLayout = layout_menu(menu, screen_width_pixels, screen_height_pixels);
+
+		self.canvas.set_blend_mode(BlendMode::Blend);
+		let dim_color: (u8, u8, u8, u8) = if menu.is_title_screen { MENU_TITLE_SCREEN_DIM_COLOR } else { MENU_DIM_COLOR };
+		self.canvas.set_draw_color(to_color(dim_color));
+		let _ = self
+			.canvas
+			.fill_rect(Rect::new(0, 0, screen_width_pixels as u32, screen_height_pixels as u32));
+
+		if !menu.is_title_screen {
+			self.canvas.set_draw_color(to_color(MENU_PANEL_COLOR));
+			let _ = self
+				.canvas
+				.fill_rect(Rect::new(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height));
+
+			self.canvas.set_draw_color(to_color(MENU_BORDER_COLOR));
+			for inset in 0..(layout.border_size as i32) {
+				let _ = self.canvas.draw_rect(Rect::new(
+					layout.panel_left + inset,
+					layout.panel_top + inset,
+					layout.panel_width - (inset as u32) * 2,
+					layout.panel_height - (inset as u32) * 2,
+				));
+			}
+		}
+
+		let title_rect: Rect = Rect::new(layout.line_left, layout.title_top, layout.line_width, layout.title_height);
+		self.draw_menu_text(&menu.title, title_rect, MENU_TITLE_COLOR, menu.is_title_screen);
+
+		for (index, line) in menu.lines.iter().enumerate() {
+			let line_rect: Rect = Rect::new(layout.line_left, layout.get_line_top(index), layout.line_width, layout.line_height);
+			let mut color: (u8, u8, u8, u8) = MENU_TEXT_COLOR;
+			if line.is_selected {
+				self.canvas.set_draw_color(to_color(MENU_SELECTION_COLOR));
+				let _ = self.canvas.fill_rect(line_rect);
+				color = MENU_SELECTED_TEXT_COLOR;
+			}
+			self.draw_menu_text(&line.text, line_rect, color, false);
+		}
+
+		return;
+	}
+
+	// centred in `rect`
+	pub(crate) fn draw_menu_text(&mut self, text: &str, rect: Rect, color: (u8, u8, u8, u8), is_large: bool) {
+		if text.is_empty() {
+			return;
+		}
+
+		let font = if is_large { &self.title_font } else { &self.font };
+		let Ok(surface) = font.render(text).blended(to_color(color)) else {
+			return;
+		};
+		let Ok(texture) = self.texture_creator.create_texture_from_surface(&surface) else {
+			return;
+		};
+
+		let left: i32 = rect.x() + (rect.width() as i32 - surface.width() as i32) / 2;
+		let top: i32 = rect.y() + (rect.height() as i32 - surface.height() as i32) / 2;
+		let _ = self.canvas.copy(&texture, None, Rect::new(left, top, surface.width(), surface.height()));
+		return;
+	}
+}
@@ -0,0 +1,96 @@
+use crate::platform::render::{
+	backend::RenderBackend,
+	message_box::{
+		MESSAGE_BOX_BORDER_COLOR, MESSAGE_BOX_CONTINUE_COLOR, MESSAGE_BOX_PANEL_COLOR, MESSAGE_BOX_PORTRAIT_COLOR, MESSAGE_BOX_TEXT_COLOR, MessageBoxLayout,
+		MessageBoxSnapshot, layout_message_box, wrap_text,
+	},
+};
+
+use sdl2::{pixels::Color, rect::Rect, render::BlendMode};
+
+use super::PcRenderer;
+
+fn to_color(color: (u8, u8, u8, u8)) -> Color {
+	return Color::RGBA(color.0, color.1, color.2, color.3);
+}
+
+impl PcRenderer<'_> {
+	// on top of the hud; the text is typed out up to revealed_chars
+	pub(crate) fn draw_message_box(&mut self, message: &MessageBoxSnapshot) {
+		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+		let text_line_height: i32 = self.text_font.recommended_line_spacing();
+		let layout: MessageBoxLayout = layout_message_box(message, screen_width_pixels, screen_height_pixels, text_line_height);
+
+		self.canvas.set_blend_mode(BlendMode::Blend);
+		self.canvas.set_draw_color(to_color(MESSAGE_BOX_PANEL_COLOR));
+		let _ = self
+			.canvas
+			.fill_rect(Rect::new(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height));
+
+		self.canvas.set_draw_color(to_color(MESSAGE_BOX_BORDER_COLOR));
+		for inset in 0..(layout.border_size as i32) {
+			let _ = self.canvas.draw_rect(Rect::new(
+				layout.panel_left + inset,
+				layout.panel_top + inset,
+				layout.panel_width - (inset as u32) * 2,
+				layout.panel_height - (inset as u32) * 2,
+			));
+		}
+
+		if let Some((left, top, size)) = layout.portrait {
+			self.canvas.set_draw_color(to_color(MESSAGE_BOX_PORTRAIT_COLOR));
+			let _ = self.canvas.fill_rect(Rect::new(left, top, size, size));
+			self.draw_hud_icon(message.speaker_icon_id, left, top, size);
+		}
+
+		self.draw_message_text(message, &layout);
+
+		if message.is_continue_marker_visible() {
+			let (left, top, size) = layout.continue_marker;
+			self.canvas.set_draw_color(to_color(MESSAGE_BOX_CONTINUE_COLOR));
+			for row in 0..(size / 2) {
+				let _ = self.canvas.fill_rect(Rect::new(left + row as i32, top + row as i32, size - row * 2, 1));
+			}
+		}
+
+		return;
+	}
+
+	fn draw_message_text(&mut self, message: &MessageBoxSnapshot, layout: &MessageBoxLayout) {
+		let mut lines: Vec<(usize, usize)> = std::mem::take(&mut self.message_lines);
+		let font = &self.text_font;
+		wrap_text(
+			&message.text,
+			layout.text_width,
+			|text| font.size_of(text).map(|(width, _)| width).unwrap_or(0),
+			&mut lines,
+		);
+
+		let revealed_len: usize = message.get_revealed_len();
+		let line_height: i32 = self.text_font.recommended_line_spacing();
+		let mut top: i32 = layout.text_top;
+		for (start, end) in lines.iter().copied() {
+			if start >= revealed_len {
+				break;
+			}
+			if top + line_height > layout.panel_top + layout.panel_height as i32 {
+				break;
+			}
+
+			let text: &str = &message.text[start..end.min(revealed_len)];
+			if !text.is_empty() {
+				let Ok(surface) = self.text_font.render(text).blended(to_color(MESSAGE_BOX_TEXT_COLOR)) else {
+					break;
+				};
+				let Ok(texture) = self.texture_creator.create_texture_from_surface(&surface) else {
+					break;
+				};
+				let _ = self.canvas.copy(&texture, None, Rect::new(layout.text_left, top, surface.width(), surface.height()));
+			}
+			top += line_height;
+		}
+
+		self.message_lines = lines;
+		return;
+	}
+}
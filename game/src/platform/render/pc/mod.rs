@@ -7,10 +7,17 @@ pub(crate) const BOOK_HEADER_HEIGHT_PIXELS: i32 = 34;
 pub(crate) const BOOK_FOOTER_HEIGHT_PIXELS: i32 = 34;
 pub(crate) const BOOK_BAR_TEXT_TOP_OFFSET_PIXELS: i32 = 8;
 
+mod assets;
 mod book_overlay;
 mod draw;
+mod inventory;
+mod level_select;
+mod menu;
+mod message_box;
 mod platform_tiles;
 mod renderer;
+mod results;
+mod transition;
 mod window;
 
-pub use renderer::PcRenderer;
+pub use renderer::{PcRenderContext, PcRenderer, PcWindow, open_window};
@@ -4,7 +4,7 @@ use sdl2::rect::Rect;
 
 use super::PcRenderer;
 
-impl PcRenderer {
+impl PcRenderer<'_> {
 	pub fn draw_platform_entity_tiles(
 		&mut self,
 		tile_cols: u32,
@@ -20,7 +20,9 @@ impl PcRenderer {
 		mid_kind: TileKind,
 		right_kind: TileKind,
 	) {
-		let texture = self.tile_texture.as_mut().expect("tile_texture is not set");
+		let Some(tile_handle) = self.tile_texture else {
+			return;
+		};
 		if width_tiles <= 0 {
 			return;
 		}
@@ -56,6 +58,9 @@ impl PcRenderer {
 			let src = Rect::new(source_left, source_top, tile_pixel, tile_pixel);
 			let dst = Rect::new(screen_left, screen_top, dest_width_pixels, dest_height_pixels);
 
+			let Some(texture) = self.assets.get_texture(tile_handle) else {
+				return;
+			};
 			let _ = self.canvas.copy(texture, src, dst);
 		}
 
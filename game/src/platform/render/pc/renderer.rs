@@ -1,103 +1,262 @@
-use super::window::{WindowSettings, load_window_settings, save_window_settings};
+use super::{
+	assets::{PcAssets, TextureHandle},
+	window::{WindowSettings, load_window_settings, save_window_settings},
+};
 use crate::{
-	RenderBackend, Session, State,
-	engine_math::Vec2,
+	RenderBackend, debugln,
 	platform::{
 		input::InputState,
-		render::{BackgroundDrawParams, BackgroundId, common::RenderCommon},
+		render::{
+			BackgroundDrawParams, BackgroundId,
+			background::BackgroundLibrary,
+			atlas::SheetHandle,
+			common::RenderCommon,
+			hud::HudElement,
+			icon_registry::IconRegistry,
+			lighting::Lightmap,
+			snapshot::RenderSnapshot,
+		},
 	},
 	runtime::{
 		assets::{get_font_path, get_gfx_root},
-		state::EntityKind,
+		accessibility::{TextFont, TextSize},
 	},
 };
 use sdl2::{
 	EventPump,
-	image::LoadTexture,
 	pixels::Color,
-	render::{BlendMode, Canvas, Texture},
+	render::{BlendMode, Canvas, Texture, TextureCreator},
 	ttf::{Font, Sdl2TtfContext},
-	video::Window,
+	video::{Window, WindowContext},
 };
 
-use std::path::PathBuf;
-
-#[derive(Clone, Copy)]
-pub(crate) enum SlimeTextureKey {
-	BlueWalk,
-	BlueRun,
-	BlueDeath,
-	UndeadWalk,
-	UndeadRun,
-	UndeadDeath,
-	LavaWalk,
-	LavaRun,
-	LavaDeath,
+use std::path::{Path, PathBuf};
+
+// the window and its event pump, before the renderer takes them over
+pub struct PcWindow {
+	pub video: sdl2::VideoSubsystem,
+	pub canvas: Canvas<Window>,
+	pub event_pump: EventPump,
 }
 
-pub struct PcRenderer {
+// what the renderer's textures and fonts are made from; run() keeps it alive for as long as the
+// renderer that borrows it
+pub struct PcRenderContext {
+	pub texture_creator: TextureCreator<WindowContext>,
+	pub ttf: Sdl2TtfContext,
+}
+
+pub struct PcRenderer<'a> {
 	pub video: sdl2::VideoSubsystem,
 	pub canvas: Canvas<Window>,
 	pub event_pump: EventPump,
 	pub common: RenderCommon,
-	pub slime_blue_walk_texture: Texture<'static>,
-	pub slime_blue_run_texture: Texture<'static>,
-	pub slime_blue_death_texture: Texture<'static>,
-	pub slime_undead_walk_texture: Texture<'static>,
-	pub slime_undead_run_texture: Texture<'static>,
-	pub slime_undead_death_texture: Texture<'static>,
-	pub slime_lava_walk_texture: Texture<'static>,
-	pub slime_lava_run_texture: Texture<'static>,
-	pub slime_lava_death_texture: Texture<'static>,
+	pub assets: PcAssets<'a>,
+	// RenderSnapshot::sprite_sheets ids -> atlas handles
+	pub(crate) sprite_sheet_handles: Vec<SheetHandle>,
+	pub icons: IconRegistry,
+	pub(crate) icon_sheet: Option<SheetHandle>,
 	pub frame_index: u32,
 	pub atlas_tile_width_pixels: u32,
 	pub atlas_tile_height_pixels: u32,
-	pub tile_texture: Option<Texture<'static>>,
-
-	background_id: BackgroundId,
-	pub bg_texture: Option<Texture<'static>>,
-	pub bg_parallax_x: f32,
-	pub bg_parallax_y: f32,
+	pub tile_texture: Option<TextureHandle>,
+
+	pub backgrounds: BackgroundLibrary,
+	pub(crate) background_id: BackgroundId,
+	// one texture per layer of the current background, same order as BackgroundDef::layers;
+	// None for a layer whose png failed to load
+	pub(crate) bg_layers: Vec<Option<TextureHandle>>,
+	pub(crate) lightmap: Lightmap,
+	pub(crate) lightmap_texture: Option<Texture<'a>>,
+	// rebuilt from RenderSnapshot::hud every frame; kept to reuse its allocation
+	pub(crate) hud_elements: Vec<HudElement>,
+	// byte ranges of the wrapped message box lines; kept to reuse its allocation
+	pub(crate) message_lines: Vec<(usize, usize)>,
 	render_scale: u32,
-	pub texture_creator: &'static sdl2::render::TextureCreator<sdl2::video::WindowContext>,
-	pub font: Font<'static, 'static>,
-	pub trigger_texture: Texture<'static>,
+	pub texture_creator: &'a TextureCreator<WindowContext>,
+	pub font: Font<'a, 'static>,
+	// the game's name on the title screen
+	pub title_font: Font<'a, 'static>,
+	// book pages and message boxes, in the font and size the accessibility settings pick;
+	// reloaded when either changes
+	pub(crate) text_font: Font<'a, 'static>,
+	pub(crate) text_font_key: (TextFont, TextSize),
+	ttf: &'a Sdl2TtfContext,
 }
 
-impl Drop for PcRenderer {
+impl Drop for PcRenderer<'_> {
 	fn drop(&mut self) {
 		save_window_settings(self.canvas.window());
 		return;
 	}
 }
 
-impl PcRenderer {
+// opens the window where it was last closed, or at 80% of the desktop's height
+pub fn open_window() -> Result<PcWindow, String> {
+	let sdl = sdl2::init()?;
+	let _ = sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "0"); // nearest
+	let _image = sdl2::image::init(sdl2::image::InitFlag::PNG)?;
+	let video = sdl.video()?;
+	let dm = video.desktop_display_mode(0)?;
+	let desktop_height_pixels: u32 = dm.h as u32;
+	let target_aspect: f32 = 16.0 / 9.0;
+	let saved: Option<WindowSettings> = load_window_settings();
+
+	let (window_width_pixels, window_height_pixels) = if let Some(s) = saved {
+		(s.width_pixels, s.height_pixels)
+	} else {
+		let mut window_height_pixels: u32 = ((desktop_height_pixels as f32) * 0.80) as u32;
+		if window_height_pixels < 360 {
+			window_height_pixels = 360;
+		}
+
+		let window_width_pixels: u32 = ((window_height_pixels as f32) * target_aspect) as u32;
+		(window_width_pixels, window_height_pixels)
+	};
+
+	let mut window = video
+		.window("jumpy", window_width_pixels, window_height_pixels)
+		.position_centered()
+		.resizable()
+		.build()
+		.map_err(|e| e.to_string())?;
+
+	if let Some(s) = saved {
+		window.set_position(sdl2::video::WindowPos::Positioned(s.left), sdl2::video::WindowPos::Positioned(s.top));
+		if s.is_maximized {
+			window.maximize();
+		}
+	}
+
+	let mut canvas = window.into_canvas().accelerated().present_vsync().build().map_err(|e| e.to_string())?;
+	canvas.set_blend_mode(BlendMode::Blend);
+
+	let event_pump = sdl.event_pump()?;
+	return Ok(PcWindow { video, canvas, event_pump });
+}
+
+impl PcRenderContext {
+	pub fn new(window: &PcWindow) -> Result<PcRenderContext, String> {
+		let ttf: Sdl2TtfContext = sdl2::ttf::init().map_err(|e| e.to_string())?;
+		return Ok(PcRenderContext {
+			texture_creator: window.canvas.texture_creator(),
+			ttf,
+		});
+	}
+}
+
+impl<'a> PcRenderer<'a> {
+	// a font that can't be read is an error, like a level that can't; missing art is only logged
+	pub fn new(window: PcWindow, context: &'a PcRenderContext) -> Result<PcRenderer<'a>, String> {
+		let ttf: &'a Sdl2TtfContext = &context.ttf;
+		let font_path: PathBuf = get_font_path().join("DejaVuSansMono.ttf");
+		let mut font = load_font(ttf, &font_path, 12)?;
+		font.set_style(sdl2::ttf::FontStyle::NORMAL);
+		let title_font = load_font(ttf, &font_path, 40)?;
+		let text_font_key: (TextFont, TextSize) = (TextFont::DejaVuSansMono, TextSize::Small);
+		let text_font = load_font(ttf, &get_font_path().join(text_font_key.0.get_file_name()), text_font_key.1.get_point_size())?;
+
+		// missing art is logged and drawn around, not fatal
+		let mut assets: PcAssets<'a> = PcAssets::new(&context.texture_creator);
+		let tile_texture: Option<TextureHandle> = assets
+			.load_texture(gfx_pc_path(&["tiles", "tiles64.png"]))
+			.map_err(|e| {
+				debugln!("tile atlas load failed: {}", e);
+			})
+			.ok();
+
+		let icons: IconRegistry = IconRegistry::load().unwrap_or_else(|e| {
+			debugln!("icon registry load failed: {}", e);
+			return IconRegistry::empty();
+		});
+		let backgrounds: BackgroundLibrary = BackgroundLibrary::load().unwrap_or_else(|e| {
+			debugln!("background library load failed: {}", e);
+			return BackgroundLibrary::empty();
+		});
+		let icon_sheet: Option<SheetHandle> = if icons.sheet.is_empty() { None } else { Some(assets.atlas.add_sheet(icons.sheet.as_str())) };
+
+		let renderer = PcRenderer {
+			video: window.video,
+			canvas: window.canvas,
+			event_pump: window.event_pump,
+			common: RenderCommon::new(),
+			assets,
+			sprite_sheet_handles: Vec::new(),
+			icons,
+			icon_sheet,
+			frame_index: 0,
+			atlas_tile_width_pixels: 64,
+			atlas_tile_height_pixels: 64,
+			tile_texture,
+			backgrounds,
+			background_id: BackgroundId::NONE,
+			bg_layers: Vec::new(),
+			lightmap: Lightmap::new(),
+			lightmap_texture: None,
+			hud_elements: Vec::new(),
+			message_lines: Vec::new(),
+			render_scale: 4,
+			texture_creator: &context.texture_creator,
+			font,
+			title_font,
+			text_font,
+			text_font_key,
+			ttf,
+		};
+
+		return Ok(renderer);
+	}
+
 	pub fn screen_size_pixels(&self) -> (u32, u32) {
 		let (width_pixels, height_pixels) = self.canvas.window().size();
 		return (width_pixels, height_pixels);
 	}
 
 	pub fn draw_background_by_id(&mut self, background_id: BackgroundId) {
-		if self.bg_texture.is_some() && self.background_id == background_id {
+		if self.background_id == background_id {
 			return;
 		}
 
 		self.background_id = background_id;
-		let file_name = parse_background_id(background_id);
-		let bg_path = gfx_pc_path(&["background", file_name]);
+		self.bg_layers.clear();
 
-		let bg_texture = load_texture(&self.texture_creator, bg_path);
-		self.bg_texture = Some(bg_texture);
+		let Some(def) = self.backgrounds.get(background_id) else {
+			debugln!("no background definition for id {}", background_id.to_u8());
+			return;
+		};
 
-		if let Some(background) = self.bg_texture.as_mut() {
-			background.set_blend_mode(BlendMode::Blend);
+		// the cache keeps earlier backgrounds, so switching back doesn't hit the disk again
+		for layer in &def.layers {
+			match self.assets.load_texture(gfx_pc_path(&[layer.image.as_str()])) {
+				Ok(handle) => self.bg_layers.push(Some(handle)),
+				Err(e) => {
+					debugln!("background layer load failed: {}", e);
+					self.bg_layers.push(None);
+				}
+			}
 		}
 
 		return;
 	}
+
+	// a font that fails to load keeps the one already in use
+	pub(crate) fn set_text_font(&mut self, font: TextFont, size: TextSize) {
+		if self.text_font_key == (font, size) {
+			return;
+		}
+
+		self.text_font_key = (font, size);
+		match self.ttf.load_font(get_font_path().join(font.get_file_name()), size.get_point_size()) {
+			Ok(font) => self.text_font = font,
+			Err(e) => {
+				debugln!("text font load failed: {}", e);
+			}
+		}
+		return;
+	}
 }
 
-impl RenderBackend for PcRenderer {
+impl RenderBackend for PcRenderer<'_> {
 	fn init(&mut self) {
 		// Nothing yet
 		return;
@@ -107,121 +266,8 @@ impl RenderBackend for PcRenderer {
 		return self.render_scale as f32;
 	}
 
-	fn new() -> Self {
-		let sdl = sdl2::init().unwrap();
-		let _ = sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "0"); // nearest
-		let _image = sdl2::image::init(sdl2::image::InitFlag::PNG).unwrap();
-		let video = sdl.video().unwrap();
-		let dm = video.desktop_display_mode(0).expect("desktop_display_mode failed");
-		let desktop_height_pixels: u32 = dm.h as u32;
-		let target_aspect: f32 = 16.0 / 9.0;
-		let saved: Option<WindowSettings> = load_window_settings();
-
-		let (window_width_pixels, window_height_pixels) = if let Some(s) = saved {
-			(s.width_pixels, s.height_pixels)
-		} else {
-			let mut window_height_pixels: u32 = ((desktop_height_pixels as f32) * 0.80) as u32;
-			if window_height_pixels < 360 {
-				window_height_pixels = 360;
-			}
-
-			let window_width_pixels: u32 = ((window_height_pixels as f32) * target_aspect) as u32;
-			(window_width_pixels, window_height_pixels)
-		};
-
-		let mut window = video
-			.window("jumpy", window_width_pixels, window_height_pixels)
-			.position_centered()
-			.resizable()
-			.build()
-			.expect("window build failed");
-
-		if let Some(s) = saved {
-			window.set_position(sdl2::video::WindowPos::Positioned(s.left), sdl2::video::WindowPos::Positioned(s.top));
-			if s.is_maximized {
-				window.maximize();
-			}
-		}
-
-		let mut canvas = window.into_canvas().accelerated().present_vsync().build().unwrap();
-		canvas.set_blend_mode(BlendMode::Blend);
-
-		let event_pump = sdl.event_pump().unwrap();
-		let texture_creator = leak_texture_creator(&canvas);
-
-		let ttf_box = Box::new(sdl2::ttf::init().unwrap());
-		let ttf: &'static Sdl2TtfContext = Box::leak(ttf_box);
-
-		let font_path: PathBuf = get_font_path().join("DejaVuSansMono.ttf");
-		let mut font = ttf.load_font(font_path, 12).unwrap();
-		font.set_style(sdl2::ttf::FontStyle::NORMAL);
-
-		let tile_path = gfx_pc_path(&["tiles", "tiles64.png"]);
-		let tile_texture = load_texture(&texture_creator, tile_path);
-
-		let slime_blue_walk_path = gfx_pc_path(&["slime", "blue", "walk_body.png"]);
-		let slime_blue_walk_texture = load_texture(&texture_creator, slime_blue_walk_path);
-
-		let slime_blue_run_path = gfx_pc_path(&["slime", "blue", "run_body.png"]);
-		let slime_blue_run_texture = load_texture(&texture_creator, slime_blue_run_path);
-
-		let slime_undead_walk_path = gfx_pc_path(&["slime", "undead", "walk_body.png"]);
-		let slime_undead_walk_texture = load_texture(&texture_creator, slime_undead_walk_path);
-
-		let slime_blue_death_path: PathBuf = gfx_pc_path(&["slime", "blue", "death_body.png"]);
-		let slime_blue_death_texture = load_texture(&texture_creator, slime_blue_death_path);
-
-		let slime_undead_run_path = gfx_pc_path(&["slime", "undead", "run_body.png"]);
-		let slime_undead_run_texture = load_texture(&texture_creator, slime_undead_run_path);
-
-		let slime_lava_walk_path = gfx_pc_path(&["slime", "lava", "walk_body.png"]);
-		let slime_lava_walk_texture = load_texture(&texture_creator, slime_lava_walk_path);
-
-		let slime_lava_run_path = gfx_pc_path(&["slime", "lava", "run_body.png"]);
-		let slime_lava_run_texture = load_texture(&texture_creator, slime_lava_run_path);
-
-		let slime_lava_death_path: PathBuf = gfx_pc_path(&["slime", "lava", "death_body.png"]);
-		let slime_lava_death_texture = texture_creator.load_texture(slime_lava_death_path).expect("failed to load slime_lava_death.png");
-
-		let slime_undead_death_path: PathBuf = gfx_pc_path(&["slime", "undead", "death_body.png"]);
-		let slime_undead_death_texture = texture_creator.load_texture(slime_undead_death_path).expect("failed to load slime_undead_death.png");
-
-		let trigger_atlas_path: PathBuf = gfx_pc_path(&["icons.png"]);
-		let trigger_texture = texture_creator.load_texture(trigger_atlas_path).expect("failed to load icons.png");
-
-		let renderer = PcRenderer {
-			video,
-			canvas,
-			event_pump,
-			common: RenderCommon::new(),
-			slime_blue_walk_texture,
-			slime_blue_run_texture,
-			slime_blue_death_texture,
-			slime_undead_walk_texture,
-			slime_undead_run_texture,
-			slime_undead_death_texture,
-			slime_lava_walk_texture,
-			slime_lava_run_texture,
-			slime_lava_death_texture,
-			frame_index: 0,
-			atlas_tile_width_pixels: 64,
-			atlas_tile_height_pixels: 64,
-			tile_texture: Some(tile_texture),
-			background_id: BackgroundId::None,
-			bg_texture: None,
-			bg_parallax_x: 0.35,
-			bg_parallax_y: 0.15,
-			render_scale: 4,
-			texture_creator,
-			font,
-			trigger_texture,
-		};
-
-		return renderer;
-	}
-
 	fn draw_background(&mut self, params: &BackgroundDrawParams) {
-		self.draw_background_internal(params.camera_left, params.camera_top, params.scale);
+		self.draw_background_internal(params);
 	}
 
 	fn get_screen_size(&self) -> (i32, i32) {
@@ -239,45 +285,35 @@ impl RenderBackend for PcRenderer {
 		return;
 	}
 
-	fn draw_level(&mut self, state: &State, session: &Session) {
-		let (camera_left, camera_top) = self.common.compute_camera(self, &state, &session);
+	fn draw_level(&mut self, snapshot: &RenderSnapshot) {
+		// levels can change under a snapshot; the layers are only reloaded when the id does
+		if snapshot.level.background_id != BackgroundId::NONE {
+			self.draw_background_by_id(snapshot.level.background_id);
+		}
 
 		let background_draw_params: BackgroundDrawParams = BackgroundDrawParams {
-			background_id: state.level.background_id,
-			camera_left: camera_left,
-			camera_top: camera_top,
+			background_id: snapshot.level.background_id,
+			camera_left: snapshot.camera_left,
+			camera_top: snapshot.camera_top,
 			scale: 4.0,
+			level_height: snapshot.level.height as i32 * snapshot.level.tile_height as i32,
+			tick: snapshot.tick,
 		};
 		self.draw_background(&background_draw_params);
-		self.draw_level_internal(state, session);
-	}
-
-	fn draw_death_entity(
-		&mut self,
-		state: &State,
-		session: &Session,
-		entity_kind: EntityKind,
-		pos: &Vec2,
-		half_height: f32,
-		camera_left: f32,
-		camera_top: f32,
-		scale: f32,
-		death_timer: u16,
-	) {
-		self.draw_death_entity_internal(state, session, entity_kind, pos, half_height, camera_left, camera_top, scale, death_timer);
+		self.set_text_font(snapshot.text_font, snapshot.text_size);
+		self.draw_level_internal(snapshot);
 	}
 
 	fn commit(&mut self) {
 		self.canvas.present();
 		self.frame_index += 1;
+		self.assets.poll_hot_reload();
 		return;
 	}
 }
 
-fn leak_texture_creator(canvas: &sdl2::render::Canvas<sdl2::video::Window>) -> &'static sdl2::render::TextureCreator<sdl2::video::WindowContext> {
-	let creator_box = Box::new(canvas.texture_creator());
-	let texture_creator: &'static sdl2::render::TextureCreator<sdl2::video::WindowContext> = Box::leak(creator_box);
-	return texture_creator;
+fn load_font<'a>(ttf: &'a Sdl2TtfContext, path: &Path, point_size: u16) -> Result<Font<'a, 'static>, String> {
+	return ttf.load_font(path, point_size).map_err(|e| format!("missing font file\npath: {}\nsdl error: {}", path.display(), e));
 }
 
 fn gfx_pc_path(segments: &[&str]) -> PathBuf {
@@ -288,20 +324,3 @@ fn gfx_pc_path(segments: &[&str]) -> PathBuf {
 
 	return path;
 }
-
-fn parse_background_id(background_id: BackgroundId) -> &'static str {
-	match background_id {
-		BackgroundId::Library => "bg_library_stone.png",
-		BackgroundId::ParallaxForest => "bg_parallax_forest.png",
-		_ => panic!("Unknown id: {:?}", background_id),
-	}
-}
-
-fn load_texture(texture_creator: &'static sdl2::render::TextureCreator<sdl2::video::WindowContext>, file_path: PathBuf) -> Texture<'static> {
-	let texture = texture_creator.load_texture(&file_path).unwrap_or_else(|err| {
-		panic!("missing texture file\npath: {}\nsdl error: {}", file_path.display(), err);
-	});
-
-	let t: Texture<'static> = unsafe { std::mem::transmute::<Texture<'_>, Texture<'static>>(texture) };
-	return t;
-}
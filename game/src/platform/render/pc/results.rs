@@ -0,0 +1,65 @@
+use crate::platform::render::{
+	backend::RenderBackend,
+	hud::HudElement,
+	menu::{MENU_BORDER_COLOR, MENU_DIM_COLOR, MENU_PANEL_COLOR, MENU_SELECTED_TEXT_COLOR, MENU_TEXT_COLOR, MENU_TITLE_COLOR},
+	results::{ResultsLayout, ResultsSnapshot, layout_results},
+};
+
+use sdl2::{pixels::Color, rect::Rect, render::BlendMode};
+
+use super::PcRenderer;
+
+fn to_color(color: (u8, u8, u8, u8)) -> Color {
+	return Color::RGBA(color.0, color.1, color.2, color.3);
+}
+
+impl PcRenderer<'_> {
+	// over the level just left: its name, a row per score, the total and the rank
+	pub(crate) fn draw_results(&mut self, results: &ResultsSnapshot) {
+		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+		let mut elements: Vec<HudElement> = std::mem::take(&mut self.hud_elements);
+		let layout: ResultsLayout = layout_results(results, screen_width_pixels, screen_height_pixels, &mut elements);
+
+		self.canvas.set_blend_mode(BlendMode::Blend);
+		self.canvas.set_draw_color(to_color(MENU_DIM_COLOR));
+		let _ = self.canvas.fill_rect(Rect::new(0, 0, screen_width_pixels as u32, screen_height_pixels as u32));
+
+		self.canvas.set_draw_color(to_color(MENU_PANEL_COLOR));
+		let _ = self
+			.canvas
+			.fill_rect(Rect::new(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height));
+
+		self.canvas.set_draw_color(to_color(MENU_BORDER_COLOR));
+		for inset in 0..(layout.border_size as i32) {
+			let _ = self.canvas.draw_rect(Rect::new(
+				layout.panel_left + inset,
+				layout.panel_top + inset,
+				layout.panel_width - (inset as u32) * 2,
+				layout.panel_height - (inset as u32) * 2,
+			));
+		}
+		let _ = self
+			.canvas
+			.fill_rect(Rect::new(layout.text_left, layout.divider_top, layout.divider_width, layout.divider_height));
+
+		self.draw_hud_elements(&elements);
+		self.hud_elements = elements;
+
+		let title_rect: Rect = Rect::new(layout.text_left, layout.title_top, layout.text_width, layout.title_height);
+		self.draw_menu_text(&results.title, title_rect, MENU_TITLE_COLOR, false);
+		let name_rect: Rect = Rect::new(layout.text_left, layout.name_top, layout.text_width, layout.line_height);
+		self.draw_menu_text(&results.name, name_rect, MENU_SELECTED_TEXT_COLOR, false);
+
+		for (row, label) in results.labels.iter().enumerate() {
+			let label_rect: Rect = Rect::new(layout.text_left, layout.get_row_top(row), layout.label_width, layout.line_height);
+			self.draw_menu_text(label, label_rect, MENU_TEXT_COLOR, false);
+		}
+
+		let rank_label_rect: Rect = Rect::new(layout.rank_left, layout.rank_label_top, layout.rank_width, layout.line_height);
+		self.draw_menu_text(&results.rank_label, rank_label_rect, MENU_TEXT_COLOR, false);
+
+		let hint_rect: Rect = Rect::new(layout.text_left, layout.hint_top, layout.text_width, layout.line_height);
+		self.draw_menu_text(&results.hint, hint_rect, MENU_TEXT_COLOR, false);
+		return;
+	}
+}
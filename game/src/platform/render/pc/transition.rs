@@ -0,0 +1,52 @@
+use crate::{
+	platform::render::{
+		backend::RenderBackend,
+		transition::{TRANSITION_COLOR, TransitionSnapshot},
+	},
+	runtime::transition::TransitionEffect,
+};
+
+use sdl2::{pixels::Color, rect::Rect, render::BlendMode};
+
+use super::PcRenderer;
+
+impl PcRenderer<'_> {
+	// over everything, menus included, so nothing of the old level shows while the next one loads
+	pub(crate) fn draw_transition(&mut self, transition: &TransitionSnapshot) {
+		if transition.cover <= 0.0 {
+			return;
+		}
+
+		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+		match transition.effect {
+			TransitionEffect::Fade => {
+				self.canvas.set_blend_mode(BlendMode::Blend);
+				self
+					.canvas
+					.set_draw_color(Color::RGBA(TRANSITION_COLOR.0, TRANSITION_COLOR.1, TRANSITION_COLOR.2, transition.get_fade_alpha()));
+				let _ = self.canvas.fill_rect(Rect::new(0, 0, screen_width_pixels as u32, screen_height_pixels as u32));
+			}
+			TransitionEffect::Iris => {
+				self.canvas.set_blend_mode(BlendMode::None);
+				self.canvas.set_draw_color(Color::RGB(TRANSITION_COLOR.0, TRANSITION_COLOR.1, TRANSITION_COLOR.2));
+				let radius: f32 = transition.get_iris_radius(screen_width_pixels, screen_height_pixels);
+				for row_top in 0..screen_height_pixels {
+					match transition.get_iris_row_opening(radius, row_top) {
+						None => {
+							let _ = self.canvas.fill_rect(Rect::new(0, row_top, screen_width_pixels as u32, 1));
+						}
+						Some((left, right)) => {
+							if left > 0 {
+								let _ = self.canvas.fill_rect(Rect::new(0, row_top, left as u32, 1));
+							}
+							if right < screen_width_pixels {
+								let _ = self.canvas.fill_rect(Rect::new(right, row_top, (screen_width_pixels - right) as u32, 1));
+							}
+						}
+					}
+				}
+			}
+		}
+		return;
+	}
+}
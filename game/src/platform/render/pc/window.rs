@@ -1,3 +1,5 @@
+use crate::runtime::assets::get_config_root;
+
 use std::{fs, io::Write, path::PathBuf};
 
 #[derive(Clone, Copy)]
@@ -10,25 +12,7 @@ pub struct WindowSettings {
 }
 
 fn window_settings_path() -> PathBuf {
-	let mut base: PathBuf = if cfg!(target_os = "windows") {
-		std::env::var_os("APPDATA").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."))
-	} else if cfg!(target_os = "macos") {
-		std::env::var_os("HOME")
-			.map(|h| PathBuf::from(h).join("Library").join("Application Support"))
-			.unwrap_or_else(|| PathBuf::from("."))
-	} else {
-		if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME") {
-			PathBuf::from(xdg)
-		} else {
-			std::env::var_os("HOME")
-				.map(|h| PathBuf::from(h).join(".config"))
-				.unwrap_or_else(|| PathBuf::from("."))
-		}
-	};
-
-	base = base.join("jumpy");
-	let _ = fs::create_dir_all(&base);
-	return base.join("window.txt");
+	return get_config_root().join("window.txt");
 }
 
 pub fn load_window_settings() -> Option<WindowSettings> {
@@ -0,0 +1,263 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+	platform::render::hud::{HudElement, HudGlyph, get_number_width, push_glyph, push_number, push_time},
+	runtime::{
+		scoring::{LevelResults, Rank},
+		session::Session,
+	},
+};
+
+// the results screen's own text
+const MESSAGE_RESULTS_TITLE: u16 = 154;
+const MESSAGE_RESULTS_COINS: u16 = 155;
+const MESSAGE_RESULTS_STOMPS: u16 = 156;
+const MESSAGE_RESULTS_TIME: u16 = 157;
+const MESSAGE_RESULTS_DEATHS: u16 = 158;
+const MESSAGE_RESULTS_TOTAL: u16 = 159;
+const MESSAGE_RESULTS_RANK: u16 = 160;
+const MESSAGE_RESULTS_HINT: u16 = 161;
+
+// same scale as the hud and the menus
+const RESULTS_REFERENCE_HEIGHT_PIXELS: i32 = 180;
+
+// coins, stomps, time and deaths, then the total under a divider
+pub const RESULTS_ROW_COUNT: usize = 5;
+const RESULTS_TOTAL_ROW: usize = 4;
+
+const RESULTS_NUMBER_COLOR: (u8, u8, u8, u8) = (255, 255, 255, 255);
+const RESULTS_PENALTY_COLOR: (u8, u8, u8, u8) = (235, 90, 80, 255);
+const RESULTS_TOTAL_COLOR: (u8, u8, u8, u8) = (255, 230, 90, 255);
+const RANK_S_COLOR: (u8, u8, u8, u8) = (255, 210, 60, 255);
+const RANK_A_COLOR: (u8, u8, u8, u8) = (90, 220, 110, 255);
+const RANK_B_COLOR: (u8, u8, u8, u8) = (100, 170, 240, 255);
+const RANK_C_COLOR: (u8, u8, u8, u8) = (200, 200, 220, 255);
+const RANK_D_COLOR: (u8, u8, u8, u8) = (130, 130, 145, 255);
+
+#[derive(Clone, Debug)]
+pub struct ResultsSnapshot {
+	pub title: String,
+	// the level just left, by its world index name
+	pub name: String,
+	// the label of each row, in row order
+	pub labels: [String; RESULTS_ROW_COUNT],
+	pub rank_label: String,
+	pub hint: String,
+	pub results: Option<LevelResults>,
+}
+
+impl ResultsSnapshot {
+	pub fn new() -> ResultsSnapshot {
+		return ResultsSnapshot {
+			title: String::new(),
+			name: String::new(),
+			labels: [String::new(), String::new(), String::new(), String::new(), String::new()],
+			rank_label: String::new(),
+			hint: String::new(),
+			results: None,
+		};
+	}
+
+	pub fn capture(&mut self, session: &Session) {
+		const LABEL_IDS: [u16; RESULTS_ROW_COUNT] = [
+			MESSAGE_RESULTS_COINS,
+			MESSAGE_RESULTS_STOMPS,
+			MESSAGE_RESULTS_TIME,
+			MESSAGE_RESULTS_DEATHS,
+			MESSAGE_RESULTS_TOTAL,
+		];
+
+		self.title.clear();
+		self.title.push_str(session.message_table.get(MESSAGE_RESULTS_TITLE));
+		for (label, message_id) in self.labels.iter_mut().zip(LABEL_IDS) {
+			label.clear();
+			label.push_str(session.message_table.get(message_id));
+		}
+		self.rank_label.clear();
+		self.rank_label.push_str(session.message_table.get(MESSAGE_RESULTS_RANK));
+		self.hint.clear();
+		self.hint.push_str(session.message_table.get(MESSAGE_RESULTS_HINT));
+
+		self.name.clear();
+		if let Some(level) = session.get_current_level_key().and_then(|key| session.world_index.get_level(key)) {
+			self.name.push_str(level.name.as_str());
+		}
+
+		self.results = session.results;
+		return;
+	}
+}
+
+// screen rectangles of the results screen, in screen pixels
+#[derive(Copy, Clone, Debug)]
+pub struct ResultsLayout {
+	pub panel_left: i32,
+	pub panel_top: i32,
+	pub panel_width: u32,
+	pub panel_height: u32,
+	pub border_size: u32,
+	pub title_top: i32,
+	pub title_height: u32,
+	// the title, level name and hint span the panel; the row labels sit in a column on the left
+	pub text_left: i32,
+	pub text_width: u32,
+	pub line_height: u32,
+	pub name_top: i32,
+	pub label_width: u32,
+	// under the rows, above the total
+	pub divider_top: i32,
+	pub divider_width: u32,
+	pub divider_height: u32,
+	// the rank's label over its letter, right of the rows
+	pub rank_left: i32,
+	pub rank_width: u32,
+	pub rank_label_top: i32,
+	pub hint_top: i32,
+	rows_top: i32,
+	row_step: i32,
+	pixel: i32,
+}
+
+impl ResultsLayout {
+	pub fn get_row_top(&self, row: usize) -> i32 {
+		let divider_gap: i32 = if row >= RESULTS_TOTAL_ROW { self.pixel * 4 } else { 0 };
+		return self.rows_top + row as i32 * self.row_step + divider_gap;
+	}
+}
+
+fn get_rank_color(rank: Rank) -> (u8, u8, u8, u8) {
+	return match rank {
+		Rank::S => RANK_S_COLOR,
+		Rank::A => RANK_A_COLOR,
+		Rank::B => RANK_B_COLOR,
+		Rank::C => RANK_C_COLOR,
+		Rank::D => RANK_D_COLOR,
+	};
+}
+
+// a panel in the middle of the screen: the title and level name, a row per score with what was
+// done in the middle and its points on the right, the total under a divider and the rank letter
+// beside them. counts, points and the rank go into `elements` like the hud
+pub fn layout_results(results: &ResultsSnapshot, screen_width: i32, screen_height: i32, elements: &mut Vec<HudElement>) -> ResultsLayout {
+	elements.clear();
+
+	let pixel: i32 = (screen_height / RESULTS_REFERENCE_HEIGHT_PIXELS).max(1);
+	let padding: i32 = pixel * 5;
+	let line_height: i32 = pixel * 8;
+	let title_height: i32 = pixel * 12;
+	let row_step: i32 = line_height + pixel * 2;
+	let label_width: i32 = pixel * 40;
+	let count_width: i32 = pixel * 44;
+	let points_width: i32 = pixel * 32;
+	let rank_width: i32 = pixel * 36;
+	let rank_pixel: i32 = pixel * 6;
+
+	let rows_width: i32 = label_width + count_width + points_width;
+	let rows_height: i32 = RESULTS_ROW_COUNT as i32 * row_step + pixel * 4;
+	let content_width: i32 = rows_width + padding + rank_width;
+	let panel_width: i32 = content_width + padding * 2;
+	let panel_height: i32 = padding * 4 + title_height + line_height * 2 + rows_height;
+	let panel_left: i32 = (screen_width - panel_width) / 2;
+	let panel_top: i32 = (screen_height - panel_height) / 2;
+	let name_top: i32 = panel_top + padding + title_height;
+	let rows_top: i32 = name_top + line_height + padding;
+	let rank_left: i32 = panel_left + padding + rows_width + padding;
+
+	let layout: ResultsLayout = ResultsLayout {
+		panel_left,
+		panel_top,
+		panel_width: panel_width as u32,
+		panel_height: panel_height as u32,
+		border_size: (pixel / 2).max(1) as u32,
+		title_top: panel_top + padding,
+		title_height: title_height as u32,
+		text_left: panel_left + padding,
+		text_width: content_width as u32,
+		line_height: line_height as u32,
+		name_top,
+		label_width: label_width as u32,
+		divider_top: rows_top + RESULTS_TOTAL_ROW as i32 * row_step + pixel,
+		divider_width: rows_width as u32,
+		divider_height: (pixel / 2).max(1) as u32,
+		rank_left,
+		rank_width: rank_width as u32,
+		rank_label_top: rows_top,
+		hint_top: rows_top + rows_height + padding,
+		rows_top,
+		row_step,
+		pixel,
+	};
+
+	let Some(level_results) = &results.results else {
+		return layout;
+	};
+
+	// counts left aligned in their column, points right aligned to the rows' edge
+	let count_left: i32 = layout.text_left + label_width;
+	let points_right: i32 = layout.text_left + rows_width;
+	let digit_top_offset: i32 = (line_height - pixel * 5) / 2;
+
+	let coins_top: i32 = layout.get_row_top(0) + digit_top_offset;
+	push_number(elements, level_results.coins, count_left, coins_top, pixel, RESULTS_NUMBER_COLOR);
+	push_points(elements, level_results.coin_points, false, points_right, coins_top, pixel, RESULTS_NUMBER_COLOR);
+
+	// a chain of stomps shows its best as "x3" after the count
+	let stomps_top: i32 = layout.get_row_top(1) + digit_top_offset;
+	let stomps_right: i32 = push_number(elements, level_results.stomps as u32, count_left, stomps_top, pixel, RESULTS_NUMBER_COLOR);
+	if level_results.best_stomp_chain > 1 {
+		push_glyph(elements, HudGlyph::Times, stomps_right + pixel * 3, stomps_top, pixel, RESULTS_TOTAL_COLOR);
+		push_number(
+			elements,
+			level_results.best_stomp_chain as u32,
+			stomps_right + pixel * 7,
+			stomps_top,
+			pixel,
+			RESULTS_TOTAL_COLOR,
+		);
+	}
+	push_points(elements, level_results.stomp_points, false, points_right, stomps_top, pixel, RESULTS_NUMBER_COLOR);
+
+	let time_top: i32 = layout.get_row_top(2) + digit_top_offset;
+	push_time(elements, level_results.time.get_igt_centis(), count_left, time_top, pixel, RESULTS_NUMBER_COLOR);
+	push_points(elements, level_results.time_points, false, points_right, time_top, pixel, RESULTS_NUMBER_COLOR);
+
+	let deaths_top: i32 = layout.get_row_top(3) + digit_top_offset;
+	push_number(elements, level_results.deaths as u32, count_left, deaths_top, pixel, RESULTS_NUMBER_COLOR);
+	let death_color: (u8, u8, u8, u8) = if level_results.death_points > 0 {
+		RESULTS_PENALTY_COLOR
+	} else {
+		RESULTS_NUMBER_COLOR
+	};
+	push_points(elements, level_results.death_points, true, points_right, deaths_top, pixel, death_color);
+
+	let total_top: i32 = layout.get_row_top(RESULTS_TOTAL_ROW) + digit_top_offset;
+	push_points(elements, level_results.total, false, points_right, total_top, pixel, RESULTS_TOTAL_COLOR);
+
+	// the letter centred under its label, as tall as the rows beside it allow
+	let rank_left: i32 = layout.rank_left + (rank_width - rank_pixel * 3) / 2;
+	let rank_top: i32 = layout.rank_label_top + line_height + pixel * 4;
+	push_glyph(
+		elements,
+		HudGlyph::Rank(level_results.rank),
+		rank_left,
+		rank_top,
+		rank_pixel,
+		get_rank_color(level_results.rank),
+	);
+
+	return layout;
+}
+
+// right aligned so the points line up whatever their width; a penalty gets a minus sign unless it's 0
+fn push_points(elements: &mut Vec<HudElement>, points: u32, is_penalty: bool, right: i32, top: i32, pixel: i32, color: (u8, u8, u8, u8)) {
+	let left: i32 = right - get_number_width(points, pixel);
+	if is_penalty && points > 0 {
+		push_glyph(elements, HudGlyph::Minus, left - pixel * 4, top, pixel, color);
+	}
+	push_number(elements, points, left, top, pixel, color);
+	return;
+}
@@ -0,0 +1,234 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{string::String, sync::Arc, vec::Vec};
+
+#[cfg(not(feature = "gba"))]
+use std::sync::Arc;
+
+use crate::{
+	debugln,
+	engine_math::Vec2,
+	platform::render::{
+		common::RenderCommon, ghost::GhostSnapshot, hud::HudSnapshot, inventory::InventorySnapshot, level_select::LevelSelectSnapshot, menu::MenuSnapshot,
+		message_box::MessageBoxSnapshot, results::ResultsSnapshot, transition::TransitionSnapshot,
+	},
+	runtime::{
+		accessibility::{TextFont, TextSize},
+		animation::{
+			animator::Animator,
+			clip::{AnimationClip, AnimationFrame, AnimationSet},
+		},
+		book::reading_state::BookReadingState,
+		game_mode::GameMode,
+		level::Level,
+		session::Session,
+		state::{EntityId, EntityKind, State},
+	},
+};
+
+// the sprite frame an animated entity shows this frame, in sheet pixels
+#[derive(Clone, Copy, Debug)]
+pub struct SpriteFrame {
+	pub sheet_id: u16,
+	pub src_left: i32,
+	pub src_top: i32,
+	pub src_width: u32,
+	pub src_height: u32,
+	// this pixel of the frame sits on the bottom-centre of the physics body
+	pub anchor_left: i32,
+	pub anchor_top: i32,
+	pub flip_horizontal: bool,
+}
+
+// one entity as the renderer sees it; position is already interpolated
+#[derive(Clone, Copy, Debug)]
+pub struct EntitySnapshot {
+	pub id: EntityId,
+	pub kind: EntityKind,
+	pub render_style: u8,
+	pub position: Vec2,
+	pub velocity: Vec2,
+	pub half_width: f32,
+	pub half_height: f32,
+	pub width_pixels: u8,
+	pub death_timer: u16,
+	pub sprite: Option<SpriteFrame>,
+}
+
+// everything a renderer needs to draw one frame, copied out of State and Session so the
+// simulation can keep running (possibly on another thread) while the frame is drawn
+#[derive(Clone)]
+pub struct RenderSnapshot {
+	pub level: Arc<Level>,
+	pub entities: Vec<EntitySnapshot>,
+	pub triggers_armed: Vec<bool>,
+	pub camera_left: i32,
+	pub camera_top: i32,
+	pub tick: u32,
+	pub enemy_sprite_scale: u8,
+	pub show_triggers: bool,
+	pub ghost: Option<GhostSnapshot>,
+	// sheet paths relative to the platform gfx root, indexed by SpriteFrame::sheet_id
+	pub sprite_sheets: Vec<String>,
+	pub book_reading: Option<BookReadingState>,
+	// book pages and message boxes
+	pub text_font: TextFont,
+	pub text_size: TextSize,
+	pub is_high_contrast: bool,
+	pub hud: HudSnapshot,
+	pub message_box: Option<MessageBoxSnapshot>,
+	// the menu of the mode on top of the game mode stack
+	pub menu: Option<MenuSnapshot>,
+	pub inventory: Option<InventorySnapshot>,
+	pub level_select: Option<LevelSelectSnapshot>,
+	pub results: Option<ResultsSnapshot>,
+	pub transition: Option<TransitionSnapshot>,
+	pub is_quit_requested: bool,
+}
+
+impl RenderSnapshot {
+	pub fn new(level: Arc<Level>) -> RenderSnapshot {
+		return RenderSnapshot {
+			level,
+			entities: Vec::new(),
+			triggers_armed: Vec::new(),
+			camera_left: 0,
+			camera_top: 0,
+			tick: 0,
+			enemy_sprite_scale: 1,
+			show_triggers: false,
+			ghost: None,
+			sprite_sheets: Vec::new(),
+			book_reading: None,
+			text_font: TextFont::DejaVuSansMono,
+			text_size: TextSize::Small,
+			is_high_contrast: false,
+			hud: HudSnapshot::new(),
+			message_box: None,
+			menu: None,
+			inventory: None,
+			level_select: None,
+			results: None,
+			transition: None,
+			is_quit_requested: false,
+		};
+	}
+
+	pub fn from_state(state: &State, session: &Session, screen_size_pixels: (i32, i32), render_scale: f32) -> RenderSnapshot {
+		let mut snapshot: RenderSnapshot = RenderSnapshot::new(Arc::clone(&state.level));
+		snapshot.capture(state, session, screen_size_pixels, render_scale);
+		return snapshot;
+	}
+
+	// refills this snapshot in place, reusing its allocations
+	pub fn capture(&mut self, state: &State, session: &Session, screen_size_pixels: (i32, i32), render_scale: f32) {
+		// the level's data is shared, not copied; only a step that changes tiles makes a new one
+		self.level = Arc::clone(&state.level);
+
+		self.entities.clear();
+		for id in state.positions.keys() {
+			let raw_kind: u8 = *state.entity_kinds.get(id).unwrap_or(&0);
+			let kind: EntityKind = EntityKind::from_u8(raw_kind);
+			if kind == EntityKind::Empty {
+				debugln!("Warning: entity id {} has unknown kind {}", id, raw_kind);
+				continue;
+			}
+
+			let (half_width, half_height) = state.get_entity_half_values(id);
+			self.entities.push(EntitySnapshot {
+				id,
+				kind,
+				render_style: *state.render_styles.get(id).unwrap_or(&0),
+				position: state.get_render_position(id).unwrap_or_default(),
+				velocity: state.velocities.get(id).copied().unwrap_or_default(),
+				half_width,
+				half_height,
+				width_pixels: *state.widths.get(id).unwrap_or(&16),
+				death_timer: state.death_timers.get(id).copied().unwrap_or(0),
+				sprite: get_sprite_frame(state.animators.get(id), session),
+			});
+		}
+
+		self.triggers_armed.clone_from(&state.triggers_armed);
+
+		let (camera_left, camera_top) = RenderCommon::new().compute_camera(screen_size_pixels, render_scale, state);
+		self.camera_left = camera_left;
+		self.camera_top = camera_top;
+
+		self.hud.capture(state, session, camera_left, camera_top, render_scale);
+
+		self.tick = state.tick;
+		self.enemy_sprite_scale = state.enemy_sprite_scale;
+		self.show_triggers = session.settings.show_triggers;
+		self.ghost = GhostSnapshot::capture(state, session);
+		self.sprite_sheets.clone_from(&session.animations.sheets);
+
+		if session.book_reading.is_open {
+			match self.book_reading.as_mut() {
+				Some(book_reading) => book_reading.clone_from(&session.book_reading),
+				None => self.book_reading = Some(session.book_reading.clone()),
+			}
+		} else {
+			self.book_reading = None;
+		}
+		self.text_font = session.settings.accessibility.text_font;
+		self.text_size = session.settings.accessibility.text_size;
+		self.is_high_contrast = session.settings.accessibility.is_high_contrast;
+
+		if session.message_box.is_open() {
+			self.message_box.get_or_insert_with(MessageBoxSnapshot::new).capture(&session.message_box);
+		} else {
+			self.message_box = None;
+		}
+
+		match session.modes.get_menu() {
+			Some(menu) => self.menu.get_or_insert_with(MenuSnapshot::new).capture(menu, session),
+			None => self.menu = None,
+		}
+		if session.modes.get_top() == GameMode::Inventory {
+			self.inventory.get_or_insert_with(InventorySnapshot::new).capture(session);
+		} else {
+			self.inventory = None;
+		}
+		if session.modes.get_top() == GameMode::LevelSelect {
+			self.level_select.get_or_insert_with(LevelSelectSnapshot::new).capture(session);
+		} else {
+			self.level_select = None;
+		}
+		if session.modes.get_top() == GameMode::Results {
+			self.results.get_or_insert_with(ResultsSnapshot::new).capture(session);
+		} else {
+			self.results = None;
+		}
+		self.transition = session
+			.transition
+			.as_ref()
+			.map(|transition| TransitionSnapshot::capture(transition, state, camera_left, camera_top, render_scale));
+		self.is_quit_requested = session.is_quit_requested;
+
+		return;
+	}
+
+	pub fn is_trigger_armed(&self, trigger_id: usize) -> bool {
+		return trigger_id < self.triggers_armed.len() && self.triggers_armed[trigger_id];
+	}
+}
+
+fn get_sprite_frame(animator: Option<&Animator>, session: &Session) -> Option<SpriteFrame> {
+	let animator: &Animator = animator?;
+	let set: &AnimationSet = session.animations.get_set(animator.set_id)?;
+	let (clip, frame): (&AnimationClip, &AnimationFrame) = animator.get_frame(set)?;
+
+	return Some(SpriteFrame {
+		sheet_id: clip.sheet_id,
+		src_left: frame.left,
+		src_top: frame.top,
+		src_width: frame.width,
+		src_height: frame.height,
+		anchor_left: set.anchor_left,
+		anchor_top: set.anchor_top,
+		flip_horizontal: animator.is_facing_right != set.faces_right,
+	});
+}
@@ -0,0 +1,847 @@
+use super::{
+	SoftwareRenderer,
+	framebuffer::{BlendMode, Rgba},
+};
+use crate::{
+	common::coords::{PixelSize, Pointf32, Size, clamp_camera_to_level_world, get_screen, visible_tile_bounds},
+	debugln,
+	engine_math::Vec2,
+	platform::render::{
+		BackgroundDrawParams,
+		backend::RenderBackend,
+		background::{BackgroundView, LayerPlacement, get_layer_placement},
+		ghost::{GHOST_FILL_COLOR, GHOST_OUTLINE_COLOR, GhostSnapshot},
+		hud::{GlyphBitmap, HudElement, HudSnapshot, get_glyph_bitmap, layout_hud},
+		inventory::{
+			INVENTORY_BAR_BACK_COLOR, INVENTORY_BAR_FILL_COLOR, INVENTORY_CELL_COLOR, InventoryItemKind, InventoryLayout, InventorySnapshot, layout_inventory,
+		},
+		level_select::{LEVEL_SELECT_COMPLETED_CELL_COLOR, LEVEL_SELECT_LOCKED_CELL_COLOR, LevelSelectLayout, LevelSelectSnapshot, layout_level_select},
+		menu::{
+			MENU_BORDER_COLOR, MENU_DIM_COLOR, MENU_PANEL_COLOR, MENU_SELECTED_TEXT_COLOR, MENU_SELECTION_COLOR, MENU_TITLE_SCREEN_DIM_COLOR, MenuLayout, MenuSnapshot,
+			layout_menu,
+		},
+		message_box::{
+			MESSAGE_BOX_BORDER_COLOR, MESSAGE_BOX_CONTINUE_COLOR, MESSAGE_BOX_PANEL_COLOR, MESSAGE_BOX_PORTRAIT_COLOR, MessageBoxLayout, MessageBoxSnapshot,
+			layout_message_box,
+		},
+		results::{ResultsLayout, ResultsSnapshot, layout_results},
+		palette::{HIGH_CONTRAST_BACKGROUND_DIM_COLOR, get_high_contrast_tile_color},
+		snapshot::{EntitySnapshot, RenderSnapshot, SpriteFrame},
+		transition::{TRANSITION_COLOR, TransitionSnapshot},
+	},
+	runtime::{
+		level::Level,
+		state::EntityKind,
+		transition::TransitionEffect,
+		triggers::TriggerKind,
+	},
+	tile::TileKind,
+};
+
+// mirrors platform/render/pc/draw.rs; keep the two in step so screenshots match the window
+impl SoftwareRenderer {
+	fn draw_debug_triggers(&mut self, snapshot: &RenderSnapshot, cam_left_world: f32, cam_top_world: f32, scale: f32) {
+		if !snapshot.show_triggers {
+			return;
+		}
+
+		let tile_width_world: f32 = snapshot.level.tile_width as f32;
+		let tile_height_world: f32 = snapshot.level.tile_height as f32;
+
+		for t in &snapshot.level.triggers {
+			let idx: usize = t.id as usize;
+			if snapshot.is_trigger_armed(idx) {
+				continue; // consumed -> don't draw
+			}
+
+			let left_world: f32 = t.left * tile_width_world;
+			let top_world: f32 = t.top * tile_height_world;
+			let width_world: f32 = t.width * tile_width_world;
+			let height_world: f32 = t.height * tile_height_world;
+
+			let left_pixels: i32 = ((left_world - cam_left_world) * scale) as i32;
+			let top_pixels: i32 = ((top_world - cam_top_world) * scale) as i32;
+			let width_pixels: u32 = (width_world * scale).max(1.0) as u32;
+			let height_pixels: u32 = (height_world * scale).max(1.0) as u32;
+
+			let color: Rgba = if t.kind == TriggerKind::Message as u8 {
+				Rgba::rgb(0, 255, 0)
+			} else {
+				Rgba::rgb(255, 255, 0)
+			};
+
+			self.framebuffer.draw_rect(left_pixels, top_pixels, width_pixels, height_pixels, color);
+		}
+	}
+
+	fn draw_filled_circle(&mut self, circle_x: i32, circle_y: i32, radius: i32, color: Rgba) {
+		let rr: i32 = radius * radius;
+		let mut y: i32 = -radius;
+		while y <= radius {
+			let yy: i32 = y * y;
+			let dx: f32 = ((rr - yy) as f32).sqrt();
+			let x0: i32 = circle_x - dx as i32;
+			let x1: i32 = circle_x + dx as i32;
+
+			self.framebuffer.draw_horizontal_line(x0, x1, circle_y + y, color);
+			y += 1;
+		}
+
+		return;
+	}
+
+	fn draw_color_only_tile(&mut self, tile_kind: TileKind, destination: (i32, i32, u32, u32)) {
+		let (left, top, width, height) = destination;
+
+		match tile_kind {
+			TileKind::Blackout => {
+				self.framebuffer.fill_rect(left, top, width, height, Rgba::new(0, 0, 0, 255), BlendMode::Blend);
+			}
+
+			TileKind::TorchGlow => {
+				// the flame itself; the light it throws comes from the lightmap
+				self.framebuffer.fill_rect(left, top, width, height, Rgba::rgb(235, 210, 145), BlendMode::Blend);
+			}
+
+			TileKind::DarkBrownRock => {
+				self.framebuffer.fill_rect(left, top, width, height, Rgba::new(0, 0, 0, 235), BlendMode::Blend);
+				self.framebuffer.fill_rect(left, top, width, height, Rgba::new(255, 235, 100, 80), BlendMode::Add);
+			}
+
+			TileKind::Door => {
+				// dark frame around lighter planks
+				let inset: u32 = (width / 8).max(1);
+				self.framebuffer.fill_rect(left, top, width, height, Rgba::rgb(70, 44, 22), BlendMode::Blend);
+				self.framebuffer.fill_rect(
+					left + inset as i32,
+					top + inset as i32,
+					width.saturating_sub(inset * 2),
+					height.saturating_sub(inset * 2),
+					Rgba::rgb(130, 84, 42),
+					BlendMode::Blend,
+				);
+			}
+
+			_ => {
+				// Silently do nothing instead of drawing the wrong thing
+			}
+		}
+
+		return;
+	}
+
+	fn draw_filled_triangle(&mut self, x: i32, y: i32, width: u32, h: u32, color: Rgba) {
+		let ww: i32 = width as i32;
+		let hh: i32 = h as i32;
+
+		let x0: i32 = x;
+		let y0: i32 = y + hh;
+
+		let x1: i32 = x + ww;
+		let y1: i32 = y + hh;
+
+		let x2: i32 = x + ww / 2;
+		let y2: i32 = y;
+
+		// simple scanline fill
+		let min_y: i32 = y2.min(y0.min(y1));
+		let max_y: i32 = y2.max(y0.max(y1));
+
+		let mut yy: i32 = min_y;
+		while yy <= max_y {
+			let mut xs: Vec<i32> = Vec::new();
+			tri_edge_intersect_y(x0, y0, x1, y1, yy, &mut xs);
+			tri_edge_intersect_y(x1, y1, x2, y2, yy, &mut xs);
+			tri_edge_intersect_y(x2, y2, x0, y0, yy, &mut xs);
+
+			if xs.len() >= 2 {
+				xs.sort();
+				self.framebuffer.draw_horizontal_line(xs[0], xs[xs.len() - 1], yy, color);
+			}
+
+			yy += 1;
+		}
+
+		return;
+	}
+
+	pub(crate) fn draw_background_internal(&mut self, params: &BackgroundDrawParams) {
+		let sw_u32: u32 = self.framebuffer.width();
+		let sh_u32: u32 = self.framebuffer.height();
+
+		let Some(def) = self.backgrounds.get(self.background_id) else {
+			self.framebuffer.fill_rect(0, 0, sw_u32, sh_u32, Rgba::rgb(60, 110, 190), BlendMode::Blend);
+			return;
+		};
+
+		let (red, green, blue) = def.fill_color;
+		self.framebuffer.fill_rect(0, 0, sw_u32, sh_u32, Rgba::rgb(red, green, blue), BlendMode::Blend);
+
+		let view: BackgroundView = BackgroundView {
+			screen_width: sw_u32 as i32,
+			screen_height: sh_u32 as i32,
+			camera_left: params.camera_left as f32 * params.scale,
+			camera_top: params.camera_top as f32 * params.scale,
+			max_camera_top: (params.level_height as f32 * params.scale - sh_u32 as f32).max(0.0),
+			tick: params.tick,
+		};
+
+		for (layer, image) in def.layers.iter().zip(self.bg_layers.iter()) {
+			if image.width == 0 || image.height == 0 {
+				continue;
+			}
+
+			let width: i32 = image.width as i32;
+			let height: i32 = image.height as i32;
+			let placement: LayerPlacement = get_layer_placement(layer, width, height, &view);
+			let full_rect: (i32, i32, u32, u32) = (0, 0, image.width, image.height);
+
+			let mut top: i32 = placement.top;
+			while top < view.screen_height {
+				let mut left: i32 = placement.left;
+				while left < view.screen_width {
+					self.framebuffer.blit(image, full_rect, (left, top, image.width, image.height), false);
+					if !placement.repeat_x {
+						break;
+					}
+					left += width;
+				}
+				if !placement.repeat_y {
+					break;
+				}
+				top += height;
+			}
+		}
+	}
+
+	fn draw_tiles_layer_atlas(&mut self, level: &Level, layer: u32, is_high_contrast: bool, camera_left: f32, camera_top: f32, scale: f32) {
+		let tile_width: f32 = level.tile_width as f32;
+		let tile_height: f32 = level.tile_height as f32;
+		let cam = Pointf32::new(camera_left, camera_top);
+		let tile_size = Size::new(level.tile_width as f32, level.tile_height as f32);
+
+		let view_pixels = PixelSize::new(self.framebuffer.width() as i32, self.framebuffer.height() as i32);
+
+		let cam = clamp_camera_to_level_world(cam, view_pixels, scale, tile_size, level.width as i32, level.height as i32);
+		let bounds = visible_tile_bounds(cam, view_pixels, scale, tile_size, level.width as i32, level.height as i32);
+		let atlas_tile_width_pixels: u32 = self.atlas_tile_width_pixels;
+		let atlas_tile_height_pixels: u32 = self.atlas_tile_height_pixels;
+		let tile_cols: u32 = self.tile_image.width / atlas_tile_width_pixels;
+
+		for tile_top in bounds.start_top..bounds.end_top {
+			for tile_left in bounds.start_left..bounds.end_left {
+				let tile_id: u8 = level.get_tile_id_at_layer(layer, tile_left, tile_top);
+				let tile_kind: TileKind = TileKind::from_u8(tile_id);
+				if tile_kind.is_empty() {
+					continue;
+				}
+
+				let tile_dest_width_pixels: i32 = (tile_width * scale) as i32;
+				let tile_dest_height_pixels: i32 = (tile_height * scale) as i32;
+
+				let camera_left_pixels: i32 = (cam.left * scale) as i32;
+				let camera_top_pixels: i32 = (cam.top * scale) as i32;
+
+				let destination_left: i32 = tile_left * tile_dest_width_pixels - camera_left_pixels;
+				let destination_top: i32 = tile_top * tile_dest_height_pixels - camera_top_pixels;
+				let destination = (destination_left, destination_top, tile_dest_width_pixels as u32, tile_dest_height_pixels as u32);
+
+				// color-only overlays (no atlas sampling)
+				if tile_kind.is_color_only() {
+					self.draw_color_only_tile(tile_kind, destination);
+					continue;
+				}
+
+				if is_high_contrast {
+					if let Some(color) = get_high_contrast_tile_color(tile_kind) {
+						let (left, top, width, height) = destination;
+						self.framebuffer.fill_rect(left, top, width, height, to_rgba(color), BlendMode::Blend);
+						continue;
+					}
+				}
+
+				let id: u32 = tile_id as u32;
+				let source_left: i32 = ((id % tile_cols) * atlas_tile_width_pixels) as i32;
+				let source_top: i32 = ((id / tile_cols) * atlas_tile_height_pixels) as i32;
+				let source = (source_left, source_top, atlas_tile_width_pixels, atlas_tile_height_pixels);
+				self.framebuffer.blit(&self.tile_image, source, destination, false);
+			}
+		}
+		return;
+	}
+
+	pub fn draw_level_internal(&mut self, snapshot: &RenderSnapshot) {
+		let (camera_left, camera_top) = (snapshot.camera_left, snapshot.camera_top);
+		let scale: f32 = self.get_render_scale();
+
+		// new sprite sheets from the animation data get packed before anything draws from the atlas
+		self.atlas.map_sheets(&snapshot.sprite_sheets, &mut self.sprite_sheet_handles);
+		if self.atlas.is_dirty() {
+			for error in self.atlas.build() {
+				debugln!("atlas: {}", error);
+			}
+		}
+
+		if snapshot.is_high_contrast {
+			let (width, height) = (self.framebuffer.width(), self.framebuffer.height());
+			self.framebuffer.fill_rect(0, 0, width, height, to_rgba(HIGH_CONTRAST_BACKGROUND_DIM_COLOR), BlendMode::Blend);
+		}
+
+		for layer in 0..(snapshot.level.layer_count as u32) {
+			self.draw_tiles_layer_atlas(&snapshot.level, layer, snapshot.is_high_contrast, camera_left as f32, camera_top as f32, scale);
+		}
+
+		self.frame_index = self.frame_index.wrapping_add(1);
+		if let Some(ghost) = &snapshot.ghost {
+			self.draw_ghost(ghost, camera_left as f32, camera_top as f32, scale);
+		}
+		self.draw_entities(snapshot, camera_left as f32, camera_top as f32, scale, self.frame_index);
+		self.draw_debug_triggers(snapshot, camera_left as f32, camera_top as f32, scale);
+		self.draw_trigger_icons(snapshot, camera_left as f32, camera_top as f32, scale);
+		self.draw_lighting(snapshot, scale);
+		self.draw_hud(&snapshot.hud);
+		if let Some(message_box) = &snapshot.message_box {
+			self.draw_message_box(message_box);
+		}
+		if let Some(menu) = &snapshot.menu {
+			self.draw_menu(menu);
+		}
+		if let Some(inventory) = &snapshot.inventory {
+			self.draw_inventory(inventory);
+		}
+		if let Some(level_select) = &snapshot.level_select {
+			self.draw_level_select(level_select);
+		}
+		if let Some(results) = &snapshot.results {
+			self.draw_results(results);
+		}
+		if let Some(transition) = &snapshot.transition {
+			self.draw_transition(transition);
+		}
+		return;
+	}
+
+	fn draw_hud(&mut self, hud: &HudSnapshot) {
+		let screen_width_pixels: i32 = self.framebuffer.width() as i32;
+		let screen_height_pixels: i32 = self.framebuffer.height() as i32;
+		let mut elements: Vec<HudElement> = std::mem::take(&mut self.hud_elements);
+		layout_hud(hud, screen_width_pixels, screen_height_pixels, &mut elements);
+		self.draw_hud_elements(&elements);
+		self.hud_elements = elements;
+		return;
+	}
+
+	fn draw_hud_elements(&mut self, elements: &[HudElement]) {
+		for element in elements {
+			match *element {
+				HudElement::Glyph {
+					glyph,
+					left,
+					top,
+					pixel_size,
+					color,
+				} => {
+					let bitmap: GlyphBitmap = get_glyph_bitmap(glyph);
+					let color: Rgba = Rgba::new(color.0, color.1, color.2, color.3);
+					for row in 0..(bitmap.rows.len() as u32) {
+						for column in 0..bitmap.width {
+							if !bitmap.is_set(column, row) {
+								continue;
+							}
+							let pixel_left: i32 = left + (column * pixel_size) as i32;
+							let pixel_top: i32 = top + (row * pixel_size) as i32;
+							self.framebuffer.fill_rect(pixel_left, pixel_top, pixel_size, pixel_size, color, BlendMode::Blend);
+						}
+					}
+				}
+				HudElement::Icon { icon_id, left, top, size } => self.draw_hud_icon(icon_id, left, top, size),
+			}
+		}
+		return;
+	}
+
+	fn draw_hud_icon(&mut self, icon_id: u16, left: i32, top: i32, size: u32) {
+		let Some(region) = self.icon_sheet.and_then(|handle| self.atlas.get_region(handle)) else {
+			return;
+		};
+		let Some(def) = self.icons.resolve_icon(icon_id) else {
+			return;
+		};
+
+		let frame_index: u16 = self.icons.get_frame_index(&def, self.frame_index);
+		let (src_left_pixels, src_top_pixels, src_width_pixels, src_height_pixels) = self.icons.get_icon_src_rect_pixels(&def, frame_index);
+
+		self.framebuffer.blit(
+			&self.atlas.pages[region.page as usize],
+			(region.left + src_left_pixels, region.top + src_top_pixels, src_width_pixels, src_height_pixels),
+			(left, top, size, size),
+			false,
+		);
+		return;
+	}
+
+	// the panel, portrait and continue marker of pc/message_box.rs; there is no font here, so no text
+	fn draw_message_box(&mut self, message: &MessageBoxSnapshot) {
+		let layout: MessageBoxLayout = layout_message_box(message, self.framebuffer.width() as i32, self.framebuffer.height() as i32, 0);
+
+		let panel_color: Rgba = to_rgba(MESSAGE_BOX_PANEL_COLOR);
+		self.framebuffer.fill_rect(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height, panel_color, BlendMode::Blend);
+
+		let border_color: Rgba = to_rgba(MESSAGE_BOX_BORDER_COLOR);
+		for inset in 0..(layout.border_size as i32) {
+			self.framebuffer.draw_rect(
+				layout.panel_left + inset,
+				layout.panel_top + inset,
+				layout.panel_width - (inset as u32) * 2,
+				layout.panel_height - (inset as u32) * 2,
+				border_color,
+			);
+		}
+
+		if let Some((left, top, size)) = layout.portrait {
+			let portrait_color: Rgba = to_rgba(MESSAGE_BOX_PORTRAIT_COLOR);
+			self.framebuffer.fill_rect(left, top, size, size, portrait_color, BlendMode::Blend);
+			self.draw_hud_icon(message.speaker_icon_id, left, top, size);
+		}
+
+		if message.is_continue_marker_visible() {
+			let (left, top, size) = layout.continue_marker;
+			let continue_color: Rgba = to_rgba(MESSAGE_BOX_CONTINUE_COLOR);
+			for row in 0..(size / 2) {
+				self.framebuffer.fill_rect(left + row as i32, top + row as i32, size - row * 2, 1, continue_color, BlendMode::Blend);
+			}
+		}
+
+		return;
+	}
+
+	// the dimming, panel and selection bar of pc/menu.rs, without the text
+	fn draw_menu(&mut self, menu: &MenuSnapshot) {
+		let screen_width_pixels: u32 = self.framebuffer.width();
+		let screen_height_pixels: u32 = self.framebuffer.height();
+		let layout: MenuLayout = layout_menu(menu, screen_width_pixels as i32, screen_height_pixels as i32);
+
+		let dim_color: Rgba = to_rgba(if menu.is_title_screen { MENU_TITLE_SCREEN_DIM_COLOR } else { MENU_DIM_COLOR });
+		self.framebuffer.fill_rect(0, 0, screen_width_pixels, screen_height_pixels, dim_color, BlendMode::Blend);
+
+		if !menu.is_title_screen {
+			self.framebuffer.fill_rect(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height, to_rgba(MENU_PANEL_COLOR), BlendMode::Blend);
+			for inset in 0..(layout.border_size as i32) {
+				self.framebuffer.draw_rect(
+					layout.panel_left + inset,
+					layout.panel_top + inset,
+					layout.panel_width - (inset as u32) * 2,
+					layout.panel_height - (inset as u32) * 2,
+					to_rgba(MENU_BORDER_COLOR),
+				);
+			}
+		}
+
+		for (index, line) in menu.lines.iter().enumerate() {
+			if line.is_selected {
+				self.framebuffer.fill_rect(layout.line_left, layout.get_line_top(index), layout.line_width, layout.line_height, to_rgba(MENU_SELECTION_COLOR), BlendMode::Blend);
+			}
+		}
+
+		return;
+	}
+
+	// the panel, cells, icons and progress bars of pc/inventory.rs, without the text
+	fn draw_inventory(&mut self, inventory: &InventorySnapshot) {
+		let screen_width_pixels: u32 = self.framebuffer.width();
+		let screen_height_pixels: u32 = self.framebuffer.height();
+		let mut elements: Vec<HudElement> = std::mem::take(&mut self.hud_elements);
+		let layout: InventoryLayout = layout_inventory(inventory, screen_width_pixels as i32, screen_height_pixels as i32, &mut elements);
+
+		self.framebuffer.fill_rect(0, 0, screen_width_pixels, screen_height_pixels, to_rgba(MENU_DIM_COLOR), BlendMode::Blend);
+		self.framebuffer.fill_rect(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height, to_rgba(MENU_PANEL_COLOR), BlendMode::Blend);
+		for inset in 0..(layout.border_size as i32) {
+			self.framebuffer.draw_rect(
+				layout.panel_left + inset,
+				layout.panel_top + inset,
+				layout.panel_width - (inset as u32) * 2,
+				layout.panel_height - (inset as u32) * 2,
+				to_rgba(MENU_BORDER_COLOR),
+			);
+		}
+
+		for (index, item) in inventory.items.iter().enumerate() {
+			let (cell_left, cell_top) = layout.get_cell(index);
+			let cell_color: (u8, u8, u8, u8) = if index == inventory.selected { MENU_SELECTION_COLOR } else { INVENTORY_CELL_COLOR };
+			self.framebuffer.fill_rect(cell_left, cell_top, layout.cell_size, layout.cell_size, to_rgba(cell_color), BlendMode::Blend);
+			if index == inventory.selected {
+				self.framebuffer.draw_rect(cell_left, cell_top, layout.cell_size, layout.cell_size, to_rgba(MENU_SELECTED_TEXT_COLOR));
+			}
+
+			if item.kind == InventoryItemKind::Book {
+				let (bar_left, bar_top, bar_width, bar_height, fill_width) = layout.get_progress_bar(index, item.progress);
+				self.framebuffer.fill_rect(bar_left, bar_top, bar_width, bar_height, to_rgba(INVENTORY_BAR_BACK_COLOR), BlendMode::Blend);
+				self.framebuffer.fill_rect(bar_left, bar_top, fill_width, bar_height, to_rgba(INVENTORY_BAR_FILL_COLOR), BlendMode::Blend);
+			}
+		}
+
+		self.draw_hud_elements(&elements);
+		self.hud_elements = elements;
+		return;
+	}
+
+	// the panel, cells, level numbers and pickup bars of pc/level_select.rs, without the text
+	fn draw_level_select(&mut self, level_select: &LevelSelectSnapshot) {
+		let screen_width_pixels: u32 = self.framebuffer.width();
+		let screen_height_pixels: u32 = self.framebuffer.height();
+		let mut elements: Vec<HudElement> = std::mem::take(&mut self.hud_elements);
+		let layout: LevelSelectLayout = layout_level_select(level_select, screen_width_pixels as i32, screen_height_pixels as i32, &mut elements);
+
+		self.framebuffer.fill_rect(0, 0, screen_width_pixels, screen_height_pixels, to_rgba(MENU_TITLE_SCREEN_DIM_COLOR), BlendMode::Blend);
+		self.framebuffer.fill_rect(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height, to_rgba(MENU_PANEL_COLOR), BlendMode::Blend);
+		for inset in 0..(layout.border_size as i32) {
+			self.framebuffer.draw_rect(
+				layout.panel_left + inset,
+				layout.panel_top + inset,
+				layout.panel_width - (inset as u32) * 2,
+				layout.panel_height - (inset as u32) * 2,
+				to_rgba(MENU_BORDER_COLOR),
+			);
+		}
+
+		for (row_index, row) in level_select.rows.iter().enumerate() {
+			for (column_index, cell) in row.cells.iter().enumerate() {
+				let (cell_left, cell_top) = layout.get_cell(row_index, column_index);
+				let is_selected: bool = row_index == level_select.selected_row && column_index == level_select.selected_column;
+				let cell_color: (u8, u8, u8, u8) = if is_selected {
+					MENU_SELECTION_COLOR
+				} else if !cell.is_unlocked {
+					LEVEL_SELECT_LOCKED_CELL_COLOR
+				} else if cell.is_completed {
+					LEVEL_SELECT_COMPLETED_CELL_COLOR
+				} else {
+					INVENTORY_CELL_COLOR
+				};
+				self.framebuffer.fill_rect(cell_left, cell_top, layout.cell_size, layout.cell_size, to_rgba(cell_color), BlendMode::Blend);
+				if is_selected {
+					self.framebuffer.draw_rect(cell_left, cell_top, layout.cell_size, layout.cell_size, to_rgba(MENU_SELECTED_TEXT_COLOR));
+				}
+
+				if cell.is_unlocked {
+					let (bar_left, bar_top, bar_width, bar_height, fill_width) = layout.get_progress_bar(row_index, column_index, cell.completion_percent);
+					self.framebuffer.fill_rect(bar_left, bar_top, bar_width, bar_height, to_rgba(INVENTORY_BAR_BACK_COLOR), BlendMode::Blend);
+					self.framebuffer.fill_rect(bar_left, bar_top, fill_width, bar_height, to_rgba(INVENTORY_BAR_FILL_COLOR), BlendMode::Blend);
+				}
+			}
+		}
+
+		self.draw_hud_elements(&elements);
+		self.hud_elements = elements;
+		return;
+	}
+
+	// the panel, divider, numbers and rank letter of pc/results.rs, without the text
+	fn draw_results(&mut self, results: &ResultsSnapshot) {
+		let screen_width_pixels: u32 = self.framebuffer.width();
+		let screen_height_pixels: u32 = self.framebuffer.height();
+		let mut elements: Vec<HudElement> = std::mem::take(&mut self.hud_elements);
+		let layout: ResultsLayout = layout_results(results, screen_width_pixels as i32, screen_height_pixels as i32, &mut elements);
+
+		self.framebuffer.fill_rect(0, 0, screen_width_pixels, screen_height_pixels, to_rgba(MENU_DIM_COLOR), BlendMode::Blend);
+		self.framebuffer.fill_rect(layout.panel_left, layout.panel_top, layout.panel_width, layout.panel_height, to_rgba(MENU_PANEL_COLOR), BlendMode::Blend);
+		for inset in 0..(layout.border_size as i32) {
+			self.framebuffer.draw_rect(
+				layout.panel_left + inset,
+				layout.panel_top + inset,
+				layout.panel_width - (inset as u32) * 2,
+				layout.panel_height - (inset as u32) * 2,
+				to_rgba(MENU_BORDER_COLOR),
+			);
+		}
+		self.framebuffer.fill_rect(layout.text_left, layout.divider_top, layout.divider_width, layout.divider_height, to_rgba(MENU_BORDER_COLOR), BlendMode::Blend);
+
+		self.draw_hud_elements(&elements);
+		self.hud_elements = elements;
+		return;
+	}
+
+	fn draw_transition(&mut self, transition: &TransitionSnapshot) {
+		if transition.cover <= 0.0 {
+			return;
+		}
+
+		let screen_width_pixels: u32 = self.framebuffer.width();
+		let screen_height_pixels: u32 = self.framebuffer.height();
+		match transition.effect {
+			TransitionEffect::Fade => {
+				let color: Rgba = Rgba::new(TRANSITION_COLOR.0, TRANSITION_COLOR.1, TRANSITION_COLOR.2, transition.get_fade_alpha());
+				self.framebuffer.fill_rect(0, 0, screen_width_pixels, screen_height_pixels, color, BlendMode::Blend);
+			}
+			TransitionEffect::Iris => {
+				let color: Rgba = Rgba::rgb(TRANSITION_COLOR.0, TRANSITION_COLOR.1, TRANSITION_COLOR.2);
+				let radius: f32 = transition.get_iris_radius(screen_width_pixels as i32, screen_height_pixels as i32);
+				for row_top in 0..(screen_height_pixels as i32) {
+					match transition.get_iris_row_opening(radius, row_top) {
+						None => self.framebuffer.fill_rect(0, row_top, screen_width_pixels, 1, color, BlendMode::Blend),
+						Some((left, right)) => {
+							self.framebuffer.fill_rect(0, row_top, left.max(0) as u32, 1, color, BlendMode::Blend);
+							self.framebuffer.fill_rect(right, row_top, (screen_width_pixels as i32 - right).max(0) as u32, 1, color, BlendMode::Blend);
+						}
+					}
+				}
+			}
+		}
+		return;
+	}
+
+	fn draw_lighting(&mut self, snapshot: &RenderSnapshot, scale: f32) {
+		let screen_width_pixels: u32 = self.framebuffer.width();
+		let screen_height_pixels: u32 = self.framebuffer.height();
+		if !self.lightmap.build(snapshot, screen_width_pixels as f32 / scale, screen_height_pixels as f32 / scale) {
+			return;
+		}
+
+		let cell_size_pixels: f32 = self.lightmap.cell_size * scale;
+		let origin_left: f32 = (self.lightmap.left - snapshot.camera_left as f32) * scale;
+		let origin_top: f32 = (self.lightmap.top - snapshot.camera_top as f32) * scale;
+
+		for row in 0..self.lightmap.rows {
+			let top: i32 = (origin_top + row as f32 * cell_size_pixels) as i32;
+			let bottom: i32 = (origin_top + (row + 1) as f32 * cell_size_pixels) as i32;
+			for column in 0..self.lightmap.columns {
+				let left: i32 = (origin_left + column as f32 * cell_size_pixels) as i32;
+				let right: i32 = (origin_left + (column + 1) as f32 * cell_size_pixels) as i32;
+				let index: usize = ((row * self.lightmap.columns + column) * 3) as usize;
+				let color: Rgba = Rgba::rgb(self.lightmap.pixels[index], self.lightmap.pixels[index + 1], self.lightmap.pixels[index + 2]);
+				self.framebuffer.fill_rect(left, top, (right - left).max(0) as u32, (bottom - top).max(0) as u32, color, BlendMode::Modulate);
+			}
+		}
+
+		return;
+	}
+
+	// returns false when the sheet is unavailable so the caller can fall back to a shape
+	fn draw_sprite_entity(&mut self, snapshot: &RenderSnapshot, entity: &EntitySnapshot, sprite: &SpriteFrame, camera_left: f32, camera_top: f32, scale: f32) -> bool {
+		let Some(handle) = self.sprite_sheet_handles.get(sprite.sheet_id as usize).copied() else {
+			return false;
+		};
+		let Some(region) = self.atlas.get_region(handle) else {
+			return false;
+		};
+
+		let src = (region.left + sprite.src_left, region.top + sprite.src_top, sprite.src_width, sprite.src_height);
+
+		let sprite_world_scale: f32 = snapshot.enemy_sprite_scale as f32;
+		let dest_width_pixels: u32 = (sprite.src_width as f32 * sprite_world_scale * scale).max(1.0) as u32;
+		let dest_height_pixels: u32 = (sprite.src_height as f32 * sprite_world_scale * scale).max(1.0) as u32;
+
+		// anchor point on physics body: bottom-center
+		let entity_bottom_center_screen_left: i32 = ((entity.position.x - camera_left) * scale) as i32;
+		let entity_bottom_center_screen_top: i32 = ((entity.position.y + entity.half_height - camera_top) * scale) as i32;
+
+		// mirrored frames keep the anchor on the same side of the body
+		let anchor_left: i32 = if sprite.flip_horizontal { sprite.src_width as i32 - sprite.anchor_left } else { sprite.anchor_left };
+		let sprite_feet_left_pixels: i32 = (dest_width_pixels as f32 * (anchor_left as f32 / sprite.src_width as f32)) as i32;
+		let sprite_feet_top_pixels: i32 = (dest_height_pixels as f32 * (sprite.anchor_top as f32 / sprite.src_height as f32)) as i32;
+
+		let dest = (
+			entity_bottom_center_screen_left - sprite_feet_left_pixels,
+			entity_bottom_center_screen_top - sprite_feet_top_pixels,
+			dest_width_pixels,
+			dest_height_pixels,
+		);
+
+		self.framebuffer.blit(&self.atlas.pages[region.page as usize], src, dest, sprite.flip_horizontal);
+		return true;
+	}
+
+	fn draw_trigger_icons(&mut self, snapshot: &RenderSnapshot, cam_left_world: f32, cam_top_world: f32, scale: f32) {
+		let Some(region) = self.icon_sheet.and_then(|handle| self.atlas.get_region(handle)) else {
+			return;
+		};
+		let icon_width_pixels: u32 = self.icons.frame_width_pixels;
+		let icon_height_pixels: u32 = self.icons.frame_height_pixels;
+
+		let tile_width: f32 = snapshot.level.tile_width as f32;
+		let tile_height: f32 = snapshot.level.tile_height as f32;
+
+		let (screen_width_pixels, screen_height_pixels) = self.get_screen_size();
+		let view_left_world: f32 = cam_left_world;
+		let view_top_world: f32 = cam_top_world;
+		let view_right_world: f32 = view_left_world + (screen_width_pixels as f32) / scale;
+		let view_bottom_world: f32 = view_top_world + (screen_height_pixels as f32) / scale;
+
+		let padding_world: f32 = 16.0;
+
+		for trigger in &snapshot.level.triggers {
+			if trigger.icon_id == 0 {
+				continue;
+			}
+
+			let trigger_id: usize = trigger.id as usize;
+			if snapshot.is_trigger_armed(trigger_id) {
+				continue; // consumed -> don't draw
+			}
+
+			let Some(def) = self.icons.resolve_icon(trigger.icon_id) else {
+				continue;
+			};
+
+			let trigger_left_world: f32 = trigger.left * tile_width;
+			let trigger_top_world: f32 = trigger.top * tile_height;
+			let trigger_width_world: f32 = trigger.width * tile_width;
+			let trigger_height_world: f32 = trigger.height * tile_height;
+
+			let trigger_right_world: f32 = trigger_left_world + trigger_width_world;
+			let trigger_bottom_world: f32 = trigger_top_world + trigger_height_world;
+
+			let visible: bool = trigger_right_world >= view_left_world - padding_world
+				&& trigger_left_world <= view_right_world + padding_world
+				&& trigger_bottom_world >= view_top_world - padding_world
+				&& trigger_top_world <= view_bottom_world + padding_world;
+
+			if !visible {
+				continue;
+			}
+
+			let icon_world_left: f32 = trigger_left_world + (trigger_width_world * 0.5);
+			let half_icon_height_world: f32 = (icon_height_pixels as f32) / scale * 0.5;
+			let bottom_padding_world: f32 = 2.0 / scale; // 2 pixels padding
+			let icon_world_top: f32 = trigger_bottom_world - half_icon_height_world - bottom_padding_world;
+
+			let frame_index: u16 = self.icons.get_frame_index(&def, self.frame_index);
+			let (src_left_pixels, src_top_pixels, src_width_pixels, src_height_pixels) = self.icons.get_icon_src_rect_pixels(&def, frame_index);
+
+			// screen-space: keep icon size constant, don’t multiply by world scale
+			let screen_left: i32 = ((icon_world_left - cam_left_world) * scale) as i32 - (icon_width_pixels as i32 / 2);
+			let screen_top: i32 = ((icon_world_top - cam_top_world) * scale) as i32 - (icon_height_pixels as i32 / 2);
+
+			self.framebuffer.blit(
+				&self.atlas.pages[region.page as usize],
+				(region.left + src_left_pixels, region.top + src_top_pixels, src_width_pixels, src_height_pixels),
+				(screen_left, screen_top, icon_width_pixels, icon_height_pixels),
+				false,
+			);
+		}
+	}
+
+	fn draw_platform_entity_tiles(&mut self, world_left: f32, world_top: f32, width_tiles: i32, level: &Level, camera_left: f32, camera_top: f32, scale: f32) {
+		if width_tiles <= 0 {
+			return;
+		}
+
+		let tile_pixel: u32 = self.atlas_tile_width_pixels;
+		let tile_cols: u32 = self.tile_image.width / tile_pixel;
+		let tile_width_world: f32 = level.tile_width as f32;
+		let dest_width_pixels: u32 = (tile_width_world * scale) as u32;
+		let dest_height_pixels: u32 = (level.tile_height as f32 * scale) as u32;
+
+		for i in 0..width_tiles {
+			let tile_kind: TileKind = if width_tiles == 1 {
+				TileKind::MovingPlatformMiddle
+			} else if i == 0 {
+				TileKind::MovingPlatformLeft
+			} else if i == width_tiles - 1 {
+				TileKind::MovingPlatformRight
+			} else {
+				TileKind::MovingPlatformMiddle
+			};
+
+			let tile_id: u32 = tile_kind as u32;
+			let source = (((tile_id % tile_cols) * tile_pixel) as i32, ((tile_id / tile_cols) * tile_pixel) as i32, tile_pixel, tile_pixel);
+
+			let screen_left: i32 = ((world_left + (i as f32) * tile_width_world - camera_left) * scale) as i32;
+			let screen_top: i32 = ((world_top - camera_top) * scale) as i32;
+
+			self.framebuffer.blit(&self.tile_image, source, (screen_left, screen_top, dest_width_pixels, dest_height_pixels), false);
+		}
+
+		return;
+	}
+
+	fn draw_ghost(&mut self, ghost: &GhostSnapshot, camera_left: f32, camera_top: f32, scale: f32) {
+		let world_left: f32 = ghost.position.x - ghost.half_width;
+		let world_top: f32 = ghost.position.y - ghost.half_height;
+		let screen = get_screen(Pointf32::new(world_left, world_top), Pointf32::new(camera_left, camera_top), scale);
+		let width: u32 = ((ghost.half_width * 2.0) * scale) as u32;
+		let height: u32 = ((ghost.half_height * 2.0) * scale) as u32;
+
+		self.framebuffer.fill_rect(screen.left, screen.top, width, height, to_rgba(GHOST_FILL_COLOR), BlendMode::Blend);
+		self.framebuffer.draw_rect(screen.left, screen.top, width, height, to_rgba(GHOST_OUTLINE_COLOR));
+		return;
+	}
+
+	fn draw_entities(&mut self, snapshot: &RenderSnapshot, camera_left: f32, camera_top: f32, scale: f32, _frame_index: u32) {
+		for entity in &snapshot.entities {
+			// already interpolated between the last two simulation steps
+			let pos: &Vec2 = &entity.position;
+			let entity_kind: EntityKind = entity.kind;
+
+			let style: u8 = entity.render_style;
+			let (half_width, half_height) = (entity.half_width, entity.half_height);
+			let world_left: f32 = pos.x - half_width;
+			let world_top: f32 = pos.y - half_height;
+			let screen = get_screen(Pointf32::new(world_left, world_top), Pointf32::new(camera_left, camera_top), scale);
+
+			let width: u32 = ((half_width * 2.0) * scale) as u32;
+			let height: u32 = ((half_height * 2.0) * scale) as u32;
+
+			if let Some(sprite) = &entity.sprite {
+				if self.draw_sprite_entity(snapshot, entity, sprite, camera_left, camera_top, scale) {
+					continue;
+				}
+			}
+
+			if entity_kind == EntityKind::MovingPlatform {
+				let width_pixels: f32 = entity.width_pixels as f32;
+				let tile_width: f32 = snapshot.level.tile_width as f32;
+				let width_tiles: i32 = ((width_pixels / tile_width).ceil() as i32).max(1);
+
+				self.draw_platform_entity_tiles(world_left, world_top, width_tiles, &snapshot.level, camera_left, camera_top, scale);
+				continue;
+			}
+
+			let color: Rgba = match entity_kind {
+				EntityKind::Empty => Rgba::rgb(0, 0, 0),
+				EntityKind::Imp => Rgba::rgb(64, 200, 64),
+				EntityKind::MovingPlatform => Rgba::rgb(255, 255, 0),
+				EntityKind::SlimeBlue => Rgba::rgb(64, 160, 255),
+				EntityKind::SlimeLava => Rgba::rgb(255, 0, 0),
+				EntityKind::SlimeUndead => Rgba::rgb(255, 255, 255),
+				EntityKind::Player => Rgba::rgb(255, 255, 255),
+			};
+
+			match style {
+				2 => {
+					let cx: i32 = screen.left + (width as i32 / 2);
+					let cy: i32 = screen.top + (height as i32 / 2);
+					let r: i32 = (width.min(height) as i32) / 2;
+					self.draw_filled_circle(cx, cy, r, color);
+				}
+				3 => {
+					self.draw_filled_triangle(screen.left, screen.top, width, height, color);
+				}
+				_ => {
+					self.framebuffer.fill_rect(screen.left, screen.top, width, height, color, BlendMode::Blend);
+				}
+			}
+		}
+
+		return;
+	}
+}
+
+fn to_rgba(color: (u8, u8, u8, u8)) -> Rgba {
+	return Rgba::new(color.0, color.1, color.2, color.3);
+}
+
+fn tri_edge_intersect_y(x0: i32, y0: i32, x1: i32, y1: i32, y: i32, out: &mut Vec<i32>) {
+	if (y < y0 && y < y1) || (y > y0 && y > y1) || (y0 == y1) {
+		return;
+	}
+
+	let dy: i32 = y1 - y0;
+	let dx: i32 = x1 - x0;
+
+	let t_num: i32 = y - y0;
+	let x: i32 = x0 + (dx * t_num) / dy;
+
+	out.push(x);
+
+	return;
+}
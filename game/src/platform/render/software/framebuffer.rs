@@ -0,0 +1,183 @@
+use super::image::RgbaImage;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rgba {
+	pub r: u8,
+	pub g: u8,
+	pub b: u8,
+	pub a: u8,
+}
+
+impl Rgba {
+	pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Rgba {
+		return Rgba { r, g, b, a };
+	}
+
+	pub const fn rgb(r: u8, g: u8, b: u8) -> Rgba {
+		return Rgba { r, g, b, a: 255 };
+	}
+}
+
+// same math as sdl's BLENDMODE_BLEND / BLENDMODE_ADD / BLENDMODE_MOD
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlendMode {
+	Blend,
+	Add,
+	Modulate,
+}
+
+pub struct Framebuffer {
+	image: RgbaImage,
+}
+
+impl Framebuffer {
+	pub fn new(width: u32, height: u32) -> Framebuffer {
+		return Framebuffer {
+			image: RgbaImage::new(width, height),
+		};
+	}
+
+	pub fn width(&self) -> u32 {
+		return self.image.width;
+	}
+
+	pub fn height(&self) -> u32 {
+		return self.image.height;
+	}
+
+	pub fn image(&self) -> &RgbaImage {
+		return &self.image;
+	}
+
+	pub fn clear(&mut self, color: Rgba) {
+		for pixel in self.image.pixels.chunks_exact_mut(4) {
+			pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+		}
+		return;
+	}
+
+	fn blend_pixel(&mut self, left: i32, top: i32, color: Rgba, mode: BlendMode) {
+		let index: usize = ((top as usize) * (self.image.width as usize) + (left as usize)) * 4;
+		let pixel: &mut [u8] = &mut self.image.pixels[index..index + 4];
+		let alpha: u32 = color.a as u32;
+
+		match mode {
+			BlendMode::Blend => {
+				pixel[0] = blend_channel(color.r, pixel[0], alpha);
+				pixel[1] = blend_channel(color.g, pixel[1], alpha);
+				pixel[2] = blend_channel(color.b, pixel[2], alpha);
+				pixel[3] = (alpha + ((pixel[3] as u32) * (255 - alpha) + 127) / 255) as u8;
+			}
+			BlendMode::Add => {
+				pixel[0] = add_channel(color.r, pixel[0], alpha);
+				pixel[1] = add_channel(color.g, pixel[1], alpha);
+				pixel[2] = add_channel(color.b, pixel[2], alpha);
+			}
+			BlendMode::Modulate => {
+				pixel[0] = ((pixel[0] as u32 * color.r as u32 + 127) / 255) as u8;
+				pixel[1] = ((pixel[1] as u32 * color.g as u32 + 127) / 255) as u8;
+				pixel[2] = ((pixel[2] as u32 * color.b as u32 + 127) / 255) as u8;
+			}
+		}
+
+		return;
+	}
+
+	pub fn fill_rect(&mut self, left: i32, top: i32, width: u32, height: u32, color: Rgba, mode: BlendMode) {
+		let start_left: i32 = left.max(0);
+		let start_top: i32 = top.max(0);
+		let end_left: i32 = (left + width as i32).min(self.image.width as i32);
+		let end_top: i32 = (top + height as i32).min(self.image.height as i32);
+
+		for y in start_top..end_top {
+			for x in start_left..end_left {
+				self.blend_pixel(x, y, color, mode);
+			}
+		}
+
+		return;
+	}
+
+	// one pixel outline, like sdl's draw_rect
+	pub fn draw_rect(&mut self, left: i32, top: i32, width: u32, height: u32, color: Rgba) {
+		if width == 0 || height == 0 {
+			return;
+		}
+
+		let right: i32 = left + width as i32 - 1;
+		let bottom: i32 = top + height as i32 - 1;
+
+		self.draw_horizontal_line(left, right, top, color);
+		if bottom != top {
+			self.draw_horizontal_line(left, right, bottom, color);
+		}
+
+		self.fill_rect(left, top + 1, 1, height.saturating_sub(2), color, BlendMode::Blend);
+		if right != left {
+			self.fill_rect(right, top + 1, 1, height.saturating_sub(2), color, BlendMode::Blend);
+		}
+
+		return;
+	}
+
+	// inclusive on both ends, like sdl's draw_line for a horizontal line
+	pub fn draw_horizontal_line(&mut self, left: i32, right: i32, top: i32, color: Rgba) {
+		let (start, end) = if left <= right { (left, right) } else { (right, left) };
+		self.fill_rect(start, top, (end - start + 1) as u32, 1, color, BlendMode::Blend);
+		return;
+	}
+
+	// nearest-neighbour scaled copy with alpha blending; source rect is clipped to the image
+	pub fn blit(&mut self, source: &RgbaImage, source_rect: (i32, i32, u32, u32), destination_rect: (i32, i32, u32, u32), flip_horizontal: bool) {
+		let (source_left, source_top, source_width, source_height) = source_rect;
+		let (destination_left, destination_top, destination_width, destination_height) = destination_rect;
+
+		if source_width == 0 || source_height == 0 || destination_width == 0 || destination_height == 0 {
+			return;
+		}
+
+		let start_left: i32 = destination_left.max(0);
+		let start_top: i32 = destination_top.max(0);
+		let end_left: i32 = (destination_left + destination_width as i32).min(self.image.width as i32);
+		let end_top: i32 = (destination_top + destination_height as i32).min(self.image.height as i32);
+
+		for y in start_top..end_top {
+			let offset_top: i64 = (y - destination_top) as i64;
+			let sample_top: i32 = source_top + ((offset_top * source_height as i64) / destination_height as i64) as i32;
+			if sample_top < 0 || sample_top >= source.height as i32 {
+				continue;
+			}
+
+			for x in start_left..end_left {
+				let offset_left: i64 = (x - destination_left) as i64;
+				let mut column: i32 = ((offset_left * source_width as i64) / destination_width as i64) as i32;
+				if flip_horizontal {
+					column = source_width as i32 - 1 - column;
+				}
+
+				let sample_left: i32 = source_left + column;
+				if sample_left < 0 || sample_left >= source.width as i32 {
+					continue;
+				}
+
+				let index: usize = ((sample_top as usize) * (source.width as usize) + (sample_left as usize)) * 4;
+				let texel: &[u8] = &source.pixels[index..index + 4];
+				if texel[3] == 0 {
+					continue;
+				}
+
+				self.blend_pixel(x, y, Rgba::new(texel[0], texel[1], texel[2], texel[3]), BlendMode::Blend);
+			}
+		}
+
+		return;
+	}
+}
+
+fn blend_channel(source: u8, destination: u8, alpha: u32) -> u8 {
+	return (((source as u32) * alpha + (destination as u32) * (255 - alpha) + 127) / 255) as u8;
+}
+
+fn add_channel(source: u8, destination: u8, alpha: u32) -> u8 {
+	return ((destination as u32) + ((source as u32) * alpha + 127) / 255).min(255) as u8;
+}
@@ -0,0 +1,64 @@
+use std::{fs::File, io::BufWriter, path::Path};
+
+// decoded image, 4 bytes per pixel, rows top to bottom
+pub struct RgbaImage {
+	pub width: u32,
+	pub height: u32,
+	pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+	pub fn new(width: u32, height: u32) -> RgbaImage {
+		return RgbaImage {
+			width,
+			height,
+			pixels: vec![0; (width as usize) * (height as usize) * 4],
+		};
+	}
+}
+
+pub fn load_png(path: &Path) -> Result<RgbaImage, String> {
+	let file = File::open(path).map_err(|e| format!("cannot open {}: {}", path.display(), e))?;
+
+	let mut decoder = png::Decoder::new(file);
+	decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+
+	let mut reader = decoder.read_info().map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+	let mut buffer: Vec<u8> = vec![0; reader.output_buffer_size()];
+	let info = reader.next_frame(&mut buffer).map_err(|e| format!("cannot decode {}: {}", path.display(), e))?;
+	buffer.truncate(info.buffer_size());
+
+	let pixel_count: usize = (info.width as usize) * (info.height as usize);
+	let pixels: Vec<u8> = match info.color_type {
+		png::ColorType::Rgba => buffer,
+		png::ColorType::Rgb => buffer.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+		png::ColorType::GrayscaleAlpha => buffer.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+		png::ColorType::Grayscale => buffer.iter().flat_map(|v| [*v, *v, *v, 255]).collect(),
+		png::ColorType::Indexed => return Err(format!("palette was not expanded in {}", path.display())),
+	};
+
+	if pixels.len() != pixel_count * 4 {
+		return Err(format!("unexpected pixel data size in {}", path.display()));
+	}
+
+	return Ok(RgbaImage {
+		width: info.width,
+		height: info.height,
+		pixels,
+	});
+}
+
+pub fn save_png(path: &Path, image: &RgbaImage) -> Result<(), String> {
+	let file = File::create(path).map_err(|e| format!("cannot create {}: {}", path.display(), e))?;
+
+	let mut encoder = png::Encoder::new(BufWriter::new(file), image.width, image.height);
+	encoder.set_color(png::ColorType::Rgba);
+	encoder.set_depth(png::BitDepth::Eight);
+	encoder.set_compression(png::Compression::Best);
+	encoder.set_adaptive_filter(png::AdaptiveFilterType::Adaptive);
+
+	let mut writer = encoder.write_header().map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+	writer.write_image_data(&image.pixels).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+
+	return Ok(());
+}
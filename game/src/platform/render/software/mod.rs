@@ -0,0 +1,11 @@
+// cpu framebuffer backend: same drawing as the sdl renderer, into an rgba buffer (screenshots, golden images)
+
+mod draw;
+mod framebuffer;
+mod image;
+mod renderer;
+pub mod screenshot;
+
+pub use framebuffer::{BlendMode, Framebuffer, Rgba};
+pub use image::{RgbaImage, load_png, save_png};
+pub use renderer::{DEFAULT_SCREEN_HEIGHT_PIXELS, DEFAULT_SCREEN_WIDTH_PIXELS, SoftwareRenderer};
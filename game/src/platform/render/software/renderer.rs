@@ -0,0 +1,163 @@
+use super::{
+	framebuffer::{Framebuffer, Rgba},
+	image::{RgbaImage, load_png, save_png},
+};
+use crate::{
+	RenderBackend,
+	platform::{
+		input::InputState,
+		render::{
+			BackgroundDrawParams, BackgroundId,
+			background::BackgroundLibrary,
+			atlas::{SheetHandle, TextureAtlas},
+			common::RenderCommon,
+			hud::HudElement,
+			icon_registry::IconRegistry,
+			lighting::Lightmap,
+			snapshot::RenderSnapshot,
+
+		},
+	},
+	runtime::assets::get_gfx_root,
+};
+
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_SCREEN_WIDTH_PIXELS: u32 = 1280;
+pub const DEFAULT_SCREEN_HEIGHT_PIXELS: u32 = 720;
+
+pub struct SoftwareRenderer {
+	pub framebuffer: Framebuffer,
+	pub common: RenderCommon,
+	pub atlas: TextureAtlas,
+	// RenderSnapshot::sprite_sheets ids -> atlas handles
+	pub(crate) sprite_sheet_handles: Vec<SheetHandle>,
+	pub icons: IconRegistry,
+	pub(crate) icon_sheet: Option<SheetHandle>,
+	pub frame_index: u32,
+	pub atlas_tile_width_pixels: u32,
+	pub atlas_tile_height_pixels: u32,
+	pub tile_image: RgbaImage,
+
+	pub backgrounds: BackgroundLibrary,
+	pub(crate) background_id: BackgroundId,
+	// one image per layer of the current background, same order as BackgroundDef::layers
+	pub(crate) bg_layers: Vec<RgbaImage>,
+	pub(crate) lightmap: Lightmap,
+	// rebuilt from RenderSnapshot::hud every frame; kept to reuse its allocation
+	pub(crate) hud_elements: Vec<HudElement>,
+	render_scale: u32,
+}
+
+impl SoftwareRenderer {
+	pub fn with_size(width_pixels: u32, height_pixels: u32) -> Result<SoftwareRenderer, String> {
+		let icons: IconRegistry = IconRegistry::load()?;
+		let backgrounds: BackgroundLibrary = BackgroundLibrary::load()?;
+		let mut atlas: TextureAtlas = TextureAtlas::new();
+		let icon_sheet: Option<SheetHandle> = if icons.sheet.is_empty() { None } else { Some(atlas.add_sheet(icons.sheet.as_str())) };
+		if let Some(error) = atlas.build().into_iter().next() {
+			return Err(error);
+		}
+
+		let renderer = SoftwareRenderer {
+			framebuffer: Framebuffer::new(width_pixels, height_pixels),
+			common: RenderCommon::new(),
+			atlas,
+			sprite_sheet_handles: Vec::new(),
+			icons,
+			icon_sheet,
+			frame_index: 0,
+			atlas_tile_width_pixels: 64,
+			atlas_tile_height_pixels: 64,
+			tile_image: load_png(&gfx_pc_path(&["tiles", "tiles64.png"]))?,
+			backgrounds,
+			background_id: BackgroundId::NONE,
+			bg_layers: Vec::new(),
+			lightmap: Lightmap::new(),
+			hud_elements: Vec::new(),
+			render_scale: 4,
+		};
+
+		return Ok(renderer);
+	}
+
+	pub fn draw_background_by_id(&mut self, background_id: BackgroundId) -> Result<(), String> {
+		if self.background_id == background_id {
+			return Ok(());
+		}
+
+		let mut layers: Vec<RgbaImage> = Vec::new();
+		if background_id != BackgroundId::NONE {
+			let Some(def) = self.backgrounds.get(background_id) else {
+				return Err(format!("background {} is not in backgrounds.txt", background_id.to_u8()));
+			};
+			for layer in &def.layers {
+				layers.push(load_png(&gfx_pc_path(&[layer.image.as_str()]))?);
+			}
+		}
+
+		self.background_id = background_id;
+		self.bg_layers = layers;
+
+		return Ok(());
+	}
+
+	pub fn save_screenshot(&self, path: &Path) -> Result<(), String> {
+		return save_png(path, self.framebuffer.image());
+	}
+}
+
+impl RenderBackend for SoftwareRenderer {
+	fn init(&mut self) {
+		// Nothing yet
+		return;
+	}
+
+	fn get_render_scale(&self) -> f32 {
+		return self.render_scale as f32;
+	}
+
+	fn draw_background(&mut self, params: &BackgroundDrawParams) {
+		self.draw_background_internal(params);
+	}
+
+	fn get_screen_size(&self) -> (i32, i32) {
+		return (self.framebuffer.width() as i32, self.framebuffer.height() as i32);
+	}
+
+	fn poll_input(&mut self) -> InputState {
+		return InputState::default();
+	}
+
+	fn begin_frame(&mut self) {
+		self.framebuffer.clear(Rgba::rgb(0, 0, 0));
+		return;
+	}
+
+	fn draw_level(&mut self, snapshot: &RenderSnapshot) {
+		let background_draw_params: BackgroundDrawParams = BackgroundDrawParams {
+			background_id: snapshot.level.background_id,
+			camera_left: snapshot.camera_left,
+			camera_top: snapshot.camera_top,
+			scale: 4.0,
+			level_height: snapshot.level.height as i32 * snapshot.level.tile_height as i32,
+			tick: snapshot.tick,
+		};
+		self.draw_background(&background_draw_params);
+		self.draw_level_internal(snapshot);
+	}
+
+	fn commit(&mut self) {
+		self.frame_index += 1;
+		return;
+	}
+}
+
+fn gfx_pc_path(segments: &[&str]) -> PathBuf {
+	let mut path = get_gfx_root().join("pc");
+	for s in segments {
+		path = path.join(s);
+	}
+
+	return path;
+}
@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use super::{RgbaImage, SoftwareRenderer, load_png};
+use crate::{
+	platform::{
+		audio::null_audio::NullAudio,
+		input::TriggerPresses,
+		level_loader::{load_level_from_file, load_level_from_name},
+		render::{backend::RenderBackend, snapshot::RenderSnapshot},
+	},
+	runtime::{
+		level::Level,
+		play_step::{PlayInput, run_play_step},
+		session::Session,
+		state::State,
+	},
+};
+
+// frame `frame` of a level with every button up, drawn by a SoftwareRenderer; each frame is one
+// play step, the same step the game loop runs. what --screenshot saves and the golden tests check
+pub fn render_level_frame(level_path: &Path, frame: u32, size: (u32, u32)) -> Result<SoftwareRenderer, String> {
+	let mut session: Session = Session::new();
+	let level: Level = load_level_from_file(level_path)?;
+	let mut state: State = State::new(level, Box::new(NullAudio::new()));
+
+	state.spawn_level_entities();
+	state.apply_player_from_persistent(&session);
+	session.current_level_name = Some(level_path.to_string_lossy().into_owned());
+
+	let mut renderer: SoftwareRenderer = SoftwareRenderer::with_size(size.0, size.1)?;
+	renderer.init();
+	renderer.draw_background_by_id(state.level.background_id)?;
+
+	state.camera.set_view_size(renderer.get_screen_size(), renderer.get_render_scale());
+	let mut snapshot: RenderSnapshot = RenderSnapshot::from_state(&state, &session, renderer.get_screen_size(), renderer.get_render_scale());
+
+	for _ in 0..=frame {
+		// screenshots change level in one step, without the transition, the preload or the results
+		if let Some(next_level_name) = session.pending_level_name.take() {
+			session.results = None;
+			session.transition_to_level(&mut state, &next_level_name, load_level_from_name);
+			renderer.draw_background_by_id(state.level.background_id)?;
+		}
+
+		run_play_step(&mut session, &mut state, PlayInput::default(), TriggerPresses::default());
+
+		snapshot.capture(&state, &session, renderer.get_screen_size(), renderer.get_render_scale());
+		renderer.begin_frame();
+		renderer.draw_level(&snapshot);
+		renderer.commit();
+	}
+
+	return Ok(renderer);
+}
+
+// Err describes how the frame differs from the reference png
+pub fn compare_with_golden(actual: &RgbaImage, golden_path: &Path) -> Result<(), String> {
+	let golden: RgbaImage = load_png(golden_path)?;
+	if golden.width != actual.width || golden.height != actual.height {
+		return Err(format!(
+			"golden mismatch: {} is {}x{}, frame is {}x{}",
+			golden_path.display(),
+			golden.width,
+			golden.height,
+			actual.width,
+			actual.height
+		));
+	}
+
+	let differing_pixels: usize = golden.pixels.chunks_exact(4).zip(actual.pixels.chunks_exact(4)).filter(|(g, a)| g != a).count();
+	if differing_pixels > 0 {
+		return Err(format!("golden mismatch: {} pixels differ from {}", differing_pixels, golden_path.display()));
+	}
+	return Ok(());
+}
@@ -0,0 +1,67 @@
+use crate::runtime::{
+	state::State,
+	transition::{TransitionEffect, TransitionState},
+};
+
+pub const TRANSITION_COLOR: (u8, u8, u8) = (0, 0, 0);
+
+// the level transition's cover, drawn over everything else
+#[derive(Copy, Clone, Debug)]
+pub struct TransitionSnapshot {
+	pub effect: TransitionEffect,
+	// 0 with the level in full view, 1 with the screen covered
+	pub cover: f32,
+	// where the iris closes to, the player's middle in screen pixels
+	pub center_left: f32,
+	pub center_top: f32,
+}
+
+impl TransitionSnapshot {
+	pub fn capture(transition: &TransitionState, state: &State, camera_left: i32, camera_top: i32, render_scale: f32) -> TransitionSnapshot {
+		let mut center_left: f32 = 0.0;
+		let mut center_top: f32 = 0.0;
+		if let Some(player_id) = state.try_get_player_id() {
+			if let Some(position) = state.get_render_position(player_id) {
+				center_left = (position.x - camera_left as f32) * render_scale;
+				center_top = (position.y - camera_top as f32) * render_scale;
+			}
+		}
+
+		return TransitionSnapshot {
+			effect: transition.effect,
+			cover: transition.get_cover().clamp(0.0, 1.0),
+			center_left,
+			center_top,
+		};
+	}
+
+	// a fade's alpha over the whole screen
+	pub fn get_fade_alpha(&self) -> u8 {
+		return (self.cover * 255.0) as u8;
+	}
+
+	// how far the iris reaches, in screen pixels: past the farthest corner when open, nothing when closed
+	pub fn get_iris_radius(&self, screen_width: i32, screen_height: i32) -> f32 {
+		let far_left: f32 = self.center_left.max(screen_width as f32 - self.center_left);
+		let far_top: f32 = self.center_top.max(screen_height as f32 - self.center_top);
+		let full_radius: f32 = libm::sqrtf(far_left * far_left + far_top * far_top);
+		return full_radius * (1.0 - self.cover);
+	}
+
+	// the part of a screen row the iris leaves open, left inclusive and right exclusive; None when
+	// the whole row is covered
+	pub fn get_iris_row_opening(&self, radius: f32, row_top: i32) -> Option<(i32, i32)> {
+		let row_offset: f32 = row_top as f32 + 0.5 - self.center_top;
+		if row_offset.abs() >= radius {
+			return None;
+		}
+
+		let half_width: f32 = libm::sqrtf(radius * radius - row_offset * row_offset);
+		let left: i32 = libm::roundf(self.center_left - half_width) as i32;
+		let right: i32 = libm::roundf(self.center_left + half_width) as i32;
+		if right <= left {
+			return None;
+		}
+		return Some((left, right));
+	}
+}
@@ -0,0 +1,67 @@
+use std::sync::{Arc, Mutex};
+
+// three copies of T: the writer fills its back buffer, the reader draws from its front buffer, and
+// publishing or reading only swaps with the shared middle buffer, so neither side ever waits on
+// the other for longer than a pointer swap and the reader always gets the newest complete value
+
+struct MiddleSlot<T> {
+	value: T,
+	is_fresh: bool,
+}
+
+pub struct TripleBufferWriter<T> {
+	back: T,
+	middle: Arc<Mutex<MiddleSlot<T>>>,
+}
+
+pub struct TripleBufferReader<T> {
+	front: T,
+	middle: Arc<Mutex<MiddleSlot<T>>>,
+}
+
+pub fn triple_buffer<T: Clone>(initial: T) -> (TripleBufferWriter<T>, TripleBufferReader<T>) {
+	let middle = Arc::new(Mutex::new(MiddleSlot {
+		value: initial.clone(),
+		is_fresh: false,
+	}));
+
+	let writer = TripleBufferWriter {
+		back: initial.clone(),
+		middle: Arc::clone(&middle),
+	};
+	let reader = TripleBufferReader { front: initial, middle };
+
+	return (writer, reader);
+}
+
+impl<T> TripleBufferWriter<T> {
+	// the buffer to fill before the next publish; it holds whatever value was swapped out last time
+	pub fn back_mut(&mut self) -> &mut T {
+		return &mut self.back;
+	}
+
+	pub fn publish(&mut self) {
+		let mut middle = self.middle.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+		std::mem::swap(&mut self.back, &mut middle.value);
+		middle.is_fresh = true;
+	}
+}
+
+impl<T> TripleBufferReader<T> {
+	// the newest published value, or the one returned last time if nothing new was published
+	pub fn read(&mut self) -> &T {
+		{
+			let mut middle = self.middle.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+			if middle.is_fresh {
+				std::mem::swap(&mut self.front, &mut middle.value);
+				middle.is_fresh = false;
+			}
+		}
+
+		return &self.front;
+	}
+
+	pub fn has_writer(&self) -> bool {
+		return Arc::strong_count(&self.middle) > 1;
+	}
+}
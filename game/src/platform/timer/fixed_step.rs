@@ -0,0 +1,57 @@
+use super::backend::TimerBackend;
+
+pub const SIMULATION_HZ: u64 = 60;
+
+// after a stall (window drag, breakpoint, slow disk) run at most this many steps in one frame and drop the rest
+pub const MAX_CATCH_UP_STEPS: u32 = 5;
+
+// accumulates real time and hands out whole simulation steps; the leftover fraction is the render alpha
+pub struct FixedStep {
+	step_ticks: u64,
+	accumulator_ticks: u64,
+	last_ticks: u64,
+}
+
+impl FixedStep {
+	pub fn new(timer: &dyn TimerBackend, hz: u64) -> FixedStep {
+		return FixedStep {
+			step_ticks: (timer.ticks_per_second() / hz).max(1),
+			accumulator_ticks: 0,
+			last_ticks: timer.now_ticks(),
+		};
+	}
+
+	// a new step rate, for assist mode's game speed; cheap enough to call every frame
+	pub fn set_hz(&mut self, timer: &dyn TimerBackend, hz: u64) {
+		self.step_ticks = (timer.ticks_per_second() / hz.max(1)).max(1);
+		return;
+	}
+
+	// forget time spent paused (book overlay, level load) so it isn't simulated afterwards
+	pub fn reset(&mut self, timer: &dyn TimerBackend) {
+		self.accumulator_ticks = 0;
+		self.last_ticks = timer.now_ticks();
+		return;
+	}
+
+	// number of simulation steps to run before the next render
+	pub fn advance(&mut self, timer: &dyn TimerBackend) -> u32 {
+		let now_ticks: u64 = timer.now_ticks();
+		self.accumulator_ticks += now_ticks.saturating_sub(self.last_ticks);
+		self.last_ticks = now_ticks;
+
+		let steps: u64 = self.accumulator_ticks / self.step_ticks;
+		if steps > MAX_CATCH_UP_STEPS as u64 {
+			self.accumulator_ticks = 0;
+			return MAX_CATCH_UP_STEPS;
+		}
+
+		self.accumulator_ticks -= steps * self.step_ticks;
+		return steps as u32;
+	}
+
+	// how far the next step has progressed, 0.0..1.0
+	pub fn get_alpha(&self) -> f32 {
+		return self.accumulator_ticks as f32 / self.step_ticks as f32;
+	}
+}
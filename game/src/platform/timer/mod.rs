@@ -1 +1,5 @@
 pub mod backend;
+pub mod fixed_step;
+
+#[cfg(feature = "pc")]
+pub mod pc;
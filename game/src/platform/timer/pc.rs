@@ -0,0 +1,30 @@
+#![cfg(feature = "pc")]
+
+use super::backend::TimerBackend;
+
+use std::time::{Duration, Instant};
+
+// microsecond ticks since the timer was created
+pub struct PcTimer {
+	start: Instant,
+}
+
+impl PcTimer {
+	pub fn new() -> PcTimer {
+		return PcTimer { start: Instant::now() };
+	}
+}
+
+impl TimerBackend for PcTimer {
+	fn now_ticks(&self) -> u64 {
+		return self.start.elapsed().as_micros() as u64;
+	}
+
+	fn ticks_per_second(&self) -> u64 {
+		return 1_000_000;
+	}
+
+	fn sleep_ms(&self, ms: u32) {
+		std::thread::sleep(Duration::from_millis(ms as u64));
+	}
+}
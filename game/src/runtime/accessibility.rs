@@ -0,0 +1,140 @@
+use crate::runtime::menu::{MESSAGE_MENU_TEXT_SIZE_LARGE, MESSAGE_MENU_TEXT_SIZE_MEDIUM, MESSAGE_MENU_TEXT_SIZE_SMALL};
+
+pub use crate::runtime::player_physics::ASSIST_EXTRA_JUMP_FRAMES_MAX;
+
+// assist mode limits; the settings menu steps between them
+pub const ASSIST_GAME_SPEED_PERCENT_MIN: u8 = 50;
+pub const ASSIST_GAME_SPEED_PERCENT_STEP: u8 = 10;
+pub const ASSIST_EXTRA_JUMP_FRAMES_STEP: u8 = 4;
+
+// how fast a player holding jump may fall with hold-to-float on, in world pixels per step
+pub const ASSIST_FLOAT_FALL_SPEED_MAX: f32 = 1.0;
+
+// the font book pages and message boxes are set in; both ship in assets/fonts
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TextFont {
+	DejaVuSansMono,
+	OpenDyslexic,
+}
+
+impl TextFont {
+	pub const ALL: [TextFont; 2] = [TextFont::DejaVuSansMono, TextFont::OpenDyslexic];
+
+	pub fn get_file_name(&self) -> &'static str {
+		return match self {
+			TextFont::DejaVuSansMono => "DejaVuSansMono.ttf",
+			TextFont::OpenDyslexic => "OpenDyslexic-Regular.ttf",
+		};
+	}
+
+	// shown in the settings menu, and the value written to the settings file
+	pub fn get_name(&self) -> &'static str {
+		return match self {
+			TextFont::DejaVuSansMono => "DejaVu Sans Mono",
+			TextFont::OpenDyslexic => "OpenDyslexic",
+		};
+	}
+
+	pub fn from_name(name: &str) -> Option<TextFont> {
+		return TextFont::ALL.iter().copied().find(|font| font.get_name().eq_ignore_ascii_case(name));
+	}
+
+	// the next or previous font, wrapping around
+	pub fn cycle(&self, delta: i32) -> TextFont {
+		let count: i32 = TextFont::ALL.len() as i32;
+		let index: i32 = TextFont::ALL.iter().position(|font| font == self).unwrap_or(0) as i32;
+		return TextFont::ALL[(index + delta).rem_euclid(count) as usize];
+	}
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TextSize {
+	Small,
+	Medium,
+	Large,
+}
+
+impl TextSize {
+	pub const ALL: [TextSize; 3] = [TextSize::Small, TextSize::Medium, TextSize::Large];
+
+	pub fn get_point_size(&self) -> u16 {
+		return match self {
+			TextSize::Small => 12,
+			TextSize::Medium => 16,
+			TextSize::Large => 20,
+		};
+	}
+
+	pub fn get_label_id(&self) -> u16 {
+		return match self {
+			TextSize::Small => MESSAGE_MENU_TEXT_SIZE_SMALL,
+			TextSize::Medium => MESSAGE_MENU_TEXT_SIZE_MEDIUM,
+			TextSize::Large => MESSAGE_MENU_TEXT_SIZE_LARGE,
+		};
+	}
+
+	// the value written to the settings file
+	pub fn get_name(&self) -> &'static str {
+		return match self {
+			TextSize::Small => "small",
+			TextSize::Medium => "medium",
+			TextSize::Large => "large",
+		};
+	}
+
+	pub fn from_name(name: &str) -> Option<TextSize> {
+		return TextSize::ALL.iter().copied().find(|size| size.get_name().eq_ignore_ascii_case(name));
+	}
+
+	pub fn cycle(&self, delta: i32) -> TextSize {
+		let count: i32 = TextSize::ALL.len() as i32;
+		let index: i32 = TextSize::ALL.iter().position(|size| size == self).unwrap_or(0) as i32;
+		return TextSize::ALL[(index + delta).rem_euclid(count) as usize];
+	}
+}
+
+// makes the game easier without changing the levels. the choices are kept while assist mode is
+// off, they just don't apply; Settings hands out the values that do
+#[derive(Copy, Clone, Debug)]
+pub struct AssistMode {
+	pub is_enabled: bool,
+	// ASSIST_GAME_SPEED_PERCENT_MIN..=100
+	pub game_speed_percent: u8,
+	// added to both coyote_frames_max and jump_buffer_frames_max
+	pub extra_jump_frames: u8,
+	pub has_infinite_hit_points: bool,
+	// holding jump while falling caps the fall at ASSIST_FLOAT_FALL_SPEED_MAX
+	pub can_hold_to_float: bool,
+}
+
+impl AssistMode {
+	pub fn new() -> AssistMode {
+		return AssistMode {
+			is_enabled: false,
+			game_speed_percent: 100,
+			extra_jump_frames: 0,
+			has_infinite_hit_points: false,
+			can_hold_to_float: false,
+		};
+	}
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Accessibility {
+	pub text_font: TextFont,
+	pub text_size: TextSize,
+	// tiles drawn as flat colours by what they do, over a darkened background
+	pub is_high_contrast: bool,
+	pub assist: AssistMode,
+}
+
+impl Accessibility {
+	pub fn new() -> Accessibility {
+		return Accessibility {
+			text_font: TextFont::DejaVuSansMono,
+			text_size: TextSize::Small,
+			is_high_contrast: false,
+			assist: AssistMode::new(),
+		};
+	}
+}
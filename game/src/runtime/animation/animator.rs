@@ -0,0 +1,193 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::vec::Vec;
+
+use super::clip::{AnimState, AnimationClip, AnimationFrame, AnimationLibrary, AnimationSet, LoopMode};
+use crate::runtime::state::{EntityId, EntityKind, State};
+
+// below this horizontal speed an entity counts as standing still and keeps its facing
+const MOVING_SPEED: f32 = 0.05;
+
+// per-entity playback position in one AnimationSet
+#[derive(Clone, Copy, Debug)]
+pub struct Animator {
+	pub set_id: u16,
+	pub state: AnimState,
+	pub frame: u16,
+	pub ticks_in_frame: u16,
+	pub is_facing_right: bool,
+	pub is_finished: bool,
+	is_reversing: bool,
+	is_one_shot: bool,
+}
+
+// an animation event fired this step, e.g. the frame an attack connects
+#[derive(Clone, Copy, Debug)]
+pub struct FiredAnimationEvent {
+	pub entity_id: EntityId,
+	pub event_id: u16,
+}
+
+impl Animator {
+	pub fn new(set_id: u16, is_facing_right: bool) -> Animator {
+		return Animator {
+			set_id,
+			state: AnimState::Idle,
+			frame: 0,
+			ticks_in_frame: 0,
+			is_facing_right,
+			is_finished: false,
+			is_reversing: false,
+			is_one_shot: false,
+		};
+	}
+
+	// restart only when the state actually changes so loops don't stutter
+	pub fn switch_to(&mut self, state: AnimState) {
+		if self.state == state {
+			return;
+		}
+
+		self.state = state;
+		self.frame = 0;
+		self.ticks_in_frame = 0;
+		self.is_finished = false;
+		self.is_reversing = false;
+		self.is_one_shot = false;
+		return;
+	}
+
+	// attack/hurt: plays to the end before locomotion takes over again
+	pub fn play_one_shot(&mut self, state: AnimState) {
+		self.state = AnimState::Idle;
+		self.switch_to(state);
+		self.is_one_shot = true;
+		return;
+	}
+
+	pub fn is_one_shot_playing(&self) -> bool {
+		return self.is_one_shot && !self.is_finished;
+	}
+
+	pub fn get_frame<'a>(&self, set: &'a AnimationSet) -> Option<(&'a AnimationClip, &'a AnimationFrame)> {
+		let clip: &AnimationClip = set.get_clip(self.state)?;
+		let frame: &AnimationFrame = clip.frames.get(self.frame as usize)?;
+		return Some((clip, frame));
+	}
+
+	fn advance(&mut self, clip: &AnimationClip, entity_id: EntityId, events: &mut Vec<FiredAnimationEvent>) {
+		if self.is_finished || clip.frames.is_empty() {
+			return;
+		}
+
+		if self.frame == 0 && self.ticks_in_frame == 0 {
+			push_frame_events(clip, 0, entity_id, events);
+		}
+
+		self.ticks_in_frame += 1;
+		let duration_ticks: u16 = clip.frames[self.frame as usize].duration_ticks;
+		if self.ticks_in_frame < duration_ticks {
+			return;
+		}
+
+		self.ticks_in_frame = 0;
+		let last_frame: u16 = (clip.frames.len() - 1) as u16;
+
+		match clip.loop_mode {
+			LoopMode::Loop => {
+				self.frame = if self.frame >= last_frame { 0 } else { self.frame + 1 };
+			}
+			LoopMode::Once => {
+				if self.frame >= last_frame {
+					self.is_finished = true;
+					return;
+				}
+				self.frame += 1;
+			}
+			LoopMode::PingPong => {
+				if last_frame == 0 {
+					return;
+				}
+				if self.is_reversing && self.frame == 0 {
+					self.is_reversing = false;
+				} else if !self.is_reversing && self.frame >= last_frame {
+					self.is_reversing = true;
+				}
+				self.frame = if self.is_reversing { self.frame - 1 } else { self.frame + 1 };
+			}
+		}
+
+		push_frame_events(clip, self.frame, entity_id, events);
+		return;
+	}
+}
+
+fn push_frame_events(clip: &AnimationClip, frame: u16, entity_id: EntityId, events: &mut Vec<FiredAnimationEvent>) {
+	for event in &clip.events {
+		if event.frame == frame {
+			events.push(FiredAnimationEvent {
+				entity_id,
+				event_id: event.event_id,
+			});
+		}
+	}
+	return;
+}
+
+// one simulation step: pick each entity's clip from its state and advance it
+pub fn tick_animators(state: &mut State, library: &AnimationLibrary) {
+	state.animation_events.clear();
+
+	let ids: Vec<EntityId> = state.positions.keys().collect();
+	for id in ids {
+		if !state.animators.has(id) {
+			let kind: EntityKind = EntityKind::from_u8(state.entity_kinds.get(id).copied().unwrap_or(0));
+			let Some(set_id) = library.find_set_for_kind(kind) else {
+				continue;
+			};
+			let is_facing_right: bool = library.get_set(set_id).map(|s| s.faces_right).unwrap_or(true);
+			state.animators.set(id, Animator::new(set_id, is_facing_right));
+		}
+
+		let velocity_x: f32 = state.velocities.get(id).map(|v| v.x).unwrap_or(0.0);
+		let is_dying: bool = state.death_animations.has(id);
+
+		let Some(animator) = state.animators.get_mut(id) else {
+			continue;
+		};
+		let Some(set) = library.get_set(animator.set_id) else {
+			continue;
+		};
+
+		if velocity_x > MOVING_SPEED {
+			animator.is_facing_right = true;
+		} else if velocity_x < -MOVING_SPEED {
+			animator.is_facing_right = false;
+		}
+
+		let wanted: AnimState = if is_dying {
+			AnimState::Death
+		} else if animator.is_one_shot_playing() {
+			animator.state
+		} else if velocity_x.abs() >= set.run_speed {
+			AnimState::Run
+		} else if velocity_x.abs() > MOVING_SPEED {
+			AnimState::Walk
+		} else {
+			AnimState::Idle
+		};
+
+		let Some(resolved) = set.resolve_state(wanted) else {
+			continue;
+		};
+		animator.switch_to(resolved);
+
+		if let Some(clip) = set.get_clip(resolved) {
+			animator.advance(clip, id, &mut state.animation_events);
+		}
+	}
+
+	return;
+}
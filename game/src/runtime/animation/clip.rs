@@ -0,0 +1,431 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{format, string::String, vec::Vec};
+
+use crate::runtime::state::EntityKind;
+
+#[cfg(feature = "pc")]
+use crate::runtime::assets::get_animations_path;
+
+#[cfg(feature = "pc")]
+use std::{fs, path::PathBuf};
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AnimState {
+	Idle = 0,
+	Walk = 1,
+	Run = 2,
+	Attack = 3,
+	Hurt = 4,
+	Death = 5,
+}
+
+pub const ANIM_STATE_COUNT: usize = 6;
+
+impl AnimState {
+	pub fn from_name(name: &str) -> Option<AnimState> {
+		return match name {
+			"idle" => Some(AnimState::Idle),
+			"walk" => Some(AnimState::Walk),
+			"run" => Some(AnimState::Run),
+			"attack" => Some(AnimState::Attack),
+			"hurt" => Some(AnimState::Hurt),
+			"death" => Some(AnimState::Death),
+			_ => None,
+		};
+	}
+
+	// what to play instead when a set has no clip for this state
+	fn get_fallback(self) -> Option<AnimState> {
+		return match self {
+			AnimState::Run => Some(AnimState::Walk),
+			AnimState::Walk => Some(AnimState::Idle),
+			_ => None,
+		};
+	}
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LoopMode {
+	Loop,
+	Once,
+	PingPong,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct AnimationFrame {
+	pub left: i32,
+	pub top: i32,
+	pub width: u32,
+	pub height: u32,
+	pub duration_ticks: u16,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct AnimationEvent {
+	pub frame: u16,
+	pub event_id: u16,
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimationClip {
+	pub sheet_id: u16,
+	pub frames: Vec<AnimationFrame>,
+	pub loop_mode: LoopMode,
+	pub events: Vec<AnimationEvent>,
+}
+
+// every clip one kind of entity can play, plus how its frames sit on the physics body
+#[derive(Clone, Debug)]
+pub struct AnimationSet {
+	pub name: String,
+	pub kind: EntityKind,
+	pub anchor_left: i32,
+	pub anchor_top: i32,
+	pub faces_right: bool,
+	pub run_speed: f32,
+	pub clips: [Option<AnimationClip>; ANIM_STATE_COUNT],
+}
+
+impl AnimationSet {
+	// the clip to play for a state, falling back run -> walk -> idle when the set lacks one
+	pub fn resolve_state(&self, state: AnimState) -> Option<AnimState> {
+		let mut current: AnimState = state;
+		loop {
+			if self.clips[current as usize].is_some() {
+				return Some(current);
+			}
+			current = current.get_fallback()?;
+		}
+	}
+
+	pub fn get_clip(&self, state: AnimState) -> Option<&AnimationClip> {
+		return self.clips[state as usize].as_ref();
+	}
+}
+
+pub struct AnimationLibrary {
+	pub sets: Vec<AnimationSet>,
+	// sheet paths relative to the platform gfx root; clips refer to them by index
+	pub sheets: Vec<String>,
+	pub event_names: Vec<String>,
+}
+
+impl AnimationLibrary {
+	pub fn empty() -> AnimationLibrary {
+		return AnimationLibrary {
+			sets: Vec::new(),
+			sheets: Vec::new(),
+			event_names: Vec::new(),
+		};
+	}
+
+	#[cfg(feature = "pc")]
+	pub fn load() -> Result<AnimationLibrary, String> {
+		let path: PathBuf = get_animations_path();
+		let text: String = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+		return AnimationLibrary::parse(text.as_str()).map_err(|e| format!("{}: {}", path.display(), e));
+	}
+
+	#[cfg(not(feature = "pc"))]
+	pub fn load() -> Result<AnimationLibrary, String> {
+		return Err(String::from("animation loading not implemented on this platform yet"));
+	}
+
+	pub fn find_set_for_kind(&self, kind: EntityKind) -> Option<u16> {
+		return self.sets.iter().position(|s| s.kind == kind).map(|index| index as u16);
+	}
+
+	pub fn get_set(&self, set_id: u16) -> Option<&AnimationSet> {
+		return self.sets.get(set_id as usize);
+	}
+
+	pub fn get_event_name(&self, event_id: u16) -> &str {
+		return self.event_names.get(event_id as usize).map(|s| s.as_str()).unwrap_or("");
+	}
+
+	fn intern(list: &mut Vec<String>, value: &str) -> u16 {
+		if let Some(index) = list.iter().position(|s| s == value) {
+			return index as u16;
+		}
+		list.push(String::from(value));
+		return (list.len() - 1) as u16;
+	}
+
+	pub fn parse(text: &str) -> Result<AnimationLibrary, String> {
+		let mut library: AnimationLibrary = AnimationLibrary::empty();
+		let mut set: Option<SetParseState> = None;
+		let mut clip: Option<ClipParseState> = None;
+
+		for (line_index, raw_line) in text.lines().enumerate() {
+			let line_number: usize = line_index + 1;
+			let line: &str = raw_line.trim();
+
+			if line.is_empty() || line.starts_with('#') || line == "{" {
+				continue;
+			}
+
+			if line == "}" {
+				if let Some(finished_clip) = clip.take() {
+					let Some(current_set) = set.as_mut() else {
+						return Err(format!("clip outside a set at line {}", line_number));
+					};
+					let state: AnimState = finished_clip.state;
+					current_set.clips[state as usize] = Some(finished_clip.finish(current_set, line_number)?);
+					continue;
+				}
+
+				let Some(finished_set) = set.take() else {
+					return Err(format!("unexpected '}}' at line {}", line_number));
+				};
+				library.sets.push(finished_set.finish(line_number)?);
+				continue;
+			}
+
+			if let Some(name) = line.strip_prefix("set ") {
+				if set.is_some() {
+					return Err(format!("set inside a set at line {}", line_number));
+				}
+				set = Some(SetParseState::new(parse_quoted(name, line_number)?));
+				continue;
+			}
+
+			if let Some(name) = line.strip_prefix("clip ") {
+				if set.is_none() || clip.is_some() {
+					return Err(format!("clip must be directly inside a set at line {}", line_number));
+				}
+				let name: String = parse_quoted(name, line_number)?;
+				let Some(state) = AnimState::from_name(name.as_str()) else {
+					return Err(format!("unknown clip '{}' at line {}, expected idle, walk, run, attack, hurt or death", name, line_number));
+				};
+				clip = Some(ClipParseState::new(state));
+				continue;
+			}
+
+			let Some(eq_index) = line.find('=') else {
+				return Err(format!("invalid line {}: missing '='", line_number));
+			};
+			let key: &str = line[..eq_index].trim();
+			let value: &str = line[eq_index + 1..].trim();
+
+			if let Some(current_clip) = clip.as_mut() {
+				current_clip.apply(key, value, &mut library, line_number)?;
+			} else if let Some(current_set) = set.as_mut() {
+				current_set.apply(key, value, line_number)?;
+			} else {
+				return Err(format!("'{}' outside a set at line {}", key, line_number));
+			}
+		}
+
+		if set.is_some() || clip.is_some() {
+			return Err(String::from("missing '}' at end of file"));
+		}
+
+		return Ok(library);
+	}
+}
+
+struct SetParseState {
+	name: String,
+	kind: EntityKind,
+	frame_width: u32,
+	frame_height: u32,
+	anchor_left: i32,
+	anchor_top: i32,
+	faces_right: bool,
+	run_speed: f32,
+	clips: [Option<AnimationClip>; ANIM_STATE_COUNT],
+}
+
+impl SetParseState {
+	fn new(name: String) -> SetParseState {
+		return SetParseState {
+			name,
+			kind: EntityKind::Empty,
+			frame_width: 64,
+			frame_height: 64,
+			anchor_left: 32,
+			anchor_top: 64,
+			faces_right: true,
+			run_speed: 3.0,
+			clips: [None, None, None, None, None, None],
+		};
+	}
+
+	fn apply(&mut self, key: &str, value: &str, line_number: usize) -> Result<(), String> {
+		match key {
+			"kind" => {
+				let name: String = parse_quoted(value, line_number)?;
+				self.kind = EntityKind::from_name(name.as_str());
+				if self.kind == EntityKind::Empty {
+					return Err(format!("unknown entity kind '{}' at line {}", name, line_number));
+				}
+			}
+			"frame_width" => self.frame_width = parse_number(value, line_number)?,
+			"frame_height" => self.frame_height = parse_number(value, line_number)?,
+			"anchor_left" => self.anchor_left = parse_number(value, line_number)?,
+			"anchor_top" => self.anchor_top = parse_number(value, line_number)?,
+			"faces" => {
+				let faces: String = parse_quoted(value, line_number)?;
+				self.faces_right = match faces.as_str() {
+					"right" => true,
+					"left" => false,
+					_ => return Err(format!("faces must be \"left\" or \"right\" at line {}", line_number)),
+				};
+			}
+			"run_speed" => self.run_speed = parse_number(value, line_number)?,
+			_ => return Err(format!("unknown set key '{}' at line {}", key, line_number)),
+		}
+
+		return Ok(());
+	}
+
+	fn finish(self, line_number: usize) -> Result<AnimationSet, String> {
+		if self.kind == EntityKind::Empty {
+			return Err(format!("set \"{}\" ending at line {} has no kind", self.name, line_number));
+		}
+
+		return Ok(AnimationSet {
+			name: self.name,
+			kind: self.kind,
+			anchor_left: self.anchor_left,
+			anchor_top: self.anchor_top,
+			faces_right: self.faces_right,
+			run_speed: self.run_speed,
+			clips: self.clips,
+		});
+	}
+}
+
+struct ClipParseState {
+	state: AnimState,
+	sheet_id: Option<u16>,
+	row: u32,
+	first_frame: u32,
+	frame_count: u32,
+	durations: Vec<u16>,
+	loop_mode: LoopMode,
+	events: Vec<AnimationEvent>,
+}
+
+impl ClipParseState {
+	fn new(state: AnimState) -> ClipParseState {
+		return ClipParseState {
+			state,
+			sheet_id: None,
+			row: 0,
+			first_frame: 0,
+			frame_count: 1,
+			durations: Vec::new(),
+			loop_mode: LoopMode::Loop,
+			events: Vec::new(),
+		};
+	}
+
+	fn apply(&mut self, key: &str, value: &str, library: &mut AnimationLibrary, line_number: usize) -> Result<(), String> {
+		match key {
+			"sheet" => {
+				let sheet: String = parse_quoted(value, line_number)?;
+				self.sheet_id = Some(AnimationLibrary::intern(&mut library.sheets, sheet.as_str()));
+			}
+			"row" => self.row = parse_number(value, line_number)?,
+			"first_frame" => self.first_frame = parse_number(value, line_number)?,
+			"frames" => self.frame_count = parse_number(value, line_number)?,
+			"duration" => {
+				self.durations.clear();
+				for part in value.split_whitespace() {
+					self.durations.push(parse_number(part, line_number)?);
+				}
+				if self.durations.is_empty() || self.durations.iter().any(|d| *d == 0) {
+					return Err(format!("durations must be at least one tick at line {}", line_number));
+				}
+			}
+			"mode" => {
+				let mode: String = parse_quoted(value, line_number)?;
+				self.loop_mode = match mode.as_str() {
+					"loop" => LoopMode::Loop,
+					"once" => LoopMode::Once,
+					"ping_pong" => LoopMode::PingPong,
+					_ => return Err(format!("invalid mode '{}' at line {}, expected loop, once or ping_pong", mode, line_number)),
+				};
+			}
+			"event" => {
+				let Some((frame, name)) = value.split_once(char::is_whitespace) else {
+					return Err(format!("event needs a frame and a name at line {}", line_number));
+				};
+				let event_id: u16 = AnimationLibrary::intern(&mut library.event_names, name.trim().trim_matches('"'));
+				self.events.push(AnimationEvent {
+					frame: parse_number(frame, line_number)?,
+					event_id,
+				});
+			}
+			_ => return Err(format!("unknown clip key '{}' at line {}", key, line_number)),
+		}
+
+		return Ok(());
+	}
+
+	fn finish(self, set: &SetParseState, line_number: usize) -> Result<AnimationClip, String> {
+		let Some(sheet_id) = self.sheet_id else {
+			return Err(format!("clip ending at line {} has no sheet", line_number));
+		};
+
+		if self.frame_count == 0 {
+			return Err(format!("clip ending at line {} has no frames", line_number));
+		}
+
+		if self.durations.len() > 1 && self.durations.len() != self.frame_count as usize {
+			return Err(format!(
+				"clip ending at line {} has {} frames but {} durations",
+				line_number,
+				self.frame_count,
+				self.durations.len()
+			));
+		}
+
+		let mut frames: Vec<AnimationFrame> = Vec::with_capacity(self.frame_count as usize);
+		for i in 0..self.frame_count {
+			let duration_ticks: u16 = if self.durations.len() > 1 {
+				self.durations[i as usize]
+			} else {
+				self.durations.first().copied().unwrap_or(6)
+			};
+
+			frames.push(AnimationFrame {
+				left: ((self.first_frame + i) * set.frame_width) as i32,
+				top: (self.row * set.frame_height) as i32,
+				width: set.frame_width,
+				height: set.frame_height,
+				duration_ticks,
+			});
+		}
+
+		for event in &self.events {
+			if event.frame as u32 >= self.frame_count {
+				return Err(format!("event on frame {} but the clip ending at line {} has {} frames", event.frame, line_number, self.frame_count));
+			}
+		}
+
+		return Ok(AnimationClip {
+			sheet_id,
+			frames,
+			loop_mode: self.loop_mode,
+			events: self.events,
+		});
+	}
+}
+
+fn parse_quoted(value: &str, line_number: usize) -> Result<String, String> {
+	let value: &str = value.trim();
+	if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+		return Ok(String::from(&value[1..value.len() - 1]));
+	}
+	return Err(format!("expected a quoted string at line {}", line_number));
+}
+
+fn parse_number<T: core::str::FromStr>(value: &str, line_number: usize) -> Result<T, String> {
+	return value.trim().parse::<T>().map_err(|_| format!("invalid number '{}' at line {}", value.trim(), line_number));
+}
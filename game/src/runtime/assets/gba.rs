@@ -20,6 +20,10 @@ pub fn get_books_root() -> &'static str {
 	return "assets/books";
 }
 
+pub fn get_pickup_map_path() -> &'static str {
+	return "assets/pickup-maps/pickups.ids.txt";
+}
+
 pub fn get_font_path() -> &'static str {
 	return "assets/fonts";
 }
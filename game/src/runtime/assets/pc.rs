@@ -10,6 +10,21 @@ pub fn get_gfx_root() -> PathBuf {
 	return root;
 }
 
+pub fn get_animations_path() -> PathBuf {
+	let path: PathBuf = get_gfx_root().join("pc").join("animations.txt");
+	return path;
+}
+
+pub fn get_icons_path() -> PathBuf {
+	let path: PathBuf = get_gfx_root().join("pc").join("icons.txt");
+	return path;
+}
+
+pub fn get_backgrounds_path() -> PathBuf {
+	let path: PathBuf = get_gfx_root().join("pc").join("backgrounds.txt");
+	return path;
+}
+
 pub fn get_audio_root() -> PathBuf {
 	let root: PathBuf = get_asset_root().join("audio");
 	return root;
@@ -25,7 +40,67 @@ pub fn get_books_root() -> PathBuf {
 	return root;
 }
 
+pub fn get_pickup_map_path() -> PathBuf {
+	let path: PathBuf = get_asset_root().join("pickup-maps").join("pickups.ids.txt");
+	return path;
+}
+
+pub fn get_worlds_root() -> PathBuf {
+	let root: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("worlds");
+	return root;
+}
+
+pub fn get_world_index_path() -> PathBuf {
+	let path: PathBuf = get_worlds_root().join("worlds.txt");
+	return path;
+}
+
 pub fn get_font_path() -> PathBuf {
 	let path: PathBuf = get_asset_root().join("fonts");
 	return path;
 }
+
+// per-user files (window placement, settings) live outside the asset tree
+pub fn get_config_root() -> PathBuf {
+	let base: PathBuf = if cfg!(target_os = "windows") {
+		std::env::var_os("APPDATA").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."))
+	} else if cfg!(target_os = "macos") {
+		std::env::var_os("HOME")
+			.map(|h| PathBuf::from(h).join("Library").join("Application Support"))
+			.unwrap_or_else(|| PathBuf::from("."))
+	} else {
+		if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME") {
+			PathBuf::from(xdg)
+		} else {
+			std::env::var_os("HOME")
+				.map(|h| PathBuf::from(h).join(".config"))
+				.unwrap_or_else(|| PathBuf::from("."))
+		}
+	};
+
+	let root: PathBuf = base.join("jumpy");
+	let _ = std::fs::create_dir_all(&root);
+	return root;
+}
+
+pub fn get_settings_path() -> PathBuf {
+	let path: PathBuf = get_config_root().join("settings.txt");
+	return path;
+}
+
+pub fn get_progress_path() -> PathBuf {
+	let path: PathBuf = get_config_root().join("progress.txt");
+	return path;
+}
+
+// the best run of each level, replayed as a ghost
+pub fn get_ghosts_root() -> PathBuf {
+	let root: PathBuf = get_config_root().join("ghosts");
+	let _ = std::fs::create_dir_all(&root);
+	return root;
+}
+
+pub fn get_ghost_path(world_id: u16, level_id: u16) -> PathBuf {
+	let path: PathBuf = get_ghosts_root().join(format!("{:02}_{:02}.ghost", world_id, level_id));
+	return path;
+}
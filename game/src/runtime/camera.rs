@@ -0,0 +1,283 @@
+use crate::{
+	engine_math::Vec2,
+	runtime::{
+		session::Session,
+		state::State,
+		triggers::TriggerKind,
+	},
+};
+
+// camera_zone lock bits (trigger p0), matching the level compiler
+pub const CAMERA_LOCK_HORIZONTAL: u16 = 1;
+pub const CAMERA_LOCK_VERTICAL: u16 = 2;
+
+pub const STOMP_SHAKE_WORLD: f32 = 1.5;
+pub const STOMP_SHAKE_FRAMES: u16 = 8;
+pub const DEATH_SHAKE_WORLD: f32 = 3.0;
+pub const DEATH_SHAKE_FRAMES: u16 = 20;
+
+// a camera_zone trigger in world units
+#[derive(Clone, Copy, Debug)]
+pub struct CameraBounds {
+	pub left: f32,
+	pub top: f32,
+	pub right: f32,
+	pub bottom: f32,
+	pub lock: u16,
+}
+
+// follows the player once per simulation step; rendering interpolates between the last two steps
+#[derive(Clone, Debug)]
+pub struct Camera {
+	// top-left of the view in world units
+	pub left: f32,
+	pub top: f32,
+	previous_left: f32,
+	previous_top: f32,
+	target_left: f32,
+	target_top: f32,
+
+	view_width: f32,
+	view_height: f32,
+
+	look_ahead: f32,
+	has_ground_line: bool,
+	ground_line: f32,
+
+	shake_frames_left: u16,
+	shake_frames_total: u16,
+	shake_magnitude: f32,
+
+	pub bounds: Option<CameraBounds>,
+	is_snap_pending: bool,
+}
+
+impl Camera {
+	pub fn new() -> Camera {
+		return Camera {
+			left: 0.0,
+			top: 0.0,
+			previous_left: 0.0,
+			previous_top: 0.0,
+			target_left: 0.0,
+			target_top: 0.0,
+			view_width: 0.0,
+			view_height: 0.0,
+			look_ahead: 0.0,
+			has_ground_line: false,
+			ground_line: 0.0,
+			shake_frames_left: 0,
+			shake_frames_total: 0,
+			shake_magnitude: 0.0,
+			bounds: None,
+			is_snap_pending: true,
+		};
+	}
+
+	pub fn set_view_size(&mut self, screen_size_pixels: (i32, i32), scale: f32) {
+		self.view_width = screen_size_pixels.0 as f32 / scale;
+		self.view_height = screen_size_pixels.1 as f32 / scale;
+		return;
+	}
+
+	pub fn get_view_size(&self) -> (f32, f32) {
+		return (self.view_width, self.view_height);
+	}
+
+	pub fn copy_view_size_from(&mut self, other: &Camera) {
+		self.view_width = other.view_width;
+		self.view_height = other.view_height;
+		return;
+	}
+
+	// jump straight to the player on the next update instead of sliding there (level start, respawn)
+	pub fn request_snap(&mut self) {
+		self.is_snap_pending = true;
+		self.has_ground_line = false;
+		return;
+	}
+
+	pub fn is_snap_pending(&self) -> bool {
+		return self.is_snap_pending;
+	}
+
+	// a stronger shake replaces a weaker one that is still running
+	pub fn add_shake(&mut self, magnitude_world: f32, frames: u16) {
+		let current: f32 = self.get_shake_strength();
+		if magnitude_world < current {
+			return;
+		}
+
+		self.shake_magnitude = magnitude_world;
+		self.shake_frames_left = frames;
+		self.shake_frames_total = frames.max(1);
+		return;
+	}
+
+	fn get_shake_strength(&self) -> f32 {
+		if self.shake_frames_left == 0 {
+			return 0.0;
+		}
+
+		return self.shake_magnitude * (self.shake_frames_left as f32 / self.shake_frames_total as f32);
+	}
+
+	// deterministic jitter so replays and screenshots stay reproducible
+	pub fn get_shake_offset(&self) -> Vec2 {
+		let strength: f32 = self.get_shake_strength();
+		if strength <= 0.0 {
+			return Vec2::zero();
+		}
+
+		let n: u32 = self.shake_frames_left as u32;
+		let x: f32 = ((n * 7_919) % 17) as f32 / 8.0 - 1.0;
+		let y: f32 = ((n * 104_729) % 13) as f32 / 6.0 - 1.0;
+		return Vec2::new(x * strength, y * strength);
+	}
+
+	pub fn get_render_left_top(&self, alpha: f32) -> Vec2 {
+		let left: f32 = self.previous_left + (self.left - self.previous_left) * alpha;
+		let top: f32 = self.previous_top + (self.top - self.previous_top) * alpha;
+		return Vec2::new(left, top);
+	}
+
+	// rooms smaller than the view, or locked on an axis, are centred rather than clamped
+	fn clamp_to(&self, left: f32, top: f32, bounds: &CameraBounds) -> (f32, f32) {
+		return (
+			clamp_axis(left, bounds.left, bounds.right, self.view_width, bounds.lock & CAMERA_LOCK_HORIZONTAL != 0),
+			clamp_axis(top, bounds.top, bounds.bottom, self.view_height, bounds.lock & CAMERA_LOCK_VERTICAL != 0),
+		);
+	}
+}
+
+// keeps a view of `view` units inside [min, max]
+fn clamp_axis(value: f32, min: f32, max: f32, view: f32, is_locked: bool) -> f32 {
+	let range: f32 = max - min;
+	if is_locked || range <= view {
+		return min + (range - view) * 0.5;
+	}
+
+	return value.clamp(min, max - view);
+}
+
+// one simulation step of camera movement; call after physics
+pub fn update_camera(state: &mut State, session: &Session) {
+	let Some(player_id) = state.try_get_player_id() else {
+		return;
+	};
+
+	let Some(player_pos) = state.positions.get(player_id).copied() else {
+		return;
+	};
+
+	let velocity: Vec2 = state.velocities.get(player_id).copied().unwrap_or_default();
+	let (_half_width, half_height) = state.get_entity_half_values(player_id);
+	let is_grounded: bool = state.is_grounded_now(player_id);
+	let bounds: Option<CameraBounds> = find_camera_zone(state, player_pos);
+
+	let settings = &session.settings;
+	let tile_width: f32 = state.level.tile_width as f32;
+	let tile_height: f32 = state.level.tile_height as f32;
+	let level_width: f32 = state.level.width as f32 * tile_width;
+	let level_height: f32 = state.level.height as f32 * tile_height;
+
+	let camera: &mut Camera = &mut state.camera;
+	camera.previous_left = camera.left;
+	camera.previous_top = camera.top;
+	camera.bounds = bounds;
+
+	if camera.shake_frames_left > 0 {
+		camera.shake_frames_left -= 1;
+	}
+
+	// --- horizontal: look ahead of the player in the direction they move, with a deadzone ---
+	let look_ahead_max: f32 = settings.camera_look_ahead_tiles * tile_width;
+	if velocity.x.abs() > 0.1 {
+		let wanted: f32 = look_ahead_max * velocity.x.signum();
+		camera.look_ahead += (wanted - camera.look_ahead) * settings.camera_look_ahead_rate;
+	}
+
+	let focus_left: f32 = player_pos.x + camera.look_ahead;
+	let deadzone_half_width: f32 = settings.camera_deadzone_half_width_tiles * tile_width;
+	let center_left: f32 = camera.target_left + camera.view_width * 0.5;
+	let dx: f32 = focus_left - center_left;
+	if dx > deadzone_half_width {
+		camera.target_left += dx - deadzone_half_width;
+	} else if dx < -deadzone_half_width {
+		camera.target_left += dx + deadzone_half_width;
+	}
+
+	// --- vertical: frame the ground the player last stood on, so jumps don't bob the view ---
+	let padding: f32 = settings.camera_bottom_padding_tiles as f32 * tile_height;
+	let player_bottom: f32 = player_pos.y + half_height;
+	let player_top: f32 = player_pos.y - half_height;
+
+	if is_grounded || !camera.has_ground_line {
+		camera.ground_line = player_bottom;
+		camera.has_ground_line = true;
+	}
+
+	// the ground sits `padding` above the bottom edge, but never so low that the player leaves the vertical deadzone
+	let deadzone_half_height: f32 = settings.camera_deadzone_half_height_tiles * tile_height;
+	let mut wanted_top: f32 = camera.ground_line + padding - camera.view_height;
+	let deadzone_top: f32 = wanted_top + camera.view_height * 0.5 - deadzone_half_height;
+	let deadzone_bottom: f32 = wanted_top + camera.view_height - padding;
+	if player_top < deadzone_top {
+		wanted_top -= deadzone_top - player_top;
+	} else if player_bottom > deadzone_bottom {
+		wanted_top += player_bottom - deadzone_bottom;
+	}
+	camera.target_top = wanted_top;
+
+	// --- rooms and level edges ---
+	let mut target_left: f32 = camera.target_left.clamp(0.0, (level_width - camera.view_width).max(0.0));
+	let mut target_top: f32 = camera.target_top.clamp(0.0, (level_height - camera.view_height).max(0.0));
+	if let Some(zone) = &bounds {
+		(target_left, target_top) = camera.clamp_to(target_left, target_top, zone);
+	}
+	camera.target_left = target_left;
+	camera.target_top = target_top;
+
+	if camera.is_snap_pending {
+		camera.left = target_left;
+		camera.top = target_top;
+		camera.previous_left = target_left;
+		camera.previous_top = target_top;
+		camera.is_snap_pending = false;
+		return;
+	}
+
+	let rate: f32 = settings.camera_follow_rate.clamp(0.0, 1.0);
+	camera.left += (target_left - camera.left) * rate;
+	camera.top += (target_top - camera.top) * rate;
+	return;
+}
+
+// the first camera_zone trigger containing the player's centre
+fn find_camera_zone(state: &State, player_pos: Vec2) -> Option<CameraBounds> {
+	let tile_width: f32 = state.level.tile_width as f32;
+	let tile_height: f32 = state.level.tile_height as f32;
+
+	for trigger in &state.level.triggers {
+		if TriggerKind::from_u8(trigger.kind) != TriggerKind::CameraZone {
+			continue;
+		}
+
+		let left: f32 = trigger.left * tile_width;
+		let top: f32 = trigger.top * tile_height;
+		let right: f32 = left + trigger.width * tile_width;
+		let bottom: f32 = top + trigger.height * tile_height;
+
+		if player_pos.x >= left && player_pos.x < right && player_pos.y >= top && player_pos.y < bottom {
+			return Some(CameraBounds {
+				left,
+				top,
+				right,
+				bottom,
+				lock: trigger.p0,
+			});
+		}
+	}
+
+	return None;
+}
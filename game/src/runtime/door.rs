@@ -0,0 +1,226 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{string::String, sync::Arc};
+
+#[cfg(not(feature = "gba"))]
+use std::sync::Arc;
+
+use crate::{
+	engine_math::do_they_overlap,
+	platform::input::TriggerPresses,
+	runtime::{
+		message_box::MessageBoxMessage,
+		progress::LevelKey,
+		session::Session,
+		settings::Settings,
+		state::{EntityId, State},
+		triggers::{LevelTrigger, TRIGGER_MODE_ACTION, TRIGGER_MODE_AUTO, TriggerKind, should_fire},
+	},
+	tile::TileKind,
+};
+
+// "it's locked", shown when the player walks into a door without its key
+const MESSAGE_DOOR_LOCKED: u16 = 144;
+
+// a door is solid, so its trigger reaches this far past the door tiles for the player to touch it
+const DOOR_REACH_TILES: f32 = 0.5;
+
+// the unlock: the door rattles for a moment, then lifts one row of tiles at a time from the bottom
+const DOOR_UNLOCK_DELAY_TICKS: u16 = 20;
+const DOOR_ROW_TICKS: u16 = 6;
+const DOOR_UNLOCK_SHAKE_WORLD: f32 = 1.0;
+const DOOR_UNLOCK_SHAKE_FRAMES: u16 = 12;
+
+// a door in the middle of its unlock animation
+#[derive(Copy, Clone, Debug)]
+pub struct OpeningDoor {
+	pub trigger_id: u16,
+	pub ticks: u16,
+	pub rows_cleared: u16,
+}
+
+/// returns true if an ACTION door consumed the action press (so caller should NOT jump)
+pub fn handle_door_triggers(session: &mut Session, state: &mut State, presses: TriggerPresses) -> bool {
+	let player_id: EntityId = state.get_player_id();
+	let Some(player_pos) = state.positions.get(player_id) else {
+		return false;
+	};
+
+	let (player_half_width, player_half_height) = state.get_entity_half_values(player_id);
+	let player_left_world: f32 = player_pos.x - player_half_width;
+	let player_top_world: f32 = player_pos.y - player_half_height;
+	let player_width_world: f32 = player_half_width * 2.0;
+	let player_height_world: f32 = player_half_height * 2.0;
+
+	let tile_width_world: f32 = state.level.tile_width as f32;
+	let tile_height_world: f32 = state.level.tile_height as f32;
+	let reach_width_world: f32 = tile_width_world * DOOR_REACH_TILES;
+	let reach_height_world: f32 = tile_height_world * DOOR_REACH_TILES;
+
+	let level_key: Option<LevelKey> = session.get_current_level_key();
+	let armed_len: usize = state.triggers_armed.len();
+	let mut consumed_action: bool = false;
+
+	for trigger in &state.level.triggers {
+		if TriggerKind::from_u8(trigger.kind) != TriggerKind::Door {
+			continue;
+		}
+
+		let trigger_index: usize = trigger.id as usize;
+		if trigger_index >= armed_len {
+			continue;
+		}
+
+		// a door with no door tiles left is open
+		if state.opening_doors.iter().any(|d| d.trigger_id == trigger.id) || !has_door_tiles(state, trigger) {
+			continue;
+		}
+
+		let is_overlapping: bool = do_they_overlap(
+			player_left_world,
+			player_top_world,
+			player_width_world,
+			player_height_world,
+			trigger.left * tile_width_world - reach_width_world,
+			trigger.top * tile_height_world - reach_height_world,
+			trigger.width * tile_width_world + reach_width_world * 2.0,
+			trigger.height * tile_height_world + reach_height_world * 2.0,
+		);
+
+		if !is_overlapping {
+			state.triggers_armed[trigger_index] = false;
+			continue;
+		}
+
+		// once per visit, so the locked message doesn't repeat while the player leans on the door
+		if state.triggers_armed[trigger_index] {
+			continue;
+		}
+
+		let mode: u16 = trigger.get_activation_mode();
+		if mode != TRIGGER_MODE_AUTO && !should_fire(mode, presses) {
+			continue;
+		}
+
+		state.triggers_armed[trigger_index] = true;
+		if mode == TRIGGER_MODE_ACTION {
+			consumed_action = true;
+		}
+
+		// the key is only spent when the session's progress can keep the door open after a death,
+		// a restart or a trip to another level; a level loaded outside the world index opens the
+		// door for this visit and leaves the key in the inventory
+		let key_id: u16 = trigger.get_door_key_id();
+		let has_key: bool = match level_key {
+			Some(_) => session.inventory.use_key(key_id),
+			None => session.inventory.has_key(key_id),
+		};
+		if !has_key {
+			let text: &str = session.message_table.get(MESSAGE_DOOR_LOCKED);
+			session.message_box.push(MessageBoxMessage {
+				message_id: MESSAGE_DOOR_LOCKED,
+				text: String::from(text),
+				speaker_icon_id: 0,
+				is_blocking: false,
+			});
+			continue;
+		}
+
+		if let Some(level_key) = level_key {
+			session.progress.open_door(level_key, trigger.id);
+		}
+		state.opening_doors.push(OpeningDoor {
+			trigger_id: trigger.id,
+			ticks: 0,
+			rows_cleared: 0,
+		});
+	}
+
+	return consumed_action;
+}
+
+pub fn tick_opening_doors(state: &mut State, settings: &Settings) {
+	let mut index: usize = 0;
+	while index < state.opening_doors.len() {
+		let mut door: OpeningDoor = state.opening_doors[index];
+		door.ticks = door.ticks.saturating_add(1);
+
+		if door.ticks == 1 && settings.is_screen_shake_enabled {
+			state.camera.add_shake(DOOR_UNLOCK_SHAKE_WORLD, DOOR_UNLOCK_SHAKE_FRAMES);
+		}
+
+		let mut is_open: bool = false;
+		if door.ticks >= DOOR_UNLOCK_DELAY_TICKS && (door.ticks - DOOR_UNLOCK_DELAY_TICKS).is_multiple_of(DOOR_ROW_TICKS) {
+			is_open = !clear_door_row(state, door.trigger_id, door.rows_cleared);
+			door.rows_cleared += 1;
+		}
+
+		if is_open {
+			state.opening_doors.remove(index);
+			continue;
+		}
+
+		state.opening_doors[index] = door;
+		index += 1;
+	}
+
+	return;
+}
+
+// doors the player already opened, cleared straight away when their level loads
+pub fn restore_opened_doors(state: &mut State, opened_door_ids: &[u16]) {
+	for trigger_id in opened_door_ids {
+		let mut row: u16 = 0;
+		while clear_door_row(state, *trigger_id, row) {
+			row += 1;
+		}
+	}
+
+	return;
+}
+
+fn has_door_tiles(state: &State, trigger: &LevelTrigger) -> bool {
+	let (start_left, start_top, end_left, end_top) = get_door_tile_range(trigger);
+	let layer: u32 = state.level.get_action_layer_index() as u32;
+	for tile_top in start_top..end_top {
+		for tile_left in start_left..end_left {
+			if state.level.get_tile_at_layer(layer, tile_left, tile_top) == TileKind::Door {
+				return true;
+			}
+		}
+	}
+	return false;
+}
+
+// clears the door tiles in the row'th row from the bottom of the trigger; false once past the top
+fn clear_door_row(state: &mut State, trigger_id: u16, row: u16) -> bool {
+	let Some(trigger) = state.level.triggers.iter().find(|t| t.id == trigger_id) else {
+		return false;
+	};
+	let (start_left, start_top, end_left, end_top) = get_door_tile_range(trigger);
+
+	let tile_top: i32 = end_top - 1 - row as i32;
+	if tile_top < start_top {
+		return false;
+	}
+
+	let layer: u32 = state.level.get_action_layer_index() as u32;
+	for tile_left in start_left..end_left {
+		if state.level.get_tile_at_layer(layer, tile_left, tile_top) == TileKind::Door {
+			Arc::make_mut(&mut state.level).set_tile_id_at_layer(layer, tile_left, tile_top, TileKind::Empty as u8);
+		}
+	}
+
+	return true;
+}
+
+// the tiles under a door trigger, same rounding as levelc's door check
+fn get_door_tile_range(trigger: &LevelTrigger) -> (i32, i32, i32, i32) {
+	let start_left: i32 = libm::floorf(trigger.left) as i32;
+	let start_top: i32 = libm::floorf(trigger.top) as i32;
+	let end_left: i32 = libm::ceilf(trigger.left + trigger.width) as i32;
+	let end_top: i32 = libm::ceilf(trigger.top + trigger.height) as i32;
+	return (start_left, start_top, end_left, end_top);
+}
@@ -0,0 +1,87 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::vec::Vec;
+
+use crate::runtime::menu::Menu;
+
+// what the game is doing; only the mode on top of the stack gets input and updates
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GameMode {
+	Title,
+	Playing,
+	Paused,
+	Settings,
+	// the accessibility page of the settings
+	Accessibility,
+	Book,
+	Inventory,
+	// the worlds of the world index and their levels, opened from the title menu
+	LevelSelect,
+	// the score breakdown and rank of the level just left; the next level loads once it's closed
+	Results,
+	// loads the pending level, or restarts the current one when none is pending, then pops itself
+	LevelTransition,
+	GameOver,
+}
+
+impl GameMode {
+	// play simulates on the fixed step and a level transition closes and opens on it, so both keep
+	// its leftover time between frames. every other mode resets it each frame, so time spent there
+	// isn't simulated afterwards
+	pub fn runs_on_fixed_step(&self) -> bool {
+		return matches!(self, GameMode::Playing | GameMode::LevelTransition);
+	}
+}
+
+struct GameModeEntry {
+	mode: GameMode,
+	menu: Option<Menu>,
+}
+
+// modes stack up: settings over the pause menu over play. a mode with a menu keeps it, cursor
+// and all, while another mode sits on top of it
+pub struct GameModeStack {
+	entries: Vec<GameModeEntry>,
+}
+
+impl GameModeStack {
+	pub fn new(root: GameMode) -> GameModeStack {
+		let mut entries: Vec<GameModeEntry> = Vec::new();
+		entries.push(GameModeEntry { mode: root, menu: None });
+		return GameModeStack { entries };
+	}
+
+	pub fn get_top(&self) -> GameMode {
+		return match self.entries.last() {
+			Some(entry) => entry.mode,
+			None => GameMode::Playing,
+		};
+	}
+
+	pub fn contains(&self, mode: GameMode) -> bool {
+		return self.entries.iter().any(|entry| entry.mode == mode);
+	}
+
+	pub fn get_menu(&self) -> Option<&Menu> {
+		return self.entries.last().and_then(|entry| entry.menu.as_ref());
+	}
+
+	pub fn get_menu_mut(&mut self) -> Option<&mut Menu> {
+		return self.entries.last_mut().and_then(|entry| entry.menu.as_mut());
+	}
+
+	pub fn push(&mut self, mode: GameMode, menu: Option<Menu>) {
+		self.entries.push(GameModeEntry { mode, menu });
+		return;
+	}
+
+	// the bottom mode stays; popping it returns None
+	pub fn pop(&mut self) -> Option<GameMode> {
+		if self.entries.len() <= 1 {
+			return None;
+		}
+		return self.entries.pop().map(|entry| entry.mode);
+	}
+}
@@ -0,0 +1,96 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::vec::Vec;
+
+use crate::runtime::inventory::KeyId;
+
+// how long a collected pickup takes to fly from the level to its hud counter
+pub const PICKUP_FLIGHT_TICKS: u32 = 30;
+
+// how long a counter stays highlighted after a pickup lands in it
+pub const COUNTER_PULSE_TICKS: u8 = 12;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HudCounter {
+	Coins,
+	Keys,
+}
+
+// a pickup on its way to the hud. the inventory already holds it; the hud leaves it out of the
+// counter until it lands so the number goes up when the pickup arrives
+#[derive(Copy, Clone, Debug)]
+pub struct PickupFlight {
+	pub counter: HudCounter,
+	// coins for Coins, the key id for Keys
+	pub value: u16,
+	// world position the pickup was collected at
+	pub start_left: f32,
+	pub start_top: f32,
+	pub start_tick: u32,
+}
+
+pub struct HudState {
+	pub flights: Vec<PickupFlight>,
+	pub coin_pulse_ticks: u8,
+	pub key_pulse_ticks: u8,
+}
+
+impl HudState {
+	pub fn new() -> HudState {
+		return HudState {
+			flights: Vec::new(),
+			coin_pulse_ticks: 0,
+			key_pulse_ticks: 0,
+		};
+	}
+
+	pub fn add_flight(&mut self, counter: HudCounter, value: u16, start_left: f32, start_top: f32, tick: u32) {
+		self.flights.push(PickupFlight {
+			counter,
+			value,
+			start_left,
+			start_top,
+			start_tick: tick,
+		});
+		return;
+	}
+
+	// once per simulation step: land finished flights and fade the counter highlights
+	pub fn tick(&mut self, tick: u32) {
+		self.coin_pulse_ticks = self.coin_pulse_ticks.saturating_sub(1);
+		self.key_pulse_ticks = self.key_pulse_ticks.saturating_sub(1);
+
+		let mut index: usize = 0;
+		while index < self.flights.len() {
+			let flight: PickupFlight = self.flights[index];
+			if tick.wrapping_sub(flight.start_tick) < PICKUP_FLIGHT_TICKS {
+				index += 1;
+				continue;
+			}
+
+			match flight.counter {
+				HudCounter::Coins => self.coin_pulse_ticks = COUNTER_PULSE_TICKS,
+				HudCounter::Keys => self.key_pulse_ticks = COUNTER_PULSE_TICKS,
+			}
+			self.flights.remove(index);
+		}
+
+		return;
+	}
+
+	pub fn get_coins_in_flight(&self) -> u16 {
+		let mut coins: u16 = 0;
+		for flight in &self.flights {
+			if flight.counter == HudCounter::Coins {
+				coins = coins.saturating_add(flight.value);
+			}
+		}
+		return coins;
+	}
+
+	pub fn is_key_in_flight(&self, key_id: KeyId) -> bool {
+		return self.flights.iter().any(|flight| flight.counter == HudCounter::Keys && flight.value == key_id);
+	}
+}
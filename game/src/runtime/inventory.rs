@@ -4,9 +4,50 @@ extern crate alloc;
 #[cfg(feature = "gba")]
 use alloc::vec::Vec;
 
-use crate::runtime::book::Book;
+use crate::runtime::book::{Book, BookId};
 pub type KeyId = u16;
 
+// the inventory screen lays its slots out in rows this wide
+pub const INVENTORY_COLUMNS: usize = 6;
+
+// one cell of the inventory screen; books and keys by their index in the inventory
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InventorySlot {
+	Coins,
+	Book(usize),
+	Key(usize),
+}
+
+// the selected slot of the inventory screen; kept while the screen is closed
+#[derive(Copy, Clone, Debug)]
+pub struct InventoryCursor {
+	pub selected: usize,
+}
+
+impl InventoryCursor {
+	pub fn new() -> InventoryCursor {
+		return InventoryCursor { selected: 0 };
+	}
+
+	// left/right step one slot, up/down a row; the cursor stops at the edges of the grid
+	pub fn move_selection(&mut self, column_delta: i32, row_delta: i32, slot_count: usize) {
+		if slot_count == 0 {
+			self.selected = 0;
+			return;
+		}
+
+		let selected: i32 = self.selected.min(slot_count - 1) as i32;
+		let column: i32 = selected % INVENTORY_COLUMNS as i32 + column_delta;
+		let next: i32 = selected + column_delta + row_delta * INVENTORY_COLUMNS as i32;
+		if column >= 0 && column < INVENTORY_COLUMNS as i32 && next >= 0 && next < slot_count as i32 {
+			self.selected = next as usize;
+		} else {
+			self.selected = selected as usize;
+		}
+		return;
+	}
+}
+
 #[allow(dead_code)]
 #[derive(Clone, Copy, Debug)]
 pub struct Key {
@@ -46,6 +87,20 @@ impl Inventory {
 		return;
 	}
 
+	// true if the player holds the key and hasn't used it yet
+	pub fn has_key(&self, key_id: KeyId) -> bool {
+		return self.keys.iter().any(|k| k.key_id == key_id && !k.is_used);
+	}
+
+	// spends an unused key; false if the player doesn't have it or already used it
+	pub fn use_key(&mut self, key_id: KeyId) -> bool {
+		let Some(key) = self.keys.iter_mut().find(|k| k.key_id == key_id && !k.is_used) else {
+			return false;
+		};
+		key.is_used = true;
+		return true;
+	}
+
 	pub fn add_book(&mut self, book_id: u16, total_pages: u16) {
 		if self.books.iter().any(|b| b.book_id == book_id) {
 			return;
@@ -61,4 +116,31 @@ impl Inventory {
 	pub fn get_book(&self, book_id: u16) -> Option<&Book> {
 		return self.books.iter().find(|b| b.book_id == book_id);
 	}
+
+	// where the reader left the book, so it opens there next time
+	pub fn set_book_page(&mut self, book_id: BookId, current_page: u16, total_pages: u16) {
+		if let Some(book) = self.books.iter_mut().find(|b| b.book_id == book_id) {
+			book.current_page = current_page;
+			book.total_pages = total_pages;
+		}
+		return;
+	}
+
+	// coins first, then the books and keys in the order they were picked up
+	pub fn get_slot_count(&self) -> usize {
+		return 1 + self.books.len() + self.keys.len();
+	}
+
+	pub fn get_slot(&self, index: usize) -> Option<InventorySlot> {
+		if index == 0 {
+			return Some(InventorySlot::Coins);
+		}
+		if index <= self.books.len() {
+			return Some(InventorySlot::Book(index - 1));
+		}
+		if index < self.get_slot_count() {
+			return Some(InventorySlot::Key(index - 1 - self.books.len()));
+		}
+		return None;
+	}
 }
@@ -14,6 +14,11 @@ use crate::{
 pub const BYTES_PER_ENTITY: usize = 24;
 pub const PLAYER_HALF_HEIGHT: f32 = 8.0;
 
+// version 3 headers end with the level's name: a length byte, then this many bytes of utf-8
+const LEVEL_NAME_MAX_BYTES: usize = 32;
+// where the name starts, after the version 2 header
+const HEADER_NAME_OFFSET: usize = 67;
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct LevelEntity {
@@ -52,9 +57,12 @@ pub struct Level {
 	pub triggers: Vec<LevelTrigger>,
 	pub background_id: BackgroundId,
 	pub music_id: MusicId,
-
-	#[allow(dead_code)]
-	pub reserved1: u8,
+	// 0 = fully lit, 255 = nothing visible outside of lights
+	pub darkness: u8,
+	// the score ranks s, a, b and c need on the results screen; 0 = the game's default
+	pub rank_thresholds: [u32; 4],
+	// the `name` from the level's header; empty before version 3
+	pub name: String,
 }
 
 #[inline(always)]
@@ -135,6 +143,35 @@ impl Level {
 		return self.tiles[index];
 	}
 
+	// for tiles that change during play, like a door opening
+	pub fn set_tile_id_at_layer(&mut self, layer: u32, tx: i32, ty: i32, tile_id: u8) {
+		if tx < 0 || ty < 0 || tx >= self.width as i32 || ty >= self.height as i32 || layer as u8 >= self.layer_count {
+			return;
+		}
+
+		let index: usize = (layer as usize) * self.tiles_per_layer + (ty as usize) * (self.width as usize) + tx as usize;
+		if index < self.tiles.len() {
+			self.tiles[index] = tile_id;
+		}
+		return;
+	}
+
+	// just the header's name, for the world index, without parsing the rest of the level
+	pub fn read_name(bytes: &[u8]) -> Result<String, String> {
+		if bytes.len() < 8 || &bytes[0..4] != b"JLVL" {
+			return Err(String::from("Bad magic (expected JLVL)"));
+		}
+
+		let mut offset: usize = 4;
+		let version = read_u16(bytes, &mut offset)?;
+		if version < 3 {
+			return Ok(String::new());
+		}
+
+		offset = HEADER_NAME_OFFSET;
+		return read_name_field(bytes, &mut offset);
+	}
+
 	// pub fn load_binary(path: &str) -> Result<Level, String> {
 	pub fn load_binary(bytes: &[u8]) -> Result<Level, String> {
 		if bytes.len() < 4 {
@@ -148,7 +185,7 @@ impl Level {
 		let mut offset: usize = 4;
 
 		// ---- header ----
-		let _version = read_u16(&bytes, &mut offset)?;
+		let version = read_u16(&bytes, &mut offset)?;
 		let header_size = read_u16(&bytes, &mut offset)? as usize;
 		let width = read_u16(&bytes, &mut offset)? as u32;
 		let height = read_u16(&bytes, &mut offset)? as u32;
@@ -161,13 +198,24 @@ impl Level {
 		let background_id = BackgroundId::from_u8(read_u8(&bytes, &mut offset)?);
 		let _gravity = read_u8(&bytes, &mut offset)?;
 		let music_id_u8 = read_u8(&bytes, &mut offset)?;
-		let reserved1 = read_u8(&bytes, &mut offset)?;
+		let darkness = read_u8(&bytes, &mut offset)?;
 		let tiles_per_layer = read_u32(&bytes, &mut offset)? as usize;
 		let tile_count_total = read_u32(&bytes, &mut offset)? as usize;
 		let _offset_layers = read_u32(&bytes, &mut offset)? as usize;
 		let offset_entities = read_u32(&bytes, &mut offset)? as usize;
 		let offset_triggers = read_u32(&bytes, &mut offset)? as usize;
 		let offset_tiles = read_u32(&bytes, &mut offset)? as usize;
+		// version 1 files end the header here
+		let mut rank_thresholds: [u32; 4] = [0; 4];
+		if version >= 2 {
+			for threshold in rank_thresholds.iter_mut() {
+				*threshold = read_u32(&bytes, &mut offset)?;
+			}
+		}
+		let mut name: String = String::new();
+		if version >= 3 {
+			name = read_name_field(&bytes, &mut offset)?;
+		}
 
 		// loader sanity
 		if header_size != offset {
@@ -379,17 +427,17 @@ impl Level {
 				);
 			} else if t.kind == 2 {
 				debugln!(
-					" {}: kind={}, pickup_type_id={} value={}, left={} top={} width={} height={} mode={}, message_id={} icon_id={}",
+					" {}: kind={}, message_id={} blocking={}, left={} top={} width={} height={} mode={} speaker={} icon_id={}",
 					i,
 					t.kind,
-					t.p0, // world_id
-					t.p1, // level_id
+					t.get_message_id(),
+					t.is_message_blocking(),
 					t.left,
 					t.top,
 					t.width,
 					t.height,
-					t.get_activation_mode(),
-					t.get_message_id(),
+					t.get_message_mode(),
+					t.get_message_speaker_icon(),
 					t.icon_id
 				);
 			} else if t.kind == 1 {
@@ -426,7 +474,9 @@ impl Level {
 			triggers: triggers,
 			background_id,
 			music_id: MusicId::from_u8(music_id_u8),
-			reserved1,
+			darkness,
+			rank_thresholds,
+			name,
 		};
 
 		level.floor_y = level.compute_floor_y();
@@ -473,6 +523,21 @@ impl Level {
 	}
 }
 
+fn read_name_field(bytes: &[u8], offset: &mut usize) -> Result<String, String> {
+	let name_len: usize = read_u8(bytes, offset)? as usize;
+	if name_len > LEVEL_NAME_MAX_BYTES {
+		return Err(format!("level name is {} bytes, the limit is {}", name_len, LEVEL_NAME_MAX_BYTES));
+	}
+	if *offset + LEVEL_NAME_MAX_BYTES > bytes.len() {
+		return Err(String::from("Unexpected eof reading the level name"));
+	}
+
+	let name_bytes: &[u8] = &bytes[*offset..*offset + name_len];
+	*offset += LEVEL_NAME_MAX_BYTES;
+	let name: &str = core::str::from_utf8(name_bytes).map_err(|_| String::from("level name is not utf-8"))?;
+	return Ok(String::from(name));
+}
+
 fn read_u32(bytes: &[u8], offset: &mut usize) -> Result<u32, String> {
 	if *offset + 4 > bytes.len() {
 		return Err(String::from("Unexpected eof reading u32"));
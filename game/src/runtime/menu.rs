@@ -0,0 +1,273 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::vec::Vec;
+
+use crate::runtime::{
+	accessibility::{TextFont, TextSize},
+	game_mode::GameMode,
+	session::Session,
+	settings::Settings,
+};
+
+// menu text lives in the message table (assets/messages/messages.ids.txt) so it follows the language
+pub const MESSAGE_GAME_TITLE: u16 = 100;
+pub const MESSAGE_MENU_START: u16 = 101;
+pub const MESSAGE_MENU_SETTINGS: u16 = 102;
+pub const MESSAGE_MENU_QUIT: u16 = 103;
+pub const MESSAGE_MENU_RESUME: u16 = 104;
+pub const MESSAGE_MENU_RESTART_LEVEL: u16 = 105;
+pub const MESSAGE_MENU_QUIT_TO_TITLE: u16 = 106;
+pub const MESSAGE_MENU_RETRY: u16 = 107;
+pub const MESSAGE_MENU_PAUSED: u16 = 108;
+pub const MESSAGE_MENU_GAME_OVER: u16 = 109;
+pub const MESSAGE_MENU_MUSIC: u16 = 110;
+pub const MESSAGE_MENU_SOUND_EFFECTS: u16 = 111;
+pub const MESSAGE_MENU_SHOW_TRIGGERS: u16 = 112;
+pub const MESSAGE_MENU_BACK: u16 = 113;
+pub const MESSAGE_MENU_ON: u16 = 114;
+pub const MESSAGE_MENU_OFF: u16 = 115;
+pub const MESSAGE_MENU_INVENTORY: u16 = 116;
+pub const MESSAGE_MENU_COINS: u16 = 117;
+pub const MESSAGE_MENU_KEYS: u16 = 118;
+pub const MESSAGE_MENU_BOOKS: u16 = 119;
+pub const MESSAGE_MENU_SCREEN_SHAKE: u16 = 120;
+pub const MESSAGE_MENU_MUSIC_VOLUME: u16 = 121;
+pub const MESSAGE_MENU_SOUND_EFFECTS_VOLUME: u16 = 122;
+pub const MESSAGE_MENU_LANGUAGE: u16 = 123;
+pub const MESSAGE_MENU_TEXT_FONT: u16 = 124;
+pub const MESSAGE_MENU_ACCESSIBILITY: u16 = 125;
+pub const MESSAGE_MENU_TEXT_SIZE: u16 = 126;
+pub const MESSAGE_MENU_HIGH_CONTRAST: u16 = 127;
+pub const MESSAGE_MENU_ASSIST_MODE: u16 = 128;
+pub const MESSAGE_MENU_GAME_SPEED: u16 = 129;
+pub const MESSAGE_MENU_EXTRA_JUMP_FRAMES: u16 = 130;
+pub const MESSAGE_MENU_INFINITE_HEARTS: u16 = 131;
+pub const MESSAGE_MENU_HOLD_TO_FLOAT: u16 = 132;
+pub const MESSAGE_MENU_TEXT_SIZE_SMALL: u16 = 133;
+pub const MESSAGE_MENU_TEXT_SIZE_MEDIUM: u16 = 134;
+pub const MESSAGE_MENU_TEXT_SIZE_LARGE: u16 = 135;
+pub const MESSAGE_MENU_LEVEL_COMPLETION: u16 = 145;
+pub const MESSAGE_MENU_SELECT_LEVEL: u16 = 146;
+pub const MESSAGE_MENU_SPEEDRUN_TIMER: u16 = 152;
+pub const MESSAGE_MENU_GHOST: u16 = 153;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MenuAction {
+	// a line that only shows a value
+	None,
+	Start,
+	OpenLevelSelect,
+	Resume,
+	RestartLevel,
+	OpenSettings,
+	OpenAccessibility,
+	QuitToTitle,
+	QuitGame,
+	Retry,
+	ToggleMusic,
+	ChangeMusicVolume,
+	ToggleSoundEffects,
+	ChangeSoundEffectsVolume,
+	ChangeLanguage,
+	ToggleScreenShake,
+	ToggleShowTriggers,
+	ToggleSpeedrunTimer,
+	ToggleGhost,
+	ChangeTextFont,
+	ChangeTextSize,
+	ToggleHighContrast,
+	ToggleAssistMode,
+	ChangeGameSpeed,
+	ChangeExtraJumpFrames,
+	ToggleInfiniteHearts,
+	ToggleHoldToFloat,
+	Back,
+}
+
+// drawn after the label: "music: on", "extra jump frames: 4", "music volume: 7/10"
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MenuValue {
+	None,
+	Toggle(bool),
+	Count(u16),
+	// out of VOLUME_LEVEL_MAX
+	Level(u8),
+	// the language code in the settings; a String can't live in a Copy value
+	Language,
+	TextFont(TextFont),
+	TextSize(TextSize),
+	Percent(u8),
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MenuItem {
+	pub action: MenuAction,
+	pub label_id: u16,
+	pub value: MenuValue,
+}
+
+#[derive(Clone, Debug)]
+pub struct Menu {
+	pub title_id: u16,
+	pub items: Vec<MenuItem>,
+	pub selected: usize,
+}
+
+impl Menu {
+	pub fn new(title_id: u16) -> Menu {
+		return Menu {
+			title_id,
+			items: Vec::new(),
+			selected: 0,
+		};
+	}
+
+	// the menu a mode opens with, or None for modes without one
+	pub fn for_mode(mode: GameMode, session: &Session) -> Option<Menu> {
+		let mut menu: Menu;
+		match mode {
+			GameMode::Title => {
+				menu = Menu::new(MESSAGE_GAME_TITLE);
+				menu.add_item(MenuAction::Start, MESSAGE_MENU_START, MenuValue::None);
+				if !session.world_index.levels.is_empty() {
+					menu.add_item(MenuAction::OpenLevelSelect, MESSAGE_MENU_SELECT_LEVEL, MenuValue::None);
+				}
+				menu.add_item(MenuAction::OpenSettings, MESSAGE_MENU_SETTINGS, MenuValue::None);
+				menu.add_item(MenuAction::QuitGame, MESSAGE_MENU_QUIT, MenuValue::None);
+			}
+			GameMode::Paused => {
+				menu = Menu::new(MESSAGE_MENU_PAUSED);
+				menu.add_item(MenuAction::Resume, MESSAGE_MENU_RESUME, MenuValue::None);
+				menu.add_item(MenuAction::RestartLevel, MESSAGE_MENU_RESTART_LEVEL, MenuValue::None);
+				menu.add_item(MenuAction::OpenSettings, MESSAGE_MENU_SETTINGS, MenuValue::None);
+				menu.add_item(MenuAction::QuitToTitle, MESSAGE_MENU_QUIT_TO_TITLE, MenuValue::None);
+				// how much of this level's pickups the player has, for levels in worlds/
+				if let Some(level_key) = session.get_current_level_key() {
+					let percent: u8 = session.progress.get_completion_percent(level_key);
+					menu.add_item(MenuAction::None, MESSAGE_MENU_LEVEL_COMPLETION, MenuValue::Percent(percent));
+				}
+			}
+			GameMode::Settings => {
+				menu = Menu::new(MESSAGE_MENU_SETTINGS);
+				menu.add_item(MenuAction::ToggleMusic, MESSAGE_MENU_MUSIC, MenuValue::None);
+				menu.add_item(MenuAction::ChangeMusicVolume, MESSAGE_MENU_MUSIC_VOLUME, MenuValue::None);
+				menu.add_item(MenuAction::ToggleSoundEffects, MESSAGE_MENU_SOUND_EFFECTS, MenuValue::None);
+				menu.add_item(MenuAction::ChangeSoundEffectsVolume, MESSAGE_MENU_SOUND_EFFECTS_VOLUME, MenuValue::None);
+				menu.add_item(MenuAction::ChangeLanguage, MESSAGE_MENU_LANGUAGE, MenuValue::None);
+				menu.add_item(MenuAction::ToggleScreenShake, MESSAGE_MENU_SCREEN_SHAKE, MenuValue::None);
+				menu.add_item(MenuAction::ToggleShowTriggers, MESSAGE_MENU_SHOW_TRIGGERS, MenuValue::None);
+				menu.add_item(MenuAction::ToggleSpeedrunTimer, MESSAGE_MENU_SPEEDRUN_TIMER, MenuValue::None);
+				menu.add_item(MenuAction::ToggleGhost, MESSAGE_MENU_GHOST, MenuValue::None);
+				menu.add_item(MenuAction::OpenAccessibility, MESSAGE_MENU_ACCESSIBILITY, MenuValue::None);
+				menu.add_item(MenuAction::Back, MESSAGE_MENU_BACK, MenuValue::None);
+				menu.refresh_settings(&session.settings);
+			}
+			GameMode::Accessibility => {
+				menu = Menu::new(MESSAGE_MENU_ACCESSIBILITY);
+				menu.add_item(MenuAction::ChangeTextFont, MESSAGE_MENU_TEXT_FONT, MenuValue::None);
+				menu.add_item(MenuAction::ChangeTextSize, MESSAGE_MENU_TEXT_SIZE, MenuValue::None);
+				menu.add_item(MenuAction::ToggleHighContrast, MESSAGE_MENU_HIGH_CONTRAST, MenuValue::None);
+				menu.add_item(MenuAction::ToggleAssistMode, MESSAGE_MENU_ASSIST_MODE, MenuValue::None);
+				menu.add_item(MenuAction::ChangeGameSpeed, MESSAGE_MENU_GAME_SPEED, MenuValue::None);
+				menu.add_item(MenuAction::ChangeExtraJumpFrames, MESSAGE_MENU_EXTRA_JUMP_FRAMES, MenuValue::None);
+				menu.add_item(MenuAction::ToggleInfiniteHearts, MESSAGE_MENU_INFINITE_HEARTS, MenuValue::None);
+				menu.add_item(MenuAction::ToggleHoldToFloat, MESSAGE_MENU_HOLD_TO_FLOAT, MenuValue::None);
+				menu.add_item(MenuAction::Back, MESSAGE_MENU_BACK, MenuValue::None);
+				menu.refresh_settings(&session.settings);
+			}
+			GameMode::GameOver => {
+				menu = Menu::new(MESSAGE_MENU_GAME_OVER);
+				menu.add_item(MenuAction::Retry, MESSAGE_MENU_RETRY, MenuValue::None);
+				menu.add_item(MenuAction::QuitToTitle, MESSAGE_MENU_QUIT_TO_TITLE, MenuValue::None);
+			}
+			// the inventory, the level select and the results draw their own screens (platform::render::inventory,
+			// level_select, results)
+			GameMode::Playing | GameMode::Book | GameMode::Inventory | GameMode::LevelSelect | GameMode::Results | GameMode::LevelTransition => return None,
+		}
+
+		return Some(menu);
+	}
+
+	pub fn add_item(&mut self, action: MenuAction, label_id: u16, value: MenuValue) {
+		self.items.push(MenuItem { action, label_id, value });
+		return;
+	}
+
+	// up/down; wraps around and skips lines that only show a value
+	pub fn move_selection(&mut self, delta: i32) {
+		let count: i32 = self.items.len() as i32;
+		if count == 0 {
+			return;
+		}
+
+		let mut index: i32 = self.selected as i32;
+		for _ in 0..count {
+			index = (index + delta).rem_euclid(count);
+			if self.items[index as usize].action != MenuAction::None {
+				self.selected = index as usize;
+				return;
+			}
+		}
+		return;
+	}
+
+	// lines whose value left/right step through
+	pub fn is_selected_adjustable(&self) -> bool {
+		return matches!(
+			self.get_selected_action(),
+			MenuAction::ToggleMusic
+				| MenuAction::ChangeMusicVolume
+				| MenuAction::ToggleSoundEffects
+				| MenuAction::ChangeSoundEffectsVolume
+				| MenuAction::ChangeLanguage
+				| MenuAction::ToggleScreenShake
+				| MenuAction::ToggleShowTriggers
+				| MenuAction::ToggleSpeedrunTimer
+				| MenuAction::ToggleGhost
+				| MenuAction::ChangeTextFont
+				| MenuAction::ChangeTextSize
+				| MenuAction::ToggleHighContrast
+				| MenuAction::ToggleAssistMode
+				| MenuAction::ChangeGameSpeed
+				| MenuAction::ChangeExtraJumpFrames
+				| MenuAction::ToggleInfiniteHearts
+				| MenuAction::ToggleHoldToFloat
+		);
+	}
+
+	pub fn get_selected_action(&self) -> MenuAction {
+		return match self.items.get(self.selected) {
+			Some(item) => item.action,
+			None => MenuAction::None,
+		};
+	}
+
+	// the settings lines show the current values
+	pub fn refresh_settings(&mut self, settings: &Settings) {
+		for item in self.items.iter_mut() {
+			item.value = match item.action {
+				MenuAction::ToggleMusic => MenuValue::Toggle(settings.is_background_music_enabled),
+				MenuAction::ChangeMusicVolume => MenuValue::Level(settings.music_volume),
+				MenuAction::ToggleSoundEffects => MenuValue::Toggle(settings.are_sound_effects_enabled),
+				MenuAction::ChangeSoundEffectsVolume => MenuValue::Level(settings.sound_effects_volume),
+				MenuAction::ChangeLanguage => MenuValue::Language,
+				MenuAction::ToggleScreenShake => MenuValue::Toggle(settings.is_screen_shake_enabled),
+				MenuAction::ToggleShowTriggers => MenuValue::Toggle(settings.show_triggers),
+				MenuAction::ToggleSpeedrunTimer => MenuValue::Toggle(settings.show_speedrun_timer),
+				MenuAction::ToggleGhost => MenuValue::Toggle(settings.show_ghost),
+				MenuAction::ChangeTextFont => MenuValue::TextFont(settings.accessibility.text_font),
+				MenuAction::ChangeTextSize => MenuValue::TextSize(settings.accessibility.text_size),
+				MenuAction::ToggleHighContrast => MenuValue::Toggle(settings.accessibility.is_high_contrast),
+				MenuAction::ToggleAssistMode => MenuValue::Toggle(settings.accessibility.assist.is_enabled),
+				MenuAction::ChangeGameSpeed => MenuValue::Percent(settings.accessibility.assist.game_speed_percent),
+				MenuAction::ChangeExtraJumpFrames => MenuValue::Count(settings.accessibility.assist.extra_jump_frames as u16),
+				MenuAction::ToggleInfiniteHearts => MenuValue::Toggle(settings.accessibility.assist.has_infinite_hit_points),
+				MenuAction::ToggleHoldToFloat => MenuValue::Toggle(settings.accessibility.assist.can_hold_to_float),
+				_ => item.value,
+			};
+		}
+		return;
+	}
+}
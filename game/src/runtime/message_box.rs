@@ -0,0 +1,163 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{string::String, vec::Vec};
+
+// typewriter speed: one more character every this many ticks
+pub const MESSAGE_TICKS_PER_CHAR: u8 = 2;
+
+// how long a non-blocking message stays up once all of its text is out
+pub const MESSAGE_LINGER_TICKS: u32 = 180;
+
+#[derive(Clone, Debug)]
+pub struct MessageBoxMessage {
+	pub message_id: u16,
+	pub text: String,
+	// icon drawn beside the text; 0 = no portrait
+	pub speaker_icon_id: u16,
+	// pauses play until the player dismisses it
+	pub is_blocking: bool,
+}
+
+// the message on screen plus the ones waiting behind it, oldest first
+pub struct MessageBoxState {
+	pub current: Option<MessageBoxMessage>,
+	pub queue: Vec<MessageBoxMessage>,
+	pub revealed_chars: usize,
+	pub total_chars: usize,
+	// ticks since the current message opened; keeps counting while play is paused
+	pub open_ticks: u32,
+	reveal_ticks: u8,
+	linger_ticks: u32,
+}
+
+impl MessageBoxState {
+	pub fn new() -> MessageBoxState {
+		return MessageBoxState {
+			current: None,
+			queue: Vec::new(),
+			revealed_chars: 0,
+			total_chars: 0,
+			open_ticks: 0,
+			reveal_ticks: 0,
+			linger_ticks: 0,
+		};
+	}
+
+	// a message already showing or waiting is not queued twice
+	pub fn push(&mut self, message: MessageBoxMessage) {
+		if let Some(current) = &self.current {
+			if current.message_id == message.message_id {
+				return;
+			}
+		}
+
+		if self.queue.iter().any(|queued| queued.message_id == message.message_id) {
+			return;
+		}
+
+		self.queue.push(message);
+		if self.current.is_none() {
+			self.open_next();
+		}
+		return;
+	}
+
+	#[inline(always)]
+	pub fn is_open(&self) -> bool {
+		return self.current.is_some();
+	}
+
+	pub fn is_blocking(&self) -> bool {
+		return match &self.current {
+			Some(message) => message.is_blocking,
+			None => false,
+		};
+	}
+
+	#[inline(always)]
+	pub fn is_fully_revealed(&self) -> bool {
+		return self.revealed_chars >= self.total_chars;
+	}
+
+	// once per simulation step (and per frame while a blocking message holds the simulation)
+	pub fn tick(&mut self) {
+		let Some(message) = &self.current else {
+			return;
+		};
+
+		self.open_ticks = self.open_ticks.wrapping_add(1);
+		if !self.is_fully_revealed() {
+			self.reveal_ticks += 1;
+			if self.reveal_ticks >= MESSAGE_TICKS_PER_CHAR {
+				self.reveal_ticks = 0;
+				self.revealed_chars += 1;
+			}
+			return;
+		}
+
+		if message.is_blocking {
+			return;
+		}
+
+		self.linger_ticks += 1;
+		if self.linger_ticks >= MESSAGE_LINGER_TICKS {
+			self.open_next();
+		}
+		return;
+	}
+
+	// the advance button: finish typing the text, or close the message if it is already out
+	pub fn advance(&mut self) {
+		if self.current.is_none() {
+			return;
+		}
+
+		if !self.is_fully_revealed() {
+			self.revealed_chars = self.total_chars;
+			return;
+		}
+
+		self.open_next();
+		return;
+	}
+
+	// the dismiss button: close the message whether or not it is all out
+	pub fn dismiss(&mut self) {
+		if self.current.is_none() {
+			return;
+		}
+
+		self.open_next();
+		return;
+	}
+
+	// a new level starts with no messages
+	pub fn clear(&mut self) {
+		self.queue.clear();
+		self.current = None;
+		self.revealed_chars = 0;
+		self.total_chars = 0;
+		return;
+	}
+
+	// replaces the current message with the oldest queued one, or closes the box
+	fn open_next(&mut self) {
+		self.open_ticks = 0;
+		self.reveal_ticks = 0;
+		self.linger_ticks = 0;
+		self.revealed_chars = 0;
+
+		if self.queue.is_empty() {
+			self.current = None;
+			self.total_chars = 0;
+			return;
+		}
+
+		let message: MessageBoxMessage = self.queue.remove(0);
+		self.total_chars = message.text.chars().count();
+		self.current = Some(message);
+		return;
+	}
+}
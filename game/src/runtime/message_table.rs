@@ -11,6 +11,8 @@ use std::{fs, path::PathBuf};
 
 pub struct MessageTable {
 	by_id: Vec<String>,
+	// messages.ids.txt, for text looked up by name rather than by a compiled-in id
+	names: Vec<(String, u16)>,
 }
 
 impl MessageTable {
@@ -18,7 +20,20 @@ impl MessageTable {
 	pub fn load(language_code: &str) -> Result<MessageTable, String> {
 		let path: PathBuf = get_messages_root().join(format!("messages.{}.txt", language_code));
 		let text: String = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
-		return MessageTable::parse(text.as_str());
+		let mut table: MessageTable = MessageTable::parse(text.as_str())?;
+
+		// without the names the table still works, only get_by_name comes up empty
+		let ids_path: PathBuf = get_messages_root().join("messages.ids.txt");
+		if let Ok(ids_text) = fs::read_to_string(&ids_path) {
+			let ids: MessageTable = MessageTable::parse(ids_text.as_str()).map_err(|e| format!("{}: {}", ids_path.display(), e))?;
+			for (id, name) in ids.by_id.iter().enumerate() {
+				if !name.is_empty() {
+					table.names.push((name.clone(), id as u16));
+				}
+			}
+		}
+
+		return Ok(table);
 	}
 
 	#[cfg(feature = "gba")]
@@ -26,6 +41,35 @@ impl MessageTable {
 		return Err(String::from("message table loading not implemented on gba yet"));
 	}
 
+	// the language codes with a messages.<code>.txt next to the id list, sorted
+	#[cfg(feature = "pc")]
+	pub fn get_available_languages() -> Vec<String> {
+		let mut languages: Vec<String> = Vec::new();
+		let Ok(entries) = fs::read_dir(get_messages_root()) else {
+			return languages;
+		};
+
+		for entry in entries.flatten() {
+			let file_name: String = entry.file_name().to_string_lossy().into_owned();
+			let Some(code) = file_name.strip_prefix("messages.").and_then(|rest| rest.strip_suffix(".txt")) else {
+				continue;
+			};
+			if code != "ids" {
+				languages.push(String::from(code));
+			}
+		}
+
+		languages.sort();
+		return languages;
+	}
+
+	#[cfg(feature = "gba")]
+	pub fn get_available_languages() -> Vec<String> {
+		let mut languages: Vec<String> = Vec::new();
+		languages.push(String::from("en-us"));
+		return languages;
+	}
+
 	pub fn get(&self, id: u16) -> &str {
 		let index: usize = id as usize;
 		if index >= self.by_id.len() {
@@ -34,6 +78,16 @@ impl MessageTable {
 		return self.by_id[index].as_str();
 	}
 
+	// None when the name isn't in messages.ids.txt or this language has no text for it
+	pub fn get_by_name(&self, name: &str) -> Option<&str> {
+		let (_, id) = self.names.iter().find(|(n, _)| n == name)?;
+		let text: &str = self.get(*id);
+		if text.is_empty() {
+			return None;
+		}
+		return Some(text);
+	}
+
 	fn parse(text: &str) -> Result<MessageTable, String> {
 		let mut max_id: u16 = 0;
 
@@ -75,6 +129,6 @@ impl MessageTable {
 			by_id[id as usize] = String::from(value);
 		}
 
-		return Ok(MessageTable { by_id });
+		return Ok(MessageTable { by_id, names: Vec::new() });
 	}
 }
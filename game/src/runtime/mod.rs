@@ -1,12 +1,29 @@
+pub mod accessibility;
+pub mod animation;
 pub mod assets;
 pub mod book;
+pub mod camera;
+pub mod door;
+pub mod game_mode;
+pub mod hud;
 pub mod inventory;
 pub mod level;
+pub mod menu;
+pub mod message_box;
 pub mod message_table;
 pub mod music;
+pub mod pickup_map;
+pub mod play_step;
+pub mod player_physics;
+pub mod progress;
+pub mod scoring;
 pub mod session;
 pub mod settings;
+pub mod speedrun;
 pub mod state;
+pub mod transition;
 pub mod triggers;
+pub mod world_index;
+pub mod world_list;
 
 pub use settings::Settings;
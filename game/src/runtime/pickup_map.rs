@@ -0,0 +1,96 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{format, string::String, vec::Vec};
+
+#[cfg(feature = "pc")]
+use crate::runtime::assets::get_pickup_map_path;
+
+#[cfg(feature = "pc")]
+use std::{fs, path::PathBuf};
+
+// the prefixes in front of book and key names in pickups.ids.txt
+pub const PICKUP_MAP_BOOK_PREFIX: &str = "book";
+pub const PICKUP_MAP_KEY_PREFIX: &str = "key";
+
+#[derive(Clone, Debug)]
+pub struct PickupMapEntry {
+	// "book:tom_sawyer", "key:w01:l01"
+	pub name: String,
+	pub id: u16,
+}
+
+// assets/pickup-maps/pickups.ids.txt, the names levelc compiles pickups against, so the game can
+// turn a book or key id back into something to show
+#[derive(Clone, Debug)]
+pub struct PickupMap {
+	entries: Vec<PickupMapEntry>,
+}
+
+impl PickupMap {
+	pub fn empty() -> PickupMap {
+		return PickupMap { entries: Vec::new() };
+	}
+
+	#[cfg(feature = "pc")]
+	pub fn load() -> Result<PickupMap, String> {
+		let path: PathBuf = get_pickup_map_path();
+		let text: String = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+		return PickupMap::parse(text.as_str()).map_err(|e| format!("{}: {}", path.display(), e));
+	}
+
+	#[cfg(not(feature = "pc"))]
+	pub fn load() -> Result<PickupMap, String> {
+		return Err(String::from("pickup map loading not implemented on this platform yet"));
+	}
+
+	// the name after "<prefix>:" for an id; "tom_sawyer" for book 100
+	pub fn get_slug(&self, prefix: &str, id: u16) -> Option<&str> {
+		for entry in &self.entries {
+			if entry.id != id {
+				continue;
+			}
+			let Some(slug) = entry.name.strip_prefix(prefix).and_then(|rest| rest.strip_prefix(':')) else {
+				continue;
+			};
+			return Some(slug);
+		}
+		return None;
+	}
+
+	pub fn get_id(&self, prefix: &str, slug: &str) -> Option<u16> {
+		return self
+			.entries
+			.iter()
+			.find(|entry| entry.name.strip_prefix(prefix).and_then(|rest| rest.strip_prefix(':')) == Some(slug))
+			.map(|entry| entry.id);
+	}
+
+	// `name = id` lines, `#` comments; the same format levelc reads
+	pub fn parse(text: &str) -> Result<PickupMap, String> {
+		let mut entries: Vec<PickupMapEntry> = Vec::new();
+
+		for (line_index, raw_line) in text.lines().enumerate() {
+			let line_number: usize = line_index + 1;
+			let line: &str = raw_line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let Some(eq_index) = line.find('=') else {
+				return Err(format!("invalid line {}: missing '='", line_number));
+			};
+			let name: &str = line[..eq_index].trim();
+			let value: &str = line[eq_index + 1..].trim();
+			if name.is_empty() {
+				return Err(format!("missing name at line {}", line_number));
+			}
+
+			let id: u16 = value.parse::<u16>().map_err(|_| format!("invalid id at line {}", line_number))?;
+			entries.push(PickupMapEntry { name: String::from(name), id });
+		}
+
+		return Ok(PickupMap { entries });
+	}
+}
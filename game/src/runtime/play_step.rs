@@ -0,0 +1,107 @@
+use crate::{
+	physics,
+	platform::input::TriggerPresses,
+	runtime::{animation::animator, camera, door, player_physics, session::Session, state::State, triggers},
+};
+
+// the buttons held during a step; the player's JumpState turns jump into presses and releases
+#[derive(Clone, Copy, Default)]
+pub struct PlayInput {
+	pub left: bool,
+	pub right: bool,
+	pub jump: bool,
+}
+
+// one fixed step of play: input, triggers, physics, animation, the camera and the speedrun clock.
+// the window's game loop and headless screenshots both run exactly this, so golden images show
+// what the game does
+pub fn run_play_step(session: &mut Session, state: &mut State, input: PlayInput, presses: TriggerPresses) {
+	// a blocking message holds everything but itself until it's dismissed
+	if session.message_box.is_blocking() {
+		session.message_box.tick();
+		return;
+	}
+
+	state.store_previous_positions();
+
+	let Some(player_id) = state.try_get_player_id() else {
+		// no player yet; still tick/render so you can see what's going on
+		state.tick = state.tick.wrapping_add(1);
+		return;
+	};
+
+	// left/right movement (held)
+	let desired_x: f32 = if input.left && !input.right {
+		-player_physics::MOVE_SPEED
+	} else if input.right && !input.left {
+		player_physics::MOVE_SPEED
+	} else {
+		0.0
+	};
+
+	if let Some(velocity) = state.velocities.get_mut(player_id) {
+		velocity.set_x(desired_x);
+	}
+
+	// --- edge detection ---
+	let mut jump_pressed: bool = false;
+	let mut jump_released: bool = false;
+
+	if let Some(js) = state.jump_states.get_mut(player_id) {
+		jump_pressed = input.jump && !js.jump_was_down;
+		jump_released = !input.jump && js.jump_was_down;
+		js.jump_was_down = input.jump;
+	}
+
+	// --- triggers run before gameplay consumes jump ---
+	let mut jump_consumed_by_triggers: bool = false;
+
+	if triggers::handle_message_triggers(session, state, presses) {
+		jump_consumed_by_triggers = true;
+	}
+
+	triggers::handle_level_exit_triggers(session, state, presses);
+
+	if triggers::handle_pickup_triggers(session, state, presses) {
+		jump_consumed_by_triggers = true;
+	}
+
+	if door::handle_door_triggers(session, state, presses) {
+		jump_consumed_by_triggers = true;
+	}
+
+	// --- gameplay jump logic (only if not consumed) ---
+	if jump_pressed && !jump_consumed_by_triggers {
+		if let Some(jump_state) = state.jump_states.get_mut(player_id) {
+			jump_state.jump_buffer_frames_left = session.settings.get_jump_buffer_frames_max();
+		}
+	}
+
+	if jump_released {
+		if let Some(velocity) = state.velocities.get_mut(player_id) {
+			if velocity.y < 0.0 {
+				velocity.y *= session.settings.jump_cut_multiplier;
+			}
+		}
+		if let Some(jump_state) = state.jump_states.get_mut(player_id) {
+			jump_state.jump_buffer_frames_left = 0;
+		}
+	}
+
+	state.tick = state.tick.wrapping_add(1);
+
+	physics::movement::patrol(state);
+	physics::gravity::apply(state, session);
+	physics::movement::move_and_collide(state, session);
+
+	state.tick_enemy_deaths();
+	door::tick_opening_doors(state, &session.settings);
+	state.hud.tick(state.tick);
+	session.message_box.tick();
+	animator::tick_animators(state, &session.animations);
+	camera::update_camera(state, session);
+
+	let is_assisted: bool = session.settings.accessibility.assist.is_enabled;
+	session.speedrun.step(state, is_assisted);
+	return;
+}
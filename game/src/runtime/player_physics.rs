@@ -0,0 +1,12 @@
+// the player's movement tuning; level_compiler's analyze includes this file so its reachability
+// search jumps the way the game does
+pub const GRAVITY: f32 = 0.35;
+pub const JUMP_VELOCITY: f32 = -6.0;
+pub const JUMP_CUT_MULTIPLIER: f32 = 0.4;
+pub const COYOTE_FRAMES_MAX: u8 = 15;
+pub const JUMP_BUFFER_FRAMES_MAX: u8 = 6;
+pub const MOVE_SPEED: f32 = 2.0;
+// horizontal speed a jump off a wall pushes the player away with
+pub const WALL_JUMP_PUSH: f32 = 2.5;
+// the most coyote and jump buffer frames assist mode may add
+pub const ASSIST_EXTRA_JUMP_FRAMES_MAX: u8 = 12;
@@ -0,0 +1,317 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{format, string::String, vec::Vec};
+
+#[cfg(feature = "pc")]
+use crate::{debugln, runtime::assets::get_progress_path};
+
+#[cfg(feature = "pc")]
+use std::{fs, path::PathBuf};
+
+use crate::runtime::{
+	book::Book,
+	inventory::{Inventory, Key},
+	speedrun::LevelTime,
+};
+
+use core::fmt::Write;
+
+// a level by its place in worlds/, "../worlds/01/02.lvlb" is world 1 level 2
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LevelKey {
+	pub world_id: u16,
+	pub level_id: u16,
+}
+
+impl LevelKey {
+	// None for a level that doesn't sit in a WW/LL.lvlb folder, which then isn't tracked
+	pub fn from_level_name(level_name: &str) -> Option<LevelKey> {
+		let mut parts = level_name.rsplit(['/', '\\']);
+		let file_name: &str = parts.next()?;
+		let world_name: &str = parts.next()?;
+		let level_stem: &str = file_name.strip_suffix(".lvlb").unwrap_or(file_name);
+
+		let world_id: u16 = world_name.parse::<u16>().ok()?;
+		let level_id: u16 = level_stem.parse::<u16>().ok()?;
+		return Some(LevelKey { world_id, level_id });
+	}
+
+	// "01/02", as the progress file and the world index write it
+	pub fn parse(text: &str) -> Option<LevelKey> {
+		let (world_name, level_name) = text.trim().split_once('/')?;
+		let world_id: u16 = world_name.trim().parse::<u16>().ok()?;
+		let level_id: u16 = level_name.trim().parse::<u16>().ok()?;
+		return Some(LevelKey { world_id, level_id });
+	}
+
+	// the path the level loaders take, relative to the game folder like the --level default
+	pub fn get_level_name(&self) -> String {
+		return format!("../worlds/{:02}/{:02}.lvlb", self.world_id, self.level_id);
+	}
+}
+
+// the triggers a level has used up, by trigger id
+#[derive(Clone, Debug)]
+pub struct LevelProgress {
+	pub key: LevelKey,
+	// how many pickups the level has, counted each time it loads
+	pub pickup_count: u16,
+	pub collected_pickups: Vec<u16>,
+	pub opened_doors: Vec<u16>,
+	// left through one of its exits at least once
+	pub is_completed: bool,
+	// the fastest run by in-game time, without assist mode
+	pub best_time: Option<LevelTime>,
+}
+
+impl LevelProgress {
+	fn new(key: LevelKey) -> LevelProgress {
+		return LevelProgress {
+			key,
+			pickup_count: 0,
+			collected_pickups: Vec::new(),
+			opened_doors: Vec::new(),
+			is_completed: false,
+			best_time: None,
+		};
+	}
+
+	// collected pickups out of all of them; a level without pickups is done
+	pub fn get_completion_percent(&self) -> u8 {
+		if self.pickup_count == 0 {
+			return 100;
+		}
+		let collected: u32 = (self.collected_pickups.len() as u32).min(self.pickup_count as u32);
+		return (collected * 100 / self.pickup_count as u32) as u8;
+	}
+}
+
+// every level the player has been to, so pickups stay collected and doors stay open when a level
+// is entered again, and so the pause menu can show how much of a level is done
+#[derive(Clone, Debug)]
+pub struct Progress {
+	pub levels: Vec<LevelProgress>,
+}
+
+impl Progress {
+	pub fn new() -> Progress {
+		return Progress { levels: Vec::new() };
+	}
+
+	pub fn get_level(&self, key: LevelKey) -> Option<&LevelProgress> {
+		return self.levels.iter().find(|l| l.key == key);
+	}
+
+	fn get_level_mut(&mut self, key: LevelKey) -> &mut LevelProgress {
+		if let Some(index) = self.levels.iter().position(|l| l.key == key) {
+			return &mut self.levels[index];
+		}
+		self.levels.push(LevelProgress::new(key));
+		let last_index: usize = self.levels.len() - 1;
+		return &mut self.levels[last_index];
+	}
+
+	pub fn set_pickup_count(&mut self, key: LevelKey, pickup_count: u16) {
+		self.get_level_mut(key).pickup_count = pickup_count;
+		return;
+	}
+
+	pub fn collect_pickup(&mut self, key: LevelKey, trigger_id: u16) {
+		let level: &mut LevelProgress = self.get_level_mut(key);
+		if !level.collected_pickups.contains(&trigger_id) {
+			level.collected_pickups.push(trigger_id);
+		}
+		return;
+	}
+
+	pub fn open_door(&mut self, key: LevelKey, trigger_id: u16) {
+		let level: &mut LevelProgress = self.get_level_mut(key);
+		if !level.opened_doors.contains(&trigger_id) {
+			level.opened_doors.push(trigger_id);
+		}
+		return;
+	}
+
+	pub fn complete_level(&mut self, key: LevelKey) {
+		self.get_level_mut(key).is_completed = true;
+		return;
+	}
+
+	pub fn set_best_time(&mut self, key: LevelKey, time: LevelTime) {
+		self.get_level_mut(key).best_time = Some(time);
+		return;
+	}
+
+	pub fn is_level_completed(&self, key: LevelKey) -> bool {
+		return self.get_level(key).is_some_and(|l| l.is_completed);
+	}
+
+	pub fn get_completion_percent(&self, key: LevelKey) -> u8 {
+		return match self.get_level(key) {
+			Some(level) => level.get_completion_percent(),
+			None => 0,
+		};
+	}
+
+	// the saved progress with its inventory; a missing or broken file starts over
+	#[cfg(feature = "pc")]
+	pub fn load(inventory: &mut Inventory) -> Progress {
+		let path: PathBuf = get_progress_path();
+		let Ok(text) = fs::read_to_string(&path) else {
+			return Progress::new();
+		};
+		return Progress::parse(text.as_str(), inventory).unwrap_or_else(|e| {
+			debugln!("{}: {}", path.display(), e);
+			*inventory = Inventory::new();
+			return Progress::new();
+		});
+	}
+
+	#[cfg(feature = "pc")]
+	pub fn save(&self, inventory: &Inventory) -> Result<(), String> {
+		let path: PathBuf = get_progress_path();
+		return fs::write(&path, self.to_text(inventory)).map_err(|e| format!("{}: {}", path.display(), e));
+	}
+
+	// `key = value` lines, `#` comments:
+	//   coins = 12
+	//   key = 300, used
+	//   book = 100, 12, 200            (id, page, total pages)
+	//   level = 01/02, 5, 2 7 9, 11, completed
+	//     (world/level, pickup count, collected pickups, opened doors, then "completed" once exited)
+	//   best = 01/02, 1830, 31250      (world/level, in-game time in steps, real time in milliseconds)
+	pub fn parse(text: &str, inventory: &mut Inventory) -> Result<Progress, String> {
+		let mut progress: Progress = Progress::new();
+		*inventory = Inventory::new();
+
+		for (line_index, raw_line) in text.lines().enumerate() {
+			let line_number: usize = line_index + 1;
+			let line: &str = raw_line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let Some(eq_index) = line.find('=') else {
+				return Err(format!("invalid line {}: missing '='", line_number));
+			};
+			let name: &str = line[..eq_index].trim();
+			let value: &str = line[eq_index + 1..].trim();
+			let fields: Vec<&str> = value.split(',').map(|f| f.trim()).collect();
+
+			match name {
+				"coins" => inventory.coins = parse_u16(value, line_number)?,
+				"key" => {
+					let key_id: u16 = parse_u16(fields[0], line_number)?;
+					let is_used: bool = fields.get(1) == Some(&"used");
+					inventory.keys.push(Key { key_id, is_used });
+				}
+				"book" => {
+					if fields.len() != 3 {
+						return Err(format!("expected id, page, total pages at line {}", line_number));
+					}
+					inventory.books.push(Book {
+						book_id: parse_u16(fields[0], line_number)?,
+						current_page: parse_u16(fields[1], line_number)?,
+						total_pages: parse_u16(fields[2], line_number)?,
+					});
+				}
+				"level" => {
+					if fields.len() < 4 {
+						return Err(format!("expected world/level, pickup count, pickups, doors at line {}", line_number));
+					}
+					let Some(level_key) = LevelKey::parse(fields[0]) else {
+						return Err(format!("expected world/level at line {}", line_number));
+					};
+					let pickup_count: u16 = parse_u16(fields[1], line_number)?;
+					let collected_pickups: Vec<u16> = parse_id_list(fields[2], line_number)?;
+					let opened_doors: Vec<u16> = parse_id_list(fields[3], line_number)?;
+
+					// a "best" line may have come first and made the entry already
+					let level: &mut LevelProgress = progress.get_level_mut(level_key);
+					level.pickup_count = pickup_count;
+					level.collected_pickups = collected_pickups;
+					level.opened_doors = opened_doors;
+					level.is_completed = fields.get(4) == Some(&"completed");
+				}
+				"best" => {
+					if fields.len() != 3 {
+						return Err(format!("expected world/level, steps, milliseconds at line {}", line_number));
+					}
+					let Some(level_key) = LevelKey::parse(fields[0]) else {
+						return Err(format!("expected world/level at line {}", line_number));
+					};
+					let time: LevelTime = LevelTime {
+						igt_steps: parse_u32(fields[1], line_number)?,
+						rta_millis: parse_u32(fields[2], line_number)?,
+					};
+					progress.set_best_time(level_key, time);
+				}
+				_ => {}
+			}
+		}
+
+		return Ok(progress);
+	}
+
+	pub fn to_text(&self, inventory: &Inventory) -> String {
+		let mut text: String = String::new();
+		let _ = writeln!(text, "# jumpy progress; rewritten on every level change");
+		let _ = writeln!(text, "coins = {}", inventory.coins);
+		for key in &inventory.keys {
+			let _ = writeln!(text, "key = {}{}", key.key_id, if key.is_used { ", used" } else { "" });
+		}
+		for book in &inventory.books {
+			let _ = writeln!(text, "book = {}, {}, {}", book.book_id, book.current_page, book.total_pages);
+		}
+
+		for level in &self.levels {
+			let _ = write!(text, "level = {:02}/{:02}, {}, ", level.key.world_id, level.key.level_id, level.pickup_count);
+			write_id_list(&mut text, &level.collected_pickups);
+			let _ = write!(text, ", ");
+			write_id_list(&mut text, &level.opened_doors);
+			if level.is_completed {
+				let _ = write!(text, ", completed");
+			}
+			let _ = writeln!(text);
+		}
+		for level in &self.levels {
+			if let Some(best_time) = level.best_time {
+				let _ = writeln!(
+					text,
+					"best = {:02}/{:02}, {}, {}",
+					level.key.world_id, level.key.level_id, best_time.igt_steps, best_time.rta_millis
+				);
+			}
+		}
+		return text;
+	}
+}
+
+fn parse_u16(value: &str, line_number: usize) -> Result<u16, String> {
+	return value.trim().parse::<u16>().map_err(|_| format!("invalid number '{}' at line {}", value, line_number));
+}
+
+fn parse_u32(value: &str, line_number: usize) -> Result<u32, String> {
+	return value.trim().parse::<u32>().map_err(|_| format!("invalid number '{}' at line {}", value, line_number));
+}
+
+// space separated trigger ids, possibly none
+fn parse_id_list(value: &str, line_number: usize) -> Result<Vec<u16>, String> {
+	let mut ids: Vec<u16> = Vec::new();
+	for part in value.split_whitespace() {
+		ids.push(parse_u16(part, line_number)?);
+	}
+	return Ok(ids);
+}
+
+fn write_id_list(text: &mut String, ids: &[u16]) {
+	for (index, id) in ids.iter().enumerate() {
+		if index > 0 {
+			text.push(' ');
+		}
+		let _ = write!(text, "{}", id);
+	}
+	return;
+}
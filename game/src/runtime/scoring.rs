@@ -0,0 +1,143 @@
+use crate::runtime::speedrun::{IGT_STEPS_PER_SECOND, LevelTime};
+
+// every coin picked up is worth this much, so a pickup of 5 coins is worth five times as much
+pub const POINTS_PER_COIN: u32 = 10;
+
+// a stomp kill is worth this much times the stomp chain it was made in, so the third enemy
+// stomped without touching the ground is worth three times the first
+pub const POINTS_PER_STOMP: u32 = 50;
+
+// leaving a level at once is worth this much, less TIME_POINTS_PER_SECOND for every second of
+// in-game time; a slow run gets nothing for time rather than losing points
+pub const TIME_BONUS_POINTS: u32 = 3000;
+pub const TIME_POINTS_PER_SECOND: u32 = 10;
+
+// taken off the total for every death
+pub const POINTS_PER_DEATH: u32 = 100;
+
+// the score ranks s, a, b and c need when the level's header doesn't set its own
+pub const DEFAULT_RANK_THRESHOLDS: [u32; 4] = [3000, 2200, 1500, 800];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rank {
+	S,
+	A,
+	B,
+	C,
+	D,
+}
+
+impl Rank {
+	pub fn get_letter(&self) -> char {
+		return match self {
+			Rank::S => 'S',
+			Rank::A => 'A',
+			Rank::B => 'B',
+			Rank::C => 'C',
+			Rank::D => 'D',
+		};
+	}
+
+	// the best rank whose threshold the score reaches; a 0 in the level's thresholds uses the default
+	pub fn from_score(score: u32, level_thresholds: [u32; 4]) -> Rank {
+		const RANKS: [Rank; 4] = [Rank::S, Rank::A, Rank::B, Rank::C];
+
+		for (index, rank) in RANKS.iter().enumerate() {
+			let threshold: u32 = if level_thresholds[index] == 0 {
+				DEFAULT_RANK_THRESHOLDS[index]
+			} else {
+				level_thresholds[index]
+			};
+			if score >= threshold {
+				return *rank;
+			}
+		}
+		return Rank::D;
+	}
+}
+
+// what the player did in the level so far; lives in the State so a restart starts it over
+#[derive(Copy, Clone, Debug)]
+pub struct ScoreTally {
+	pub coins: u32,
+	pub stomps: u16,
+	// the stomp points already multiplied by their chains
+	pub stomp_points: u32,
+	pub best_stomp_chain: u16,
+	pub deaths: u16,
+}
+
+impl ScoreTally {
+	pub fn new() -> ScoreTally {
+		return ScoreTally {
+			coins: 0,
+			stomps: 0,
+			stomp_points: 0,
+			best_stomp_chain: 0,
+			deaths: 0,
+		};
+	}
+
+	pub fn add_coins(&mut self, value: u16) {
+		self.coins = self.coins.saturating_add(value as u32);
+		return;
+	}
+
+	// chain is the stomp count since the player last stood on the ground, this stomp included
+	pub fn add_stomp(&mut self, chain: u16) {
+		let chain: u16 = chain.max(1);
+		self.stomps = self.stomps.saturating_add(1);
+		self.stomp_points = self.stomp_points.saturating_add(POINTS_PER_STOMP * chain as u32);
+		self.best_stomp_chain = self.best_stomp_chain.max(chain);
+		return;
+	}
+
+	pub fn add_death(&mut self) {
+		self.deaths = self.deaths.saturating_add(1);
+		return;
+	}
+}
+
+// the breakdown on the results screen once a level exit fires
+#[derive(Copy, Clone, Debug)]
+pub struct LevelResults {
+	pub coins: u32,
+	pub coin_points: u32,
+	pub stomps: u16,
+	pub best_stomp_chain: u16,
+	pub stomp_points: u32,
+	pub time: LevelTime,
+	pub time_points: u32,
+	pub deaths: u16,
+	// taken off the others; the total never goes below 0
+	pub death_points: u32,
+	pub total: u32,
+	pub rank: Rank,
+}
+
+impl LevelResults {
+	pub fn tally(score: &ScoreTally, time: LevelTime, rank_thresholds: [u32; 4]) -> LevelResults {
+		let coin_points: u32 = score.coins.saturating_mul(POINTS_PER_COIN);
+		let seconds: u32 = time.igt_steps / IGT_STEPS_PER_SECOND;
+		let time_points: u32 = TIME_BONUS_POINTS.saturating_sub(seconds.saturating_mul(TIME_POINTS_PER_SECOND));
+		let death_points: u32 = (score.deaths as u32) * POINTS_PER_DEATH;
+		let total: u32 = coin_points
+			.saturating_add(score.stomp_points)
+			.saturating_add(time_points)
+			.saturating_sub(death_points);
+
+		return LevelResults {
+			coins: score.coins,
+			coin_points,
+			stomps: score.stomps,
+			best_stomp_chain: score.best_stomp_chain,
+			stomp_points: score.stomp_points,
+			time,
+			time_points,
+			deaths: score.deaths,
+			death_points,
+			total,
+			rank: Rank::from_score(total, rank_thresholds),
+		};
+	}
+}
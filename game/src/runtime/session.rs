@@ -2,22 +2,36 @@
 extern crate alloc;
 
 #[cfg(feature = "gba")]
-use alloc::string::String;
+use alloc::{format, string::String};
 
 use crate::{
 	State, debugln,
 	runtime::{
 		Settings,
+		animation::clip::AnimationLibrary,
 		book::{ActiveBookReader, ActiveBookTextSource, reading_state::BookReadingState},
-		inventory::Inventory,
+		door::restore_opened_doors,
+		game_mode::{GameMode, GameModeStack},
+		inventory::{Inventory, InventoryCursor},
 		level::Level,
+		message_box::{MessageBoxMessage, MessageBoxState},
 		message_table::MessageTable,
 		music::MusicId,
+		pickup_map::PickupMap,
+		progress::{LevelKey, Progress},
+		scoring::LevelResults,
+		speedrun::SpeedrunState,
+		transition::{MESSAGE_LEVEL_LOAD_FAILED, TransitionState},
+		triggers::TriggerKind,
+		world_index::{LevelSelectCursor, WorldIndex},
 	},
 };
 
 pub const MAX_PLAYERS: usize = 4;
 
+// a fresh player's hit points; the hud draws this many hearts
+pub const PLAYER_MAX_HIT_POINTS: u16 = 5;
+
 #[derive(Clone, Debug)]
 pub struct PlayerPersistentState {
 	pub hit_points: u16,
@@ -25,7 +39,9 @@ pub struct PlayerPersistentState {
 
 impl PlayerPersistentState {
 	pub fn new_default() -> PlayerPersistentState {
-		return PlayerPersistentState { hit_points: 5 };
+		return PlayerPersistentState {
+			hit_points: PLAYER_MAX_HIT_POINTS,
+		};
 	}
 }
 
@@ -35,25 +51,63 @@ pub struct Session {
 	pub pending_level_name: Option<String>,
 	pub settings: Settings,
 	pub inventory: Inventory,
+	pub inventory_cursor: InventoryCursor,
+	// names for the book and key ids in the inventory
+	pub pickup_map: PickupMap,
+	// collected pickups and opened doors of every level visited, saved with the inventory
+	pub progress: Progress,
+	// worlds/worlds.txt: level names, their order and what unlocks them
+	pub world_index: WorldIndex,
+	pub level_select_cursor: LevelSelectCursor,
 	#[allow(dead_code)]
 	pub random_state_u32: u32,
 	pub random_state_u16: u16,
 	pub message_table: MessageTable,
+	pub message_box: MessageBoxState,
 	pub book_reader: ActiveBookReader,
 	pub book_reading: BookReadingState,
 	pub active_music_id: MusicId,
+	pub animations: AnimationLibrary,
+	pub modes: GameModeStack,
+	// the screen closing and opening around a level change, while the LevelTransition mode is up
+	pub transition: Option<TransitionState>,
+	// the timer, splits and ghost of the level being played
+	pub speedrun: SpeedrunState,
+	// the level just left, while the Results mode shows it
+	pub results: Option<LevelResults>,
+	// set by the quit menu item; the main loop exits once it sees it in a snapshot
+	pub is_quit_requested: bool,
 }
 
 impl Session {
 	pub fn new() -> Session {
+		return Session::with_settings(Settings::new());
+	}
+
+	// the player's saved settings rather than the defaults; the message table follows their language
+	pub fn with_settings(settings: Settings) -> Session {
 		const LINES_PER_PAGE: usize = 25;
 
-		let settings: Settings = Settings::new();
 		let message_table: MessageTable = MessageTable::load(settings.language_code.as_str()).unwrap_or_else(|e| {
 			debugln!("message table load failed: {}", e);
 			return MessageTable::load("en-us").unwrap();
 		});
 
+		let animations: AnimationLibrary = AnimationLibrary::load().unwrap_or_else(|e| {
+			debugln!("animation load failed: {}", e);
+			return AnimationLibrary::empty();
+		});
+
+		let pickup_map: PickupMap = PickupMap::load().unwrap_or_else(|e| {
+			debugln!("pickup map load failed: {}", e);
+			return PickupMap::empty();
+		});
+
+		let world_index: WorldIndex = WorldIndex::load().unwrap_or_else(|e| {
+			debugln!("world index load failed: {}", e);
+			return WorldIndex::empty();
+		});
+
 		return Session {
 			players: [
 				PlayerPersistentState::new_default(),
@@ -65,15 +119,36 @@ impl Session {
 			pending_level_name: None,
 			settings: settings,
 			inventory: Inventory::new(),
+			inventory_cursor: InventoryCursor::new(),
+			pickup_map,
+			progress: Progress::new(),
+			world_index,
+			level_select_cursor: LevelSelectCursor::new(),
 			random_state_u32: 0x1234_5678,
 			random_state_u16: 0xACE1,
 			message_table,
+			message_box: MessageBoxState::new(),
 			book_reader: ActiveBookReader::new(ActiveBookTextSource::new(), LINES_PER_PAGE),
 			book_reading: BookReadingState::closed(),
 			active_music_id: MusicId::None,
+			animations,
+			modes: GameModeStack::new(GameMode::Playing),
+			transition: None,
+			speedrun: SpeedrunState::new(),
+			results: None,
+			is_quit_requested: false,
 		};
 	}
 
+	// progress.txt and the ghost of any record it holds are written together, so a best time is
+	// never saved without its run
+	#[cfg(feature = "pc")]
+	pub fn save_progress(&mut self) -> Result<(), String> {
+		let ghost_result: Result<(), String> = self.speedrun.save_new_best_ghost();
+		self.progress.save(&self.inventory)?;
+		return ghost_result;
+	}
+
 	pub fn transition_to_level<FLoad>(&mut self, state: &mut State, level_name: &str, load_level: FLoad) -> bool
 	where
 		FLoad: Fn(&str) -> Result<Level, String>,
@@ -82,14 +157,63 @@ impl Session {
 		state.save_player_to_persistent(self);
 
 		// 2) load next level (pc now; gba later via assets wrapper)
+		if !self.load_into_state(state, level_name, load_level) {
+			return false;
+		}
+
+		// self.current_level_name = Some(level_name.to_string());
+		self.current_level_name = Some(String::from(level_name));
+		return true;
+	}
+
+	// a level read ahead by the transition's preload; a failed load leaves the player where they
+	// were, with the error on screen
+	pub fn apply_loaded_level(&mut self, state: &mut State, level_name: &str, loaded: Result<Level, String>, is_restart: bool) -> bool {
+		let next_level: Level = match loaded {
+			Ok(l) => l,
+			Err(e) => {
+				self.report_level_load_failure(&e);
+				return false;
+			}
+		};
+
+		if !is_restart {
+			state.save_player_to_persistent(self);
+		}
+		self.install_level(state, level_name, next_level);
+		self.current_level_name = Some(String::from(level_name));
+		return true;
+	}
+
+	fn load_into_state<FLoad>(&mut self, state: &mut State, level_name: &str, load_level: FLoad) -> bool
+	where
+		FLoad: Fn(&str) -> Result<Level, String>,
+	{
 		let next_level: Level = match load_level(level_name) {
 			Ok(l) => l,
 			Err(e) => {
-				debugln!("level load failed: {}", e);
+				self.report_level_load_failure(&e);
 				return false;
 			}
 		};
 
+		self.install_level(state, level_name, next_level);
+		return true;
+	}
+
+	fn report_level_load_failure(&mut self, error: &str) {
+		debugln!("level load failed: {}", error);
+		let text: String = format!("{} ({})", self.message_table.get(MESSAGE_LEVEL_LOAD_FAILED), error);
+		self.message_box.push(MessageBoxMessage {
+			message_id: MESSAGE_LEVEL_LOAD_FAILED,
+			text,
+			speaker_icon_id: 0,
+			is_blocking: false,
+		});
+		return;
+	}
+
+	fn install_level(&mut self, state: &mut State, level_name: &str, next_level: Level) {
 		let next_music_id: MusicId = next_level.music_id;
 
 		// 3) move audio backend into the new state (can't clone Box<dyn AudioEngine>)
@@ -101,12 +225,12 @@ impl Session {
 		// 5) spawn entities + apply player persistent
 		new_state.spawn_level_entities();
 		new_state.apply_player_from_persistent(self);
+		new_state.camera.copy_view_size_from(&state.camera);
+		self.apply_level_progress(&mut new_state, level_name);
 
 		// 7) swap
 		*state = new_state;
-
-		// self.current_level_name = Some(level_name.to_string());
-		self.current_level_name = Some(String::from(level_name));
+		self.message_box.clear();
 
 		if self.active_music_id != next_music_id {
 			if self.settings.is_background_music_enabled {
@@ -115,7 +239,32 @@ impl Session {
 			}
 		}
 
-		return true;
+		return;
+	}
+
+	// a freshly loaded level as the player left it: collected pickups stay gone, opened doors open
+	pub fn apply_level_progress(&mut self, state: &mut State, level_name: &str) {
+		let Some(level_key) = LevelKey::from_level_name(level_name) else {
+			return;
+		};
+
+		let pickup_count: usize = state.level.triggers.iter().filter(|t| TriggerKind::from_u8(t.kind) == TriggerKind::Pickup).count();
+		self.progress.set_pickup_count(level_key, pickup_count as u16);
+
+		let Some(level_progress) = self.progress.get_level(level_key) else {
+			return;
+		};
+		for trigger_id in &level_progress.collected_pickups {
+			if let Some(is_armed) = state.triggers_armed.get_mut(*trigger_id as usize) {
+				*is_armed = true;
+			}
+		}
+		restore_opened_doors(state, &level_progress.opened_doors);
+		return;
+	}
+
+	pub fn get_current_level_key(&self) -> Option<LevelKey> {
+		return LevelKey::from_level_name(self.current_level_name.as_deref()?);
 	}
 
 	#[inline(always)]
@@ -2,7 +2,23 @@
 extern crate alloc;
 
 #[cfg(feature = "gba")]
-use alloc::string::String;
+use alloc::{format, string::String};
+
+#[cfg(feature = "pc")]
+use crate::{debugln, runtime::assets::get_settings_path};
+
+#[cfg(feature = "pc")]
+use std::{fs, path::PathBuf};
+
+use crate::runtime::{
+	accessibility::{ASSIST_EXTRA_JUMP_FRAMES_MAX, ASSIST_FLOAT_FALL_SPEED_MAX, ASSIST_GAME_SPEED_PERCENT_MIN, Accessibility, AssistMode, TextFont, TextSize},
+	player_physics,
+};
+
+use core::fmt::Write;
+
+// volumes go from silent to full in this many steps
+pub const VOLUME_LEVEL_MAX: u8 = 10;
 
 #[derive(Clone, Debug)]
 pub struct Settings {
@@ -14,35 +30,203 @@ pub struct Settings {
 	pub stomp_bounce_multiplier: f32,
 	pub bounce_separator: f32,
 	pub camera_bottom_padding_tiles: u8,
+	pub camera_follow_rate: f32,
+	pub camera_look_ahead_tiles: f32,
+	pub camera_look_ahead_rate: f32,
+	pub camera_deadzone_half_width_tiles: f32,
+	pub camera_deadzone_half_height_tiles: f32,
+	pub is_screen_shake_enabled: bool,
 	pub is_background_music_enabled: bool,
 	pub are_sound_effects_enabled: bool,
+	// 0..=VOLUME_LEVEL_MAX
+	pub music_volume: u8,
+	pub sound_effects_volume: u8,
 	pub stomp_chain_gain_per_stomp: u16,
 	pub stomp_bonus_cap: u8,
 	pub language_code: String,
 	pub show_triggers: bool,
-	pub enemy_death_frame_count: u8,
-	pub frame_count: u32,
+	// the in-game and real time in the top right, and each level's split when it's left
+	pub show_speedrun_timer: bool,
+	// the best run of a level replayed as a see-through player
+	pub show_ghost: bool,
+	pub accessibility: Accessibility,
 }
 
 impl Settings {
 	pub fn new() -> Self {
 		return Self {
-			coyote_frames_max: 15,
-			jump_buffer_frames_max: 6,
-			jump_cut_multiplier: 0.4,
-			jump_velocity: -6.0,
+			coyote_frames_max: player_physics::COYOTE_FRAMES_MAX,
+			jump_buffer_frames_max: player_physics::JUMP_BUFFER_FRAMES_MAX,
+			jump_cut_multiplier: player_physics::JUMP_CUT_MULTIPLIER,
+			jump_velocity: player_physics::JUMP_VELOCITY,
 			stomp_bounce_multiplier: 0.6,
-			gravity: 0.35,
+			gravity: player_physics::GRAVITY,
 			bounce_separator: 0.5,
 			camera_bottom_padding_tiles: 2,
+			camera_follow_rate: 0.15,
+			camera_look_ahead_tiles: 2.0,
+			camera_look_ahead_rate: 0.04,
+			camera_deadzone_half_width_tiles: 1.0,
+			camera_deadzone_half_height_tiles: 2.0,
+			is_screen_shake_enabled: true,
 			is_background_music_enabled: false,
 			are_sound_effects_enabled: false,
+			music_volume: VOLUME_LEVEL_MAX,
+			sound_effects_volume: VOLUME_LEVEL_MAX,
 			stomp_chain_gain_per_stomp: 1,
 			stomp_bonus_cap: 5,
 			language_code: String::from("en-us"),
 			show_triggers: true,
-			enemy_death_frame_count: 30,
-			frame_count: 30,
+			show_speedrun_timer: false,
+			show_ghost: true,
+			accessibility: Accessibility::new(),
+		};
+	}
+
+	// the gameplay values below include assist mode; read them instead of the fields they build on
+
+	pub fn get_coyote_frames_max(&self) -> u8 {
+		return self.coyote_frames_max.saturating_add(self.get_extra_jump_frames());
+	}
+
+	pub fn get_jump_buffer_frames_max(&self) -> u8 {
+		return self.jump_buffer_frames_max.saturating_add(self.get_extra_jump_frames());
+	}
+
+	fn get_extra_jump_frames(&self) -> u8 {
+		let assist: &AssistMode = &self.accessibility.assist;
+		return if assist.is_enabled { assist.extra_jump_frames } else { 0 };
+	}
+
+	// 100 is full speed
+	pub fn get_game_speed_percent(&self) -> u8 {
+		let assist: &AssistMode = &self.accessibility.assist;
+		return if assist.is_enabled {
+			assist.game_speed_percent.clamp(ASSIST_GAME_SPEED_PERCENT_MIN, 100)
+		} else {
+			100
+		};
+	}
+
+	pub fn has_infinite_hit_points(&self) -> bool {
+		let assist: &AssistMode = &self.accessibility.assist;
+		return assist.is_enabled && assist.has_infinite_hit_points;
+	}
+
+	// None unless hold-to-float is on
+	pub fn get_float_fall_speed_max(&self) -> Option<f32> {
+		let assist: &AssistMode = &self.accessibility.assist;
+		return if assist.is_enabled && assist.can_hold_to_float {
+			Some(ASSIST_FLOAT_FALL_SPEED_MAX)
+		} else {
+			None
 		};
 	}
+
+	// the defaults with the player's choices from the settings file on top; a missing or broken
+	// file leaves the defaults
+	#[cfg(feature = "pc")]
+	pub fn load() -> Settings {
+		let path: PathBuf = get_settings_path();
+		let Ok(text) = fs::read_to_string(&path) else {
+			return Settings::new();
+		};
+		return Settings::parse(text.as_str()).unwrap_or_else(|e| {
+			debugln!("{}: {}", path.display(), e);
+			return Settings::new();
+		});
+	}
+
+	#[cfg(feature = "pc")]
+	pub fn save(&self) -> Result<(), String> {
+		let path: PathBuf = get_settings_path();
+		return fs::write(&path, self.to_text()).map_err(|e| format!("{}: {}", path.display(), e));
+	}
+
+	// `key = value` lines, `#` comments. only the choices the settings menu offers are read;
+	// unknown keys are skipped so an older build can read a newer file
+	pub fn parse(text: &str) -> Result<Settings, String> {
+		let mut settings: Settings = Settings::new();
+
+		for (line_index, raw_line) in text.lines().enumerate() {
+			let line_number: usize = line_index + 1;
+			let line: &str = raw_line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let Some(eq_index) = line.find('=') else {
+				return Err(format!("invalid line {}: missing '='", line_number));
+			};
+			let key: &str = line[..eq_index].trim();
+			let value: &str = line[eq_index + 1..].trim();
+
+			match key {
+				"music" => settings.is_background_music_enabled = parse_bool(value, line_number)?,
+				"music_volume" => settings.music_volume = parse_volume(value, line_number)?,
+				"sound_effects" => settings.are_sound_effects_enabled = parse_bool(value, line_number)?,
+				"sound_effects_volume" => settings.sound_effects_volume = parse_volume(value, line_number)?,
+				"language" => settings.language_code = String::from(value),
+				"screen_shake" => settings.is_screen_shake_enabled = parse_bool(value, line_number)?,
+				"show_triggers" => settings.show_triggers = parse_bool(value, line_number)?,
+				"speedrun_timer" => settings.show_speedrun_timer = parse_bool(value, line_number)?,
+				"ghost" => settings.show_ghost = parse_bool(value, line_number)?,
+				"text_font" => {
+					settings.accessibility.text_font = TextFont::from_name(value).ok_or_else(|| format!("unknown text font '{}' at line {}", value, line_number))?;
+				}
+				"text_size" => {
+					settings.accessibility.text_size = TextSize::from_name(value).ok_or_else(|| format!("unknown text size '{}' at line {}", value, line_number))?;
+				}
+				"high_contrast" => settings.accessibility.is_high_contrast = parse_bool(value, line_number)?,
+				"assist" => settings.accessibility.assist.is_enabled = parse_bool(value, line_number)?,
+				"assist_game_speed" => {
+					let percent: u8 = value.parse::<u8>().map_err(|_| format!("invalid game speed at line {}", line_number))?;
+					settings.accessibility.assist.game_speed_percent = percent.clamp(ASSIST_GAME_SPEED_PERCENT_MIN, 100);
+				}
+				"assist_extra_jump_frames" => {
+					let frames: u8 = value.parse::<u8>().map_err(|_| format!("invalid frame count at line {}", line_number))?;
+					settings.accessibility.assist.extra_jump_frames = frames.min(ASSIST_EXTRA_JUMP_FRAMES_MAX);
+				}
+				"assist_infinite_hearts" => settings.accessibility.assist.has_infinite_hit_points = parse_bool(value, line_number)?,
+				"assist_hold_to_float" => settings.accessibility.assist.can_hold_to_float = parse_bool(value, line_number)?,
+				_ => {}
+			}
+		}
+
+		return Ok(settings);
+	}
+
+	pub fn to_text(&self) -> String {
+		let mut text: String = String::new();
+		let _ = writeln!(text, "# jumpy settings; rewritten whenever the settings menu closes");
+		let _ = writeln!(text, "music = {}", self.is_background_music_enabled);
+		let _ = writeln!(text, "music_volume = {}", self.music_volume);
+		let _ = writeln!(text, "sound_effects = {}", self.are_sound_effects_enabled);
+		let _ = writeln!(text, "sound_effects_volume = {}", self.sound_effects_volume);
+		let _ = writeln!(text, "language = {}", self.language_code);
+		let _ = writeln!(text, "screen_shake = {}", self.is_screen_shake_enabled);
+		let _ = writeln!(text, "show_triggers = {}", self.show_triggers);
+		let _ = writeln!(text, "speedrun_timer = {}", self.show_speedrun_timer);
+		let _ = writeln!(text, "ghost = {}", self.show_ghost);
+
+		let accessibility: &Accessibility = &self.accessibility;
+		let _ = writeln!(text, "text_font = {}", accessibility.text_font.get_name());
+		let _ = writeln!(text, "text_size = {}", accessibility.text_size.get_name());
+		let _ = writeln!(text, "high_contrast = {}", accessibility.is_high_contrast);
+		let _ = writeln!(text, "assist = {}", accessibility.assist.is_enabled);
+		let _ = writeln!(text, "assist_game_speed = {}", accessibility.assist.game_speed_percent);
+		let _ = writeln!(text, "assist_extra_jump_frames = {}", accessibility.assist.extra_jump_frames);
+		let _ = writeln!(text, "assist_infinite_hearts = {}", accessibility.assist.has_infinite_hit_points);
+		let _ = writeln!(text, "assist_hold_to_float = {}", accessibility.assist.can_hold_to_float);
+		return text;
+	}
+}
+
+fn parse_bool(value: &str, line_number: usize) -> Result<bool, String> {
+	return value.parse::<bool>().map_err(|_| format!("expected true or false at line {}", line_number));
+}
+
+fn parse_volume(value: &str, line_number: usize) -> Result<u8, String> {
+	let volume: u8 = value.parse::<u8>().map_err(|_| format!("invalid volume at line {}", line_number))?;
+	return Ok(volume.min(VOLUME_LEVEL_MAX));
 }
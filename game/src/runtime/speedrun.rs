@@ -0,0 +1,271 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{format, string::String, vec::Vec};
+
+#[cfg(feature = "pc")]
+use crate::{debugln, runtime::assets::get_ghost_path};
+
+#[cfg(feature = "pc")]
+use std::{fs, path::PathBuf};
+
+use crate::{
+	engine_math::Vec2,
+	runtime::{
+		progress::{LevelKey, Progress},
+		state::State,
+	},
+};
+
+// in-game time counts simulation steps, at the simulation's fixed 60 Hz (SIMULATION_HZ)
+pub const IGT_STEPS_PER_SECOND: u32 = 60;
+
+// how long the last level's split stays on the hud once the next level is playing, in steps
+pub const SPLIT_SHOW_STEPS: u16 = 300;
+
+// a ghost stops recording after this many steps (20 minutes) and replays no further
+pub const GHOST_MAX_STEPS: usize = 20 * 60 * IGT_STEPS_PER_SECOND as usize;
+
+const GHOST_MAGIC: &[u8; 4] = b"JGST";
+const GHOST_VERSION: u16 = 1;
+
+// one attempt at a level, from the level opening to leaving through an exit
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LevelTime {
+	// steps of play; pauses, menus, books and blocking messages don't count
+	pub igt_steps: u32,
+	// real time, everything included
+	pub rta_millis: u32,
+}
+
+impl LevelTime {
+	pub fn get_igt_centis(&self) -> u32 {
+		return (self.igt_steps as u64 * 100 / IGT_STEPS_PER_SECOND as u64) as u32;
+	}
+
+	pub fn get_rta_centis(&self) -> u32 {
+		return self.rta_millis / 10;
+	}
+}
+
+// shown on the hud after a level exit: the level's time against the best before it
+#[derive(Copy, Clone, Debug)]
+pub struct Split {
+	pub key: LevelKey,
+	pub time: LevelTime,
+	// None the first time the level is finished
+	pub previous_best: Option<LevelTime>,
+	pub is_new_best: bool,
+	pub show_steps: u16,
+}
+
+impl Split {
+	// in-game time against the previous best, negative when faster
+	pub fn get_delta_centis(&self) -> Option<i32> {
+		let previous_best: LevelTime = self.previous_best?;
+		return Some(self.time.get_igt_centis() as i32 - previous_best.get_igt_centis() as i32);
+	}
+}
+
+// the player's position on every step of a level's best run, replayed as a see-through player
+#[derive(Clone, Debug)]
+pub struct Ghost {
+	pub positions: Vec<Vec2>,
+}
+
+impl Ghost {
+	// None when the level has no ghost yet or its file is broken
+	#[cfg(feature = "pc")]
+	pub fn load(key: LevelKey) -> Option<Ghost> {
+		let path: PathBuf = get_ghost_path(key.world_id, key.level_id);
+		let bytes: Vec<u8> = fs::read(&path).ok()?;
+		return match Ghost::from_bytes(&bytes) {
+			Ok(ghost) => Some(ghost),
+			Err(e) => {
+				debugln!("{}: {}", path.display(), e);
+				None
+			}
+		};
+	}
+
+	#[cfg(feature = "pc")]
+	pub fn save(&self, key: LevelKey) -> Result<(), String> {
+		let path: PathBuf = get_ghost_path(key.world_id, key.level_id);
+		return fs::write(&path, self.to_bytes()).map_err(|e| format!("{}: {}", path.display(), e));
+	}
+
+	// "JGST", u16 version, u32 step count, then an f32 x and y per step; all little endian
+	pub fn from_bytes(bytes: &[u8]) -> Result<Ghost, String> {
+		if bytes.len() < 10 || &bytes[0..4] != GHOST_MAGIC {
+			return Err(String::from("not a ghost file"));
+		}
+		let version: u16 = u16::from_le_bytes([bytes[4], bytes[5]]);
+		if version != GHOST_VERSION {
+			return Err(format!("unsupported ghost version {}", version));
+		}
+		let step_count: usize = u32::from_le_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]) as usize;
+		if step_count > GHOST_MAX_STEPS || bytes.len() != 10 + step_count * 8 {
+			return Err(format!("expected {} steps", step_count));
+		}
+
+		let mut positions: Vec<Vec2> = Vec::with_capacity(step_count);
+		for step in bytes[10..].chunks_exact(8) {
+			let x: f32 = f32::from_le_bytes([step[0], step[1], step[2], step[3]]);
+			let y: f32 = f32::from_le_bytes([step[4], step[5], step[6], step[7]]);
+			positions.push(Vec2 { x, y });
+		}
+		return Ok(Ghost { positions });
+	}
+
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes: Vec<u8> = Vec::with_capacity(10 + self.positions.len() * 8);
+		bytes.extend_from_slice(GHOST_MAGIC);
+		bytes.extend_from_slice(&GHOST_VERSION.to_le_bytes());
+		bytes.extend_from_slice(&(self.positions.len() as u32).to_le_bytes());
+		for position in &self.positions {
+			bytes.extend_from_slice(&position.x.to_le_bytes());
+			bytes.extend_from_slice(&position.y.to_le_bytes());
+		}
+		return bytes;
+	}
+}
+
+// the timer for the level being played, the run being recorded for its ghost and the ghost of
+// its best run
+pub struct SpeedrunState {
+	pub is_running: bool,
+	pub igt_steps: u32,
+	pub rta_micros: u64,
+	// assist mode was on at some point; such a run never becomes a record
+	pub is_assisted: bool,
+	pub recording: Vec<Vec2>,
+	pub ghost: Option<Ghost>,
+	pub last_split: Option<Split>,
+	// the run that just set a level's record, until save_new_best_ghost writes it with the progress
+	// that holds its time
+	pub new_best_ghost: Option<(LevelKey, Ghost)>,
+}
+
+impl SpeedrunState {
+	pub fn new() -> SpeedrunState {
+		return SpeedrunState {
+			is_running: false,
+			igt_steps: 0,
+			rta_micros: 0,
+			is_assisted: false,
+			recording: Vec::new(),
+			ghost: None,
+			last_split: None,
+			new_best_ghost: None,
+		};
+	}
+
+	// a level opened, fresh or again
+	pub fn start_level(&mut self, ghost: Option<Ghost>) {
+		self.is_running = true;
+		self.igt_steps = 0;
+		self.rta_micros = 0;
+		self.is_assisted = false;
+		self.recording.clear();
+		self.ghost = ghost;
+		return;
+	}
+
+	// every frame, whatever is on screen
+	pub fn add_real_time(&mut self, micros: u64) {
+		if self.is_running {
+			self.rta_micros += micros;
+		}
+		return;
+	}
+
+	// once per simulation step of play, after the player has moved
+	pub fn step(&mut self, state: &State, is_assisted: bool) {
+		if let Some(split) = self.last_split.as_mut() {
+			split.show_steps = split.show_steps.saturating_sub(1);
+		}
+		if !self.is_running {
+			return;
+		}
+
+		self.igt_steps += 1;
+		self.is_assisted |= is_assisted;
+		if let Some(player_id) = state.try_get_player_id() {
+			if let Some(position) = state.positions.get(player_id) {
+				if self.recording.len() < GHOST_MAX_STEPS {
+					self.recording.push(*position);
+				}
+			}
+		}
+		return;
+	}
+
+	pub fn get_time(&self) -> LevelTime {
+		return LevelTime {
+			igt_steps: self.igt_steps,
+			rta_millis: (self.rta_micros / 1000).min(u32::MAX as u64) as u32,
+		};
+	}
+
+	// a level exit fired: stop the clock, keep the time if it beats the level's best and show the split
+	pub fn finish_level(&mut self, key: LevelKey, progress: &mut Progress) {
+		if !self.is_running {
+			return;
+		}
+		self.is_running = false;
+
+		let time: LevelTime = self.get_time();
+		let previous_best: Option<LevelTime> = progress.get_level(key).and_then(|l| l.best_time);
+		let is_new_best: bool = !self.is_assisted && previous_best.is_none_or(|best| time.igt_steps < best.igt_steps);
+		if is_new_best {
+			progress.set_best_time(key, time);
+			let positions: Vec<Vec2> = core::mem::take(&mut self.recording);
+			self.new_best_ghost = Some((key, Ghost { positions }));
+		}
+
+		self.last_split = Some(Split {
+			key,
+			time,
+			previous_best,
+			is_new_best,
+			show_steps: SPLIT_SHOW_STEPS,
+		});
+		return;
+	}
+
+	// writes the ghost of a record set since the last save; a failed write keeps it for the next
+	#[cfg(feature = "pc")]
+	pub fn save_new_best_ghost(&mut self) -> Result<(), String> {
+		let Some((key, ghost)) = self.new_best_ghost.take() else {
+			return Ok(());
+		};
+		if let Err(e) = ghost.save(key) {
+			self.new_best_ghost = Some((key, ghost));
+			return Err(e);
+		}
+		return Ok(());
+	}
+
+	// where the best run was after as many steps as the current one has taken, part way to its next
+	// step like the other entities; None once it has finished
+	pub fn get_ghost_position(&self, render_alpha: f32) -> Option<Vec2> {
+		let ghost: &Ghost = self.ghost.as_ref()?;
+		let step_index: usize = (self.igt_steps as usize).saturating_sub(1);
+		let position: Vec2 = *ghost.positions.get(step_index)?;
+		if !self.is_running || self.igt_steps == 0 {
+			return Some(position);
+		}
+		let Some(next) = ghost.positions.get(step_index + 1) else {
+			return Some(position);
+		};
+		return Some(Vec2 {
+			x: position.x + (next.x - position.x) * render_alpha,
+			y: position.y + (next.y - position.y) * render_alpha,
+		});
+	}
+
+	pub fn get_showing_split(&self) -> Option<&Split> {
+		return self.last_split.as_ref().filter(|split| split.show_steps > 0);
+	}
+}
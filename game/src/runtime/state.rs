@@ -2,14 +2,27 @@
 extern crate alloc;
 
 #[cfg(feature = "gba")]
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+#[cfg(not(feature = "gba"))]
+use std::sync::Arc;
 
 use crate::{
 	ecs::component_store::ComponentStore,
 	engine_math::Vec2,
 	physics::collision,
 	platform::audio::{AudioEngine, SfxId},
-	runtime::level::Level,
+	runtime::{
+		animation::{
+			animator::{Animator, FiredAnimationEvent},
+			clip::AnimState,
+		},
+		camera::{Camera, DEATH_SHAKE_FRAMES, DEATH_SHAKE_WORLD},
+		door::OpeningDoor,
+		hud::HudState,
+		level::Level,
+		scoring::ScoreTally,
+	},
 	tile::TileCollision,
 };
 
@@ -94,6 +107,19 @@ impl EntityKind {
 		}
 	}
 
+	// snake_case names used by data files, e.g. "slime_blue"; Empty when unknown
+	pub fn from_name(name: &str) -> EntityKind {
+		match name {
+			"player" => EntityKind::Player,
+			"slime_blue" => EntityKind::SlimeBlue,
+			"imp" => EntityKind::Imp,
+			"moving_platform" => EntityKind::MovingPlatform,
+			"slime_undead" => EntityKind::SlimeUndead,
+			"slime_lava" => EntityKind::SlimeLava,
+			_ => EntityKind::Empty,
+		}
+	}
+
 	pub fn from_u8(v: u8) -> EntityKind {
 		match v {
 			1 => EntityKind::Player,
@@ -109,8 +135,12 @@ impl EntityKind {
 
 /// Represents the state, containing entities and their properties (runtime state).
 pub struct State {
-	pub level: Level,
+	// shared with the render snapshots; tile changes copy it on write, so a snapshot keeps the
+	// tiles of the step it was taken on
+	pub level: Arc<Level>,
 	pub positions: ComponentStore<Vec2>,
+	pub previous_positions: ComponentStore<Vec2>,
+	pub render_alpha: f32,
 	pub velocities: ComponentStore<Vec2>,
 	pub player_id: Option<EntityId>,
 	pub spawn_point: Vec2,
@@ -133,14 +163,21 @@ pub struct State {
 	pub jump_states: ComponentStore<JumpState>,
 	pub respawn_states: ComponentStore<RespawnState>,
 	pub respawn_cooldown_frames: u8,
-	pub camera_baseline_max_bottom_world: Option<f32>,
+	pub camera: Camera,
+	pub hud: HudState,
 	pub base_stomp_damages: ComponentStore<u16>,
 	pub stomp_chains: ComponentStore<u16>,
 	pub hit_points: ComponentStore<u16>,
 	pub audio: Box<dyn AudioEngine>,
 	pub death_animations: ComponentStore<u8>,
 	pub death_timers: ComponentStore<u16>,
+	pub animators: ComponentStore<Animator>,
+	pub animation_events: Vec<FiredAnimationEvent>,
 	pub triggers_armed: Vec<bool>,
+	// doors unlocked this visit that are still lifting
+	pub opening_doors: Vec<OpeningDoor>,
+	// coins, stomps and deaths of this attempt at the level, for its results screen
+	pub score: ScoreTally,
 	pub enemy_sprite_scale: u8,
 	next_entity_id: EntityId,
 	pub player_ids: [Option<EntityId>; MAX_PLAYERS],
@@ -152,8 +189,10 @@ impl State {
 		let spawn_left_tiles: u16 = current_level.player_spawn_left as u16;
 
 		let mut state = State {
-			level: current_level,
+			level: Arc::new(current_level),
 			positions: ComponentStore::new(),
+			previous_positions: ComponentStore::new(),
+			render_alpha: 1.0,
 			velocities: ComponentStore::new(),
 			player_id: None,
 			spawn_point: Vec2::zero(),
@@ -176,13 +215,18 @@ impl State {
 			respawn_states: ComponentStore::new(),
 			enemy_ids: Vec::new(),
 			respawn_cooldown_frames: 0,
-			camera_baseline_max_bottom_world: None,
+			camera: Camera::new(),
+			hud: HudState::new(),
 			stomp_chains: ComponentStore::new(),
 			hit_points: ComponentStore::new(),
 			base_stomp_damages: ComponentStore::new(),
 			death_animations: ComponentStore::new(),
 			death_timers: ComponentStore::new(),
+			animators: ComponentStore::new(),
+			animation_events: Vec::new(),
 			triggers_armed: Vec::new(),
+			opening_doors: Vec::new(),
+			score: ScoreTally::new(),
 			enemy_sprite_scale: 1,
 			audio,
 			tick: 0,
@@ -261,13 +305,24 @@ impl State {
 	}
 
 	pub fn kill_player(&mut self, session: &Session, player_id: EntityId) {
+		if session.settings.is_screen_shake_enabled {
+			self.camera.add_shake(DEATH_SHAKE_WORLD, DEATH_SHAKE_FRAMES);
+		}
+
 		if let Some(respawn_state) = self.respawn_states.get_mut(player_id) {
 			respawn_state.respawn_cooldown_frames = self.respawn_cooldown_frames;
 		}
 
+		// every death costs a heart; the game is over once they are gone
+		if !session.settings.has_infinite_hit_points() {
+			let hit_points: u16 = self.hit_points.get(player_id).copied().unwrap_or(0);
+			self.hit_points.set(player_id, hit_points.saturating_sub(1));
+		}
+
 		if session.settings.are_sound_effects_enabled {
 			self.audio.play_sfx_and_wait(SfxId::Player1Died);
 		}
+		self.score.add_death();
 		self.respawn_cooldown_frames = 20;
 		self.respawn_player(player_id);
 	}
@@ -288,6 +343,8 @@ impl State {
 		if let Some(vel) = self.velocities.get_mut(player_id) {
 			*vel = Vec2::zero();
 		}
+
+		self.camera.request_snap();
 	}
 
 	#[inline(always)]
@@ -371,6 +428,30 @@ impl State {
 		return hit;
 	}
 
+	// call before each fixed simulation step; the renderer blends from these to `positions` by `render_alpha`
+	pub fn store_previous_positions(&mut self) {
+		self.previous_positions.clear();
+		for (id, pos) in self.positions.iter() {
+			self.previous_positions.set(id, *pos);
+		}
+	}
+
+	pub fn get_render_position(&self, id: EntityId) -> Option<Vec2> {
+		let current: Vec2 = *self.positions.get(id)?;
+		let Some(previous) = self.previous_positions.get(id).copied() else {
+			return Some(current);
+		};
+
+		// respawns and other teleports snap instead of sliding across the screen
+		let max_step_world: f32 = self.level.tile_width as f32 * 2.0;
+		let delta: Vec2 = current - previous;
+		if delta.x.abs() > max_step_world || delta.y.abs() > max_step_world {
+			return Some(current);
+		}
+
+		return Some(previous + delta * self.render_alpha);
+	}
+
 	pub fn get_entity_half_values(&self, id: EntityId) -> (f32, f32) {
 		let width: f32 = self.widths.get(id).copied().unwrap_or(16) as f32;
 		let height: f32 = self.heights.get(id).copied().unwrap_or(16) as f32;
@@ -486,6 +567,13 @@ impl State {
 		}
 	}
 
+	// restarts an attack/hurt clip; entities without an animation set ignore it
+	pub fn play_one_shot_animation(&mut self, id: EntityId, anim: AnimState) {
+		if let Some(animator) = self.animators.get_mut(id) {
+			animator.play_one_shot(anim);
+		}
+	}
+
 	pub fn tick_enemy_deaths(&mut self) {
 		let ids: Vec<EntityId> = self.death_timers.keys().collect();
 
@@ -515,6 +603,7 @@ impl State {
 
 	pub fn remove_entity(&mut self, id: EntityId) {
 		self.positions.remove(id);
+		self.previous_positions.remove(id);
 		self.velocities.remove(id);
 		self.entity_kinds.remove(id);
 		self.render_styles.remove(id);
@@ -534,6 +623,7 @@ impl State {
 		self.base_stomp_damages.remove(id);
 		self.death_animations.remove(id);
 		self.death_timers.remove(id);
+		self.animators.remove(id);
 		// linear scan is fine. I’ll have maybe dozens of enemies, not millions.
 		self.enemy_ids.retain(|&e| e != id);
 
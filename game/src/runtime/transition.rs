@@ -0,0 +1,81 @@
+// the message box text when the next level can't be loaded; the error follows it in brackets
+pub const MESSAGE_LEVEL_LOAD_FAILED: u16 = 151;
+
+// steps for the screen to close before the load and to open again after it
+pub const TRANSITION_CLOSE_TICKS: u16 = 24;
+pub const TRANSITION_OPEN_TICKS: u16 = 24;
+
+// how the screen covers a level change
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TransitionEffect {
+	// everything fades to black
+	Fade,
+	// a circle closes in on the player, then opens from them in the next level
+	Iris,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TransitionPhase {
+	Closing,
+	// fully covered while the next level loads
+	Loading,
+	Opening,
+}
+
+// the LevelTransition mode's progress: close over the old level, wait for the load, open on the
+// new one. a load that fails opens back on the level the player was in
+#[derive(Copy, Clone, Debug)]
+pub struct TransitionState {
+	pub effect: TransitionEffect,
+	pub phase: TransitionPhase,
+	pub ticks: u16,
+	// the current level again (restart, retry) rather than the pending one
+	pub is_restart: bool,
+}
+
+impl TransitionState {
+	pub fn new(effect: TransitionEffect, is_restart: bool) -> TransitionState {
+		return TransitionState {
+			effect,
+			phase: TransitionPhase::Closing,
+			ticks: 0,
+			is_restart,
+		};
+	}
+
+	// one fixed step; true once the screen is fully open again
+	pub fn tick(&mut self) -> bool {
+		match self.phase {
+			TransitionPhase::Closing => {
+				self.ticks += 1;
+				if self.ticks >= TRANSITION_CLOSE_TICKS {
+					self.phase = TransitionPhase::Loading;
+					self.ticks = 0;
+				}
+			}
+			TransitionPhase::Loading => {}
+			TransitionPhase::Opening => {
+				self.ticks += 1;
+				if self.ticks >= TRANSITION_OPEN_TICKS {
+					return true;
+				}
+			}
+		}
+		return false;
+	}
+
+	pub fn start_opening(&mut self) {
+		self.phase = TransitionPhase::Opening;
+		self.ticks = 0;
+		return;
+	}
+
+	// 0 with the level in full view, 1 with the screen covered
+	pub fn get_cover(&self) -> f32 {
+		return match self.phase {
+			TransitionPhase::Closing => self.ticks as f32 / TRANSITION_CLOSE_TICKS as f32,
+			TransitionPhase::Loading => 1.0,
+			TransitionPhase::Opening => 1.0 - self.ticks as f32 / TRANSITION_OPEN_TICKS as f32,
+		};
+	}
+}
@@ -2,13 +2,17 @@
 extern crate alloc;
 
 #[cfg(feature = "gba")]
-use alloc::{format, string::String};
+use alloc::string::String;
 
 use crate::{
 	debugln,
 	engine_math::{do_they_overlap, random_u16},
 	platform::input::TriggerPresses,
 	runtime::{
+		hud::{HudCounter, HudState},
+		message_box::MessageBoxMessage,
+		progress::LevelKey,
+		scoring::LevelResults,
 		session::Session,
 		state::{EntityId, State},
 	},
@@ -21,6 +25,12 @@ pub const TRIGGER_MODE_DOWN: u16 = 3;
 pub const TRIGGER_MODE_LEFT: u16 = 4;
 pub const TRIGGER_MODE_RIGHT: u16 = 5;
 
+// a message keeps its activation mode in the low byte of p0 and its flags above it, which frees
+// the mode byte for the speaker's icon id; levelc rejects a mode that would spill into the flags
+pub const MESSAGE_MODE_MASK: u16 = 0x00ff;
+// set in a message trigger's p0 when the message pauses play until dismissed
+pub const MESSAGE_BLOCKING_FLAG: u16 = 0x100;
+
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum TriggerKind {
@@ -28,6 +38,9 @@ pub enum TriggerKind {
 	LevelExit = 1,
 	Message = 2,
 	Pickup = 3,
+	CameraZone = 4,
+	Light = 5,
+	Door = 6,
 }
 
 #[repr(u8)]
@@ -58,6 +71,9 @@ impl TriggerKind {
 			1 => TriggerKind::LevelExit,
 			2 => TriggerKind::Message,
 			3 => TriggerKind::Pickup,
+			4 => TriggerKind::CameraZone,
+			5 => TriggerKind::Light,
+			6 => TriggerKind::Door,
 			_ => TriggerKind::Empty,
 		}
 	}
@@ -83,8 +99,12 @@ pub struct LevelTrigger {
 
 impl LevelTrigger {
 	// --- shared ---
+	// TRIGGER_MODE_*, whichever field the trigger's kind keeps it in
 	#[inline(always)]
 	pub fn get_activation_mode(&self) -> u16 {
+		if TriggerKind::from_u8(self.kind) == TriggerKind::Message {
+			return self.get_message_mode();
+		}
 		return self.activation_mode as u16;
 	}
 
@@ -94,6 +114,23 @@ impl LevelTrigger {
 		return self.p1;
 	}
 
+	// TRIGGER_MODE_*; the high byte of p0 holds the message flags
+	#[inline(always)]
+	pub fn get_message_mode(&self) -> u16 {
+		return self.p0 & MESSAGE_MODE_MASK;
+	}
+
+	#[inline(always)]
+	pub fn is_message_blocking(&self) -> bool {
+		return (self.p0 & MESSAGE_BLOCKING_FLAG) != 0;
+	}
+
+	// messages keep their speaker's portrait icon in the mode byte; 0 = none
+	#[inline(always)]
+	pub fn get_message_speaker_icon(&self) -> u16 {
+		return self.activation_mode as u16;
+	}
+
 	// --- level exit ---
 	#[inline(always)]
 	pub fn get_world_id(&self) -> u16 {
@@ -104,10 +141,37 @@ impl LevelTrigger {
 	pub fn get_level_id(&self) -> u16 {
 		return self.p1;
 	}
+
+	// --- light ---
+	#[inline(always)]
+	pub fn get_light_radius(&self) -> u16 {
+		return self.p0;
+	}
+
+	// p1 is rgb565
+	pub fn get_light_color(&self) -> (u8, u8, u8) {
+		let red: u8 = ((self.p1 >> 11) & 0x1f) as u8;
+		let green: u8 = ((self.p1 >> 5) & 0x3f) as u8;
+		let blue: u8 = (self.p1 & 0x1f) as u8;
+		return ((red << 3) | (red >> 2), (green << 2) | (green >> 4), (blue << 3) | (blue >> 2));
+	}
+
+	// EntityKind the light follows; 0 means it stays at the trigger
+	#[inline(always)]
+	pub fn get_light_attached_kind(&self) -> u8 {
+		return self.activation_mode;
+	}
+
+	// --- door ---
+	// the KeyId that unlocks it
+	#[inline(always)]
+	pub fn get_door_key_id(&self) -> u16 {
+		return self.p0;
+	}
 }
 
 /// returns true if an ACTION trigger consumed the action press (so caller should NOT jump)
-pub fn handle_message_triggers(session: &Session, state: &mut State, trigger_presses: TriggerPresses) -> bool {
+pub fn handle_message_triggers(session: &mut Session, state: &mut State, trigger_presses: TriggerPresses) -> bool {
 	let player_id: EntityId = state.get_player_id();
 
 	let Some(player_pos) = state.positions.get(player_id) else {
@@ -158,22 +222,17 @@ pub fn handle_message_triggers(session: &Session, state: &mut State, trigger_pre
 			continue;
 		}
 
-		let activation_mode: u16 = trigger.p0;
-		let message_id: u16 = trigger.get_message_id();
+		let activation_mode: u16 = trigger.get_message_mode();
 
 		if activation_mode == TRIGGER_MODE_AUTO {
 			if !state.triggers_armed[trigger_index] {
 				state.triggers_armed[trigger_index] = true;
-
-				let msg: &str = session.message_table.get(message_id);
-				debugln!("{}", msg);
+				show_message(session, trigger);
 			}
 		} else if activation_mode == TRIGGER_MODE_ACTION {
 			if trigger_presses.action_pressed && !state.triggers_armed[trigger_index] {
 				state.triggers_armed[trigger_index] = true;
-
-				let msg: &str = session.message_table.get(message_id);
-				debugln!("{}", msg);
+				show_message(session, trigger);
 
 				consumed_action = true;
 			}
@@ -183,6 +242,20 @@ pub fn handle_message_triggers(session: &Session, state: &mut State, trigger_pre
 	return consumed_action;
 }
 
+fn show_message(session: &mut Session, trigger: &LevelTrigger) {
+	let message_id: u16 = trigger.get_message_id();
+	let text: &str = session.message_table.get(message_id);
+	debugln!("{}", text);
+
+	session.message_box.push(MessageBoxMessage {
+		message_id,
+		text: String::from(text),
+		speaker_icon_id: trigger.get_message_speaker_icon(),
+		is_blocking: trigger.is_message_blocking(),
+	});
+	return;
+}
+
 pub fn handle_level_exit_triggers(session: &mut Session, game: &mut State, presses: TriggerPresses) {
 	let player_id: EntityId = game.get_player_id();
 	let Some(player_pos) = game.positions.get(player_id) else {
@@ -246,8 +319,23 @@ pub fn handle_level_exit_triggers(session: &mut Session, game: &mut State, press
 
 		game.triggers_armed[trigger_index] = true;
 
-		let next_level_name: String = format!("../worlds/{:02}/{:02}.lvlb", trigger.get_world_id(), trigger.get_level_id());
-		session.pending_level_name = Some(next_level_name);
+		// leaving through an exit finishes the level, which is what the world index unlocks on, and
+		// stops its clock
+		if let Some(level_key) = session.get_current_level_key() {
+			session.progress.complete_level(level_key);
+			session.speedrun.finish_level(level_key, &mut session.progress);
+		}
+
+		// the results screen comes up before the next level loads
+		let results: LevelResults = LevelResults::tally(&game.score, session.speedrun.get_time(), game.level.rank_thresholds);
+		debugln!("level results: {} points, rank {}", results.total, results.rank.get_letter());
+		session.results = Some(results);
+
+		let next_level_key: LevelKey = LevelKey {
+			world_id: trigger.get_world_id(),
+			level_id: trigger.get_level_id(),
+		};
+		session.pending_level_name = Some(next_level_key.get_level_name());
 		return;
 	}
 }
@@ -312,14 +400,21 @@ pub fn handle_pickup_triggers(session: &mut Session, state: &mut State, presses:
 			continue;
 		}
 
-		// consume
+		// consume, for this visit and, through the session's progress, for later ones
 		state.triggers_armed[trigger_index] = true;
+		if let Some(level_key) = session.get_current_level_key() {
+			session.progress.collect_pickup(level_key, trigger.id);
+		}
 
 		if mode == TRIGGER_MODE_ACTION {
 			// todo remove trigger here
 			consumed_action = true;
 		}
 
+		// the hud flies the pickup from here to its counter
+		let flight_left_world: f32 = trig_left_world + trig_width_world * 0.5;
+		let flight_top_world: f32 = trig_top_world + trig_height_world * 0.5;
+
 		match kind {
 			TriggerKind::Pickup => {
 				let pickup_kind = PickupKind::from_u8(trigger.p0 as u8);
@@ -327,11 +422,17 @@ pub fn handle_pickup_triggers(session: &mut Session, state: &mut State, presses:
 				match pickup_kind {
 					PickupKind::Coin | PickupKind::Key | PickupKind::Book => {
 						apply_pickup(session, trigger.p0, trigger.p1);
+						if pickup_kind == PickupKind::Coin {
+							state.score.add_coins(trigger.p1);
+						}
+						add_pickup_flight(&mut state.hud, trigger.p0, trigger.p1, flight_left_world, flight_top_world, state.tick);
 					}
 					PickupKind::Random => {
 						// coin only for now
 						let value = random_u16(&mut session.random_state_u16);
 						apply_pickup(session, 1, value);
+						state.score.add_coins(value);
+						add_pickup_flight(&mut state.hud, 1, value, flight_left_world, flight_top_world, state.tick);
 					}
 
 					_ => {
@@ -368,8 +469,23 @@ fn apply_pickup(session: &mut Session, pickup_type: u16, value: u16) {
 	return;
 }
 
+// books have no hud counter, so they don't fly anywhere
+fn add_pickup_flight(hud: &mut HudState, pickup_type: u16, value: u16, left_world: f32, top_world: f32, tick: u32) {
+	if pickup_type == 1 {
+		hud.add_flight(HudCounter::Coins, value, left_world, top_world, tick);
+		return;
+	}
+
+	if pickup_type == 2 {
+		hud.add_flight(HudCounter::Keys, value, left_world, top_world, tick);
+		return;
+	}
+
+	return;
+}
+
 #[inline(always)]
-fn should_fire(mode: u16, presses: TriggerPresses) -> bool {
+pub(crate) fn should_fire(mode: u16, presses: TriggerPresses) -> bool {
 	let result: bool = match mode {
 		TRIGGER_MODE_AUTO => true,
 		TRIGGER_MODE_ACTION => presses.action_pressed,
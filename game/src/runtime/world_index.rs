@@ -0,0 +1,197 @@
+#[cfg(feature = "gba")]
+extern crate alloc;
+
+#[cfg(feature = "gba")]
+use alloc::{format, string::String, vec::Vec};
+
+#[cfg(feature = "pc")]
+use crate::{
+	platform::level_loader::read_level_name_from_file,
+	runtime::assets::{get_world_index_path, get_worlds_root},
+};
+
+#[cfg(not(feature = "pc"))]
+use crate::platform::level_loader::read_level_name;
+
+#[cfg(feature = "pc")]
+use std::{fs, path::PathBuf};
+
+use crate::{
+	debugln,
+	runtime::{
+		progress::{LevelKey, Progress},
+		world_list::{WorldList, WorldListUnlock, parse_world_list},
+	},
+};
+
+// what has to be finished before a level can be picked on the level select screen
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum UnlockRule {
+	Start,
+	AfterLevel(LevelKey),
+	AfterWorld(u16),
+}
+
+#[derive(Clone, Debug)]
+pub struct WorldEntry {
+	pub world_id: u16,
+	pub name: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct WorldLevelEntry {
+	pub key: LevelKey,
+	// the `name` from the level's header
+	pub name: String,
+	pub unlock: UnlockRule,
+}
+
+// a world's row and a level's column in it on the level select screen
+#[derive(Copy, Clone, Debug)]
+pub struct LevelSelectCursor {
+	pub row: usize,
+	pub column: usize,
+}
+
+impl LevelSelectCursor {
+	pub fn new() -> LevelSelectCursor {
+		return LevelSelectCursor { row: 0, column: 0 };
+	}
+
+	// left/right step through a world's levels, up/down change world; the cursor stops at the edges
+	// and lands on a shorter world's last level
+	pub fn move_selection(&mut self, column_delta: i32, row_delta: i32, world_index: &WorldIndex) {
+		if world_index.worlds.is_empty() {
+			*self = LevelSelectCursor::new();
+			return;
+		}
+
+		let last_row: i32 = world_index.worlds.len() as i32 - 1;
+		self.row = (self.row as i32 + row_delta).clamp(0, last_row) as usize;
+		let level_count: usize = world_index.get_world_level_count(world_index.worlds[self.row].world_id);
+		let last_column: i32 = level_count.max(1) as i32 - 1;
+		self.column = (self.column as i32 + column_delta).clamp(0, last_column) as usize;
+		return;
+	}
+
+	pub fn get_selected_level<'a>(&self, world_index: &'a WorldIndex) -> Option<&'a WorldLevelEntry> {
+		let world: &WorldEntry = world_index.worlds.get(self.row)?;
+		return world_index.get_world_level(world.world_id, self.column);
+	}
+}
+
+// worlds/worlds.txt, every world and level in play order; the level select screen is built from it
+// and build.rs embeds the same levels into the gba rom
+#[derive(Clone, Debug)]
+pub struct WorldIndex {
+	pub worlds: Vec<WorldEntry>,
+	pub levels: Vec<WorldLevelEntry>,
+}
+
+impl WorldIndex {
+	pub fn empty() -> WorldIndex {
+		return WorldIndex {
+			worlds: Vec::new(),
+			levels: Vec::new(),
+		};
+	}
+
+	#[cfg(feature = "pc")]
+	pub fn load() -> Result<WorldIndex, String> {
+		let path: PathBuf = get_world_index_path();
+		let text: String = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+		let mut index: WorldIndex = WorldIndex::parse(text.as_str()).map_err(|e| format!("{}: {}", path.display(), e))?;
+		index.read_level_names(|key| {
+			let level_path: PathBuf = get_worlds_root().join(format!("{:02}", key.world_id)).join(format!("{:02}.lvlb", key.level_id));
+			return read_level_name_from_file(&level_path);
+		});
+		return Ok(index);
+	}
+
+	// the rom has no file system; the index is compiled in next to the levels it lists
+	#[cfg(not(feature = "pc"))]
+	pub fn load() -> Result<WorldIndex, String> {
+		let mut index: WorldIndex = WorldIndex::parse(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../worlds/worlds.txt")))?;
+		index.read_level_names(|key| read_level_name(&key.get_level_name()));
+		return Ok(index);
+	}
+
+	// the names come from the levels' own headers, so the index doesn't repeat them; a level whose
+	// header has none, or can't be read, shows its number
+	fn read_level_names<FRead>(&mut self, read_name: FRead)
+	where
+		FRead: Fn(LevelKey) -> Result<String, String>,
+	{
+		for level in self.levels.iter_mut() {
+			let name: String = read_name(level.key).unwrap_or_else(|e| {
+				debugln!("level {:02}/{:02} name read failed: {}", level.key.world_id, level.key.level_id, e);
+				return String::new();
+			});
+			level.name = if name.is_empty() {
+				format!("{:02}/{:02}", level.key.world_id, level.key.level_id)
+			} else {
+				name
+			};
+		}
+		return;
+	}
+
+	// where a new game starts
+	pub fn get_first_level(&self) -> Option<LevelKey> {
+		return self.levels.first().map(|l| l.key);
+	}
+
+	pub fn get_level(&self, key: LevelKey) -> Option<&WorldLevelEntry> {
+		return self.levels.iter().find(|l| l.key == key);
+	}
+
+	pub fn get_world_level_count(&self, world_id: u16) -> usize {
+		return self.levels.iter().filter(|l| l.key.world_id == world_id).count();
+	}
+
+	// the column'th level of a world, in index order
+	pub fn get_world_level(&self, world_id: u16, column: usize) -> Option<&WorldLevelEntry> {
+		return self.levels.iter().filter(|l| l.key.world_id == world_id).nth(column);
+	}
+
+	pub fn is_unlocked(&self, key: LevelKey, progress: &Progress) -> bool {
+		let Some(level) = self.get_level(key) else {
+			return false;
+		};
+		return match level.unlock {
+			UnlockRule::Start => true,
+			UnlockRule::AfterLevel(other) => progress.is_level_completed(other),
+			UnlockRule::AfterWorld(world_id) => self.levels.iter().filter(|l| l.key.world_id == world_id).all(|l| progress.is_level_completed(l.key)),
+		};
+	}
+
+	// worlds.txt's format is in world_list.rs; level names are left empty for load() to read from
+	// the levels
+	pub fn parse(text: &str) -> Result<WorldIndex, String> {
+		let list: WorldList = parse_world_list(text)?;
+
+		let mut index: WorldIndex = WorldIndex::empty();
+		for world in &list.worlds {
+			index.worlds.push(WorldEntry {
+				world_id: world.world_id,
+				name: String::from(world.name),
+			});
+		}
+		for level in &list.levels {
+			index.levels.push(WorldLevelEntry {
+				key: LevelKey {
+					world_id: level.world_id,
+					level_id: level.level_id,
+				},
+				name: String::new(),
+				unlock: match level.unlock {
+					WorldListUnlock::Start => UnlockRule::Start,
+					WorldListUnlock::AfterLevel(world_id, level_id) => UnlockRule::AfterLevel(LevelKey { world_id, level_id }),
+					WorldListUnlock::AfterWorld(world_id) => UnlockRule::AfterWorld(world_id),
+				},
+			});
+		}
+
+		return Ok(index);
+	}
+}
@@ -0,0 +1,118 @@
+#[cfg(feature = "gba")]
+use alloc::{format, string::String, vec::Vec};
+
+// worlds/worlds.txt as written. world_index.rs turns it into the level select screen and build.rs
+// includes this file to embed the same levels into the gba rom, so it uses nothing from the crate
+pub struct WorldListWorld<'a> {
+	pub world_id: u16,
+	pub name: &'a str,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WorldListUnlock {
+	Start,
+	// world, level
+	AfterLevel(u16, u16),
+	AfterWorld(u16),
+}
+
+pub struct WorldListLevel {
+	pub world_id: u16,
+	pub level_id: u16,
+	pub unlock: WorldListUnlock,
+}
+
+pub struct WorldList<'a> {
+	pub worlds: Vec<WorldListWorld<'a>>,
+	pub levels: Vec<WorldListLevel>,
+}
+
+// `key = value` lines, `#` comments:
+//   world = 01, Meadow
+//   level = 01/02, after 01/01
+pub fn parse_world_list(text: &str) -> Result<WorldList<'_>, String> {
+	let mut list: WorldList = WorldList {
+		worlds: Vec::new(),
+		levels: Vec::new(),
+	};
+
+	for (line_index, raw_line) in text.lines().enumerate() {
+		let line_number: usize = line_index + 1;
+		let line: &str = raw_line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+
+		let Some(eq_index) = line.find('=') else {
+			return Err(format!("invalid line {}: missing '='", line_number));
+		};
+		let name: &str = line[..eq_index].trim();
+		let value: &str = line[eq_index + 1..].trim();
+		let fields: Vec<&str> = value.split(',').map(|f| f.trim()).collect();
+
+		match name {
+			"world" => {
+				if fields.len() != 2 {
+					return Err(format!("expected world, name at line {}", line_number));
+				}
+				let world_id: u16 = fields[0]
+					.parse::<u16>()
+					.map_err(|_| format!("invalid world '{}' at line {}", fields[0], line_number))?;
+				if list.worlds.iter().any(|w| w.world_id == world_id) {
+					return Err(format!("world {:02} listed twice at line {}", world_id, line_number));
+				}
+				list.worlds.push(WorldListWorld { world_id, name: fields[1] });
+			}
+			"level" => {
+				if fields.len() != 2 {
+					return Err(format!("expected world/level, unlock rule at line {}", line_number));
+				}
+				let Some((world_id, level_id)) = parse_level_pair(fields[0]) else {
+					return Err(format!("expected world/level at line {}", line_number));
+				};
+				if !list.worlds.iter().any(|w| w.world_id == world_id) {
+					return Err(format!("level {} comes before its world at line {}", fields[0], line_number));
+				}
+				if list.levels.iter().any(|l| l.world_id == world_id && l.level_id == level_id) {
+					return Err(format!("level {} listed twice at line {}", fields[0], line_number));
+				}
+				list.levels.push(WorldListLevel {
+					world_id,
+					level_id,
+					unlock: parse_unlock(fields[1], line_number)?,
+				});
+			}
+			_ => return Err(format!("unknown entry '{}' at line {}", name, line_number)),
+		}
+	}
+
+	return Ok(list);
+}
+
+// "01/02" -> (1, 2)
+fn parse_level_pair(text: &str) -> Option<(u16, u16)> {
+	let (world_name, level_name) = text.trim().split_once('/')?;
+	let world_id: u16 = world_name.trim().parse::<u16>().ok()?;
+	let level_id: u16 = level_name.trim().parse::<u16>().ok()?;
+	return Some((world_id, level_id));
+}
+
+// "start", "after 01/02" or "world 01"
+fn parse_unlock(value: &str, line_number: usize) -> Result<WorldListUnlock, String> {
+	if value == "start" {
+		return Ok(WorldListUnlock::Start);
+	}
+	if let Some(level_name) = value.strip_prefix("after ") {
+		return parse_level_pair(level_name)
+			.map(|(world_id, level_id)| WorldListUnlock::AfterLevel(world_id, level_id))
+			.ok_or_else(|| format!("invalid level '{}' at line {}", level_name, line_number));
+	}
+	if let Some(world_name) = value.strip_prefix("world ") {
+		return world_name
+			.trim()
+			.parse::<u16>()
+			.map(WorldListUnlock::AfterWorld)
+			.map_err(|_| format!("invalid world '{}' at line {}", world_name, line_number));
+	}
+	return Err(format!("unknown unlock rule '{}' at line {}", value, line_number));
+}
@@ -0,0 +1,46 @@
+use jumpy::{
+	platform::render::software::{
+		SoftwareRenderer,
+		screenshot::{compare_with_golden, render_level_frame},
+	},
+	runtime::assets::get_worlds_root,
+};
+use std::path::{Path, PathBuf};
+
+// the same frame and size as golden.bat; a deliberate visual change means new pngs in game/golden
+const GOLDEN_FRAME: u32 = 120;
+const GOLDEN_SIZE: (u32, u32) = (640, 360);
+
+// renders worlds/WW/LL.lvlb headless and compares it with game/golden/WW_LL.png; a mismatch saves
+// the frame to target/golden_WW_LL.png to look at
+fn check_golden(world: &str, level: &str) {
+	let level_path: PathBuf = get_worlds_root().join(world).join(format!("{}.lvlb", level));
+	let golden_path: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("golden").join(format!("{}_{}.png", world, level));
+
+	let renderer: SoftwareRenderer = render_level_frame(&level_path, GOLDEN_FRAME, GOLDEN_SIZE).unwrap_or_else(|e| panic!("{}: {}", level_path.display(), e));
+	if let Err(e) = compare_with_golden(renderer.framebuffer.image(), &golden_path) {
+		let actual_path: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("target").join(format!("golden_{}_{}.png", world, level));
+		let _ = renderer.save_screenshot(&actual_path);
+		panic!("{} (frame saved to {})", e, actual_path.display());
+	}
+}
+
+#[test]
+fn golden_00_01() {
+	check_golden("00", "01");
+}
+
+#[test]
+fn golden_00_02() {
+	check_golden("00", "02");
+}
+
+#[test]
+fn golden_01_01() {
+	check_golden("01", "01");
+}
+
+#[test]
+fn golden_02_01() {
+	check_golden("02", "01");
+}
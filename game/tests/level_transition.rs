@@ -0,0 +1,103 @@
+use jumpy::{
+	platform::timer::{
+		backend::TimerBackend,
+		fixed_step::{FixedStep, SIMULATION_HZ},
+	},
+	runtime::{
+		game_mode::GameMode,
+		transition::{TRANSITION_CLOSE_TICKS, TRANSITION_OPEN_TICKS, TransitionEffect, TransitionPhase, TransitionState},
+	},
+};
+use std::cell::Cell;
+
+// a clock that only moves when the test says so, in microseconds
+struct ManualTimer {
+	now_micros: Cell<u64>,
+}
+
+impl TimerBackend for ManualTimer {
+	fn now_ticks(&self) -> u64 {
+		return self.now_micros.get();
+	}
+
+	fn ticks_per_second(&self) -> u64 {
+		return 1_000_000;
+	}
+
+	fn sleep_ms(&self, _ms: u32) {}
+}
+
+// the LevelTransition mode's frame in Game::update: advance the fixed step, tick the transition
+// that many times, then reset the fixed step if the mode doesn't run on it. returns the frames
+// taken to reach the loading phase, or None if it never does
+fn frames_until_loading(frame_micros: u64, max_frames: u32) -> Option<u32> {
+	let timer: ManualTimer = ManualTimer { now_micros: Cell::new(0) };
+	let mut fixed_step: FixedStep = FixedStep::new(&timer, SIMULATION_HZ);
+	let mut transition: TransitionState = TransitionState::new(TransitionEffect::Iris, false);
+
+	for frame in 1..=max_frames {
+		timer.now_micros.set(timer.now_micros.get() + frame_micros);
+		for _ in 0..fixed_step.advance(&timer) {
+			transition.tick();
+		}
+		if !GameMode::LevelTransition.runs_on_fixed_step() {
+			fixed_step.reset(&timer);
+		}
+		if transition.phase == TransitionPhase::Loading {
+			return Some(frame);
+		}
+	}
+	return None;
+}
+
+#[test]
+fn transition_closes_with_frames_shorter_than_a_step() {
+	let close_micros: u64 = TRANSITION_CLOSE_TICKS as u64 * 1_000_000 / SIMULATION_HZ;
+	for frame_micros in [1_000, 6_900, 8_300, 16_000] {
+		let frames: Option<u32> = frames_until_loading(frame_micros, 100_000);
+		let Some(frames) = frames else {
+			panic!("transition never closed at {} us frames", frame_micros);
+		};
+		// a frame's worth of slack for the step that straddles frames
+		assert!(
+			frames as u64 * frame_micros <= close_micros + frame_micros + 1_000_000 / SIMULATION_HZ,
+			"transition took {} frames of {} us",
+			frames,
+			frame_micros
+		);
+	}
+}
+
+#[test]
+fn transition_closes_with_long_frames() {
+	assert_eq!(frames_until_loading(1_000_000 / SIMULATION_HZ + 1, 1_000), Some(TRANSITION_CLOSE_TICKS as u32));
+}
+
+#[test]
+fn transition_opens_and_finishes() {
+	let mut transition: TransitionState = TransitionState::new(TransitionEffect::Fade, true);
+	transition.start_opening();
+	let mut ticks: u16 = 0;
+	while !transition.tick() {
+		ticks += 1;
+		assert!(ticks < TRANSITION_OPEN_TICKS, "transition never opened");
+	}
+	assert_eq!(ticks + 1, TRANSITION_OPEN_TICKS);
+}
+
+#[test]
+fn only_play_and_transitions_keep_the_fixed_step() {
+	assert!(GameMode::Playing.runs_on_fixed_step());
+	assert!(GameMode::LevelTransition.runs_on_fixed_step());
+	for mode in [
+		GameMode::Title,
+		GameMode::Paused,
+		GameMode::Book,
+		GameMode::Inventory,
+		GameMode::LevelSelect,
+		GameMode::Results,
+		GameMode::GameOver,
+	] {
+		assert!(!mode.runs_on_fixed_step(), "{:?} should reset the fixed step", mode);
+	}
+}
@@ -0,0 +1,43 @@
+use jumpy::runtime::{
+	inventory::Inventory,
+	progress::{LevelKey, Progress},
+	speedrun::LevelTime,
+};
+
+fn parse_progress(text: &str) -> Progress {
+	let mut inventory: Inventory = Inventory::new();
+	return Progress::parse(text, &mut inventory).unwrap_or_else(|e| panic!("{}", e));
+}
+
+fn assert_level_01_02(progress: &Progress) {
+	let key: LevelKey = LevelKey { world_id: 1, level_id: 2 };
+	assert_eq!(progress.levels.len(), 1, "01/02 is in progress.txt once");
+
+	let Some(level) = progress.get_level(key) else {
+		panic!("01/02 is missing");
+	};
+	assert_eq!(level.pickup_count, 4);
+	assert_eq!(level.collected_pickups, vec![3, 5]);
+	assert_eq!(level.opened_doors, vec![7]);
+	assert!(level.is_completed);
+	assert_eq!(
+		level.best_time,
+		Some(LevelTime {
+			igt_steps: 1234,
+			rta_millis: 21000
+		})
+	);
+}
+
+// a level's "level" and "best" lines fill the same entry whichever comes first
+#[test]
+fn level_and_best_lines_in_either_order() {
+	let level_first: Progress = parse_progress("coins = 0\nlevel = 01/02, 4, 3 5, 7, completed\nbest = 01/02, 1234, 21000\n");
+	assert_level_01_02(&level_first);
+
+	let best_first: Progress = parse_progress("coins = 0\nbest = 01/02, 1234, 21000\nlevel = 01/02, 4, 3 5, 7, completed\n");
+	assert_level_01_02(&best_first);
+
+	let inventory: Inventory = Inventory::new();
+	assert_level_01_02(&parse_progress(&best_first.to_text(&inventory)));
+}
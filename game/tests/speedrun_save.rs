@@ -0,0 +1,49 @@
+use jumpy::{
+	platform::{audio::null_audio::NullAudio, level_loader::load_level_from_file},
+	runtime::{
+		inventory::Inventory,
+		level::Level,
+		progress::{LevelKey, Progress},
+		session::Session,
+		speedrun::{Ghost, LevelTime},
+		state::State,
+	},
+};
+use std::path::{Path, PathBuf};
+
+// a record's time and its ghost land on disk together, whether or not another level starts after it
+#[test]
+fn new_best_time_and_its_ghost_are_saved_together() {
+	// the config folder follows XDG_CONFIG_HOME; this test is its own process, so nothing else reads it
+	let config_home: PathBuf = std::env::temp_dir().join(format!("jumpy_speedrun_save_{}", std::process::id()));
+	let _ = std::fs::remove_dir_all(&config_home);
+	unsafe {
+		std::env::set_var("XDG_CONFIG_HOME", &config_home);
+	}
+
+	let key: LevelKey = LevelKey { world_id: 1, level_id: 1 };
+	let level_path: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("../worlds/01/01.lvlb");
+	let level: Level = load_level_from_file(&level_path).unwrap_or_else(|e| panic!("{}", e));
+	let mut state: State = State::new(level, Box::new(NullAudio::new()));
+	state.spawn_level_entities();
+
+	let mut session: Session = Session::new();
+	session.speedrun.start_level(None);
+	for _ in 0..90 {
+		session.speedrun.step(&state, false);
+	}
+	session.speedrun.finish_level(key, &mut session.progress);
+	session.save_progress().unwrap_or_else(|e| panic!("{}", e));
+
+	let mut inventory: Inventory = Inventory::new();
+	let progress: Progress = Progress::load(&mut inventory);
+	let best_time: Option<LevelTime> = progress.get_level(key).and_then(|l| l.best_time);
+	assert_eq!(best_time.map(|t| t.igt_steps), Some(90));
+
+	let Some(ghost) = Ghost::load(key) else {
+		panic!("the record's ghost was not saved");
+	};
+	assert_eq!(ghost.positions.len(), 90);
+
+	let _ = std::fs::remove_dir_all(&config_home);
+}
@@ -0,0 +1,69 @@
+// tile.rs stands alone, so it is compiled straight into this test
+#[allow(dead_code)]
+#[path = "../src/tile.rs"]
+mod tile;
+
+use std::{fs, path::PathBuf};
+use tile::TileKind;
+
+// GrassTop -> grass_top, the spelling tiles.ids.txt uses
+fn get_snake_case_name(kind: TileKind) -> String {
+	let mut out: String = String::new();
+	for (i, c) in format!("{:?}", kind).chars().enumerate() {
+		if c.is_ascii_uppercase() {
+			if i > 0 {
+				out.push('_');
+			}
+			out.push(c.to_ascii_lowercase());
+		} else {
+			out.push(c);
+		}
+	}
+	return out;
+}
+
+fn read_tile_ids() -> Vec<(String, u8)> {
+	let path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.join("..")
+		.join("assets")
+		.join("tiles")
+		.join("tiles.ids.txt");
+	let text: String = fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+
+	let mut ids: Vec<(String, u8)> = Vec::new();
+	for line in text.lines() {
+		let line: &str = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+		let Some((name, id_text)) = line.split_once('=') else {
+			panic!("tiles.ids.txt line '{}' is not name=id", line);
+		};
+		let id: u8 = id_text
+			.trim()
+			.parse::<u8>()
+			.unwrap_or_else(|e| panic!("tiles.ids.txt id '{}' of {}: {}", id_text, name, e));
+		ids.push((name.trim().to_string(), id));
+	}
+	return ids;
+}
+
+// levelc writes the ids from tiles.ids.txt into the .lvlb and the game reads them back with from_u8
+#[test]
+fn tile_ids_match_tile_kinds() {
+	let ids: Vec<(String, u8)> = read_tile_ids();
+	assert!(!ids.is_empty());
+
+	for (name, id) in &ids {
+		let kind: TileKind = TileKind::from_u8(*id);
+		assert_eq!(kind as u8, *id, "tiles.ids.txt {}={} has no TileKind", name, id);
+		assert_eq!(&get_snake_case_name(kind), name, "tiles.ids.txt {}={} is TileKind::{:?}", name, id, kind);
+	}
+
+	for id in 0..=u8::MAX {
+		let kind: TileKind = TileKind::from_u8(id);
+		if kind as u8 == id {
+			assert!(ids.iter().any(|(_, i)| *i == id), "TileKind::{:?} ({}) is missing from tiles.ids.txt", kind, id);
+		}
+	}
+}
@@ -0,0 +1,50 @@
+use jumpy::runtime::{
+	progress::LevelKey,
+	world_index::{UnlockRule, WorldIndex},
+};
+
+fn get_level_name(index: &WorldIndex, world_id: u16, level_id: u16) -> &str {
+	let key: LevelKey = LevelKey { world_id, level_id };
+	let Some(level) = index.get_level(key) else {
+		panic!("{:02}/{:02} is not in the world index", world_id, level_id);
+	};
+	return level.name.as_str();
+}
+
+// worlds.txt doesn't name its levels; the names are the `name` each .level header compiled into its .lvlb
+#[test]
+fn level_names_come_from_the_level_headers() {
+	let index: WorldIndex = WorldIndex::load().unwrap_or_else(|e| panic!("{}", e));
+	assert_eq!(get_level_name(&index, 1, 1), "first jump");
+	assert_eq!(get_level_name(&index, 2, 1), "Happy");
+	assert_eq!(get_level_name(&index, 0, 1), "debug");
+	assert_eq!(get_level_name(&index, 0, 2), "Library");
+}
+
+// build.rs embeds levels through the same reader, so the rom and the level select agree on these
+#[test]
+fn world_index_accepts_and_rejects() {
+	let index: WorldIndex =
+		WorldIndex::parse("# worlds\nworld = 01, Meadow\nlevel = 01/01, start\nlevel = 01/02, after 01/01\nworld = 02, Cave\nlevel = 02/01, world 01\n")
+			.unwrap_or_else(|e| panic!("{}", e));
+	assert_eq!(index.worlds.len(), 2);
+	assert_eq!(index.worlds[1].name, "Cave");
+	assert_eq!(index.levels.len(), 3);
+	assert_eq!(index.levels[0].unlock, UnlockRule::Start);
+	assert_eq!(index.levels[1].unlock, UnlockRule::AfterLevel(LevelKey { world_id: 1, level_id: 1 }));
+	assert_eq!(index.levels[2].unlock, UnlockRule::AfterWorld(1));
+
+	let rejected: [&str; 8] = [
+		"world 01, Meadow",
+		"world = 01",
+		"world = 01, Meadow\nworld = 01, Again",
+		"level = 01/01, start",
+		"world = 01, Meadow\nlevel = 01-01, start",
+		"world = 01, Meadow\nlevel = 01/01, start\nlevel = 01/01, start",
+		"world = 01, Meadow\nlevel = 01/01, later",
+		"stage = 01",
+	];
+	for text in rejected {
+		assert!(WorldIndex::parse(text).is_err(), "{:?} parsed", text);
+	}
+}
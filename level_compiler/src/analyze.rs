@@ -0,0 +1,534 @@
+use crate::{
+	compile::{is_one_way_tile_id, is_solid_tile_id, split_row_cells},
+	player_physics,
+	runtime::*,
+	source::*,
+};
+
+use std::collections::{HashSet, VecDeque};
+
+// the game's movement values, from game/src/runtime/player_physics.rs
+pub struct PhysicsSettings {
+	pub gravity: f32,
+	pub jump_velocity: f32,
+	pub jump_cut_multiplier: f32,
+	pub coyote_frames_max: u8,
+	pub move_speed: f32,
+	pub wall_jump_push: f32,
+}
+
+impl PhysicsSettings {
+	pub fn new() -> Self {
+		return Self {
+			gravity: player_physics::GRAVITY,
+			jump_velocity: player_physics::JUMP_VELOCITY,
+			jump_cut_multiplier: player_physics::JUMP_CUT_MULTIPLIER,
+			coyote_frames_max: player_physics::COYOTE_FRAMES_MAX,
+			move_speed: player_physics::MOVE_SPEED,
+			wall_jump_push: player_physics::WALL_JUMP_PUSH,
+		};
+	}
+
+	// the most forgiving assist mode: its extra frames lengthen coyote time
+	pub fn with_assist() -> Self {
+		let mut settings: PhysicsSettings = PhysicsSettings::new();
+		settings.coyote_frames_max = settings.coyote_frames_max.saturating_add(player_physics::ASSIST_EXTRA_JUMP_FRAMES_MAX);
+		return settings;
+	}
+}
+
+pub struct UnreachableTrigger {
+	pub index: usize,
+	pub description: String,
+}
+
+pub struct Analysis {
+	pub width: usize,
+	pub height: usize,
+	pub visit_counts: Vec<u32>,
+	pub unreachable: Vec<UnreachableTrigger>,
+	pub explored_states: usize,
+	pub hit_state_limit: bool,
+}
+
+#[derive(Clone, Copy)]
+struct SimState {
+	x: f32,
+	y: f32,
+	vy: f32,
+	coyote: u8,
+	// set by a wall jump, replaces the next frame's horizontal input
+	wall_push: f32,
+}
+
+// what the player holds for one frame, and the player's own jump and gravity multipliers
+#[derive(Clone, Copy)]
+struct StepInput {
+	dx: f32,
+	jump: bool,
+	cut: bool,
+	gravity_multiplier: f32,
+	jump_velocity: f32,
+}
+
+// read-only view of the compiled action layer, same lookup rules as Level::get_tile_id_at_layer
+struct TileView<'a> {
+	tiles: &'a [u8],
+	offset: usize,
+	width: i32,
+	height: i32,
+	tile_width: f32,
+	tile_height: f32,
+}
+
+impl<'a> TileView<'a> {
+	fn get_tile_id(&self, tx: i32, ty: i32) -> u8 {
+		if tx < 0 || ty < 0 || tx >= self.width || ty >= self.height {
+			return 0;
+		}
+
+		let index: usize = self.offset + (ty * self.width + tx) as usize;
+		return self.tiles.get(index).copied().unwrap_or(0);
+	}
+
+	// doors count as locked, so whatever is behind one shows up as unreachable
+	fn is_solid(&self, tx: i32, ty: i32) -> bool {
+		return is_solid_tile_id(self.get_tile_id(tx, ty));
+	}
+
+	fn is_one_way(&self, tx: i32, ty: i32) -> bool {
+		return is_one_way_tile_id(self.get_tile_id(tx, ty));
+	}
+
+	fn is_solid_f32(&self, x: f32, y: f32) -> bool {
+		return self.is_solid((x / self.tile_width) as i32, (y / self.tile_height) as i32);
+	}
+}
+
+const PLAYER_HALF: f32 = 8.0;
+const OUT_OF_BOUNDS_MARGIN: f32 = 64.0;
+const MAX_STATES: usize = 10_000_000;
+
+// explores every input sequence (left/right/none, jump, jump release) from player_start
+pub fn analyze_level(source: &LevelSource, compiled: &CompiledLevel, settings: &PhysicsSettings) -> Result<Analysis, String> {
+	let Some(player) = compiled.entities.iter().find(|e| e.kind == EntityKind::Player as u8) else {
+		return Err("level has no player_start entity".to_string());
+	};
+
+	// same rule as Level::get_action_layer_index
+	let action_layer: usize = if compiled.header.layer_count == 1 { 0 } else { 1 };
+
+	let view: TileView = TileView {
+		tiles: &compiled.tiles,
+		offset: action_layer * compiled.header.tiles_per_layer as usize,
+		width: compiled.header.width as i32,
+		height: compiled.header.height as i32,
+		tile_width: compiled.header.tile_width as f32,
+		tile_height: compiled.header.tile_height as f32,
+	};
+
+	// runtime keeps only the integer part of the Q4.4 value (see get_gravity_from_file)
+	let gravity_multiplier: f32 = (player.gravity_multiplier / 16) as f32;
+	let jump_velocity: f32 = settings.jump_velocity * player.jump_multiplier as f32;
+
+	let mut start: SimState = SimState {
+		x: (player.left as f32 + 0.5) * view.tile_width,
+		y: (player.top as f32 + 0.5) * view.tile_height,
+		vy: 0.0,
+		coyote: 0,
+		wall_push: 0.0,
+	};
+
+	if gravity_multiplier > 0.0 {
+		scan_down_to_ground(&view, &mut start, 30);
+	}
+
+	let width: usize = view.width as usize;
+	let height: usize = view.height as usize;
+	let mut visit_counts: Vec<u32> = vec![0; width * height];
+	let mut reached: Vec<bool> = vec![false; compiled.triggers.len()];
+
+	let mut seen: HashSet<u64> = HashSet::new();
+	let mut queue: VecDeque<SimState> = VecDeque::new();
+	seen.insert(state_key(&start));
+	queue.push_back(start);
+
+	let mut hit_state_limit: bool = false;
+
+	while let Some(state) = queue.pop_front() {
+		mark_visit(&view, &state, &mut visit_counts);
+		mark_triggers(compiled, &view, &state, &mut reached);
+
+		if seen.len() >= MAX_STATES {
+			hit_state_limit = true;
+			continue;
+		}
+
+		for dx in [-settings.move_speed, 0.0, settings.move_speed] {
+			for jump in [false, true] {
+				for cut in [false, true] {
+					let input: StepInput = StepInput {
+						dx,
+						jump,
+						cut,
+						gravity_multiplier,
+						jump_velocity,
+					};
+					let Some(next) = step(&view, settings, state, input) else {
+						continue;
+					};
+
+					if seen.insert(state_key(&next)) {
+						queue.push_back(next);
+					}
+				}
+			}
+		}
+	}
+
+	let mut unreachable: Vec<UnreachableTrigger> = Vec::new();
+	for (index, trigger) in source.triggers.iter().enumerate() {
+		if matches!(trigger.kind, TriggerKindSource::CameraZone { .. } | TriggerKindSource::Light { .. }) {
+			continue;
+		}
+
+		if index < reached.len() && !reached[index] {
+			unreachable.push(UnreachableTrigger {
+				index,
+				description: describe_trigger(trigger),
+			});
+		}
+	}
+
+	return Ok(Analysis {
+		width,
+		height,
+		visit_counts,
+		unreachable,
+		explored_states: seen.len(),
+		hit_state_limit,
+	});
+}
+
+// quantized so that near-identical arcs collapse into one search node
+fn state_key(state: &SimState) -> u64 {
+	let x: u64 = (state.x.round() as i32 as u16) as u64;
+	let y: u64 = (state.y.round() as i32 as u16) as u64;
+	let vy: u64 = ((state.vy * 2.0).round() as i32 as u16) as u64;
+	let coyote: u64 = state.coyote.div_ceil(4) as u64;
+	let wall_push: u64 = (state.wall_push.signum() as i32 + 1) as u64;
+	return (x << 40) | (y << 24) | (vy << 8) | (wall_push << 6) | coyote;
+}
+
+// one frame of run_play_step input handling, gravity::apply and move_and_collide for the player.
+// the game jumps before it moves, this moves first, so a wall jump's push lands one frame late
+fn step(view: &TileView, settings: &PhysicsSettings, state: SimState, input: StepInput) -> Option<SimState> {
+	let mut x: f32 = state.x;
+	let mut y: f32 = state.y;
+	let mut vx: f32 = if state.wall_push != 0.0 { state.wall_push } else { input.dx };
+	let mut vy: f32 = state.vy;
+	let mut coyote: u8 = state.coyote;
+	let mut wall_push: f32 = 0.0;
+
+	// releasing jump while rising
+	if input.cut {
+		if vy >= 0.0 {
+			return None;
+		}
+		vy *= settings.jump_cut_multiplier;
+	}
+
+	vy += settings.gravity * input.gravity_multiplier;
+
+	let prev_bottom: f32 = y + PLAYER_HALF;
+	x += vx;
+	y += vy;
+
+	resolve_wall_collision(view, &mut x, y, &mut vx);
+	resolve_ceiling_collision(view, x, &mut y, &mut vy);
+	resolve_floor_collision(view, x, &mut y, &mut vy, prev_bottom);
+
+	let grounded: bool = is_grounded(view, x, y);
+	let on_left: bool = on_wall(view, x, y, -1.0);
+	let on_right: bool = on_wall(view, x, y, 1.0);
+
+	if grounded {
+		coyote = settings.coyote_frames_max;
+	} else {
+		coyote = coyote.saturating_sub(1);
+	}
+
+	// same gate and push as try_jump
+	if input.jump {
+		if !grounded && coyote == 0 && !on_left && !on_right {
+			return None;
+		}
+		if !grounded && coyote == 0 {
+			wall_push = if on_left { settings.wall_jump_push } else { -settings.wall_jump_push };
+		}
+		vy = input.jump_velocity;
+		coyote = 0;
+	}
+
+	let level_width_pixels: f32 = view.width as f32 * view.tile_width;
+	let level_height_pixels: f32 = view.height as f32 * view.tile_height;
+
+	let max_x: f32 = (level_width_pixels - PLAYER_HALF).max(PLAYER_HALF);
+	x = x.clamp(PLAYER_HALF, max_x);
+
+	let top: f32 = y - PLAYER_HALF;
+	let bottom: f32 = y + PLAYER_HALF;
+	if bottom < -OUT_OF_BOUNDS_MARGIN || top > level_height_pixels + OUT_OF_BOUNDS_MARGIN {
+		return None;
+	}
+
+	return Some(SimState { x, y, vy, coyote, wall_push });
+}
+
+fn resolve_wall_collision(view: &TileView, x: &mut f32, y: f32, vx: &mut f32) {
+	if *vx == 0.0 {
+		return;
+	}
+
+	let inset: f32 = 0.5;
+	let probe_x: f32 = if *vx > 0.0 { *x + PLAYER_HALF + 0.5 } else { *x - PLAYER_HALF - 0.5 };
+
+	let tx: i32 = (probe_x / view.tile_width) as i32;
+	let ty_top: i32 = ((y - PLAYER_HALF + inset) / view.tile_height) as i32;
+	let ty_middle: i32 = (y / view.tile_height) as i32;
+	let ty_bottom: i32 = ((y + PLAYER_HALF - inset) / view.tile_height) as i32;
+
+	let middle_blocks: bool = view.is_solid(tx, ty_middle) || view.is_one_way(tx, ty_middle);
+	let hit: bool = middle_blocks && (view.get_tile_id(tx, ty_top) != 0 || view.get_tile_id(tx, ty_middle) != 0 || view.get_tile_id(tx, ty_bottom) != 0);
+
+	if hit {
+		if *vx > 0.0 {
+			*x = (tx as f32) * view.tile_width - PLAYER_HALF;
+		} else {
+			*x = ((tx + 1) as f32) * view.tile_width + PLAYER_HALF;
+		}
+		*vx = 0.0;
+	}
+
+	return;
+}
+
+fn resolve_ceiling_collision(view: &TileView, x: f32, y: &mut f32, vy: &mut f32) {
+	if *vy >= 0.0 {
+		return;
+	}
+
+	let ty: i32 = ((*y - PLAYER_HALF - 0.5) / view.tile_height) as i32;
+	let tx_left: i32 = ((x - PLAYER_HALF + 0.5) / view.tile_width) as i32;
+	let tx_right: i32 = ((x + PLAYER_HALF - 0.5) / view.tile_width) as i32;
+
+	if view.is_solid(tx_left, ty) || view.is_solid(tx_right, ty) {
+		*y = ((ty + 1) as f32) * view.tile_height + PLAYER_HALF;
+		*vy = 0.0;
+	}
+
+	return;
+}
+
+fn resolve_floor_collision(view: &TileView, x: f32, y: &mut f32, vy: &mut f32, prev_bottom: f32) {
+	if *vy <= 0.0 {
+		return;
+	}
+
+	let bottom: f32 = *y + PLAYER_HALF;
+	let tile_top: i32 = ((bottom + 0.5) / view.tile_height) as i32;
+	let tile_left: i32 = ((x - PLAYER_HALF + 0.5) / view.tile_width) as i32;
+	let tile_right: i32 = ((x + PLAYER_HALF - 0.5) / view.tile_width) as i32;
+	let tile_surface: f32 = (tile_top as f32) * view.tile_height;
+
+	for tx in tile_left..=tile_right {
+		let solid: bool = view.is_solid(tx, tile_top);
+		let one_way_landing: bool = view.is_one_way(tx, tile_top) && prev_bottom <= tile_surface && bottom >= tile_surface;
+
+		if solid || one_way_landing {
+			*y = tile_surface - PLAYER_HALF;
+			*vy = 0.0;
+			return;
+		}
+	}
+
+	return;
+}
+
+fn is_grounded(view: &TileView, x: f32, y: f32) -> bool {
+	let eps: f32 = 0.05;
+	let probe_tile_y: i32 = ((y + PLAYER_HALF + eps) / view.tile_height) as i32;
+
+	for foot_x in [x - PLAYER_HALF + eps, x + PLAYER_HALF - eps] {
+		let tx: i32 = (foot_x / view.tile_width) as i32;
+		if view.is_solid(tx, probe_tile_y) || view.is_one_way(tx, probe_tile_y) {
+			return true;
+		}
+	}
+
+	return false;
+}
+
+fn on_wall(view: &TileView, x: f32, y: f32, direction: f32) -> bool {
+	let inset: f32 = 0.5;
+	let probe_x: f32 = x + direction * (PLAYER_HALF + inset);
+
+	return view.is_solid_f32(probe_x, y - PLAYER_HALF + inset) || view.is_solid_f32(probe_x, y) || view.is_solid_f32(probe_x, y + PLAYER_HALF - inset);
+}
+
+fn scan_down_to_ground(view: &TileView, state: &mut SimState, max_scan_tiles: i32) {
+	let mut ty: i32 = ((state.y + PLAYER_HALF + 0.5) / view.tile_height) as i32;
+	let tx_left: i32 = ((state.x - PLAYER_HALF + 0.5) / view.tile_width) as i32;
+	let tx_right: i32 = ((state.x + PLAYER_HALF - 0.5) / view.tile_width) as i32;
+
+	let mut steps: i32 = 0;
+	while steps <= max_scan_tiles && ty < view.height {
+		if view.is_solid(tx_left, ty) || view.is_solid(tx_right, ty) {
+			state.y = (ty as f32) * view.tile_height - PLAYER_HALF;
+			return;
+		}
+		ty += 1;
+		steps += 1;
+	}
+
+	return;
+}
+
+fn mark_visit(view: &TileView, state: &SimState, visit_counts: &mut [u32]) {
+	let start_tx: i32 = ((state.x - PLAYER_HALF) / view.tile_width).floor() as i32;
+	let end_tx: i32 = ((state.x + PLAYER_HALF - 0.001) / view.tile_width).floor() as i32;
+	let start_ty: i32 = ((state.y - PLAYER_HALF) / view.tile_height).floor() as i32;
+	let end_ty: i32 = ((state.y + PLAYER_HALF - 0.001) / view.tile_height).floor() as i32;
+
+	for ty in start_ty.max(0)..=end_ty.min(view.height - 1) {
+		for tx in start_tx.max(0)..=end_tx.min(view.width - 1) {
+			let index: usize = (ty * view.width + tx) as usize;
+			visit_counts[index] = visit_counts[index].saturating_add(1);
+		}
+	}
+
+	return;
+}
+
+// same strict overlap as engine_math::do_they_overlap; compiled trigger rects are stored x16
+fn mark_triggers(compiled: &CompiledLevel, view: &TileView, state: &SimState, reached: &mut [bool]) {
+	let player_left: f32 = state.x - PLAYER_HALF;
+	let player_right: f32 = state.x + PLAYER_HALF;
+	let player_top: f32 = state.y - PLAYER_HALF;
+	let player_bottom: f32 = state.y + PLAYER_HALF;
+
+	for (index, trigger) in compiled.triggers.iter().enumerate() {
+		if reached[index] {
+			continue;
+		}
+
+		let mut left: f32 = trigger.left as f32 / 16.0 * view.tile_width;
+		let mut top: f32 = trigger.top as f32 / 16.0 * view.tile_height;
+		let mut right: f32 = left + trigger.width as f32 / 16.0 * view.tile_width;
+		let mut bottom: f32 = top + trigger.height as f32 / 16.0 * view.tile_height;
+
+		// a door is solid, so like the game its trigger reaches half a tile past the door tiles
+		if trigger.kind == TriggerKind::Door as u8 {
+			left -= view.tile_width * 0.5;
+			top -= view.tile_height * 0.5;
+			right += view.tile_width * 0.5;
+			bottom += view.tile_height * 0.5;
+		}
+
+		if player_left < right && player_right > left && player_top < bottom && player_bottom > top {
+			reached[index] = true;
+		}
+	}
+
+	return;
+}
+
+fn describe_trigger(trigger: &TriggerSource) -> String {
+	let kind: String = match &trigger.kind {
+		TriggerKindSource::LevelExit { target, level, .. } => format!("level_exit to {}/{}", target, level),
+		TriggerKindSource::Message { text_id, .. } => format!("message \"{}\"", text_id),
+		TriggerKindSource::Pickup { pickup, .. } => format!("pickup \"{}\"", pickup),
+		TriggerKindSource::CameraZone { .. } => "camera_zone".to_string(),
+		TriggerKindSource::Light { .. } => "light".to_string(),
+		TriggerKindSource::Door { key, .. } => format!("door \"{}\"", key),
+	};
+
+	return format!("{} at left={} top={}", kind, trigger.left, trigger.top);
+}
+
+// one character per tile: solid and one-way tiles keep their level art, other tiles show
+// 1-9 for how often the player's box passed through them, or their art if it never did.
+// a note line goes first since wall jumps are only approximated
+pub fn render_heat_map(source: &LevelSource, compiled: &CompiledLevel, analysis: &Analysis) -> String {
+	let action_layer: usize = if compiled.header.layer_count == 1 { 0 } else { 1 };
+	let tile_offset: usize = action_layer * compiled.header.tiles_per_layer as usize;
+	let rows: &[String] = source.layers.get(action_layer).map(|l| l.rows.as_slice()).unwrap_or(&[]);
+
+	let mut cells: Vec<char> = vec![' '; analysis.width * analysis.height];
+	let max_count: u32 = analysis.visit_counts.iter().copied().max().unwrap_or(0).max(2);
+
+	for ty in 0..analysis.height {
+		// wide cells (cell_width > 1) show their first character
+		let row_chars: Vec<char> = rows
+			.get(ty)
+			.map(|r| split_row_cells(r, source.header.cell_width as usize).iter().map(|c| c.chars().next().unwrap_or('.')).collect())
+			.unwrap_or_default();
+
+		for tx in 0..analysis.width {
+			let index: usize = ty * analysis.width + tx;
+			let count: u32 = analysis.visit_counts[index];
+			let tile_id: u8 = compiled.tiles.get(tile_offset + index).copied().unwrap_or(0);
+			let blocks: bool = is_solid_tile_id(tile_id) || is_one_way_tile_id(tile_id);
+
+			if count > 0 && !blocks {
+				// log scale so a single pass still shows up next to the spawn area
+				let level: u32 = 1 + ((count as f32).ln() / (max_count as f32).ln() * 8.0) as u32;
+				cells[index] = char::from_digit(level.min(9), 10).unwrap_or('9');
+				continue;
+			}
+
+			let tile_char: char = row_chars.get(tx).copied().unwrap_or('.');
+			if tile_char != '.' {
+				cells[index] = tile_char;
+			}
+		}
+	}
+
+	for trigger in &source.triggers {
+		let marker: char = match &trigger.kind {
+			TriggerKindSource::LevelExit { .. } => 'X',
+			TriggerKindSource::Message { .. } => '?',
+			TriggerKindSource::Pickup { .. } => '$',
+			TriggerKindSource::Door { .. } => 'K',
+			// a room, not a place to reach
+			TriggerKindSource::CameraZone { .. } => continue,
+			TriggerKindSource::Light { .. } => continue,
+		};
+		set_marker(&mut cells, analysis, trigger.left as i32, trigger.top as i32, marker);
+	}
+
+	for entity in &source.entities {
+		if matches!(entity.kind, EntityKindSource::PlayerStart) {
+			set_marker(&mut cells, analysis, entity.left, entity.top, '@');
+		}
+	}
+
+	let mut out: String = String::with_capacity((analysis.width + 1) * (analysis.height + 1));
+	out.push_str("# wall jumps are approximated: their push lands one frame late\n");
+	for ty in 0..analysis.height {
+		let row: String = cells[ty * analysis.width..(ty + 1) * analysis.width].iter().collect();
+		out.push_str(row.trim_end());
+		out.push('\n');
+	}
+
+	return out;
+}
+
+fn set_marker(cells: &mut [char], analysis: &Analysis, tx: i32, ty: i32, marker: char) {
+	if tx < 0 || ty < 0 || tx as usize >= analysis.width || ty as usize >= analysis.height {
+		return;
+	}
+
+	cells[ty as usize * analysis.width + tx as usize] = marker;
+	return;
+}
@@ -104,13 +104,18 @@ fn write_header(buffer: &mut Vec<u8>, h: &FileHeader) -> Result<(), String> {
 	write_u8(buffer, h.background_id)?;
 	write_u8(buffer, h.gravity)?;
 	write_u8(buffer, h.music_id)?;
-	write_u8(buffer, h.reserved1)?;
+	write_u8(buffer, h.darkness)?;
 	write_u32(buffer, h.tiles_per_layer)?;
 	write_u32(buffer, h.tile_count_total)?;
 	write_u32(buffer, h.offset_layers)?;
 	write_u32(buffer, h.offset_entities)?;
 	write_u32(buffer, h.offset_triggers)?;
 	write_u32(buffer, h.offset_tiles)?;
+	for threshold in h.rank_thresholds {
+		write_u32(buffer, threshold)?;
+	}
+	write_u8(buffer, h.name_len)?;
+	buffer.extend_from_slice(&h.name);
 
 	return Ok(());
 }
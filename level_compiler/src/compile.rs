@@ -1,6 +1,6 @@
-const HEADER_SIZE: u16 = 51;
+const HEADER_SIZE: u16 = 100;
 
-use crate::{binary_writer::serialize_level, message_registry::MessageRegistry, runtime::*, source::*, text_parse::TriggerActivationMode};
+use crate::{message_registry::MessageRegistry, runtime::*, source::*, text_parse::TriggerActivationMode};
 
 use std::{
 	collections::HashMap,
@@ -27,12 +27,6 @@ fn clamp_u16(v: i32) -> u16 {
 	return v as u16;
 }
 
-pub fn compile_and_serialize(source: &LevelSource) -> Result<Vec<u8>, String> {
-	let compiled = compile_level(source)?;
-	let bytes = serialize_level(&compiled)?;
-	return Ok(bytes);
-}
-
 fn resolve_world_id(text: &str) -> Result<u16, String> {
 	let id: u16 = text.trim().parse::<u16>().map_err(|_| format!("invalid target world id '{}'", text))?;
 	return Ok(id);
@@ -50,6 +44,7 @@ pub fn compile_level(source: &LevelSource) -> Result<CompiledLevel, String> {
 
 	let width = source.header.width as usize;
 	let height = source.header.height as usize;
+	let cell_width = source.header.cell_width.max(1) as usize;
 
 	if source.layers.is_empty() {
 		return Err("level must have at least one layer".to_string());
@@ -62,8 +57,8 @@ pub fn compile_level(source: &LevelSource) -> Result<CompiledLevel, String> {
 
 		for (y, row) in layer.rows.iter().enumerate() {
 			let count = row.chars().count();
-			if count != width {
-				return Err(format!("layer {} row {} has {} columns, expected {}", i, y, count, width));
+			if count != width * cell_width {
+				return Err(format!("layer {} row {} has {} characters, expected {} ({} tiles x cell_width {})", i, y, count, width * cell_width, width, cell_width));
 			}
 		}
 	}
@@ -73,7 +68,8 @@ pub fn compile_level(source: &LevelSource) -> Result<CompiledLevel, String> {
 	let pickup_ids_path: PathBuf = get_asset_root().join("pickup-maps").join("pickups.ids.txt");
 	let pickup_ids: HashMap<String, u16> = load_ids_map(&pickup_ids_path)?;
 
-	let tile_palette = build_tile_palette();
+	let tile_registry: HashMap<String, u16> = load_tile_registry()?;
+	let tile_palette: HashMap<String, u8> = build_tile_palette(source, &tile_registry)?;
 	let layer_count = source.layers.len() as u8;
 	let tiles_per_layer = (width * height) as u32;
 	let tile_count_total = tiles_per_layer * layer_count as u32;
@@ -92,11 +88,11 @@ pub fn compile_level(source: &LevelSource) -> Result<CompiledLevel, String> {
 	let mut tiles = Vec::with_capacity(tile_count_total as usize);
 	for layer in &source.layers {
 		for row in &layer.rows {
-			for ch in row.chars() {
-				let tile_id = match tile_palette.get(&ch) {
+			for cell in split_row_cells(row, cell_width) {
+				let tile_id = match tile_palette.get(&cell) {
 					Some(id) => *id,
 					None => {
-						return Err(format!("unknown tile character '{}'", ch));
+						return Err(format!("unknown tile '{}' (add it to the palette block)", cell));
 					}
 				};
 				tiles.push(tile_id);
@@ -266,8 +262,19 @@ pub fn compile_level(source: &LevelSource) -> Result<CompiledLevel, String> {
 					icon_id,
 				}
 			}
-			TriggerKindSource::Message { text_id, activation_mode } => {
+			// p0 = activation mode, plus MESSAGE_BLOCKING_FLAG; mode byte = speaker icon id (0 = none)
+			// see TriggerRuntime::pack_message_p0
+			TriggerKindSource::Message {
+				text_id,
+				activation_mode,
+				speaker,
+				blocking,
+			} => {
 				let msg_id: u16 = message_registry.resolve_message_id(text_id)?;
+				// a message the player asked for pauses the game; one walked into doesn't
+				let is_blocking: bool = blocking.unwrap_or(*activation_mode != TriggerActivationMode::Auto as u8);
+				let p0: u16 = TriggerRuntime::pack_message_p0(*activation_mode, is_blocking);
+
 				TriggerRuntime {
 					kind: TriggerKind::Message as u8,
 					gravity_multiplier: 0,
@@ -275,9 +282,68 @@ pub fn compile_level(source: &LevelSource) -> Result<CompiledLevel, String> {
 					top,
 					width,
 					height,
-					p0: *activation_mode as u16,
+					p0,
 					p1: msg_id,
-					activation_mode: TriggerActivationMode::Action as u8,
+					activation_mode: *speaker,
+					icon_id,
+				}
+			}
+			TriggerKindSource::CameraZone { lock } => TriggerRuntime {
+				kind: TriggerKind::CameraZone as u8,
+				gravity_multiplier: 0,
+				left,
+				top,
+				width,
+				height,
+				p0: *lock as u16,
+				p1: 0,
+				activation_mode: TriggerActivationMode::Auto as u8,
+				icon_id,
+			},
+			// p0 = radius in world pixels, p1 = rgb565, mode byte = EntityKind the light follows (0 = none)
+			TriggerKindSource::Light { radius, color, attach } => {
+				if !radius.is_finite() || *radius <= 0.0 {
+					return Err(format!("light radius must be positive, got {}", radius));
+				}
+				let radius_pixels: f32 = (radius * source.header.tile_width as f32).round();
+				if radius_pixels > u16::MAX as f32 {
+					return Err(format!("light radius {} out of range", radius));
+				}
+
+				TriggerRuntime {
+					kind: TriggerKind::Light as u8,
+					gravity_multiplier: 0,
+					left,
+					top,
+					width,
+					height,
+					p0: radius_pixels as u16,
+					p1: parse_color_rgb565(color)?,
+					activation_mode: resolve_light_attach(attach)?,
+					icon_id,
+				}
+			}
+			// p0 = key id (the key's mapped id in pickups.ids.txt), opens the door tiles under the trigger
+			TriggerKindSource::Door { key, activation_mode } => {
+				let key_name: &str = key.trim();
+				if key_name.is_empty() {
+					return Err("door trigger is missing a key".to_string());
+				}
+				if !key_name.starts_with("key:") {
+					return Err(format!("door key '{}' must be a key: pickup", key_name));
+				}
+				let key_id: u16 = *pickup_ids.get(key_name).ok_or_else(|| format!("unknown pickup id '{}'", key_name))?;
+
+				TriggerRuntime {
+					kind: TriggerKind::Door as u8,
+					gravity_multiplier: 0,
+					left,
+					top,
+					width,
+					height,
+					p0: key_id,
+					p1: 0,
+					activation_mode: *activation_mode,
 					icon_id,
 				}
 			}
@@ -286,13 +352,16 @@ pub fn compile_level(source: &LevelSource) -> Result<CompiledLevel, String> {
 		triggers_runtime.push(runtime);
 	}
 
-	let background_id = resolve_background_id(&source.header.background)?;
+	let backgrounds_path: PathBuf = get_asset_root().join("gfx").join("pc").join("backgrounds.txt");
+	let background_ids: HashMap<String, u8> = load_background_ids(&backgrounds_path)?;
+	let background_id = resolve_background_id(&background_ids, &source.header.background)?;
 	let gravity_fixed = gravity_to_fixed(source.header.gravity);
 	let music_id: u8 = resolve_music_id(&source.header.music)?;
+	let (name_len, name) = encode_level_name(&source.header.name)?;
 
 	let header = FileHeader {
 		magic: *b"JLVL",
-		version: 1,
+		version: 3,
 		header_size: HEADER_SIZE,
 		width: source.header.width as u16,
 		height: source.header.height as u16,
@@ -311,7 +380,10 @@ pub fn compile_level(source: &LevelSource) -> Result<CompiledLevel, String> {
 		offset_triggers: 0,
 		offset_tiles: 0,
 		music_id,
-		reserved1: source.header.reserved1 as u8,
+		darkness: darkness_to_u8(source.header.darkness)?,
+		rank_thresholds: source.header.rank_thresholds,
+		name_len,
+		name,
 	};
 
 	let compiled = CompiledLevel {
@@ -325,42 +397,182 @@ pub fn compile_level(source: &LevelSource) -> Result<CompiledLevel, String> {
 	return Ok(compiled);
 }
 
-fn build_tile_palette() -> HashMap<char, u8> {
-	let mut map = HashMap::new();
-	map.insert('.', 0); // Empty
-	map.insert('#', 1); // Stone
-	map.insert('^', 2); // SpikeUp
-	map.insert('~', 3); // Water Surface
-	map.insert('=', 4); // GrassTop
-	map.insert('v', 5); // SpikeDown
-	map.insert('<', 6); // SpikeLeft
-	map.insert('>', 7); // SpikeRight
-	map.insert('w', 8); // Water Body
-	map.insert('[', 9); // moving platform left
-	map.insert('-', 10); // moving platform middle
-	map.insert(']', 11); // moving platform right
-	map.insert('d', 12); // Dirt right
-	map.insert('b', 13); // Sign Begin
-	map.insert('e', 14); // Sign End
-	map.insert('(', 15); // platform left
-	map.insert('_', 16); // platform middle
-	map.insert(')', 17); // platform right
-	map.insert('B', 255); // Black
-	map.insert('G', 254); // Torch Glow
-	map.insert('D', 253); // Dark Brown Rock
-	return map;
+// characters every level can use without a palette block; names resolve through assets/tiles/tiles.ids.txt
+const DEFAULT_PALETTE: [(char, &str); 22] = [
+	('.', "empty"),
+	('#', "dirt"),
+	('^', "spike_up"),
+	('~', "water"),
+	('=', "grass_top"),
+	('v', "spike_down"),
+	('<', "spike_left"),
+	('>', "spike_right"),
+	('w', "water_body"),
+	('[', "moving_platform_left"),
+	('-', "moving_platform_middle"),
+	(']', "moving_platform_right"),
+	('d', "stone"),
+	('b', "sign_begin"),
+	('e', "sign_end"),
+	('(', "platform_left"),
+	('_', "platform_middle"),
+	(')', "platform_right"),
+	('B', "blackout"),
+	('G', "torch_glow"),
+	('D', "dark_brown_rock"),
+	('|', "door"),
+];
+
+pub fn get_default_palette_char(tile_name: &str) -> Option<char> {
+	return DEFAULT_PALETTE.iter().find(|(_, name)| *name == tile_name).map(|(ch, _)| *ch);
 }
 
-fn resolve_background_id(name: &str) -> Result<u8, String> {
-	if name.to_ascii_lowercase().eq_ignore_ascii_case("bg_library_stone") {
-		return Ok(1);
+pub fn is_default_palette_char(ch: char) -> bool {
+	return DEFAULT_PALETTE.iter().any(|(c, _)| *c == ch);
+}
+
+pub fn load_tile_registry() -> Result<HashMap<String, u16>, String> {
+	let path: PathBuf = get_asset_root().join("tiles").join("tiles.ids.txt");
+	return load_ids_map(&path);
+}
+
+fn resolve_tile_name(registry: &HashMap<String, u16>, name: &str) -> Result<u8, String> {
+	let id: u16 = match registry.get(name.trim()) {
+		Some(id) => *id,
+		None => return Err(format!("unknown tile '{}' (see assets/tiles/tiles.ids.txt)", name)),
+	};
+
+	let id: u8 = u8::try_from(id).map_err(|_| format!("tile '{}' has id {} which does not fit in a byte", name, id))?;
+	return Ok(id);
+}
+
+// cell text -> tile id. with cell_width 1 the default palette is always there and the level's
+// palette block overrides or extends it. wider cells also accept a default character repeated
+// to fill the cell ("##", "..")
+fn build_tile_palette(source: &LevelSource, registry: &HashMap<String, u16>) -> Result<HashMap<String, u8>, String> {
+	let cell_width: usize = source.header.cell_width as usize;
+	if cell_width == 0 {
+		return Err("cell_width must be > 0".to_string());
 	}
 
-	if name.to_ascii_lowercase().eq_ignore_ascii_case("bg_parallax_forest") {
-		return Ok(2);
+	let mut map: HashMap<String, u8> = HashMap::new();
+	for (ch, name) in DEFAULT_PALETTE {
+		let cell: String = std::iter::repeat_n(ch, cell_width).collect();
+		map.insert(cell, resolve_tile_name(registry, name)?);
+	}
+
+	for entry in &source.palette {
+		if entry.cell.chars().count() != cell_width {
+			return Err(format!("palette cell '{}' must be {} character(s) wide (cell_width)", entry.cell, cell_width));
+		}
+
+		let id: u8 = resolve_tile_name(registry, &entry.tile).map_err(|e| format!("palette cell '{}': {}", entry.cell, e))?;
+		map.insert(entry.cell.clone(), id);
+	}
+
+	return Ok(map);
+}
+
+pub fn split_row_cells(row: &str, cell_width: usize) -> Vec<String> {
+	let chars: Vec<char> = row.chars().collect();
+	let cells: Vec<String> = chars.chunks(cell_width.max(1)).map(|c| c.iter().collect()).collect();
+	return cells;
+}
+
+// mirrors TileKind::is_solid on the runtime side (Dirt, GrassTop, Stone, Door)
+pub fn is_solid_tile_id(tile_id: u8) -> bool {
+	return tile_id == 1 || tile_id == 4 || tile_id == 12 || is_door_tile_id(tile_id);
+}
+
+// TileKind::Door, the tiles a door trigger clears once it's unlocked
+pub fn is_door_tile_id(tile_id: u8) -> bool {
+	return tile_id == 252;
+}
+
+// mirrors TileKind::get_collision_kind == OneWay (moving platform and platform pieces)
+pub fn is_one_way_tile_id(tile_id: u8) -> bool {
+	return (9..=11).contains(&tile_id) || (15..=17).contains(&tile_id);
+}
+
+// the header keeps the name in a fixed size field so the game can read it without parsing the level
+fn encode_level_name(name: &str) -> Result<(u8, [u8; LEVEL_NAME_MAX_BYTES]), String> {
+	let bytes: &[u8] = name.as_bytes();
+	if bytes.len() > LEVEL_NAME_MAX_BYTES {
+		return Err(format!("level name '{}' is {} bytes, the limit is {}", name, bytes.len(), LEVEL_NAME_MAX_BYTES));
+	}
+
+	let mut encoded: [u8; LEVEL_NAME_MAX_BYTES] = [0; LEVEL_NAME_MAX_BYTES];
+	encoded[..bytes.len()].copy_from_slice(bytes);
+	return Ok((bytes.len() as u8, encoded));
+}
+
+fn resolve_background_id(backgrounds: &HashMap<String, u8>, name: &str) -> Result<u8, String> {
+	match backgrounds.get(&name.trim().to_ascii_lowercase()) {
+		Some(id) => return Ok(*id),
+		None => return Err(format!("unknown background '{}' (not in backgrounds.txt)", name)),
+	}
+}
+
+// background names -> ids from the `background <id> "<name>"` lines of the game's background
+// definitions; the layers themselves are only read by the game
+fn load_background_ids(path: &PathBuf) -> Result<HashMap<String, u8>, String> {
+	let text: String = std::fs::read_to_string(path).map_err(|e| format!("failed to read {:?}: {}", path, e))?;
+	let mut map: HashMap<String, u8> = HashMap::new();
+
+	for (i, raw) in text.lines().enumerate() {
+		let line_no: usize = i + 1;
+		let Some(rest) = raw.trim().strip_prefix("background ") else {
+			continue;
+		};
+
+		let mut parts = rest.trim().splitn(2, char::is_whitespace);
+		let id_text: &str = parts.next().unwrap_or("").trim();
+		let name: &str = parts.next().unwrap_or("").trim().trim_matches('"');
+
+		let id: u8 = id_text.parse::<u8>().map_err(|e| format!("invalid background id at {:?}:{} -> '{}': {}", path, line_no, id_text, e))?;
+		if name.is_empty() {
+			return Err(format!("background without a name at {:?}:{}", path, line_no));
+		}
+		if map.insert(name.to_ascii_lowercase(), id).is_some() {
+			return Err(format!("background '{}' is defined twice ({:?}:{})", name, path, line_no));
+		}
 	}
 
-	return Err(format!("unknown background '{}'", name));
+	return Ok(map);
+}
+
+fn darkness_to_u8(darkness: f32) -> Result<u8, String> {
+	if !darkness.is_finite() || !(0.0..=1.0).contains(&darkness) {
+		return Err(format!("darkness {} out of range (0..1)", darkness));
+	}
+	return Ok((darkness * 255.0).round() as u8);
+}
+
+// "rrggbb" (an optional leading '#' is allowed) -> rgb565
+fn parse_color_rgb565(text: &str) -> Result<u16, String> {
+	let hex: &str = text.trim().trim_start_matches('#');
+	if hex.len() != 6 {
+		return Err(format!("invalid color '{}', expected rrggbb", text));
+	}
+	let value: u32 = u32::from_str_radix(hex, 16).map_err(|_| format!("invalid color '{}', expected rrggbb", text))?;
+
+	let red: u32 = (value >> 16) & 0xff;
+	let green: u32 = (value >> 8) & 0xff;
+	let blue: u32 = value & 0xff;
+	return Ok((((red >> 3) << 11) | ((green >> 2) << 5) | (blue >> 3)) as u16);
+}
+
+fn resolve_light_attach(attach: &str) -> Result<u8, String> {
+	match attach.trim() {
+		"" | "none" => return Ok(0),
+		"player" => return Ok(EntityKind::Player as u8),
+		"slime_blue" => return Ok(EntityKind::SlimeBlue as u8),
+		"slime_undead" => return Ok(EntityKind::SlimeUndead as u8),
+		"slime_lava" => return Ok(EntityKind::SlimeLava as u8),
+		"imp" => return Ok(EntityKind::Imp as u8),
+		"moving_platform" => return Ok(EntityKind::MovingPlatform as u8),
+		_ => return Err(format!("unknown light attach '{}'", attach)),
+	}
 }
 
 fn gravity_to_fixed(g: f32) -> i16 {
@@ -1,4 +1,4 @@
-use crate::source::{EntityKindSource, EntitySource};
+use crate::source::{EntityKindSource, EntitySource, SourceLocation};
 pub struct EntityParseState {
 	pub kind: Option<EntityKindSource>,
 	pub top: i32,
@@ -62,7 +62,7 @@ impl EntityParseState {
 		self.invulnerability_time = 0;
 	}
 
-	pub fn to_entity_source(&mut self, line_number: usize) -> Result<EntitySource, String> {
+	pub fn to_entity_source(&mut self, line_number: &SourceLocation) -> Result<EntitySource, String> {
 		let kind = match self.kind.take() {
 			Some(k) => k,
 			None => {
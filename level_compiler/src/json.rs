@@ -0,0 +1,253 @@
+// minimal json reader, enough for tiled map exports
+
+#[derive(Debug, Clone)]
+pub enum JsonValue {
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<JsonValue>),
+	Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+	pub fn get(&self, key: &str) -> Option<&JsonValue> {
+		match self {
+			JsonValue::Object(fields) => return fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+			_ => return None,
+		}
+	}
+
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			JsonValue::String(s) => return Some(s.as_str()),
+			_ => return None,
+		}
+	}
+
+	pub fn as_f64(&self) -> Option<f64> {
+		match self {
+			JsonValue::Number(n) => return Some(*n),
+			_ => return None,
+		}
+	}
+
+	pub fn as_bool(&self) -> Option<bool> {
+		match self {
+			JsonValue::Bool(b) => return Some(*b),
+			_ => return None,
+		}
+	}
+
+	pub fn as_array(&self) -> Option<&[JsonValue]> {
+		match self {
+			JsonValue::Array(items) => return Some(items.as_slice()),
+			_ => return None,
+		}
+	}
+}
+
+pub fn parse_json(text: &str) -> Result<JsonValue, String> {
+	let mut parser = JsonParser {
+		chars: text.chars().collect(),
+		position: 0,
+	};
+
+	let value = parser.parse_value()?;
+	parser.skip_whitespace();
+
+	if parser.position != parser.chars.len() {
+		return Err(format!("unexpected trailing json at {}", parser.describe_position()));
+	}
+
+	return Ok(value);
+}
+
+struct JsonParser {
+	chars: Vec<char>,
+	position: usize,
+}
+
+impl JsonParser {
+	fn describe_position(&self) -> String {
+		let consumed: &[char] = &self.chars[..self.position.min(self.chars.len())];
+		let line: usize = consumed.iter().filter(|c| **c == '\n').count() + 1;
+		let column: usize = consumed.iter().rev().take_while(|c| **c != '\n').count() + 1;
+		return format!("line {} column {}", line, column);
+	}
+
+	fn peek(&self) -> Option<char> {
+		return self.chars.get(self.position).copied();
+	}
+
+	fn next(&mut self) -> Option<char> {
+		let c = self.peek();
+		if c.is_some() {
+			self.position += 1;
+		}
+		return c;
+	}
+
+	fn skip_whitespace(&mut self) {
+		while let Some(c) = self.peek() {
+			if !c.is_whitespace() {
+				break;
+			}
+			self.position += 1;
+		}
+	}
+
+	fn expect(&mut self, expected: char) -> Result<(), String> {
+		match self.next() {
+			Some(c) if c == expected => return Ok(()),
+			Some(c) => return Err(format!("expected '{}' but found '{}' at {}", expected, c, self.describe_position())),
+			None => return Err(format!("expected '{}' but reached end of json", expected)),
+		}
+	}
+
+	fn expect_word(&mut self, word: &str, value: JsonValue) -> Result<JsonValue, String> {
+		for expected in word.chars() {
+			self.expect(expected)?;
+		}
+		return Ok(value);
+	}
+
+	fn parse_value(&mut self) -> Result<JsonValue, String> {
+		self.skip_whitespace();
+
+		match self.peek() {
+			Some('{') => return self.parse_object(),
+			Some('[') => return self.parse_array(),
+			Some('"') => return Ok(JsonValue::String(self.parse_string()?)),
+			Some('t') => return self.expect_word("true", JsonValue::Bool(true)),
+			Some('f') => return self.expect_word("false", JsonValue::Bool(false)),
+			Some('n') => return self.expect_word("null", JsonValue::Null),
+			Some(c) if c == '-' || c.is_ascii_digit() => return self.parse_number(),
+			Some(c) => return Err(format!("unexpected '{}' at {}", c, self.describe_position())),
+			None => return Err("unexpected end of json".to_string()),
+		}
+	}
+
+	fn parse_object(&mut self) -> Result<JsonValue, String> {
+		self.expect('{')?;
+		let mut fields: Vec<(String, JsonValue)> = Vec::new();
+
+		self.skip_whitespace();
+		if self.peek() == Some('}') {
+			self.position += 1;
+			return Ok(JsonValue::Object(fields));
+		}
+
+		loop {
+			self.skip_whitespace();
+			let key = self.parse_string()?;
+			self.skip_whitespace();
+			self.expect(':')?;
+			let value = self.parse_value()?;
+			fields.push((key, value));
+
+			self.skip_whitespace();
+			match self.next() {
+				Some(',') => continue,
+				Some('}') => return Ok(JsonValue::Object(fields)),
+				_ => return Err(format!("expected ',' or '}}' in object at {}", self.describe_position())),
+			}
+		}
+	}
+
+	fn parse_array(&mut self) -> Result<JsonValue, String> {
+		self.expect('[')?;
+		let mut items: Vec<JsonValue> = Vec::new();
+
+		self.skip_whitespace();
+		if self.peek() == Some(']') {
+			self.position += 1;
+			return Ok(JsonValue::Array(items));
+		}
+
+		loop {
+			items.push(self.parse_value()?);
+
+			self.skip_whitespace();
+			match self.next() {
+				Some(',') => continue,
+				Some(']') => return Ok(JsonValue::Array(items)),
+				_ => return Err(format!("expected ',' or ']' in array at {}", self.describe_position())),
+			}
+		}
+	}
+
+	fn parse_string(&mut self) -> Result<String, String> {
+		self.expect('"')?;
+		let mut out = String::new();
+
+		loop {
+			let Some(c) = self.next() else {
+				return Err("unterminated string in json".to_string());
+			};
+
+			match c {
+				'"' => return Ok(out),
+				'\\' => {
+					let escaped = match self.next() {
+						Some('"') => '"',
+						Some('\\') => '\\',
+						Some('/') => '/',
+						Some('b') => '\u{8}',
+						Some('f') => '\u{c}',
+						Some('n') => '\n',
+						Some('r') => '\r',
+						Some('t') => '\t',
+						Some('u') => self.parse_unicode_escape()?,
+						_ => return Err(format!("invalid escape in string at {}", self.describe_position())),
+					};
+					out.push(escaped);
+				}
+				_ => out.push(c),
+			}
+		}
+	}
+
+	fn parse_hex4(&mut self) -> Result<u32, String> {
+		let mut value: u32 = 0;
+		for _ in 0..4 {
+			let digit = self.next().and_then(|c| c.to_digit(16));
+			match digit {
+				Some(d) => value = value * 16 + d,
+				None => return Err(format!("invalid \\u escape at {}", self.describe_position())),
+			}
+		}
+		return Ok(value);
+	}
+
+	fn parse_unicode_escape(&mut self) -> Result<char, String> {
+		let high = self.parse_hex4()?;
+
+		// surrogate pair
+		if (0xD800..0xDC00).contains(&high) {
+			self.expect('\\')?;
+			self.expect('u')?;
+			let low = self.parse_hex4()?;
+			let combined = 0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+			return char::from_u32(combined).ok_or_else(|| format!("invalid surrogate pair at {}", self.describe_position()));
+		}
+
+		return char::from_u32(high).ok_or_else(|| format!("invalid \\u escape at {}", self.describe_position()));
+	}
+
+	fn parse_number(&mut self) -> Result<JsonValue, String> {
+		let start = self.position;
+
+		while let Some(c) = self.peek() {
+			if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' || c == 'e' || c == 'E' {
+				self.position += 1;
+			} else {
+				break;
+			}
+		}
+
+		let text: String = self.chars[start..self.position].iter().collect();
+		let value = text.parse::<f64>().map_err(|e| format!("invalid number '{}' at {}: {}", text, self.describe_position(), e))?;
+		return Ok(JsonValue::Number(value));
+	}
+}
@@ -21,7 +21,7 @@ impl LayerParseState {
 		self.rows.clear();
 	}
 
-	pub fn to_layer_source(&mut self, line_number: usize) -> Result<LayerSource, String> {
+	pub fn to_layer_source(&mut self, line_number: &SourceLocation) -> Result<LayerSource, String> {
 		if self.name.is_empty() {
 			return Err(format!("layer missing name at line {}", line_number));
 		}
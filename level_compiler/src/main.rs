@@ -1,28 +1,65 @@
+mod analyze;
 mod binary_writer;
 mod compile;
 mod entity_parse_state;
+mod json;
 mod layer_parse_state;
 mod message_registry;
+// shared with the game so analyze moves the player the same way
+#[allow(dead_code)]
+#[path = "../../game/src/runtime/player_physics.rs"]
+mod player_physics;
 mod runtime;
 mod source;
 mod text_parse;
+mod tiled_parse;
 mod trigger_parse_state;
+mod validate;
 
 use std::{env, fs, io::Write, path::Path};
 
-use crate::{compile::compile_and_serialize, text_parse::load_level_from_str};
+use crate::{
+	analyze::{Analysis, PhysicsSettings, analyze_level, render_heat_map},
+	binary_writer::serialize_level,
+	compile::compile_level,
+	runtime::CompiledLevel,
+	source::LevelSource,
+	text_parse::load_level_from_str,
+	tiled_parse::load_level_from_tiled_str,
+	validate::{Severity, has_errors, validate_level},
+};
 
 fn main() {
 	let mut args = env::args().skip(1);
 
-	let input_path = match args.next() {
+	let mut input_path = match args.next() {
 		Some(p) => p,
 		None => {
-			eprintln!("usage: level_compiler <input.level> [output.lvlb]");
+			eprintln!("usage: level_compiler <input.level|input.tmj> [output.lvlb]");
+			eprintln!("       level_compiler analyze [--assist] <input.level|input.tmj> [output.txt]");
 			std::process::exit(1);
 		}
 	};
 
+	if input_path == "analyze" {
+		let mut is_assisted: bool = false;
+		input_path = match args.next() {
+			Some(p) if p == "--assist" => {
+				is_assisted = true;
+				args.next().unwrap_or_default()
+			}
+			Some(p) => p,
+			None => String::new(),
+		};
+		if input_path.is_empty() {
+			eprintln!("usage: level_compiler analyze [--assist] <input.level> [output.txt]");
+			std::process::exit(1);
+		}
+
+		run_analyze(&input_path, args.next(), is_assisted);
+		return;
+	}
+
 	let output_path = match args.next() {
 		Some(p) => p,
 		None => replace_extension(&input_path, "lvlb"),
@@ -33,13 +70,53 @@ fn main() {
 		std::process::exit(1);
 	}
 
-	let path = Path::new(&input_path);
+	let (level_source, compiled) = load_and_compile(&input_path);
+
+	let diagnostics = validate_level(&level_source, &compiled);
+	for d in &diagnostics {
+		match d.severity {
+			Severity::Warning => eprintln!("warning in {}: {}", input_path, d.message),
+			Severity::Error => eprintln!("error in {}: {}", input_path, d.message),
+		}
+	}
+
+	if has_errors(&diagnostics) {
+		eprintln!("validation failed for {}", input_path);
+		std::process::exit(6);
+	}
+
+	let bytes = match serialize_level(&compiled) {
+		Ok(b) => b,
+		Err(e) => {
+			eprintln!("compile error in {}: {}", input_path, e);
+			std::process::exit(4);
+		}
+	};
+
+	let write_result = fs::File::create(&output_path).and_then(|mut f| {
+		let result = f.write_all(&bytes);
+		return result;
+	});
+
+	match write_result {
+		Ok(_) => {
+			println!("wrote {}", output_path);
+		}
+		Err(e) => {
+			eprintln!("failed to write {}: {}", output_path, e);
+			std::process::exit(5);
+		}
+	}
+}
+
+fn load_and_compile(input_path: &str) -> (LevelSource, CompiledLevel) {
+	let path = Path::new(input_path);
 	if !path.exists() {
 		eprintln!("File {} Not Found.", input_path);
 		std::process::exit(1);
 	}
 
-	let text = match fs::read_to_string(&input_path) {
+	let text = match fs::read_to_string(input_path) {
 		Ok(t) => t,
 		Err(e) => {
 			eprintln!("failed to read {}: {}", input_path, e);
@@ -47,7 +124,15 @@ fn main() {
 		}
 	};
 
-	let level_source = match load_level_from_str(&text) {
+	// tiled maps are imported from their json export, everything else is the .level text format
+	let extension: String = path.extension().map(|e| e.to_string_lossy().to_ascii_lowercase()).unwrap_or_default();
+	let parsed = match extension.as_str() {
+		"tmj" | "json" => load_level_from_tiled_str(&text, path.parent().unwrap_or(Path::new("."))),
+		"tmx" => Err("tiled .tmx (xml) maps are not supported, export the map as json (.tmj)".to_string()),
+		_ => load_level_from_str(&text, path.parent().unwrap_or(Path::new("."))),
+	};
+
+	let level_source = match parsed {
 		Ok(l) => l,
 		Err(e) => {
 			eprintln!("parse error in {}: {}", input_path, e);
@@ -55,27 +140,60 @@ fn main() {
 		}
 	};
 
-	let bytes = match compile_and_serialize(&level_source) {
-		Ok(b) => b,
+	let compiled = match compile_level(&level_source) {
+		Ok(c) => c,
 		Err(e) => {
 			eprintln!("compile error in {}: {}", input_path, e);
 			std::process::exit(4);
 		}
 	};
 
-	let write_result = fs::File::create(&output_path).and_then(|mut f| {
-		let result = f.write_all(&bytes);
-		return result;
-	});
+	return (level_source, compiled);
+}
 
-	match write_result {
-		Ok(_) => {
-			println!("wrote {}", output_path);
-		}
+fn run_analyze(input_path: &str, output_path: Option<String>, is_assisted: bool) {
+	let (level_source, compiled) = load_and_compile(input_path);
+
+	let settings: PhysicsSettings = if is_assisted { PhysicsSettings::with_assist() } else { PhysicsSettings::new() };
+	let analysis: Analysis = match analyze_level(&level_source, &compiled, &settings) {
+		Ok(a) => a,
 		Err(e) => {
-			eprintln!("failed to write {}: {}", output_path, e);
-			std::process::exit(5);
+			eprintln!("analyze error in {}: {}", input_path, e);
+			std::process::exit(4);
+		}
+	};
+
+	let heat_map = render_heat_map(&level_source, &compiled, &analysis);
+
+	match output_path {
+		Some(p) => {
+			if let Err(e) = fs::write(&p, &heat_map) {
+				eprintln!("failed to write {}: {}", p, e);
+				std::process::exit(5);
+			}
+			println!("wrote {}", p);
 		}
+		None => print!("{}", heat_map),
+	}
+
+	println!(
+		"{}: {} of {} triggers reachable ({} states explored)",
+		input_path,
+		compiled.triggers.len() - analysis.unreachable.len(),
+		compiled.triggers.len(),
+		analysis.explored_states
+	);
+
+	if analysis.hit_state_limit {
+		eprintln!("warning in {}: search stopped after {} states, results may be incomplete", input_path, analysis.explored_states);
+	}
+
+	for u in &analysis.unreachable {
+		eprintln!("unreachable in {}: trigger {} ({})", input_path, u.index, u.description);
+	}
+
+	if !analysis.unreachable.is_empty() {
+		std::process::exit(7);
 	}
 }
 
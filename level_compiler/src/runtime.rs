@@ -1,8 +1,11 @@
+// the longest level name the header has room for, in bytes
+pub const LEVEL_NAME_MAX_BYTES: usize = 32;
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
 pub struct FileHeader {
 	pub magic: [u8; 4],   // "JLVL"
-	pub version: u16,     // 1
+	pub version: u16,     // 3
 	pub header_size: u16, // sizeof(FileHeader)
 
 	pub width: u16,
@@ -19,7 +22,7 @@ pub struct FileHeader {
 	pub gravity: u8,
 
 	pub music_id: u8,
-	pub reserved1: u8,
+	pub darkness: u8, // 0 = fully lit
 
 	pub tiles_per_layer: u32,
 	pub tile_count_total: u32,
@@ -28,6 +31,13 @@ pub struct FileHeader {
 	pub offset_entities: u32,
 	pub offset_triggers: u32,
 	pub offset_tiles: u32,
+
+	// since version 2: the score each rank needs, s, a, b then c; 0 = the game's default
+	pub rank_thresholds: [u32; 4],
+
+	// since version 3: the level's display name as utf-8, zero padded; the world index reads it
+	pub name_len: u8,
+	pub name: [u8; LEVEL_NAME_MAX_BYTES],
 }
 
 #[repr(C)]
@@ -80,6 +90,31 @@ pub struct TriggerRuntime {
 	pub icon_id: u16,
 }
 
+// a message keeps its activation mode in the low byte of p0 and its flags above it, which frees
+// the mode byte for the speaker's icon id
+impl TriggerRuntime {
+	pub fn pack_message_p0(activation_mode: u8, is_blocking: bool) -> u16 {
+		let mut p0: u16 = activation_mode as u16 & MESSAGE_MODE_MASK;
+		if is_blocking {
+			p0 |= MESSAGE_BLOCKING_FLAG;
+		}
+		return p0;
+	}
+
+	pub fn get_message_mode(&self) -> u16 {
+		return self.p0 & MESSAGE_MODE_MASK;
+	}
+
+	pub fn is_message_blocking(&self) -> bool {
+		return (self.p0 & MESSAGE_BLOCKING_FLAG) != 0;
+	}
+
+	// 0 = none
+	pub fn get_message_speaker_icon(&self) -> u8 {
+		return self.activation_mode;
+	}
+}
+
 #[derive(Debug)]
 pub struct CompiledLevel {
 	pub header: FileHeader,
@@ -105,4 +140,12 @@ pub enum TriggerKind {
 	LevelExit = 1,
 	Message = 2,
 	Pickup = 3,
+	CameraZone = 4,
+	Light = 5,
+	Door = 6,
 }
+
+// the bits of a message trigger's p0 that hold its activation mode
+pub const MESSAGE_MODE_MASK: u16 = 0x00ff;
+// set in a message trigger's p0 when the message pauses play until dismissed
+pub const MESSAGE_BLOCKING_FLAG: u16 = 0x100;
@@ -1,6 +1,32 @@
+use std::fmt;
+
+// where a line of .level text came from. lines from an included file carry that file's name and
+// its own line numbers, so messages read "line 4 in common/enemies.level"
+#[derive(Clone, Debug)]
+pub struct SourceLocation {
+	pub file: Option<String>,
+	pub line: usize,
+}
+
+impl SourceLocation {
+	pub fn new(file: Option<String>, line: usize) -> SourceLocation {
+		return SourceLocation { file, line };
+	}
+}
+
+impl fmt::Display for SourceLocation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.file {
+			Some(file) => return write!(f, "{} in {}", self.line, file),
+			None => return write!(f, "{}", self.line),
+		}
+	}
+}
+
 #[derive(Debug)]
 pub struct LevelSource {
 	pub header: LevelHeader,
+	pub palette: Vec<PaletteEntrySource>,
 	pub layers: Vec<LayerSource>,
 	pub entities: Vec<EntitySource>,
 	pub triggers: Vec<TriggerSource>,
@@ -18,7 +44,16 @@ pub struct LevelHeader {
 	pub gravity: f32,
 	pub background: String,
 	pub music: String,
-	pub reserved1: u32,
+	pub darkness: f32, // 0 = fully lit, 1 = only lights show anything
+	pub rank_thresholds: [u32; 4], // score needed for ranks s, a, b and c; 0 = the game's default
+	pub cell_width: u32, // characters per tile in layer rows (1 unless the level needs more than one char per tile)
+}
+
+// one `'#' = "stone"` line from the palette block; `tile` is a name from assets/tiles/tiles.ids.txt
+#[derive(Debug)]
+pub struct PaletteEntrySource {
+	pub cell: String,
+	pub tile: String,
 }
 
 #[allow(dead_code)]
@@ -81,6 +116,23 @@ pub struct TriggerSource {
 #[derive(Debug)]
 pub enum TriggerKindSource {
 	LevelExit { target: String, level: String, activation_mode: u8 },
-	Message { text_id: String, activation_mode: u8 },
+	Message { text_id: String, activation_mode: u8, speaker: u8, blocking: Option<bool> }, // speaker is an icon id (0 = none); blocking None = pause only for action messages
 	Pickup { pickup: String, amount: u16, activation_mode: u8 },
+	CameraZone { lock: u8 }, // CAMERA_LOCK_* bits
+	Light { radius: f32, color: String, attach: String }, // radius in tiles, color "rrggbb", attach an entity kind or ""
+	Door { key: String, activation_mode: u8 }, // key is a "key:..." name from pickups.ids.txt
+}
+
+// camera_zone lock bits; an unlocked zone only clamps the camera to its rectangle
+pub const CAMERA_LOCK_HORIZONTAL: u8 = 1;
+pub const CAMERA_LOCK_VERTICAL: u8 = 2;
+
+pub fn parse_camera_lock(value: &str) -> Option<u8> {
+	return match value {
+		"none" => Some(0),
+		"x" => Some(CAMERA_LOCK_HORIZONTAL),
+		"y" => Some(CAMERA_LOCK_VERTICAL),
+		"both" => Some(CAMERA_LOCK_HORIZONTAL | CAMERA_LOCK_VERTICAL),
+		_ => None,
+	};
 }
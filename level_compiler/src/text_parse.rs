@@ -1,5 +1,11 @@
 use crate::{entity_parse_state::EntityParseState, layer_parse_state::LayerParseState, source::*, trigger_parse_state::TriggerParseState};
 
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+};
+
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum TriggerActivationMode {
@@ -15,29 +21,52 @@ pub enum TriggerActivationMode {
 enum Section {
 	None,
 	Header,
+	Palette,
 	Layers,
 	LayerBody,
 	Entities,
 	EntityBody,
 	Triggers,
 	TriggerBody,
+	TemplateBody,
+}
+
+struct SourceLine {
+	text: String,
+	location: SourceLocation,
+}
+
+// `template "name" { ... }`: entity body lines replayed before the body of an entity that uses it
+struct Template {
+	lines: Vec<SourceLine>,
+	location: SourceLocation,
 }
 
-pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
+// `base_dir` is where `include "..."` paths are resolved from (the folder of the .level file)
+pub fn load_level_from_str(text: &str, base_dir: &Path) -> Result<LevelSource, String> {
+	let mut source_lines: Vec<SourceLine> = Vec::new();
+	let mut include_stack: Vec<PathBuf> = Vec::new();
+	expand_includes(text, None, base_dir, &mut include_stack, &mut source_lines)?;
+
 	let mut section = Section::None;
+	let mut section_before_template = Section::None;
+	let mut templates: HashMap<String, Template> = HashMap::new();
+	let mut template_name = String::new();
+	let mut template_lines: Vec<SourceLine> = Vec::new();
+	let mut template_location = SourceLocation::new(None, 0);
 	let mut reading_tiles = false;
 	let mut entities: Vec<EntitySource> = Vec::new();
 	let mut header_opt: Option<LevelHeader> = None;
+	let mut palette: Vec<PaletteEntrySource> = Vec::new();
 	let mut layers: Vec<LayerSource> = Vec::new();
 	let mut ent = EntityParseState::new();
 	let mut trigger = TriggerParseState::new();
 	let mut layer = LayerParseState::new();
-	let mut line_number = 0;
 	let mut triggers: Vec<TriggerSource> = Vec::new();
 
-	for raw_line in text.lines() {
-		line_number += 1;
-		let line = raw_line.trim();
+	for source_line in &source_lines {
+		let line_number: &SourceLocation = &source_line.location;
+		let line = source_line.text.trim();
 
 		if line.is_empty() {
 			continue;
@@ -47,11 +76,39 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 			continue;
 		}
 
+		if line.starts_with("template ") && matches!(section, Section::None | Section::Layers | Section::Entities | Section::Triggers) {
+			let (declaration, base) = split_template_suffix(line);
+			template_name = parse_kind_string_after_keyword(declaration, "template", line_number)?;
+			template_location = line_number.clone();
+			template_lines.clear();
+
+			// a template can extend another one; the base's lines come first
+			if let Some(base) = base {
+				let Some(base_template) = templates.get(&base) else {
+					return Err(format!("unknown template '{}' at line {}", base, line_number));
+				};
+				for base_line in &base_template.lines {
+					template_lines.push(SourceLine {
+						text: base_line.text.clone(),
+						location: base_line.location.clone(),
+					});
+				}
+			}
+
+			section_before_template = std::mem::replace(&mut section, Section::TemplateBody);
+			continue;
+		}
+
 		if line == "header" {
 			section = Section::Header;
 			continue;
 		}
 
+		if line == "palette" {
+			section = Section::Palette;
+			continue;
+		}
+
 		if line == "layers" {
 			section = Section::Layers;
 			continue;
@@ -85,9 +142,22 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 						triggers.push(t);
 						section = Section::Triggers;
 					}
-					Section::Header => {
+					Section::Header | Section::Palette => {
 						section = Section::None;
 					}
+					Section::TemplateBody => {
+						if templates.contains_key(&template_name) {
+							return Err(format!("template '{}' defined again at line {}", template_name, line_number));
+						}
+						templates.insert(
+							template_name.clone(),
+							Template {
+								lines: std::mem::take(&mut template_lines),
+								location: template_location.clone(),
+							},
+						);
+						section = std::mem::replace(&mut section_before_template, Section::None);
+					}
 					_ => {}
 				}
 			}
@@ -100,6 +170,15 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 				let header = parse_header_line(line, header_opt)?;
 				header_opt = Some(header);
 			}
+			Section::Palette => {
+				palette.push(parse_palette_line(line, line_number)?);
+			}
+			Section::TemplateBody => {
+				template_lines.push(SourceLine {
+					text: line.to_string(),
+					location: line_number.clone(),
+				});
+			}
 			Section::Layers => {
 				if line.starts_with("layer ") {
 					layer.clear();
@@ -139,6 +218,7 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 			}
 			Section::Entities => {
 				ent.clear();
+				let (line, used_template) = split_template_suffix(line);
 				if line.starts_with("player_start") {
 					ent.kind = Some(EntityKindSource::PlayerStart);
 					section = Section::EntityBody;
@@ -155,7 +235,6 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 						range_max: 0,
 					});
 					section = Section::EntityBody;
-					continue;
 				} else if line.starts_with("platform ") {
 					ent.clear();
 					let platform_kind = parse_kind_string_after_keyword(line, "platform", line_number)?;
@@ -170,87 +249,22 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 				} else {
 					return Err(format!("unexpected line in entities section at {}: {}", line_number, line));
 				}
-			}
-			Section::EntityBody => {
-				if line.starts_with("top") {
-					ent.top = parse_i32_value(line, "top", line_number)?;
-				} else if line.starts_with("left") {
-					ent.left = parse_i32_value(line, "left", line_number)?;
-				} else if line.starts_with("range_min") {
-					let value = parse_i32_value(line, "range_min", line_number)?;
 
-					match ent.kind.as_mut() {
-						Some(EntityKindSource::Enemy { range_min, .. }) => {
-							*range_min = value;
-						}
-						Some(EntityKindSource::MovingPlatform { range_min, .. }) => {
-							*range_min = value;
-						}
-
-						_ => ent.range_min = value,
-					}
-				} else if line.starts_with("range_max") {
-					let value = parse_i32_value(line, "range_max", line_number)?;
-					match ent.kind.as_mut() {
-						Some(EntityKindSource::Enemy { range_max, .. }) => {
-							*range_max = value;
-						}
-						Some(EntityKindSource::MovingPlatform { range_max, .. }) => {
-							*range_max = value;
-						}
+				if let Some(name) = used_template {
+					let Some(template) = templates.get(&name) else {
+						return Err(format!("unknown template '{}' at line {}", name, line_number));
+					};
 
-						_ => ent.range_max = value,
-					}
-				} else if line.starts_with("size") {
-					let value = parse_i32_value(line, "size", line_number)?;
-					match ent.kind.as_mut() {
-						Some(EntityKindSource::MovingPlatform { size, .. }) => {
-							*size = value;
-						}
-						_ => {
-							return Err(format!("size not allowed for this entity at line {}", line_number));
-						}
-					}
-				} else if line.starts_with("speed") {
-					ent.speed = parse_i32_value(line, "speed", line_number)?;
-				} else if line.starts_with("gravity_multiplier") {
-					ent.gravity_multiplier = parse_f32_value(line, "gravity_multiplier", line_number)?;
-				} else if line.starts_with("jump_multiplier") {
-					ent.jump_multiplier = parse_f32_value(line, "jump_multiplier", line_number)?;
-				} else if line.starts_with("attack_power") {
-					ent.attack_power = parse_i32_value(line, "attack_power", line_number)?;
-				} else if line.starts_with("hit_points") {
-					ent.hit_points = parse_i32_value(line, "hit_points", line_number)?;
-				} else if line.starts_with("enemy_kind") {
-					let value = parse_string_value(line, "enemy_kind", line_number)?;
-					match ent.kind.as_mut() {
-						Some(EntityKindSource::Enemy { enemy_kind, .. }) => {
-							*enemy_kind = value;
-						}
-						_ => {
-							return Err(format!("enemy_kind not allowed for this entity at line {}", line_number));
-						}
+					for template_line in &template.lines {
+						apply_entity_body_line(&mut ent, &template_line.text, &template_line.location).map_err(|e| {
+							format!("{} (template \"{}\" defined at line {}, used at line {})", e, name, template.location, line_number)
+						})?;
 					}
-				} else if line.starts_with("render_style") {
-					ent.render_style = parse_u8_value(line, "render_style", line_number)?;
-				} else if line.starts_with("width") {
-					ent.width = parse_f32_value(line, "width", line_number)?;
-				} else if line.starts_with("height") {
-					ent.height = parse_f32_value(line, "height", line_number)?;
-				} else if line.starts_with("speed") {
-					ent.speed = parse_i32_value(line, "speed", line_number)?;
-				} else if line.starts_with("strength") {
-					ent.strength = parse_i32_value(line, "strength", line_number)?;
-				} else if line.starts_with("luck") {
-					ent.luck = parse_i32_value(line, "luck", line_number)?;
-				} else if line.starts_with("health_regen_rate") {
-					ent.health_regen_rate = parse_i32_value(line, "health_regen_rate", line_number)?;
-				} else if line.starts_with("invulnerability_time") {
-					ent.invulnerability_time = parse_i32_value(line, "invulnerability_time", line_number)?;
-				} else {
-					return Err(format!("Error: unexpected line in entity body at {}: {}", line_number, line));
 				}
 			}
+			Section::EntityBody => {
+				apply_entity_body_line(&mut ent, line, line_number)?;
+			}
 			Section::Triggers => {
 				if line.starts_with("trigger ") {
 					trigger.clear();
@@ -266,6 +280,8 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 						TriggerKindSource::Message {
 							text_id: String::new(),
 							activation_mode: 0,
+							speaker: 0,
+							blocking: None,
 						}
 					} else if trigger_kind == "pickup" {
 						TriggerKindSource::Pickup {
@@ -273,6 +289,19 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 							amount: 0,
 							activation_mode: 0,
 						}
+					} else if trigger_kind == "camera_zone" {
+						TriggerKindSource::CameraZone { lock: 0 }
+					} else if trigger_kind == "light" {
+						TriggerKindSource::Light {
+							radius: 3.0,
+							color: String::from("ffffff"),
+							attach: String::new(),
+						}
+					} else if trigger_kind == "door" {
+						TriggerKindSource::Door {
+							key: String::new(),
+							activation_mode: 0,
+						}
 					} else {
 						return Err(format!("unknown trigger kind '{}' at line {}", trigger_kind, line_number));
 					};
@@ -347,46 +376,95 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 						Some(TriggerKindSource::Pickup { activation_mode, .. }) => {
 							*activation_mode = mode;
 						}
+						Some(TriggerKindSource::Door { activation_mode, .. }) => {
+							*activation_mode = mode;
+						}
 						_ => {
 							return Err(format!("mode not allowed for this trigger at line {}", line_number));
 						}
 					}
-				} else if line.starts_with("mode") {
-					let s: String = parse_string_value(line, "mode", line_number)?;
-					let mode: u8 = match s.as_str() {
-						"auto" => TriggerActivationMode::Auto as u8,
-						"action" => TriggerActivationMode::Action as u8,
-						"up" => TriggerActivationMode::Up as u8,
-						"down" => TriggerActivationMode::Down as u8,
-						"left" => TriggerActivationMode::Left as u8,
-						"right" => TriggerActivationMode::Right as u8,
+				} else if line.starts_with("pickup") {
+					let s: String = parse_string_value(line, "pickup", line_number)?;
+					match trigger.kind.as_mut() {
+						Some(TriggerKindSource::Pickup { pickup, .. }) => {
+							*pickup = s;
+						}
 						_ => {
-							return Err(format!("Invalid trigger mode '{}' at line {}", s, line_number));
+							return Err(format!("pickup not allowed for this trigger at line {}", line_number));
+						}
+					}
+				} else if line.starts_with("key") {
+					let s: String = parse_string_value(line, "key", line_number)?;
+					match trigger.kind.as_mut() {
+						Some(TriggerKindSource::Door { key, .. }) => {
+							*key = s;
 						}
+						_ => {
+							return Err(format!("key not allowed for this trigger at line {}", line_number));
+						}
+					}
+				} else if line.starts_with("lock") {
+					let s: String = parse_string_value(line, "lock", line_number)?;
+					let Some(lock_bits) = parse_camera_lock(&s) else {
+						return Err(format!("invalid camera lock '{}' at line {}, expected none, x, y or both", s, line_number));
 					};
 
 					match trigger.kind.as_mut() {
-						Some(TriggerKindSource::Message { activation_mode, .. }) => {
-							*activation_mode = mode;
+						Some(TriggerKindSource::CameraZone { lock }) => {
+							*lock = lock_bits;
 						}
-						Some(TriggerKindSource::LevelExit { activation_mode, .. }) => {
-							*activation_mode = mode;
+						_ => {
+							return Err(format!("lock not allowed for this trigger at line {}", line_number));
 						}
-						Some(TriggerKindSource::Pickup { activation_mode, .. }) => {
-							*activation_mode = mode;
+					}
+				} else if line.starts_with("radius") {
+					let value: f32 = parse_f32_value(line, "radius", line_number)?;
+					match trigger.kind.as_mut() {
+						Some(TriggerKindSource::Light { radius, .. }) => {
+							*radius = value;
 						}
 						_ => {
-							return Err(format!("mode not allowed for this trigger at line {}", line_number));
+							return Err(format!("radius not allowed for this trigger at line {}", line_number));
 						}
 					}
-				} else if line.starts_with("pickup") {
-					let s: String = parse_string_value(line, "pickup", line_number)?;
+				} else if line.starts_with("color") {
+					let s: String = parse_string_value(line, "color", line_number)?;
 					match trigger.kind.as_mut() {
-						Some(TriggerKindSource::Pickup { pickup, .. }) => {
-							*pickup = s;
+						Some(TriggerKindSource::Light { color, .. }) => {
+							*color = s;
 						}
 						_ => {
-							return Err(format!("pickup not allowed for this trigger at line {}", line_number));
+							return Err(format!("color not allowed for this trigger at line {}", line_number));
+						}
+					}
+				} else if line.starts_with("attach") {
+					let s: String = parse_string_value(line, "attach", line_number)?;
+					match trigger.kind.as_mut() {
+						Some(TriggerKindSource::Light { attach, .. }) => {
+							*attach = s;
+						}
+						_ => {
+							return Err(format!("attach not allowed for this trigger at line {}", line_number));
+						}
+					}
+				} else if line.starts_with("speaker") {
+					let value: u8 = parse_u8_value(line, "speaker", line_number)?;
+					match trigger.kind.as_mut() {
+						Some(TriggerKindSource::Message { speaker, .. }) => {
+							*speaker = value;
+						}
+						_ => {
+							return Err(format!("speaker not allowed for this trigger at line {}", line_number));
+						}
+					}
+				} else if line.starts_with("blocking") {
+					let value: bool = parse_bool_value(line, "blocking", line_number)?;
+					match trigger.kind.as_mut() {
+						Some(TriggerKindSource::Message { blocking, .. }) => {
+							*blocking = Some(value);
+						}
+						_ => {
+							return Err(format!("blocking not allowed for this trigger at line {}", line_number));
 						}
 					}
 				} else if line.starts_with("amount") {
@@ -422,6 +500,7 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 
 	let level = LevelSource {
 		header,
+		palette,
 		layers,
 		entities,
 		triggers,
@@ -430,6 +509,154 @@ pub fn load_level_from_str(text: &str) -> Result<LevelSource, String> {
 	return Ok(level);
 }
 
+// inlines `include "path"` lines, recursively; paths are relative to the including file
+fn expand_includes(text: &str, file: Option<String>, base_dir: &Path, include_stack: &mut Vec<PathBuf>, out: &mut Vec<SourceLine>) -> Result<(), String> {
+	for (index, raw_line) in text.lines().enumerate() {
+		let location = SourceLocation::new(file.clone(), index + 1);
+		let line = raw_line.trim();
+
+		if !line.starts_with("include ") {
+			out.push(SourceLine {
+				text: raw_line.to_string(),
+				location,
+			});
+			continue;
+		}
+
+		let relative: String = parse_kind_string_after_keyword(line, "include", &location)?;
+		let path: PathBuf = base_dir.join(&relative);
+		let canonical: PathBuf = fs::canonicalize(&path).map_err(|e| format!("cannot include '{}' at line {}: {}", relative, location, e))?;
+
+		if include_stack.contains(&canonical) {
+			return Err(format!("include cycle: '{}' includes itself again at line {}", relative, location));
+		}
+
+		let included_text: String = fs::read_to_string(&canonical).map_err(|e| format!("cannot include '{}' at line {}: {}", relative, location, e))?;
+
+		// name included lines relative to the top-level file so messages stay short
+		let label: String = match &file {
+			Some(parent) => Path::new(parent).parent().unwrap_or(Path::new("")).join(&relative).to_string_lossy().replace('\\', "/"),
+			None => relative.clone(),
+		};
+
+		include_stack.push(canonical.clone());
+		expand_includes(&included_text, Some(label), canonical.parent().unwrap_or(Path::new(".")), include_stack, out)?;
+		include_stack.pop();
+	}
+
+	return Ok(());
+}
+
+// `enemy "slime_blue" : slime` -> (`enemy "slime_blue"`, Some("slime")); the template name may be quoted
+fn split_template_suffix(line: &str) -> (&str, Option<String>) {
+	let Some(colon) = find_colon_outside_quotes(line) else {
+		return (line, None);
+	};
+
+	let name: &str = line[colon + 1..].trim().trim_matches('"');
+	if name.is_empty() {
+		return (line, None);
+	}
+
+	return (line[..colon].trim_end(), Some(name.to_string()));
+}
+
+fn find_colon_outside_quotes(line: &str) -> Option<usize> {
+	let mut in_quotes: bool = false;
+	for (i, c) in line.char_indices() {
+		if c == '"' {
+			in_quotes = !in_quotes;
+		} else if c == ':' && !in_quotes {
+			return Some(i);
+		}
+	}
+	return None;
+}
+
+// one `key = value` line of an entity body; also replays template lines
+fn apply_entity_body_line(ent: &mut EntityParseState, line: &str, line_number: &SourceLocation) -> Result<(), String> {
+	if line.starts_with("top") {
+		ent.top = parse_i32_value(line, "top", line_number)?;
+	} else if line.starts_with("left") {
+		ent.left = parse_i32_value(line, "left", line_number)?;
+	} else if line.starts_with("range_min") {
+		let value = parse_i32_value(line, "range_min", line_number)?;
+
+		match ent.kind.as_mut() {
+			Some(EntityKindSource::Enemy { range_min, .. }) => {
+				*range_min = value;
+			}
+			Some(EntityKindSource::MovingPlatform { range_min, .. }) => {
+				*range_min = value;
+			}
+
+			_ => ent.range_min = value,
+		}
+	} else if line.starts_with("range_max") {
+		let value = parse_i32_value(line, "range_max", line_number)?;
+		match ent.kind.as_mut() {
+			Some(EntityKindSource::Enemy { range_max, .. }) => {
+				*range_max = value;
+			}
+			Some(EntityKindSource::MovingPlatform { range_max, .. }) => {
+				*range_max = value;
+			}
+
+			_ => ent.range_max = value,
+		}
+	} else if line.starts_with("size") {
+		let value = parse_i32_value(line, "size", line_number)?;
+		match ent.kind.as_mut() {
+			Some(EntityKindSource::MovingPlatform { size, .. }) => {
+				*size = value;
+			}
+			_ => {
+				return Err(format!("size not allowed for this entity at line {}", line_number));
+			}
+		}
+	} else if line.starts_with("speed") {
+		ent.speed = parse_i32_value(line, "speed", line_number)?;
+	} else if line.starts_with("gravity_multiplier") {
+		ent.gravity_multiplier = parse_f32_value(line, "gravity_multiplier", line_number)?;
+	} else if line.starts_with("jump_multiplier") {
+		ent.jump_multiplier = parse_f32_value(line, "jump_multiplier", line_number)?;
+	} else if line.starts_with("attack_power") {
+		ent.attack_power = parse_i32_value(line, "attack_power", line_number)?;
+	} else if line.starts_with("hit_points") {
+		ent.hit_points = parse_i32_value(line, "hit_points", line_number)?;
+	} else if line.starts_with("enemy_kind") {
+		let value = parse_string_value(line, "enemy_kind", line_number)?;
+		match ent.kind.as_mut() {
+			Some(EntityKindSource::Enemy { enemy_kind, .. }) => {
+				*enemy_kind = value;
+			}
+			_ => {
+				return Err(format!("enemy_kind not allowed for this entity at line {}", line_number));
+			}
+		}
+	} else if line.starts_with("render_style") {
+		ent.render_style = parse_u8_value(line, "render_style", line_number)?;
+	} else if line.starts_with("width") {
+		ent.width = parse_f32_value(line, "width", line_number)?;
+	} else if line.starts_with("height") {
+		ent.height = parse_f32_value(line, "height", line_number)?;
+	} else if line.starts_with("speed") {
+		ent.speed = parse_i32_value(line, "speed", line_number)?;
+	} else if line.starts_with("strength") {
+		ent.strength = parse_i32_value(line, "strength", line_number)?;
+	} else if line.starts_with("luck") {
+		ent.luck = parse_i32_value(line, "luck", line_number)?;
+	} else if line.starts_with("health_regen_rate") {
+		ent.health_regen_rate = parse_i32_value(line, "health_regen_rate", line_number)?;
+	} else if line.starts_with("invulnerability_time") {
+		ent.invulnerability_time = parse_i32_value(line, "invulnerability_time", line_number)?;
+	} else {
+		return Err(format!("Error: unexpected line in entity body at {}: {}", line_number, line));
+	}
+
+	return Ok(());
+}
+
 fn parse_header_line(line: &str, existing: Option<LevelHeader>) -> Result<LevelHeader, String> {
 	let mut header = match existing {
 		Some(h) => h,
@@ -444,7 +671,9 @@ fn parse_header_line(line: &str, existing: Option<LevelHeader>) -> Result<LevelH
 			gravity: 0.0,
 			background: String::new(),
 			music: String::new(),
-			reserved1: 0,
+			darkness: 0.0,
+			rank_thresholds: [0; 4],
+			cell_width: 1,
 		},
 	};
 
@@ -481,6 +710,21 @@ fn parse_header_line(line: &str, existing: Option<LevelHeader>) -> Result<LevelH
 		"music" => {
 			header.music = parse_quoted(value_str)?;
 		}
+		"darkness" => {
+			header.darkness = value_str.parse::<f32>().map_err(|e| format!("invalid darkness value '{}': {}", value_str, e))?;
+		}
+		"rank_s" | "rank_a" | "rank_b" | "rank_c" => {
+			let index: usize = match key {
+				"rank_s" => 0,
+				"rank_a" => 1,
+				"rank_b" => 2,
+				_ => 3,
+			};
+			header.rank_thresholds[index] = value_str.parse::<u32>().map_err(|e| format!("invalid {} value '{}': {}", key, value_str, e))?;
+		}
+		"cell_width" => {
+			header.cell_width = value_str.parse::<u32>().map_err(|e| format!("invalid cell_width value '{}': {}", value_str, e))?;
+		}
 		"#" => {}
 		"//" => {}
 		_ => {
@@ -491,7 +735,7 @@ fn parse_header_line(line: &str, existing: Option<LevelHeader>) -> Result<LevelH
 	return Ok(header);
 }
 
-fn parse_layer_declaration(line: &str, line_number: usize) -> Result<(String, bool), String> {
+fn parse_layer_declaration(line: &str, line_number: &SourceLocation) -> Result<(String, bool), String> {
 	let rest = line.trim_start_matches("layer").trim();
 	let mut has_brace = false;
 	let name_part: String;
@@ -507,13 +751,39 @@ fn parse_layer_declaration(line: &str, line_number: usize) -> Result<(String, bo
 	return Ok((name, has_brace));
 }
 
-fn parse_tile_row(line: &str, line_number: usize) -> Result<String, String> {
+// '#' = "stone"  or, with cell_width = 2,  "g1" = "grass_top"
+fn parse_palette_line(line: &str, line_number: &SourceLocation) -> Result<PaletteEntrySource, String> {
+	let quote: char = match line.chars().next() {
+		Some(c) if c == '\'' || c == '"' => c,
+		_ => return Err(format!("expected quoted palette cell at line {}: {}", line_number, line)),
+	};
+
+	let rest: &str = &line[1..];
+	let Some(end) = rest.find(quote) else {
+		return Err(format!("unterminated palette cell at line {}: {}", line_number, line));
+	};
+
+	let cell: String = rest[..end].to_string();
+	if cell.is_empty() {
+		return Err(format!("empty palette cell at line {}", line_number));
+	}
+
+	let value: &str = rest[end + 1..].trim();
+	let Some(value) = value.strip_prefix('=') else {
+		return Err(format!("expected '=' after palette cell at line {}: {}", line_number, line));
+	};
+
+	let tile: String = parse_quoted(value).map_err(|e| format!("invalid palette tile name at line {}: {}", line_number, e))?;
+	return Ok(PaletteEntrySource { cell, tile });
+}
+
+fn parse_tile_row(line: &str, line_number: &SourceLocation) -> Result<String, String> {
 	let trimmed = line.trim();
 	let s = parse_quoted(trimmed).map_err(|e| format!("invalid tile row at line {}: {}", line_number, e))?;
 	return Ok(s);
 }
 
-fn parse_bool_value(line: &str, expected_key: &str, line_number: usize) -> Result<bool, String> {
+fn parse_bool_value(line: &str, expected_key: &str, line_number: &SourceLocation) -> Result<bool, String> {
 	let (key, value_str) = split_key_value(line)?;
 	if key != expected_key {
 		return Err(format!("expected key '{}' at line {}, got '{}'", expected_key, line_number, key));
@@ -530,7 +800,7 @@ fn parse_bool_value(line: &str, expected_key: &str, line_number: usize) -> Resul
 	return Err(format!("invalid bool value '{}' at line {}", value_str, line_number));
 }
 
-fn parse_f32_value(line: &str, expected_key: &str, line_number: usize) -> Result<f32, String> {
+fn parse_f32_value(line: &str, expected_key: &str, line_number: &SourceLocation) -> Result<f32, String> {
 	let (key, value_str) = split_key_value(line)?;
 	if key != expected_key {
 		return Err(format!("expected key '{}' at line {}, got '{}'", expected_key, line_number, key));
@@ -543,7 +813,7 @@ fn parse_f32_value(line: &str, expected_key: &str, line_number: usize) -> Result
 	return Ok(value);
 }
 
-fn parse_i32_value(line: &str, expected_key: &str, line_number: usize) -> Result<i32, String> {
+fn parse_i32_value(line: &str, expected_key: &str, line_number: &SourceLocation) -> Result<i32, String> {
 	let (key, value_str) = split_key_value(line)?;
 	if key != expected_key {
 		return Err(format!("expected key '{}' at line {}, got '{}'", expected_key, line_number, key));
@@ -555,7 +825,7 @@ fn parse_i32_value(line: &str, expected_key: &str, line_number: usize) -> Result
 	return Ok(value);
 }
 
-fn parse_u8_value(line: &str, expected_key: &str, line_number: usize) -> Result<u8, String> {
+fn parse_u8_value(line: &str, expected_key: &str, line_number: &SourceLocation) -> Result<u8, String> {
 	let (key, value_str) = split_key_value(line)?;
 	if key != expected_key {
 		return Err(format!("expected key '{}' at line {}, got '{}'", expected_key, line_number, key));
@@ -567,7 +837,7 @@ fn parse_u8_value(line: &str, expected_key: &str, line_number: usize) -> Result<
 	return Ok(value);
 }
 
-fn parse_string_value(line: &str, expected_key: &str, line_number: usize) -> Result<String, String> {
+fn parse_string_value(line: &str, expected_key: &str, line_number: &SourceLocation) -> Result<String, String> {
 	let (key, value_str) = split_key_value(line)?;
 	if key != expected_key {
 		return Err(format!("expected key '{}' at line {}, got '{}'", expected_key, line_number, key));
@@ -577,7 +847,7 @@ fn parse_string_value(line: &str, expected_key: &str, line_number: usize) -> Res
 	return Ok(s);
 }
 
-fn parse_kind_string_after_keyword(line: &str, keyword: &str, line_number: usize) -> Result<String, String> {
+fn parse_kind_string_after_keyword(line: &str, keyword: &str, line_number: &SourceLocation) -> Result<String, String> {
 	let rest = line.trim_start_matches(keyword).trim();
 	let s = parse_quoted(rest).map_err(|e| format!("invalid kind string after '{}' at line {}: {}", keyword, line_number, e))?;
 	return Ok(s);
@@ -607,7 +877,7 @@ fn parse_quoted(value_str: &str) -> Result<String, String> {
 }
 
 #[allow(dead_code)]
-fn parse_enemy_kind(value: &str, line_number: usize) -> Result<u8, String> {
+fn parse_enemy_kind(value: &str, line_number: &SourceLocation) -> Result<u8, String> {
 	match value {
 		"slime" => Ok(1),
 		"imp" => Ok(2),
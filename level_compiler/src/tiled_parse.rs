@@ -0,0 +1,542 @@
+use crate::{
+	compile::{get_default_palette_char, is_default_palette_char},
+	entity_parse_state::EntityParseState,
+	json::{JsonValue, parse_json},
+	source::*,
+	text_parse::TriggerActivationMode,
+	trigger_parse_state::TriggerParseState,
+};
+
+use std::{collections::HashMap, fs, path::Path};
+
+// tiled stores flip/rotation in the top bits of every gid
+const GID_MASK: u32 = 0x1FFF_FFFF;
+
+// reads a map exported from tiled as json (.tmj); external tilesets must be .tsj and are
+// resolved relative to the map file
+pub fn load_level_from_tiled_str(text: &str, map_dir: &Path) -> Result<LevelSource, String> {
+	let map = parse_json(text)?;
+
+	if map.get("infinite").and_then(|v| v.as_bool()).unwrap_or(false) {
+		return Err("infinite tiled maps are not supported, turn off 'infinite' in the map properties".to_string());
+	}
+
+	let width = get_u32(&map, "width", "map")?;
+	let height = get_u32(&map, "height", "map")?;
+	let tile_width = get_u32(&map, "tilewidth", "map")?;
+	let tile_height = get_u32(&map, "tileheight", "map")?;
+
+	let properties = read_properties(&map);
+	let header = LevelHeader {
+		version: property_u32(&properties, "version")?.unwrap_or(1),
+		name: property_string(&properties, "name")?.unwrap_or_default(),
+		author: property_string(&properties, "author")?.unwrap_or_default(),
+		width,
+		height,
+		tile_width,
+		tile_height,
+		gravity: property_f32(&properties, "gravity")?.unwrap_or(0.0),
+		background: property_string(&properties, "background")?.unwrap_or_default(),
+		music: property_string(&properties, "music")?.unwrap_or_default(),
+		darkness: property_f32(&properties, "darkness")?.unwrap_or(0.0),
+		rank_thresholds: [
+			property_u32(&properties, "rank_s")?.unwrap_or(0),
+			property_u32(&properties, "rank_a")?.unwrap_or(0),
+			property_u32(&properties, "rank_b")?.unwrap_or(0),
+			property_u32(&properties, "rank_c")?.unwrap_or(0),
+		],
+		cell_width: 1,
+	};
+
+	let mut palette: Vec<PaletteEntrySource> = Vec::new();
+	let tile_chars = load_tilesets(&map, map_dir, &mut palette)?;
+
+	let mut level = LevelSource {
+		header,
+		palette,
+		layers: Vec::new(),
+		entities: Vec::new(),
+		triggers: Vec::new(),
+	};
+
+	let layers = map.get("layers").and_then(|v| v.as_array()).ok_or("map has no 'layers' array")?;
+	for layer in layers {
+		read_layer(layer, &tile_chars, &mut level)?;
+	}
+
+	return Ok(level);
+}
+
+fn read_layer(layer: &JsonValue, tile_chars: &HashMap<u32, char>, level: &mut LevelSource) -> Result<(), String> {
+	let name = layer.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
+	let layer_type = layer.get("type").and_then(|v| v.as_str()).unwrap_or("");
+
+	match layer_type {
+		"tilelayer" => {
+			let layer_source = read_tile_layer(layer, &name, tile_chars, level.header.width, level.header.height)?;
+			level.layers.push(layer_source);
+		}
+		"objectgroup" => {
+			let objects = layer.get("objects").and_then(|v| v.as_array()).unwrap_or(&[]);
+			for object in objects {
+				read_object(object, &level.header, &mut level.entities, &mut level.triggers)?;
+			}
+		}
+		"group" => {
+			let children = layer.get("layers").and_then(|v| v.as_array()).unwrap_or(&[]);
+			for child in children {
+				read_layer(child, tile_chars, level)?;
+			}
+		}
+		// image layers are editor-only
+		_ => {}
+	}
+
+	return Ok(());
+}
+
+fn read_tile_layer(layer: &JsonValue, name: &str, tile_chars: &HashMap<u32, char>, width: u32, height: u32) -> Result<LayerSource, String> {
+	if name.is_empty() {
+		return Err("tile layer missing name".to_string());
+	}
+
+	if layer.get("encoding").and_then(|v| v.as_str()).unwrap_or("csv") != "csv" || layer.get("compression").and_then(|v| v.as_str()).is_some_and(|c| !c.is_empty()) {
+		return Err(format!("layer '{}' must use the CSV tile layer format", name));
+	}
+
+	let data = layer.get("data").and_then(|v| v.as_array()).ok_or_else(|| format!("layer '{}' has no 'data' array", name))?;
+	let expected = (width * height) as usize;
+	if data.len() != expected {
+		return Err(format!("layer '{}' has {} tiles, expected {}", name, data.len(), expected));
+	}
+
+	let mut rows: Vec<String> = Vec::with_capacity(height as usize);
+	for y in 0..height as usize {
+		let mut row = String::with_capacity(width as usize);
+
+		for x in 0..width as usize {
+			let raw = data[y * width as usize + x].as_f64().ok_or_else(|| format!("layer '{}' has a non-numeric tile at {},{}", name, x, y))?;
+			let gid = (raw as u32) & GID_MASK;
+
+			if gid == 0 {
+				row.push('.');
+				continue;
+			}
+
+			match tile_chars.get(&gid) {
+				Some(ch) => row.push(*ch),
+				None => return Err(format!("layer '{}' tile {},{} uses gid {} which has no 'kind' property", name, x, y, gid)),
+			}
+		}
+
+		rows.push(row);
+	}
+
+	let properties = read_properties(layer);
+	let collision = property_bool(&properties, "collision")?.unwrap_or(false);
+
+	return Ok(LayerSource {
+		name: name.to_string(),
+		collision,
+		rows,
+	});
+}
+
+// gid -> palette character, from each tile's 'kind' property (a name from assets/tiles/tiles.ids.txt).
+// tiles without a default palette character get a free one and a palette entry
+fn load_tilesets(map: &JsonValue, map_dir: &Path, palette: &mut Vec<PaletteEntrySource>) -> Result<HashMap<u32, char>, String> {
+	let mut tile_chars: HashMap<u32, char> = HashMap::new();
+	let mut free_chars = ('!'..='~').filter(|c| !is_default_palette_char(*c) && *c != '\'' && *c != '"');
+
+	let tilesets = map.get("tilesets").and_then(|v| v.as_array()).unwrap_or(&[]);
+	for tileset_ref in tilesets {
+		let first_gid = get_u32(tileset_ref, "firstgid", "tileset")?;
+
+		let external: JsonValue;
+		let tileset: &JsonValue = match tileset_ref.get("source").and_then(|v| v.as_str()) {
+			Some(source) => {
+				let path = map_dir.join(source);
+				if path.extension().and_then(|e| e.to_str()) != Some("tsj") && path.extension().and_then(|e| e.to_str()) != Some("json") {
+					return Err(format!("tileset '{}' must be exported as json (.tsj)", source));
+				}
+
+				let text = fs::read_to_string(&path).map_err(|e| format!("failed to read tileset {}: {}", path.display(), e))?;
+				external = parse_json(&text).map_err(|e| format!("tileset {}: {}", path.display(), e))?;
+				&external
+			}
+			None => tileset_ref,
+		};
+
+		let tileset_name = tileset.get("name").and_then(|v| v.as_str()).unwrap_or("");
+		let tiles = tileset.get("tiles").and_then(|v| v.as_array()).unwrap_or(&[]);
+
+		for tile in tiles {
+			let local_id = get_u32(tile, "id", "tileset tile")?;
+			let properties = read_properties(tile);
+
+			let kind = match property_string(&properties, "kind")? {
+				Some(k) => k,
+				None => continue,
+			};
+
+			let kind = kind.trim().to_ascii_lowercase();
+			let ch = match get_default_palette_char(&kind) {
+				Some(c) => c,
+				None => match palette.iter().find(|p| p.tile == kind) {
+					Some(entry) => entry.cell.chars().next().unwrap_or('.'),
+					None => {
+						let Some(c) = free_chars.next() else {
+							return Err(format!("tileset '{}' tile {}: too many distinct tile kinds", tileset_name, local_id));
+						};
+						palette.push(PaletteEntrySource {
+							cell: c.to_string(),
+							tile: kind.clone(),
+						});
+						c
+					}
+				},
+			};
+			tile_chars.insert(first_gid + local_id, ch);
+		}
+	}
+
+	return Ok(tile_chars);
+}
+
+fn read_object(object: &JsonValue, header: &LevelHeader, entities: &mut Vec<EntitySource>, triggers: &mut Vec<TriggerSource>) -> Result<(), String> {
+	let object_id = object.get("id").and_then(|v| v.as_f64()).unwrap_or(0.0) as usize;
+
+	// tiled 1.9 renamed 'type' to 'class'
+	let object_type = object.get("type").and_then(|v| v.as_str()).filter(|s| !s.is_empty()).or_else(|| object.get("class").and_then(|v| v.as_str())).unwrap_or("");
+	let object_name = object.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+	let tile_width = header.tile_width as f32;
+	let tile_height = header.tile_height as f32;
+
+	let x = object.get("x").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+	let mut y = object.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+	let width = object.get("width").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+	let height = object.get("height").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+
+	// tile objects are anchored at their bottom-left corner
+	if object.get("gid").is_some() {
+		y -= height;
+	}
+
+	let properties = read_properties(object);
+
+	match object_type {
+		"player_start" | "enemy" | "platform" => {
+			let mut ent = EntityParseState::new();
+
+			ent.kind = Some(match object_type {
+				"player_start" => EntityKindSource::PlayerStart,
+				"enemy" => EntityKindSource::Enemy {
+					enemy_kind: property_string(&properties, "enemy_kind")?.unwrap_or(object_name),
+					range_min: 0,
+					range_max: 0,
+				},
+				_ => EntityKindSource::MovingPlatform {
+					platform_kind: property_string(&properties, "platform_kind")?.unwrap_or(object_name),
+					size: 1,
+					speed: 1,
+					range_min: 0,
+					range_max: 0,
+				},
+			});
+
+			ent.left = (x / tile_width).floor() as i32;
+			ent.top = (y / tile_height).floor() as i32;
+			if width > 0.0 {
+				ent.width = width / tile_width;
+			}
+			if height > 0.0 {
+				ent.height = height / tile_height;
+			}
+
+			for (key, value) in &properties {
+				apply_entity_property(&mut ent, key, value).map_err(|e| format!("object {}: {}", object_id, e))?;
+			}
+
+			entities.push(ent.to_entity_source(&SourceLocation::new(None, object_id))?);
+		}
+		"level_exit" | "message" | "pickup" | "camera_zone" | "light" | "door" => {
+			let mut trigger = TriggerParseState::new();
+
+			trigger.kind = Some(match object_type {
+				"level_exit" => TriggerKindSource::LevelExit {
+					target: String::new(),
+					level: String::new(),
+					activation_mode: 0,
+				},
+				"message" => TriggerKindSource::Message {
+					text_id: String::new(),
+					activation_mode: 0,
+					speaker: 0,
+					blocking: None,
+				},
+				"camera_zone" => TriggerKindSource::CameraZone { lock: 0 },
+				"light" => TriggerKindSource::Light {
+					radius: 3.0,
+					color: String::from("ffffff"),
+					attach: String::new(),
+				},
+				"door" => TriggerKindSource::Door {
+					key: String::new(),
+					activation_mode: 0,
+				},
+				_ => TriggerKindSource::Pickup {
+					pickup: String::new(),
+					amount: 0,
+					activation_mode: 0,
+				},
+			});
+
+			trigger.left = x / tile_width;
+			trigger.top = y / tile_height;
+			if width > 0.0 {
+				trigger.width = width / tile_width;
+			}
+			if height > 0.0 {
+				trigger.height = height / tile_height;
+			}
+
+			for (key, value) in &properties {
+				apply_trigger_property(&mut trigger, key, value).map_err(|e| format!("object {}: {}", object_id, e))?;
+			}
+
+			triggers.push(trigger.to_trigger_source(&SourceLocation::new(None, object_id))?);
+		}
+		"" => {
+			return Err(format!("object {} ('{}') has no type", object_id, object_name));
+		}
+		_ => {
+			return Err(format!("object {} has unknown type '{}'", object_id, object_type));
+		}
+	}
+
+	return Ok(());
+}
+
+// same keys as an entity body in the .level format
+fn apply_entity_property(ent: &mut EntityParseState, key: &str, value: &JsonValue) -> Result<(), String> {
+	match key {
+		"top" => ent.top = value_i32(value, key)?,
+		"left" => ent.left = value_i32(value, key)?,
+		"range_min" => {
+			let v = value_i32(value, key)?;
+			match ent.kind.as_mut() {
+				Some(EntityKindSource::Enemy { range_min, .. }) => *range_min = v,
+				Some(EntityKindSource::MovingPlatform { range_min, .. }) => *range_min = v,
+				_ => ent.range_min = v,
+			}
+		}
+		"range_max" => {
+			let v = value_i32(value, key)?;
+			match ent.kind.as_mut() {
+				Some(EntityKindSource::Enemy { range_max, .. }) => *range_max = v,
+				Some(EntityKindSource::MovingPlatform { range_max, .. }) => *range_max = v,
+				_ => ent.range_max = v,
+			}
+		}
+		"size" => {
+			let v = value_i32(value, key)?;
+			match ent.kind.as_mut() {
+				Some(EntityKindSource::MovingPlatform { size, .. }) => *size = v,
+				_ => return Err("size not allowed for this entity".to_string()),
+			}
+		}
+		"speed" => ent.speed = value_i32(value, key)?,
+		"gravity_multiplier" => ent.gravity_multiplier = value_f32(value, key)?,
+		"jump_multiplier" => ent.jump_multiplier = value_f32(value, key)?,
+		"attack_power" => ent.attack_power = value_i32(value, key)?,
+		"hit_points" => ent.hit_points = value_i32(value, key)?,
+		"render_style" => {
+			let v = value_i32(value, key)?;
+			ent.render_style = u8::try_from(v).map_err(|_| format!("render_style out of range: {}", v))?;
+		}
+		"width" => ent.width = value_f32(value, key)?,
+		"height" => ent.height = value_f32(value, key)?,
+		"strength" => ent.strength = value_i32(value, key)?,
+		"luck" => ent.luck = value_i32(value, key)?,
+		"health_regen_rate" => ent.health_regen_rate = value_i32(value, key)?,
+		"invulnerability_time" => ent.invulnerability_time = value_i32(value, key)?,
+		// already consumed when the kind was built
+		"enemy_kind" | "platform_kind" => {}
+		_ => return Err(format!("unknown entity property '{}'", key)),
+	}
+
+	return Ok(());
+}
+
+// same keys as a trigger body in the .level format
+fn apply_trigger_property(trigger: &mut TriggerParseState, key: &str, value: &JsonValue) -> Result<(), String> {
+	match key {
+		"top" => trigger.top = value_f32(value, key)?,
+		"left" => trigger.left = value_f32(value, key)?,
+		"width" => trigger.width = value_f32(value, key)?,
+		"height" => trigger.height = value_f32(value, key)?,
+		"icon_id" => trigger.icon_id = value_i32(value, key)?,
+		"target" => match trigger.kind.as_mut() {
+			Some(TriggerKindSource::LevelExit { target, .. }) => *target = value_string(value, key)?,
+			_ => return Err("target not allowed for this trigger".to_string()),
+		},
+		"level" => match trigger.kind.as_mut() {
+			Some(TriggerKindSource::LevelExit { level, .. }) => *level = value_string(value, key)?,
+			_ => return Err("level not allowed for this trigger".to_string()),
+		},
+		"text_id" => match trigger.kind.as_mut() {
+			Some(TriggerKindSource::Message { text_id, .. }) => *text_id = value_string(value, key)?,
+			_ => return Err("text_id not allowed for this trigger".to_string()),
+		},
+		"pickup" => match trigger.kind.as_mut() {
+			Some(TriggerKindSource::Pickup { pickup, .. }) => *pickup = value_string(value, key)?,
+			_ => return Err("pickup not allowed for this trigger".to_string()),
+		},
+		"key" => match trigger.kind.as_mut() {
+			Some(TriggerKindSource::Door { key: door_key, .. }) => *door_key = value_string(value, key)?,
+			_ => return Err("key not allowed for this trigger".to_string()),
+		},
+		"lock" => {
+			let s = value_string(value, key)?;
+			let Some(lock_bits) = parse_camera_lock(&s) else {
+				return Err(format!("invalid camera lock '{}', expected none, x, y or both", s));
+			};
+
+			match trigger.kind.as_mut() {
+				Some(TriggerKindSource::CameraZone { lock }) => *lock = lock_bits,
+				_ => return Err("lock not allowed for this trigger".to_string()),
+			}
+		}
+		"radius" => match trigger.kind.as_mut() {
+			Some(TriggerKindSource::Light { radius, .. }) => *radius = value_f32(value, key)?,
+			_ => return Err("radius not allowed for this trigger".to_string()),
+		},
+		"color" => match trigger.kind.as_mut() {
+			Some(TriggerKindSource::Light { color, .. }) => *color = value_string(value, key)?,
+			_ => return Err("color not allowed for this trigger".to_string()),
+		},
+		"attach" => match trigger.kind.as_mut() {
+			Some(TriggerKindSource::Light { attach, .. }) => *attach = value_string(value, key)?,
+			_ => return Err("attach not allowed for this trigger".to_string()),
+		},
+		"speaker" => {
+			let v = value_i32(value, key)?;
+			if v < 0 || v > (u8::MAX as i32) {
+				return Err("speaker out of range".to_string());
+			}
+
+			match trigger.kind.as_mut() {
+				Some(TriggerKindSource::Message { speaker, .. }) => *speaker = v as u8,
+				_ => return Err("speaker not allowed for this trigger".to_string()),
+			}
+		}
+		"blocking" => match trigger.kind.as_mut() {
+			Some(TriggerKindSource::Message { blocking, .. }) => *blocking = Some(value_bool(value, key)?),
+			_ => return Err("blocking not allowed for this trigger".to_string()),
+		},
+		"amount" => {
+			let v = value_i32(value, key)?;
+			if v < 0 || v > (u16::MAX as i32) {
+				return Err("amount out of range".to_string());
+			}
+
+			match trigger.kind.as_mut() {
+				Some(TriggerKindSource::Pickup { amount, .. }) => *amount = v as u16,
+				_ => return Err("amount not allowed for this trigger".to_string()),
+			}
+		}
+		"mode" => {
+			let s = value_string(value, key)?;
+			let mode: u8 = match s.as_str() {
+				"auto" => TriggerActivationMode::Auto as u8,
+				"action" => TriggerActivationMode::Action as u8,
+				"up" => TriggerActivationMode::Up as u8,
+				"down" => TriggerActivationMode::Down as u8,
+				"left" => TriggerActivationMode::Left as u8,
+				"right" => TriggerActivationMode::Right as u8,
+				_ => return Err(format!("Invalid trigger mode '{}'", s)),
+			};
+
+			match trigger.kind.as_mut() {
+				Some(TriggerKindSource::Message { activation_mode, .. }) => *activation_mode = mode,
+				Some(TriggerKindSource::LevelExit { activation_mode, .. }) => *activation_mode = mode,
+				Some(TriggerKindSource::Pickup { activation_mode, .. }) => *activation_mode = mode,
+				Some(TriggerKindSource::Door { activation_mode, .. }) => *activation_mode = mode,
+				Some(TriggerKindSource::CameraZone { .. }) | Some(TriggerKindSource::Light { .. }) | None => return Err("mode not allowed for this trigger".to_string()),
+			}
+		}
+		_ => return Err(format!("unknown trigger property '{}'", key)),
+	}
+
+	return Ok(());
+}
+
+// tiled custom properties: [{ "name": "...", "type": "int", "value": 3 }, ...]
+fn read_properties(value: &JsonValue) -> Vec<(String, JsonValue)> {
+	let mut out: Vec<(String, JsonValue)> = Vec::new();
+
+	let properties = value.get("properties").and_then(|v| v.as_array()).unwrap_or(&[]);
+	for property in properties {
+		let Some(name) = property.get("name").and_then(|v| v.as_str()) else {
+			continue;
+		};
+		let property_value = property.get("value").cloned().unwrap_or(JsonValue::Null);
+		out.push((name.to_string(), property_value));
+	}
+
+	return out;
+}
+
+fn find_property<'a>(properties: &'a [(String, JsonValue)], key: &str) -> Option<&'a JsonValue> {
+	return properties.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+}
+
+fn property_string(properties: &[(String, JsonValue)], key: &str) -> Result<Option<String>, String> {
+	return find_property(properties, key).map(|v| value_string(v, key)).transpose();
+}
+
+fn property_u32(properties: &[(String, JsonValue)], key: &str) -> Result<Option<u32>, String> {
+	return find_property(properties, key).map(|v| value_i32(v, key).map(|n| n.max(0) as u32)).transpose();
+}
+
+fn property_f32(properties: &[(String, JsonValue)], key: &str) -> Result<Option<f32>, String> {
+	return find_property(properties, key).map(|v| value_f32(v, key)).transpose();
+}
+
+fn property_bool(properties: &[(String, JsonValue)], key: &str) -> Result<Option<bool>, String> {
+	return find_property(properties, key).map(|v| v.as_bool().ok_or_else(|| format!("property '{}' must be a bool", key))).transpose();
+}
+
+fn value_string(value: &JsonValue, key: &str) -> Result<String, String> {
+	match value {
+		JsonValue::String(s) => return Ok(s.clone()),
+		// level ids are often typed as numbers in the editor
+		JsonValue::Number(n) => return Ok(format!("{}", n)),
+		_ => return Err(format!("property '{}' must be a string", key)),
+	}
+}
+
+fn value_f32(value: &JsonValue, key: &str) -> Result<f32, String> {
+	return value.as_f64().map(|n| n as f32).ok_or_else(|| format!("property '{}' must be a number", key));
+}
+
+fn value_bool(value: &JsonValue, key: &str) -> Result<bool, String> {
+	return value.as_bool().ok_or_else(|| format!("property '{}' must be a bool", key));
+}
+
+fn value_i32(value: &JsonValue, key: &str) -> Result<i32, String> {
+	let n = value.as_f64().ok_or_else(|| format!("property '{}' must be a number", key))?;
+	if n.fract() != 0.0 {
+		return Err(format!("property '{}' must be an integer, got {}", key, n));
+	}
+	return Ok(n as i32);
+}
+
+fn get_u32(value: &JsonValue, key: &str, what: &str) -> Result<u32, String> {
+	let n = value.get(key).and_then(|v| v.as_f64()).ok_or_else(|| format!("{} is missing '{}'", what, key))?;
+	if n < 0.0 {
+		return Err(format!("{} '{}' must not be negative", what, key));
+	}
+	return Ok(n as u32);
+}
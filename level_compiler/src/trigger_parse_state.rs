@@ -30,7 +30,7 @@ impl TriggerParseState {
 		self.icon_id = 0;
 	}
 
-	pub fn to_trigger_source(&mut self, line_number: usize) -> Result<TriggerSource, String> {
+	pub fn to_trigger_source(&mut self, line_number: &SourceLocation) -> Result<TriggerSource, String> {
 		let kind = match self.kind.take() {
 			Some(k) => k,
 			None => {
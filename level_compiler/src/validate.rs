@@ -0,0 +1,500 @@
+use crate::{
+	compile::{get_asset_root, is_door_tile_id, is_solid_tile_id},
+	message_registry::MessageRegistry,
+	runtime::*,
+	source::*,
+	text_parse::TriggerActivationMode,
+};
+
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	path::{Path, PathBuf},
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Severity {
+	Warning,
+	Error,
+}
+
+#[derive(Debug)]
+pub struct Diagnostic {
+	pub severity: Severity,
+	pub message: String,
+}
+
+impl Diagnostic {
+	fn warning(message: String) -> Diagnostic {
+		return Diagnostic {
+			severity: Severity::Warning,
+			message,
+		};
+	}
+
+	fn error(message: String) -> Diagnostic {
+		return Diagnostic {
+			severity: Severity::Error,
+			message,
+		};
+	}
+}
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+	return diagnostics.iter().any(|d| d.severity == Severity::Error);
+}
+
+// runs after compile_level; everything here is something the runtime would reject or mishandle
+pub fn validate_level(source: &LevelSource, compiled: &CompiledLevel) -> Vec<Diagnostic> {
+	let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+	validate_name(source, &mut diagnostics);
+	validate_player_start(source, &mut diagnostics);
+	validate_entity_bounds(source, &mut diagnostics);
+	validate_trigger_bounds(source, &mut diagnostics);
+	validate_trigger_solid_overlap(source, compiled, &mut diagnostics);
+	validate_exit_targets(source, &mut diagnostics);
+	validate_duplicate_pickups(source, &mut diagnostics);
+	validate_doors(source, compiled, &mut diagnostics);
+	validate_message_translations(source, &mut diagnostics);
+	validate_message_packing(source, compiled, &mut diagnostics);
+	validate_rank_thresholds(source, &mut diagnostics);
+
+	return diagnostics;
+}
+
+pub fn get_worlds_root() -> PathBuf {
+	let root: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("worlds");
+	return root;
+}
+
+fn describe_entity(index: usize, entity: &EntitySource) -> String {
+	match &entity.kind {
+		EntityKindSource::PlayerStart => return format!("entity {} (player_start)", index),
+		EntityKindSource::Enemy { enemy_kind, .. } => return format!("entity {} (enemy \"{}\")", index, enemy_kind),
+		EntityKindSource::MovingPlatform { platform_kind, .. } => return format!("entity {} (platform \"{}\")", index, platform_kind),
+	}
+}
+
+fn describe_trigger(index: usize, trigger: &TriggerSource) -> String {
+	match &trigger.kind {
+		TriggerKindSource::LevelExit { .. } => return format!("trigger {} (level_exit)", index),
+		TriggerKindSource::Message { text_id, .. } => return format!("trigger {} (message \"{}\")", index, text_id),
+		TriggerKindSource::Pickup { pickup, .. } => return format!("trigger {} (pickup \"{}\")", index, pickup),
+		TriggerKindSource::CameraZone { .. } => return format!("trigger {} (camera_zone)", index),
+		TriggerKindSource::Light { .. } => return format!("trigger {} (light)", index),
+		TriggerKindSource::Door { key, .. } => return format!("trigger {} (door \"{}\")", index, key),
+	}
+}
+
+// the world index and the level select show the header's name
+fn validate_name(source: &LevelSource, diagnostics: &mut Vec<Diagnostic>) {
+	if source.header.name.trim().is_empty() {
+		diagnostics.push(Diagnostic::warning("level has no name, the level select shows its number instead".to_string()));
+	}
+
+	return;
+}
+
+fn validate_player_start(source: &LevelSource, diagnostics: &mut Vec<Diagnostic>) {
+	let count: usize = source.entities.iter().filter(|e| matches!(e.kind, EntityKindSource::PlayerStart)).count();
+
+	if count == 0 {
+		diagnostics.push(Diagnostic::error("level has no player_start entity".to_string()));
+	} else if count > 1 {
+		diagnostics.push(Diagnostic::warning(format!("level has {} player_start entities, only the first is used", count)));
+	}
+
+	return;
+}
+
+// the ranks a level sets must get harder from c up to s, or the lower one can never be given
+fn validate_rank_thresholds(source: &LevelSource, diagnostics: &mut Vec<Diagnostic>) {
+	const RANK_KEYS: [&str; 4] = ["rank_s", "rank_a", "rank_b", "rank_c"];
+
+	let mut higher: Option<(usize, u32)> = None;
+	for (index, threshold) in source.header.rank_thresholds.iter().enumerate() {
+		if *threshold == 0 {
+			continue;
+		}
+		if let Some((higher_index, higher_threshold)) = higher
+			&& *threshold >= higher_threshold
+		{
+			diagnostics.push(Diagnostic::warning(format!(
+				"{} = {} is not below {} = {}, the lower rank can't be reached",
+				RANK_KEYS[index], threshold, RANK_KEYS[higher_index], higher_threshold
+			)));
+		}
+		higher = Some((index, *threshold));
+	}
+
+	return;
+}
+
+// a message shares p0 between its mode and its flags, and the mode byte holds its speaker; each
+// has to read back as written, or the game fires it on the wrong button or shows the wrong speaker
+fn validate_message_packing(source: &LevelSource, compiled: &CompiledLevel, diagnostics: &mut Vec<Diagnostic>) {
+	for (index, (trigger, runtime)) in source.triggers.iter().zip(compiled.triggers.iter()).enumerate() {
+		let TriggerKindSource::Message {
+			activation_mode,
+			speaker,
+			blocking,
+			..
+		} = &trigger.kind
+		else {
+			continue;
+		};
+		let name: String = describe_trigger(index, trigger);
+
+		if *activation_mode > TriggerActivationMode::Right as u8 {
+			diagnostics.push(Diagnostic::error(format!("{} has unknown mode {}", name, activation_mode)));
+		}
+		if runtime.get_message_mode() != *activation_mode as u16 || (runtime.p0 & !(MESSAGE_MODE_MASK | MESSAGE_BLOCKING_FLAG)) != 0 {
+			diagnostics.push(Diagnostic::error(format!(
+				"{} mode {} overlaps the message flags (p0 = {:#06x})",
+				name, activation_mode, runtime.p0
+			)));
+		}
+		let is_blocking: bool = blocking.unwrap_or(*activation_mode != TriggerActivationMode::Auto as u8);
+		if runtime.is_message_blocking() != is_blocking {
+			diagnostics.push(Diagnostic::error(format!("{} blocking flag does not read back as {}", name, is_blocking)));
+		}
+		if runtime.get_message_speaker_icon() != *speaker {
+			diagnostics.push(Diagnostic::error(format!("{} speaker {} does not fit the mode byte", name, speaker)));
+		}
+	}
+
+	return;
+}
+
+fn validate_entity_bounds(source: &LevelSource, diagnostics: &mut Vec<Diagnostic>) {
+	let level_width: i32 = source.header.width as i32;
+	let level_height: i32 = source.header.height as i32;
+
+	for (index, entity) in source.entities.iter().enumerate() {
+		let name: String = describe_entity(index, entity);
+
+		if entity.left < 0 || entity.left >= level_width || entity.top < 0 || entity.top >= level_height {
+			diagnostics.push(Diagnostic::error(format!(
+				"{} at left={} top={} is outside the level ({}x{})",
+				name, entity.left, entity.top, level_width, level_height
+			)));
+			continue;
+		}
+
+		let right: f32 = entity.left as f32 + entity.width;
+		if right > level_width as f32 {
+			diagnostics.push(Diagnostic::error(format!("{} extends past the right edge (left={} width={})", name, entity.left, entity.width)));
+		}
+
+		let bottom: f32 = entity.top as f32 + entity.height;
+		if bottom > level_height as f32 {
+			diagnostics.push(Diagnostic::error(format!("{} extends past the bottom edge (top={} height={})", name, entity.top, entity.height)));
+		}
+
+		let (range_min, range_max) = match &entity.kind {
+			EntityKindSource::Enemy { range_min, range_max, .. } => (*range_min, *range_max),
+			EntityKindSource::MovingPlatform { range_min, range_max, .. } => (*range_min, *range_max),
+			EntityKindSource::PlayerStart => continue,
+		};
+
+		// 0/0 means "no patrol range"
+		if range_min == 0 && range_max == 0 {
+			continue;
+		}
+
+		if range_min > range_max {
+			diagnostics.push(Diagnostic::error(format!("{} has range_min {} > range_max {}", name, range_min, range_max)));
+		}
+
+		if range_min < 0 || range_max > level_width {
+			diagnostics.push(Diagnostic::warning(format!(
+				"{} patrol range {}..{} reaches outside the level width {}",
+				name, range_min, range_max, level_width
+			)));
+		}
+	}
+
+	return;
+}
+
+fn validate_trigger_bounds(source: &LevelSource, diagnostics: &mut Vec<Diagnostic>) {
+	let level_width: f32 = source.header.width as f32;
+	let level_height: f32 = source.header.height as f32;
+
+	for (index, trigger) in source.triggers.iter().enumerate() {
+		let name: String = describe_trigger(index, trigger);
+
+		if trigger.width <= 0.0 || trigger.height <= 0.0 {
+			diagnostics.push(Diagnostic::error(format!("{} has an empty area (width={} height={})", name, trigger.width, trigger.height)));
+			continue;
+		}
+
+		let right: f32 = trigger.left + trigger.width;
+		let bottom: f32 = trigger.top + trigger.height;
+
+		if trigger.left < 0.0 || trigger.top < 0.0 || right > level_width || bottom > level_height {
+			diagnostics.push(Diagnostic::error(format!(
+				"{} at left={} top={} width={} height={} is outside the level ({}x{})",
+				name, trigger.left, trigger.top, trigger.width, trigger.height, level_width, level_height
+			)));
+		}
+	}
+
+	return;
+}
+
+fn validate_trigger_solid_overlap(source: &LevelSource, compiled: &CompiledLevel, diagnostics: &mut Vec<Diagnostic>) {
+	let width: i32 = compiled.header.width as i32;
+	let height: i32 = compiled.header.height as i32;
+	let tiles_per_layer: usize = compiled.header.tiles_per_layer as usize;
+
+	// same rule as Level::get_action_layer_index
+	let action_layer: usize = if compiled.header.layer_count == 1 { 0 } else { 1 };
+
+	for (index, trigger) in source.triggers.iter().enumerate() {
+		// camera zones cover whole rooms, walls included; lights may sit in a wall; doors cover their own solid tiles
+		if matches!(trigger.kind, TriggerKindSource::CameraZone { .. } | TriggerKindSource::Light { .. } | TriggerKindSource::Door { .. }) {
+			continue;
+		}
+
+		let start_left: i32 = (trigger.left.floor() as i32).max(0);
+		let start_top: i32 = (trigger.top.floor() as i32).max(0);
+		let end_left: i32 = ((trigger.left + trigger.width).ceil() as i32).min(width);
+		let end_top: i32 = ((trigger.top + trigger.height).ceil() as i32).min(height);
+
+		let mut solid_count: usize = 0;
+		for tile_top in start_top..end_top {
+			for tile_left in start_left..end_left {
+				let tile_index: usize = action_layer * tiles_per_layer + (tile_top * width + tile_left) as usize;
+				if tile_index < compiled.tiles.len() && is_solid_tile_id(compiled.tiles[tile_index]) {
+					solid_count += 1;
+				}
+			}
+		}
+
+		if solid_count > 0 {
+			diagnostics.push(Diagnostic::warning(format!(
+				"{} overlaps {} solid tile(s); the player may not be able to reach it",
+				describe_trigger(index, trigger),
+				solid_count
+			)));
+		}
+	}
+
+	return;
+}
+
+fn validate_exit_targets(source: &LevelSource, diagnostics: &mut Vec<Diagnostic>) {
+	let worlds_root: PathBuf = get_worlds_root();
+
+	for (index, trigger) in source.triggers.iter().enumerate() {
+		let TriggerKindSource::LevelExit { target, level, .. } = &trigger.kind else {
+			continue;
+		};
+
+		// compile_level has already rejected non-numeric ids
+		let (Ok(world_id), Ok(level_id)) = (target.trim().parse::<u16>(), level.trim().parse::<u16>()) else {
+			continue;
+		};
+
+		let folder: PathBuf = worlds_root.join(format!("{:02}", world_id));
+		let binary_path: PathBuf = folder.join(format!("{:02}.lvlb", level_id));
+		let source_path: PathBuf = folder.join(format!("{:02}.level", level_id));
+
+		if binary_path.exists() {
+			continue;
+		}
+
+		let name: String = describe_trigger(index, trigger);
+		if source_path.exists() {
+			diagnostics.push(Diagnostic::warning(format!("{} targets worlds/{:02}/{:02} which has not been compiled yet", name, world_id, level_id)));
+		} else {
+			diagnostics.push(Diagnostic::error(format!("{} targets worlds/{:02}/{:02} which does not exist", name, world_id, level_id)));
+		}
+	}
+
+	return;
+}
+
+fn validate_duplicate_pickups(source: &LevelSource, diagnostics: &mut Vec<Diagnostic>) {
+	let mut first_index_by_id: HashMap<&str, usize> = HashMap::new();
+
+	for (index, trigger) in source.triggers.iter().enumerate() {
+		let TriggerKindSource::Pickup { pickup, .. } = &trigger.kind else {
+			continue;
+		};
+
+		// coins and random pickups are meant to repeat; keys and books are unique
+		let pickup_id: &str = pickup.trim();
+		if !pickup_id.starts_with("key:") && !pickup_id.starts_with("book:") {
+			continue;
+		}
+
+		if let Some(first_index) = first_index_by_id.get(pickup_id) {
+			diagnostics.push(Diagnostic::warning(format!(
+				"{} duplicates trigger {}; the second pickup of '{}' does nothing",
+				describe_trigger(index, trigger),
+				first_index,
+				pickup_id
+			)));
+			continue;
+		}
+
+		first_index_by_id.insert(pickup_id, index);
+	}
+
+	return;
+}
+
+// a door trigger has to cover door tiles to open anything, and door tiles outside every door
+// trigger can never open
+fn validate_doors(source: &LevelSource, compiled: &CompiledLevel, diagnostics: &mut Vec<Diagnostic>) {
+	let width: i32 = compiled.header.width as i32;
+	let height: i32 = compiled.header.height as i32;
+	let tiles_per_layer: usize = compiled.header.tiles_per_layer as usize;
+	let action_layer: usize = if compiled.header.layer_count == 1 { 0 } else { 1 };
+
+	let mut covered: Vec<bool> = vec![false; (width * height).max(0) as usize];
+	for (index, trigger) in source.triggers.iter().enumerate() {
+		if !matches!(trigger.kind, TriggerKindSource::Door { .. }) {
+			continue;
+		}
+
+		let start_left: i32 = (trigger.left.floor() as i32).max(0);
+		let start_top: i32 = (trigger.top.floor() as i32).max(0);
+		let end_left: i32 = ((trigger.left + trigger.width).ceil() as i32).min(width);
+		let end_top: i32 = ((trigger.top + trigger.height).ceil() as i32).min(height);
+
+		let mut door_count: usize = 0;
+		for tile_top in start_top..end_top {
+			for tile_left in start_left..end_left {
+				let cell_index: usize = (tile_top * width + tile_left) as usize;
+				let tile_index: usize = action_layer * tiles_per_layer + cell_index;
+				if tile_index < compiled.tiles.len() && is_door_tile_id(compiled.tiles[tile_index]) {
+					door_count += 1;
+					covered[cell_index] = true;
+				}
+			}
+		}
+
+		if door_count == 0 {
+			diagnostics.push(Diagnostic::warning(format!("{} covers no door tiles; it opens nothing", describe_trigger(index, trigger))));
+		}
+	}
+
+	let mut uncovered_count: usize = 0;
+	for (cell_index, is_covered) in covered.iter().enumerate() {
+		let tile_index: usize = action_layer * tiles_per_layer + cell_index;
+		if !is_covered && tile_index < compiled.tiles.len() && is_door_tile_id(compiled.tiles[tile_index]) {
+			uncovered_count += 1;
+		}
+	}
+
+	if uncovered_count > 0 {
+		diagnostics.push(Diagnostic::warning(format!("{} door tile(s) are outside every door trigger and will never open", uncovered_count)));
+	}
+
+	return;
+}
+
+fn validate_message_translations(source: &LevelSource, diagnostics: &mut Vec<Diagnostic>) {
+	let messages_root: PathBuf = get_asset_root().join("messages");
+	let ids_path: PathBuf = messages_root.join("messages.ids.txt");
+
+	let Some(ids_path_str) = ids_path.to_str() else {
+		return;
+	};
+
+	// compile_level has already reported unknown keys
+	let Ok(registry) = MessageRegistry::load_from_file(ids_path_str) else {
+		return;
+	};
+
+	let languages: Vec<(String, HashSet<u16>)> = match load_language_tables(&messages_root) {
+		Ok(l) => l,
+		Err(e) => {
+			diagnostics.push(Diagnostic::error(e));
+			return;
+		}
+	};
+
+	if languages.is_empty() {
+		diagnostics.push(Diagnostic::error(format!("no messages.<lang>.txt files found in {}", messages_root.display())));
+		return;
+	}
+
+	for (index, trigger) in source.triggers.iter().enumerate() {
+		let TriggerKindSource::Message { text_id, .. } = &trigger.kind else {
+			continue;
+		};
+
+		let Ok(message_id) = registry.resolve_message_id(text_id) else {
+			continue;
+		};
+
+		let missing: Vec<&str> = languages.iter().filter(|(_, ids)| !ids.contains(&message_id)).map(|(code, _)| code.as_str()).collect();
+
+		if missing.is_empty() {
+			continue;
+		}
+
+		let name: String = describe_trigger(index, trigger);
+		if missing.len() == languages.len() {
+			diagnostics.push(Diagnostic::error(format!("{} uses message id {} which has no text in any language", name, message_id)));
+		} else {
+			diagnostics.push(Diagnostic::warning(format!("{} uses message id {} which is missing in: {}", name, message_id, missing.join(", "))));
+		}
+	}
+
+	return;
+}
+
+// returns (language_code, ids with text) for every messages.<lang>.txt except messages.ids.txt
+fn load_language_tables(messages_root: &Path) -> Result<Vec<(String, HashSet<u16>)>, String> {
+	let entries = fs::read_dir(messages_root).map_err(|e| format!("failed to read {}: {}", messages_root.display(), e))?;
+	let mut languages: Vec<(String, HashSet<u16>)> = Vec::new();
+
+	for entry in entries {
+		let path: PathBuf = entry.map_err(|e| e.to_string())?.path();
+		let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+			continue;
+		};
+
+		let Some(language_code) = file_name.strip_prefix("messages.").and_then(|n| n.strip_suffix(".txt")) else {
+			continue;
+		};
+
+		if language_code == "ids" {
+			continue;
+		}
+
+		let text: String = fs::read_to_string(&path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+		let mut ids: HashSet<u16> = HashSet::new();
+
+		for raw_line in text.lines() {
+			let line: &str = raw_line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let Some(eq_index) = line.find('=') else {
+				continue;
+			};
+
+			let value: &str = line[eq_index + 1..].trim();
+			if value.is_empty() {
+				continue;
+			}
+
+			if let Ok(id) = line[..eq_index].trim().parse::<u16>() {
+				ids.insert(id);
+			}
+		}
+
+		languages.push((language_code.to_string(), ids));
+	}
+
+	languages.sort_by(|a, b| a.0.cmp(&b.0));
+	return Ok(languages);
+}
@@ -0,0 +1,69 @@
+// json.rs has no dependencies on the rest of levelc, so it is compiled straight into this test
+#[allow(dead_code)]
+#[path = "../src/json.rs"]
+mod json;
+
+use json::{JsonValue, parse_json};
+
+fn parse_ok(text: &str) -> JsonValue {
+	return parse_json(text).unwrap_or_else(|e| panic!("failed to parse {:?}: {}", text, e));
+}
+
+#[test]
+fn string_escapes() {
+	let value: JsonValue = parse_ok(r#""a\"b\\c\/d\n\t\r\b\f""#);
+	assert_eq!(value.as_str(), Some("a\"b\\c/d\n\t\r\u{8}\u{c}"));
+
+	let value: JsonValue = parse_ok(r#""\u00e9\u20AC""#);
+	assert_eq!(value.as_str(), Some("é€"));
+
+	// surrogate pair
+	let value: JsonValue = parse_ok(r#""\ud83d\ude00""#);
+	assert_eq!(value.as_str(), Some("😀"));
+
+	assert!(parse_json(r#""\q""#).is_err());
+	assert!(parse_json(r#""\u12G4""#).is_err());
+}
+
+#[test]
+fn nested_arrays() {
+	let value: JsonValue = parse_ok("[[1, [2, 3]], [], [[[]]], {\"a\": [true, null]}]");
+	let items: &[JsonValue] = value.as_array().unwrap();
+	assert_eq!(items.len(), 4);
+
+	let first: &[JsonValue] = items[0].as_array().unwrap();
+	assert_eq!(first[0].as_f64(), Some(1.0));
+	assert_eq!(first[1].as_array().unwrap()[1].as_f64(), Some(3.0));
+
+	assert!(items[1].as_array().unwrap().is_empty());
+	assert_eq!(items[2].as_array().unwrap()[0].as_array().unwrap()[0].as_array().map(|a| a.len()), Some(0));
+
+	let inner: &[JsonValue] = items[3].get("a").and_then(|v| v.as_array()).unwrap();
+	assert_eq!(inner[0].as_bool(), Some(true));
+	assert!(matches!(inner[1], JsonValue::Null));
+}
+
+#[test]
+fn numbers_with_exponents() {
+	assert_eq!(parse_ok("1e3").as_f64(), Some(1000.0));
+	assert_eq!(parse_ok("2.5E-2").as_f64(), Some(0.025));
+	assert_eq!(parse_ok("-4e+1").as_f64(), Some(-40.0));
+	assert_eq!(parse_ok("[0.5e1, -0]").as_array().unwrap()[0].as_f64(), Some(5.0));
+
+	assert!(parse_json("1e").is_err());
+	assert!(parse_json("1.2.3").is_err());
+}
+
+// every prefix of a valid document is an error, never a panic
+#[test]
+fn truncated_input_is_an_error() {
+	let text: &str = r#"{"name": "map", "layers": [{"data": [1, 2.5e1, -3], "visible": true}], "note": "é\n", "empty": null}"#;
+	assert!(parse_json(text).is_ok());
+
+	for (end, _) in text.char_indices().skip(1) {
+		let prefix: &str = &text[..end];
+		assert!(parse_json(prefix).is_err(), "{:?} parsed", prefix);
+	}
+	assert!(parse_json("").is_err());
+	assert!(parse_json("   ").is_err());
+}
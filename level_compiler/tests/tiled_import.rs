@@ -0,0 +1,34 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+fn compile_fixture(input: &str, output_dir: &Path) -> Vec<u8> {
+	let input_path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(input);
+	let output_path: PathBuf = output_dir.join(format!("{}.lvlb", input));
+
+	let result = Command::new(env!("CARGO_BIN_EXE_levelc"))
+		.arg(&input_path)
+		.arg(&output_path)
+		.output()
+		.unwrap_or_else(|e| panic!("failed to run levelc: {}", e));
+	assert!(result.status.success(), "levelc failed on {}: {}", input, String::from_utf8_lossy(&result.stderr));
+
+	return fs::read(&output_path).unwrap_or_else(|e| panic!("failed to read {}: {}", output_path.display(), e));
+}
+
+// a tiled map and the .level it was drawn from compile to the same bytes
+#[test]
+fn tiled_map_compiles_like_its_level() {
+	let output_dir: PathBuf = std::env::temp_dir().join(format!("levelc_tiled_import_{}", std::process::id()));
+	fs::create_dir_all(&output_dir).unwrap_or_else(|e| panic!("failed to create {}: {}", output_dir.display(), e));
+
+	let from_level: Vec<u8> = compile_fixture("tiled_import.level", &output_dir);
+	let from_tiled: Vec<u8> = compile_fixture("tiled_import.tmj", &output_dir);
+	let _ = fs::remove_dir_all(&output_dir);
+
+	assert_eq!(from_level.len(), from_tiled.len(), "the .lvlb files differ in size");
+	let first_difference: Option<usize> = from_level.iter().zip(from_tiled.iter()).position(|(a, b)| a != b);
+	assert_eq!(first_difference, None, "the .lvlb files differ");
+}
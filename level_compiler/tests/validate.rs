@@ -0,0 +1,78 @@
+use std::{fs, path::PathBuf, process::Command};
+
+const LEVEL_TEMPLATE: &str = r#"header
+{
+	version = 1
+	name = "bounds"
+	width = 4
+	height = 3
+	tile_width = 16
+	tile_height = 16
+	background = "bg_library_stone"
+	music = "world2"
+}
+
+layers
+{
+	layer "main"
+	{
+		collision = true
+		tiles =
+		[
+			"...."
+			"...."
+			"dddd"
+		]
+	}
+}
+
+entities
+{
+	player_start "player"
+	{
+		top = 1
+		left = 1
+		width = 1
+		height = 1
+	}
+
+	enemy "slime_blue"
+	{
+		top = TOP
+		left = 2
+		width = 1
+		height = HEIGHT
+	}
+}
+"#;
+
+// compiles the level with the enemy placed as given; the exit code and stderr
+fn compile_with_enemy(test_name: &str, top: &str, height: &str) -> (i32, String) {
+	let dir: PathBuf = std::env::temp_dir().join(format!("levelc_validate_{}_{}", test_name, std::process::id()));
+	fs::create_dir_all(&dir).unwrap_or_else(|e| panic!("failed to create {}: {}", dir.display(), e));
+	let input_path: PathBuf = dir.join("bounds.level");
+	fs::write(&input_path, LEVEL_TEMPLATE.replace("TOP", top).replace("HEIGHT", height)).unwrap_or_else(|e| panic!("failed to write {}: {}", input_path.display(), e));
+
+	let result = Command::new(env!("CARGO_BIN_EXE_levelc"))
+		.arg(&input_path)
+		.arg(dir.join("bounds.lvlb"))
+		.output()
+		.unwrap_or_else(|e| panic!("failed to run levelc: {}", e));
+	let _ = fs::remove_dir_all(&dir);
+
+	return (result.status.code().unwrap_or(-1), String::from_utf8_lossy(&result.stderr).into_owned());
+}
+
+#[test]
+fn entity_inside_the_level_compiles() {
+	let (code, stderr) = compile_with_enemy("inside", "1", "1");
+	assert_eq!(code, 0, "{}", stderr);
+}
+
+// an entity whose top row is inside but whose height reaches below the last row
+#[test]
+fn entity_past_the_bottom_edge_is_rejected() {
+	let (code, stderr) = compile_with_enemy("bottom", "2", "2");
+	assert_eq!(code, 6, "{}", stderr);
+	assert!(stderr.contains("extends past the bottom edge"), "{}", stderr);
+}